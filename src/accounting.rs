@@ -0,0 +1,126 @@
+use serde::Serialize;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The running totals of the requests of a `Browser` and of its clones, as returned by
+/// `Browser::stats`.
+#[derive(Debug, Default)]
+pub struct Accounting {
+    requests: AtomicU64,
+    bytes: AtomicU64,
+    pages: AtomicU64,
+    cache_hits: AtomicU64,
+}
+
+/// A snapshot of the requests made by a `Browser`, to estimate the cost of a batch job.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::accounting::RequestStats;
+///
+/// let stats = RequestStats {
+///     requests: 12,
+///     bytes: 1_536_000,
+///     pages: 10,
+///     cache_hits: 2,
+/// };
+/// assert_eq!(
+///     stats.to_string(),
+///     "12 requests, 1.5 MiB downloaded, 10 pages, 2 cache hits"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RequestStats {
+    /// The requests sent over the network, to DuckDuckGo and to the sites of the results.
+    pub requests: u64,
+    /// The bytes of the response bodies downloaded.
+    pub bytes: u64,
+    /// The pages of results fetched.
+    pub pages: u64,
+    /// The requests answered without the network, from a cassette, a HAR file, or the favicon
+    /// cache.
+    pub cache_hits: u64,
+}
+
+impl Accounting {
+    /// Creates a set of totals at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a snapshot of the totals.
+    pub fn stats(&self) -> RequestStats {
+        RequestStats {
+            requests: self.requests.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+            pages: self.pages.load(Ordering::Relaxed),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sets every total back to zero.
+    pub fn reset(&self) {
+        for counter in [&self.requests, &self.bytes, &self.pages, &self.cache_hits] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn record_request(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_bytes(&self, bytes: usize) {
+        self.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    #[cfg(any(
+        feature = "api",
+        feature = "lite",
+        feature = "html",
+        feature = "images",
+        feature = "news"
+    ))]
+    pub(crate) fn record_page(&self) {
+        self.pages.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl fmt::Display for RequestStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |count: u64, noun: &str| {
+            if count == 1 {
+                format!("1 {}", noun)
+            } else {
+                format!("{} {}s", count, noun)
+            }
+        };
+        write!(
+            f,
+            "{}, {} downloaded, {}, {}",
+            plural(self.requests, "request"),
+            format_bytes(self.bytes),
+            plural(self.pages, "page"),
+            plural(self.cache_hits, "cache hit")
+        )
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 MiB`.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
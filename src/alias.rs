@@ -0,0 +1,166 @@
+use anyhow::{Context, Result, bail};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The placeholder replaced by the arguments of an alias in its template.
+pub const ALIAS_PLACEHOLDER: &str = "{}";
+
+/// Saved searches: named query templates, such as `crates = site:crates.io {}`, run with the
+/// arguments in place of the `{}` placeholder.
+///
+/// The aliases are stored in a file of `name = template` lines. Blank lines and lines starting
+/// with `#` are ignored.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::alias::Aliases;
+///
+/// let path = std::env::temp_dir().join("ddg-aliases-doctest");
+///
+/// let mut aliases = Aliases::default();
+/// aliases.add("crates", "site:crates.io {}").unwrap();
+/// aliases.save(&path).unwrap();
+///
+/// let aliases = Aliases::load(&path).unwrap();
+/// assert_eq!(aliases.expand("crates", &["serde"]).unwrap(), "site:crates.io serde");
+/// assert!(aliases.expand("docs", &["serde"]).is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Aliases {
+    templates: BTreeMap<String, String>,
+}
+
+impl Aliases {
+    /// Returns the default aliases file: `ddg/aliases` in the user config directory
+    /// (`$XDG_CONFIG_HOME`, or `~/.config`), or `None` if there is none.
+    pub fn default_path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+            .map(|base| base.join("ddg").join("aliases"))
+    }
+
+    /// Loads the aliases of a file saved with `save`, or no aliases if the file does not exist.
+    ///
+    /// # Arguments
+    /// * `path` - The aliases file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to read aliases file {}", path.display()));
+            }
+        };
+
+        let mut aliases = Self::default();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((name, template)) = line.split_once('=') else {
+                bail!(
+                    "{}:{}: expected a `name = template` entry",
+                    path.display(),
+                    index + 1
+                );
+            };
+            aliases
+                .add(name.trim(), template.trim())
+                .with_context(|| format!("{}:{}", path.display(), index + 1))?;
+        }
+        Ok(aliases)
+    }
+
+    /// Saves the aliases, one `name = template` line each, creating the parent directory if
+    /// needed.
+    ///
+    /// # Arguments
+    /// * `path` - The aliases file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+        }
+
+        let text: String = self
+            .iter()
+            .map(|(name, template)| format!("{} = {}\n", name, template))
+            .collect();
+        fs::write(path, text)
+            .with_context(|| format!("Failed to write aliases file {}", path.display()))
+    }
+
+    /// Adds an alias, replacing any alias with the same name.
+    ///
+    /// # Arguments
+    /// * `name` - The name the alias is invoked with, without whitespace or `=`.
+    /// * `template` - The query, with `{}` standing for the arguments of the alias. Without a
+    ///   placeholder, the arguments are appended to the query.
+    ///
+    /// # Errors
+    /// Fails if the name or the template is empty, or if the name is not a single word.
+    pub fn add(&mut self, name: &str, template: &str) -> Result<()> {
+        if name.is_empty() || template.trim().is_empty() {
+            bail!("empty alias name or template");
+        }
+        if name.starts_with('-') || name.contains(|c: char| c.is_whitespace() || c == '=') {
+            bail!("invalid alias name '{}'", name);
+        }
+        self.templates
+            .insert(name.to_string(), template.trim().to_string());
+        Ok(())
+    }
+
+    /// Removes an alias, returning `true` if it existed.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the alias.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.templates.remove(name).is_some()
+    }
+
+    /// Returns the template of an alias, if it exists.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the alias.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.templates.get(name).map(String::as_str)
+    }
+
+    /// Returns the aliases and their templates, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.templates
+            .iter()
+            .map(|(name, template)| (name.as_str(), template.as_str()))
+    }
+
+    /// Returns the query an alias stands for with the given arguments, or `None` if there is no
+    /// such alias.
+    ///
+    /// The arguments, joined with spaces, replace every `{}` of the template, or are appended to
+    /// it if it has no placeholder.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the alias.
+    /// * `args` - The arguments the alias was invoked with.
+    pub fn expand(&self, name: &str, args: &[impl AsRef<str>]) -> Option<String> {
+        let template = self.get(name)?;
+        let args: Vec<&str> = args.iter().map(AsRef::as_ref).collect();
+        let args = args.join(" ");
+        let query = if template.contains(ALIAS_PLACEHOLDER) {
+            template.replace(ALIAS_PLACEHOLDER, &args)
+        } else {
+            format!("{} {}", template, args)
+        };
+        Some(query.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+}
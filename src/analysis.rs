@@ -0,0 +1,595 @@
+use crate::response::SearchResult;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::net::Ipv4Addr;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The width of the buckets of the snippet length histogram, in characters.
+pub const SNIPPET_BUCKET: usize = 50;
+
+/// The number of domains and TLDs listed by the text summary of `ResultStats`.
+const LISTED_COUNTS: usize = 10;
+
+/// The width of the longest bar of the histograms of the text summary, in characters.
+const BAR_WIDTH: usize = 30;
+
+/// A summary of a set of results: how many come from each domain and top-level domain, how
+/// long their snippets are, and, for news, on which days they were published.
+///
+/// Its `Display` implementation prints the summary as text, with histograms.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::analysis::ResultStats;
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+///
+/// let result = |url: &str, snippet: &str| {
+///     SearchResult::Text(LiteSearchResult {
+///         url: url.into(),
+///         snippet: snippet.into(),
+///         ..Default::default()
+///     })
+/// };
+/// let stats = ResultStats::new(&[
+///     result("https://www.rust-lang.org/", "A language empowering everyone."),
+///     result("https://doc.rust-lang.org/book/", "The Rust Programming Language book."),
+///     result("https://rust-lang.org/learn", ""),
+///     result("https://github.com/rust-lang/rust", "Empowering everyone to build software."),
+/// ]);
+/// assert_eq!(stats.domains[0].value, "rust-lang.org");
+/// assert_eq!(stats.domains[0].count, 2);
+/// assert_eq!(stats.tlds[0].value, "org");
+/// assert_eq!(stats.tlds[0].count, 3);
+///
+/// let lengths = stats.snippet_lengths.unwrap();
+/// assert_eq!((lengths.count, lengths.min, lengths.max), (3, 31, 38));
+/// assert_eq!(lengths.median, 35.0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResultStats {
+    /// The number of results.
+    pub results: usize,
+    /// The number of results per domain, without a `www.` prefix, most frequent first.
+    pub domains: Vec<Count>,
+    /// The number of results per top-level domain (e.g. `org`), most frequent first.
+    pub tlds: Vec<Count>,
+    /// The distribution of the lengths of the snippets, or `None` if no result has one.
+    pub snippet_lengths: Option<LengthStats>,
+    /// The number of news results per publication day in UTC (`YYYY-MM-DD`), oldest first.
+    #[cfg(feature = "news")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dates: Vec<Count>,
+}
+
+/// The number of results sharing a value, such as a domain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Count {
+    /// The shared value.
+    pub value: String,
+    /// The number of results.
+    pub count: usize,
+}
+
+/// The distribution of the lengths of the non-empty snippets of results, in characters.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LengthStats {
+    /// The number of non-empty snippets.
+    pub count: usize,
+    /// The length of the shortest snippet.
+    pub min: usize,
+    /// The length of the longest snippet.
+    pub max: usize,
+    /// The mean length.
+    pub mean: f64,
+    /// The median length.
+    pub median: f64,
+    /// The number of snippets in each bucket of `SNIPPET_BUCKET` characters, from the bucket
+    /// of the shortest snippet to the bucket of the longest one.
+    pub histogram: Vec<LengthBucket>,
+}
+
+/// A bucket of the snippet length histogram of `LengthStats`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LengthBucket {
+    /// The shortest length of the bucket.
+    pub from: usize,
+    /// The longest length of the bucket.
+    pub to: usize,
+    /// The number of snippets in the bucket.
+    pub count: usize,
+}
+
+impl ResultStats {
+    /// Summarizes a set of results.
+    ///
+    /// # Arguments
+    /// * `results` - The results to summarize.
+    pub fn new(results: &[SearchResult]) -> Self {
+        let mut domains = Counter::default();
+        let mut tlds = Counter::default();
+        for result in results {
+            let Some(host) = result.domain() else {
+                continue;
+            };
+            let host = host.to_lowercase();
+            domains.add(host.strip_prefix("www.").unwrap_or(&host));
+            if let Some(tld) = top_level_domain(&host) {
+                tlds.add(tld);
+            }
+        }
+
+        #[cfg(feature = "news")]
+        let dates = {
+            let mut dates = Counter::default();
+            for result in results {
+                if let SearchResult::News(news) = result
+                    && let Some(date) = news.date
+                {
+                    dates.add(&date.format("%Y-%m-%d").to_string());
+                }
+            }
+            let mut dates = dates.0;
+            dates.sort_by(|a, b| a.value.cmp(&b.value));
+            dates
+        };
+
+        ResultStats {
+            results: results.len(),
+            domains: domains.into_sorted(),
+            tlds: tlds.into_sorted(),
+            snippet_lengths: LengthStats::new(
+                results
+                    .iter()
+                    .map(|result| result.snippet().trim().chars().count())
+                    .filter(|&length| length > 0)
+                    .collect(),
+            ),
+            #[cfg(feature = "news")]
+            dates,
+        }
+    }
+}
+
+impl LengthStats {
+    /// Computes the distribution of some lengths, or returns `None` if there are none.
+    fn new(mut lengths: Vec<usize>) -> Option<Self> {
+        lengths.sort_unstable();
+        let (&min, &max) = (lengths.first()?, lengths.last()?);
+        let count = lengths.len();
+        let median = if count.is_multiple_of(2) {
+            (lengths[count / 2 - 1] + lengths[count / 2]) as f64 / 2.0
+        } else {
+            lengths[count / 2] as f64
+        };
+        let histogram = (min / SNIPPET_BUCKET..=max / SNIPPET_BUCKET)
+            .map(|bucket| {
+                let from = bucket * SNIPPET_BUCKET;
+                let to = from + SNIPPET_BUCKET - 1;
+                LengthBucket {
+                    from,
+                    to,
+                    count: lengths
+                        .iter()
+                        .filter(|&&l| (from..=to).contains(&l))
+                        .count(),
+                }
+            })
+            .collect();
+        Some(LengthStats {
+            count,
+            min,
+            max,
+            mean: lengths.iter().sum::<usize>() as f64 / count as f64,
+            median,
+            histogram,
+        })
+    }
+}
+
+/// Counts values in the order they first appear.
+#[derive(Default)]
+struct Counter(Vec<Count>);
+
+impl Counter {
+    fn add(&mut self, value: &str) {
+        match self.0.iter_mut().find(|count| count.value == value) {
+            Some(count) => count.count += 1,
+            None => self.0.push(Count {
+                value: value.to_string(),
+                count: 1,
+            }),
+        }
+    }
+
+    /// Returns the counts, most frequent first, ties keeping the order of first appearance.
+    fn into_sorted(mut self) -> Vec<Count> {
+        self.0.sort_by_key(|count| std::cmp::Reverse(count.count));
+        self.0
+    }
+}
+
+/// Returns the last label of a host name, or `None` if the host is an IP address or has a
+/// single label.
+fn top_level_domain(host: &str) -> Option<&str> {
+    if host.starts_with('[') || host.parse::<Ipv4Addr>().is_ok() {
+        return None;
+    }
+    host.trim_end_matches('.')
+        .rsplit_once('.')
+        .map(|(_, tld)| tld)
+}
+
+impl fmt::Display for ResultStats {
+    /// Formats the summary as text: the most frequent domains and TLDs, the snippet lengths,
+    /// and the publication days of news, with histograms.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Results: {}", self.results)?;
+        for (title, counts) in [("Domains", &self.domains), ("TLDs", &self.tlds)] {
+            if counts.is_empty() {
+                continue;
+            }
+            writeln!(f, "\n{} ({}):", title, counts.len())?;
+            for count in counts.iter().take(LISTED_COUNTS) {
+                writeln!(f, "{:>6}  {}", count.count, count.value)?;
+            }
+            if counts.len() > LISTED_COUNTS {
+                writeln!(f, "{:>6}  … and {} more", "", counts.len() - LISTED_COUNTS)?;
+            }
+        }
+        if let Some(lengths) = &self.snippet_lengths {
+            writeln!(
+                f,
+                "\nSnippet lengths ({} snippets): min {}, median {}, mean {:.1}, max {} characters",
+                lengths.count, lengths.min, lengths.median, lengths.mean, lengths.max
+            )?;
+            let peak = lengths.histogram.iter().map(|b| b.count).max().unwrap_or(0);
+            for bucket in &lengths.histogram {
+                let range = format!("{}-{}", bucket.from, bucket.to);
+                writeln!(
+                    f,
+                    "{:>6}  {:<9}  {}",
+                    bucket.count,
+                    range,
+                    bar(bucket.count, peak)
+                )?;
+            }
+        }
+        #[cfg(feature = "news")]
+        if !self.dates.is_empty() {
+            writeln!(f, "\nPublication days ({}):", self.dates.len())?;
+            let peak = self.dates.iter().map(|d| d.count).max().unwrap_or(0);
+            for date in &self.dates {
+                writeln!(
+                    f,
+                    "{:>6}  {}  {}",
+                    date.count,
+                    date.value,
+                    bar(date.count, peak)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns a histogram bar for a count, `BAR_WIDTH` characters long for the peak count.
+fn bar(count: usize, peak: usize) -> String {
+    if peak == 0 {
+        return String::new();
+    }
+    "█".repeat((count * BAR_WIDTH).div_ceil(peak))
+}
+
+/// Common English words left out of `TermFrequencies`.
+pub const STOPWORDS: &[&str] = &[
+    "a",
+    "about",
+    "above",
+    "after",
+    "again",
+    "against",
+    "all",
+    "also",
+    "am",
+    "an",
+    "and",
+    "any",
+    "are",
+    "as",
+    "at",
+    "be",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "by",
+    "can",
+    "could",
+    "did",
+    "do",
+    "does",
+    "doing",
+    "down",
+    "during",
+    "each",
+    "etc",
+    "few",
+    "for",
+    "from",
+    "further",
+    "get",
+    "had",
+    "has",
+    "have",
+    "having",
+    "he",
+    "her",
+    "here",
+    "hers",
+    "herself",
+    "him",
+    "himself",
+    "his",
+    "how",
+    "if",
+    "in",
+    "into",
+    "is",
+    "it",
+    "its",
+    "itself",
+    "just",
+    "me",
+    "more",
+    "most",
+    "my",
+    "myself",
+    "new",
+    "no",
+    "nor",
+    "not",
+    "now",
+    "of",
+    "off",
+    "on",
+    "once",
+    "one",
+    "only",
+    "or",
+    "other",
+    "our",
+    "ours",
+    "ourselves",
+    "out",
+    "over",
+    "own",
+    "same",
+    "she",
+    "should",
+    "so",
+    "some",
+    "such",
+    "than",
+    "that",
+    "the",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "to",
+    "too",
+    "under",
+    "until",
+    "up",
+    "use",
+    "used",
+    "very",
+    "via",
+    "was",
+    "we",
+    "were",
+    "what",
+    "when",
+    "where",
+    "which",
+    "while",
+    "who",
+    "whom",
+    "why",
+    "will",
+    "with",
+    "would",
+    "you",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
+
+/// The most frequent terms and pairs of consecutive terms (bigrams) of the titles and snippets
+/// of results, to discover the vocabulary related to a query.
+///
+/// The text is split into words, lowercased. Stopwords (`STOPWORDS`), the words of the query,
+/// words without a letter, and single letters are left out, and break bigrams, as does
+/// punctuation.
+///
+/// Its `Display` implementation prints the terms and bigrams as text.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::analysis::TermFrequencies;
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+///
+/// let result = |title: &str, snippet: &str| {
+///     SearchResult::Text(LiteSearchResult {
+///         title: title.into(),
+///         snippet: snippet.into(),
+///         ..Default::default()
+///     })
+/// };
+/// let terms = TermFrequencies::new(
+///     &[
+///         result("Axum web framework", "A web framework built on the Tokio async runtime."),
+///         result("Actix Web", "A powerful, pragmatic web framework for the Tokio async runtime."),
+///     ],
+///     "rust web",
+/// );
+/// assert_eq!(terms.terms[0].term, "framework");
+/// assert_eq!(terms.terms[0].count, 3);
+/// assert_eq!(terms.terms[0].results, 2);
+/// assert!(terms.terms.iter().all(|term| term.term != "web" && term.term != "the"));
+/// assert_eq!(terms.bigrams[0].term, "tokio async");
+/// assert_eq!(terms.bigrams[0].count, 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TermFrequencies {
+    /// The terms, most frequent first.
+    pub terms: Vec<Term>,
+    /// The bigrams, as two terms separated by a space, most frequent first.
+    pub bigrams: Vec<Term>,
+}
+
+/// A term or a bigram of `TermFrequencies`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Term {
+    /// The term, or the two terms of the bigram separated by a space.
+    pub term: String,
+    /// The number of times it appears.
+    pub count: usize,
+    /// The number of results it appears in.
+    pub results: usize,
+}
+
+impl TermFrequencies {
+    /// Counts the terms and bigrams of the titles and snippets of results.
+    ///
+    /// # Arguments
+    /// * `results` - The results to analyze.
+    /// * `query` - The query of the results, whose words are left out.
+    pub fn new(results: &[SearchResult], query: &str) -> Self {
+        let query: HashSet<String> = query.unicode_words().map(str::to_lowercase).collect();
+        let mut terms = TermCounter::default();
+        let mut bigrams = TermCounter::default();
+        for (index, result) in results.iter().enumerate() {
+            for text in [result.title(), result.snippet()] {
+                let mut previous: Option<String> = None;
+                for segment in text.split_word_bounds() {
+                    if segment.trim().is_empty() {
+                        continue;
+                    }
+                    let word = segment.to_lowercase();
+                    if !is_term(&word) || query.contains(&word) {
+                        previous = None;
+                        continue;
+                    }
+                    if let Some(previous) = &previous {
+                        bigrams.add(&format!("{} {}", previous, word), index);
+                    }
+                    terms.add(&word, index);
+                    previous = Some(word);
+                }
+            }
+        }
+        TermFrequencies {
+            terms: terms.into_sorted(),
+            bigrams: bigrams.into_sorted(),
+        }
+    }
+
+    /// Keeps only the `n` most frequent terms and bigrams.
+    pub fn truncate(&mut self, n: usize) {
+        self.terms.truncate(n);
+        self.bigrams.truncate(n);
+    }
+}
+
+/// Returns `true` if a lowercase word counts as a term: not a stopword, with a letter, and
+/// longer than one character.
+fn is_term(word: &str) -> bool {
+    word.chars().nth(1).is_some()
+        && word.chars().any(char::is_alphabetic)
+        && !STOPWORDS.contains(&word)
+}
+
+/// Counts terms in the order they first appear, with the number of results they appear in.
+#[derive(Default)]
+struct TermCounter {
+    terms: Vec<Term>,
+    /// The index of each term in `terms`, and the last result it appeared in.
+    index: HashMap<String, (usize, usize)>,
+}
+
+impl TermCounter {
+    fn add(&mut self, term: &str, result: usize) {
+        match self.index.get_mut(term) {
+            Some((index, last)) => {
+                let counted = &mut self.terms[*index];
+                counted.count += 1;
+                if *last != result {
+                    counted.results += 1;
+                    *last = result;
+                }
+            }
+            None => {
+                self.index
+                    .insert(term.to_string(), (self.terms.len(), result));
+                self.terms.push(Term {
+                    term: term.to_string(),
+                    count: 1,
+                    results: 1,
+                });
+            }
+        }
+    }
+
+    /// Returns the terms, most frequent first, ties keeping the order of first appearance.
+    fn into_sorted(mut self) -> Vec<Term> {
+        self.terms.sort_by_key(|term| std::cmp::Reverse(term.count));
+        self.terms
+    }
+}
+
+impl fmt::Display for TermFrequencies {
+    /// Formats the terms and the bigrams as text, with the number of times and of results
+    /// they appear in.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (title, terms)) in [("Terms", &self.terms), ("Bigrams", &self.bigrams)]
+            .into_iter()
+            .enumerate()
+        {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "{}:", title)?;
+            if terms.is_empty() {
+                writeln!(f, "{:>6}  (none)", "")?;
+            }
+            for term in terms {
+                let results = match term.results {
+                    1 => String::from("1 result"),
+                    n => format!("{} results", n),
+                };
+                writeln!(f, "{:>6}  {:<32} in {}", term.count, term.term, results)?;
+            }
+        }
+        Ok(())
+    }
+}
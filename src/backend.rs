@@ -0,0 +1,611 @@
+#[cfg(any(
+    feature = "api",
+    feature = "lite",
+    feature = "html",
+    feature = "images",
+    feature = "news"
+))]
+use crate::browser::Browser;
+use crate::merge::rrf;
+use crate::params::SearchParams;
+use crate::plan::PlannedRequest;
+#[cfg(feature = "api")]
+use crate::response::LiteSearchResult;
+use crate::response::{SearchPage, SearchResult};
+use anyhow::Result;
+use async_trait::async_trait;
+use futures_util::future::join_all;
+
+/// A search backend that turns `SearchParams` into a list of results.
+///
+/// The built-in backends wrap the corresponding `Browser` methods. Custom backends can implement
+/// this trait to be used interchangeably with them.
+///
+/// # Examples
+/// ```
+/// use anyhow::Result;
+/// use async_trait::async_trait;
+/// use duckduckgo::backend::SearchBackend;
+/// use duckduckgo::params::SearchParams;
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+///
+/// struct Echo;
+///
+/// #[async_trait]
+/// impl SearchBackend for Echo {
+///     fn name(&self) -> &str {
+///         "echo"
+///     }
+///
+///     async fn search(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+///         Ok(vec![SearchResult::Text(LiteSearchResult {
+///             title: params.query.clone(),
+///             url: "https://example.com".into(),
+///             backend: String::from("echo"),
+///             ..Default::default()
+///         })])
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<()> {
+///     let backend: Box<dyn SearchBackend> = Box::new(Echo);
+///     let results = backend.search(&SearchParams::new("rust")).await?;
+///     assert_eq!(results[0].title(), "rust");
+///     Ok(())
+/// }
+/// ```
+#[async_trait]
+pub trait SearchBackend: Send + Sync {
+    /// Returns the name of the backend (e.g. `"lite"`).
+    fn name(&self) -> &str;
+
+    /// Performs a search and returns the results.
+    async fn search(&self, params: &SearchParams) -> Result<Vec<SearchResult>>;
+
+    /// Performs a search and returns the results together with page hints, such as spelling
+    /// corrections and related searches.
+    ///
+    /// Backends without page hints return an empty set of hints.
+    async fn search_page(&self, params: &SearchParams) -> Result<SearchPage<SearchResult>> {
+        Ok(SearchPage {
+            results: self.search(params).await?,
+            ..Default::default()
+        })
+    }
+
+    /// Returns the requests a search would send, without sending them, as printed by
+    /// `--dry-run`.
+    ///
+    /// Backends that cannot tell return no requests.
+    fn plan(&self, _params: &SearchParams) -> Result<Vec<PlannedRequest>> {
+        Ok(Vec::new())
+    }
+}
+
+/// The Instant Answer API backend, returning related topics as text results.
+#[cfg(feature = "api")]
+pub struct ApiBackend<'a> {
+    browser: &'a Browser,
+}
+
+/// The DuckDuckGo Lite backend.
+#[cfg(feature = "lite")]
+pub struct LiteBackend<'a> {
+    browser: &'a Browser,
+}
+
+/// The DuckDuckGo HTML backend.
+#[cfg(feature = "html")]
+pub struct HtmlBackend<'a> {
+    browser: &'a Browser,
+}
+
+/// The image search backend.
+#[cfg(feature = "images")]
+pub struct ImagesBackend<'a> {
+    browser: &'a Browser,
+}
+
+/// The news search backend.
+#[cfg(feature = "news")]
+pub struct NewsBackend<'a> {
+    browser: &'a Browser,
+}
+
+/// A backend trying a chain of backends in turn, until one of them returns results.
+///
+/// A backend that fails or returns no results is skipped, so that e.g. the Instant Answer API,
+/// which has no related topics for most ordinary queries, can fall back to the HTML and Lite
+/// backends.
+///
+/// # Examples
+/// ```
+/// use anyhow::Result;
+/// use async_trait::async_trait;
+/// use duckduckgo::backend::{FallbackBackend, SearchBackend};
+/// use duckduckgo::params::SearchParams;
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+///
+/// struct Empty;
+///
+/// #[async_trait]
+/// impl SearchBackend for Empty {
+///     fn name(&self) -> &str {
+///         "empty"
+///     }
+///
+///     async fn search(&self, _params: &SearchParams) -> Result<Vec<SearchResult>> {
+///         Ok(Vec::new())
+///     }
+/// }
+///
+/// struct Echo;
+///
+/// #[async_trait]
+/// impl SearchBackend for Echo {
+///     fn name(&self) -> &str {
+///         "echo"
+///     }
+///
+///     async fn search(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+///         Ok(vec![SearchResult::Text(LiteSearchResult {
+///             title: params.query.clone(),
+///             url: "https://example.com".into(),
+///             backend: String::from("echo"),
+///             ..Default::default()
+///         })])
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<()> {
+///     let backend = FallbackBackend::new(vec![Box::new(Empty), Box::new(Echo)]);
+///     let (served_by, page) = backend.search_fallback(&SearchParams::new("rust")).await?;
+///     assert_eq!(served_by.name(), "echo");
+///     assert_eq!(page.results.len(), 1);
+///     Ok(())
+/// }
+/// ```
+pub struct FallbackBackend<'a> {
+    backends: Vec<Box<dyn SearchBackend + 'a>>,
+    min_results: usize,
+}
+
+/// A backend running several backends concurrently and merging their results.
+///
+/// Requests still go through the rate limiter of the `Browser`, which bounds how fast they are
+/// sent.
+///
+/// # Examples
+/// ```
+/// use anyhow::Result;
+/// use async_trait::async_trait;
+/// use duckduckgo::backend::{MetaBackend, SearchBackend};
+/// use duckduckgo::params::SearchParams;
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+///
+/// struct Echo(&'static str);
+///
+/// #[async_trait]
+/// impl SearchBackend for Echo {
+///     fn name(&self) -> &str {
+///         self.0
+///     }
+///
+///     async fn search(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+///         Ok(vec![SearchResult::Text(LiteSearchResult {
+///             title: params.query.clone(),
+///             url: format!("https://example.com/{}", self.0).into(),
+///             backend: self.0.to_string(),
+///             ..Default::default()
+///         })])
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<()> {
+///     let backend = MetaBackend::new(vec![Box::new(Echo("a")), Box::new(Echo("b"))]);
+///     let sections = backend.search_all(&SearchParams::new("rust")).await;
+///     assert_eq!(sections.len(), 2);
+///
+///     let merged = backend.search_merged(&SearchParams::new("rust")).await?;
+///     assert_eq!(merged[1].backend(), "b");
+///     Ok(())
+/// }
+/// ```
+pub struct MetaBackend<'a> {
+    backends: Vec<Box<dyn SearchBackend + 'a>>,
+}
+
+/// The outcome of one backend of a `MetaBackend` search.
+#[derive(Debug)]
+pub struct BackendResults {
+    /// The name of the backend.
+    pub backend: String,
+    /// The page returned by the backend, or the error it failed with.
+    pub page: Result<SearchPage<SearchResult>>,
+}
+
+#[cfg(feature = "api")]
+impl<'a> ApiBackend<'a> {
+    /// Creates a new `ApiBackend` using the given browser.
+    pub fn new(browser: &'a Browser) -> Self {
+        ApiBackend { browser }
+    }
+}
+
+impl<'a> FallbackBackend<'a> {
+    /// Creates a new `FallbackBackend` trying the given backends in order.
+    pub fn new(backends: Vec<Box<dyn SearchBackend + 'a>>) -> Self {
+        FallbackBackend {
+            backends,
+            min_results: 1,
+        }
+    }
+
+    /// Falls back to the next backend when one returns fewer than `min` results, instead of
+    /// only when it returns none.
+    ///
+    /// # Arguments
+    /// * `min` - The number of results a backend must return to serve the search.
+    pub fn min_results(mut self, min: usize) -> Self {
+        self.min_results = min.max(1);
+        self
+    }
+
+    /// Creates the chain used by the `auto` backend: the Instant Answer API, then the HTML
+    /// backend, then the Lite backend.
+    #[cfg(all(feature = "api", feature = "html", feature = "lite"))]
+    pub fn auto(browser: &'a Browser) -> Self {
+        FallbackBackend::new(vec![
+            Box::new(ApiBackend::new(browser)),
+            Box::new(HtmlBackend::new(browser)),
+            Box::new(LiteBackend::new(browser)),
+        ])
+    }
+
+    /// Returns the backends of the chain, in the order they are tried.
+    pub fn backends(&self) -> &[Box<dyn SearchBackend + 'a>] {
+        &self.backends
+    }
+
+    /// Performs a search with each backend in turn, until one of them returns results, or at
+    /// least `min_results` results if set.
+    ///
+    /// # Arguments
+    /// * `params` - The search parameters.
+    ///
+    /// # Returns
+    /// The backend that served the page, and the page. If no backend returned enough
+    /// results, the page with the most results (the last one on a tie) of the backends that
+    /// succeeded, or the error of the last backend if they all failed.
+    pub async fn search_fallback(
+        &self,
+        params: &SearchParams,
+    ) -> Result<(&(dyn SearchBackend + 'a), SearchPage<SearchResult>)> {
+        let mut short: Option<(&(dyn SearchBackend + 'a), SearchPage<SearchResult>)> = None;
+        let mut last_err = None;
+        for backend in &self.backends {
+            match backend.search_page(params).await {
+                Ok(page) if page.results.len() >= self.min_results => {
+                    return Ok((backend.as_ref(), page));
+                }
+                Ok(page) => {
+                    if short
+                        .as_ref()
+                        .is_none_or(|(_, best)| page.results.len() >= best.results.len())
+                    {
+                        short = Some((backend.as_ref(), page));
+                    }
+                }
+                Err(err) => {
+                    last_err = Some(err.context(format!("The {} backend failed", backend.name())))
+                }
+            }
+        }
+
+        match (short, last_err) {
+            (Some(short), _) => Ok(short),
+            (None, Some(err)) => Err(err),
+            (None, None) => anyhow::bail!("No backend to search with"),
+        }
+    }
+}
+
+impl<'a> MetaBackend<'a> {
+    /// Creates a new `MetaBackend` running the given backends.
+    pub fn new(backends: Vec<Box<dyn SearchBackend + 'a>>) -> Self {
+        MetaBackend { backends }
+    }
+
+    /// Creates the backend used by `--backend all`: text (HTML), image, and news searches.
+    #[cfg(all(feature = "html", feature = "images", feature = "news"))]
+    pub fn all(browser: &'a Browser) -> Self {
+        MetaBackend::new(vec![
+            Box::new(HtmlBackend::new(browser)),
+            Box::new(ImagesBackend::new(browser)),
+            Box::new(NewsBackend::new(browser)),
+        ])
+    }
+
+    /// Returns the backends, in the order their results are merged.
+    pub fn backends(&self) -> &[Box<dyn SearchBackend + 'a>] {
+        &self.backends
+    }
+
+    /// Runs all backends concurrently.
+    ///
+    /// # Arguments
+    /// * `params` - The search parameters, shared by all backends.
+    ///
+    /// # Returns
+    /// The outcome of each backend, in the order of the backends. A failing backend does not
+    /// affect the others.
+    pub async fn search_all(&self, params: &SearchParams) -> Vec<BackendResults> {
+        let searches = self.backends.iter().map(|backend| async move {
+            BackendResults {
+                backend: backend.name().to_string(),
+                page: backend.search_page(params).await,
+            }
+        });
+        join_all(searches).await
+    }
+
+    /// Runs all backends concurrently and merges their results.
+    ///
+    /// Each result still tells which backend returned it through `SearchResult::backend`.
+    ///
+    /// # Arguments
+    /// * `params` - The search parameters, shared by all backends.
+    ///
+    /// # Returns
+    /// The results of the backends that succeeded, in the order of the backends, or the error
+    /// of the first backend if they all failed.
+    pub async fn search_merged(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+        self.merge_with(params, |lists| lists.into_iter().flatten().collect())
+            .await
+    }
+
+    /// Runs all backends concurrently and merges their results with reciprocal-rank fusion
+    /// (see `merge::rrf`), so that the results ranked high by several backends come first.
+    ///
+    /// # Arguments
+    /// * `params` - The search parameters, shared by all backends.
+    ///
+    /// # Returns
+    /// The fused results of the backends that succeeded, or the error of the first backend if
+    /// they all failed.
+    pub async fn search_fused(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+        self.merge_with(params, rrf).await
+    }
+
+    /// Runs all backends concurrently and merges the results of those that succeeded.
+    async fn merge_with(
+        &self,
+        params: &SearchParams,
+        merge: impl FnOnce(Vec<Vec<SearchResult>>) -> Vec<SearchResult>,
+    ) -> Result<Vec<SearchResult>> {
+        let mut lists = Vec::new();
+        let mut first_err = None;
+        let mut succeeded = false;
+        for section in self.search_all(params).await {
+            match section.page {
+                Ok(page) => {
+                    succeeded = true;
+                    lists.push(page.results);
+                }
+                Err(err) => {
+                    let err = err.context(format!("The {} backend failed", section.backend));
+                    first_err.get_or_insert(err);
+                }
+            }
+        }
+
+        match first_err {
+            Some(err) if !succeeded => Err(err),
+            _ => Ok(merge(lists)),
+        }
+    }
+}
+
+#[cfg(feature = "lite")]
+impl<'a> LiteBackend<'a> {
+    /// Creates a new `LiteBackend` using the given browser.
+    pub fn new(browser: &'a Browser) -> Self {
+        LiteBackend { browser }
+    }
+}
+
+#[cfg(feature = "html")]
+impl<'a> HtmlBackend<'a> {
+    /// Creates a new `HtmlBackend` using the given browser.
+    pub fn new(browser: &'a Browser) -> Self {
+        HtmlBackend { browser }
+    }
+}
+
+#[cfg(feature = "images")]
+impl<'a> ImagesBackend<'a> {
+    /// Creates a new `ImagesBackend` using the given browser.
+    pub fn new(browser: &'a Browser) -> Self {
+        ImagesBackend { browser }
+    }
+}
+
+#[cfg(feature = "news")]
+impl<'a> NewsBackend<'a> {
+    /// Creates a new `NewsBackend` using the given browser.
+    pub fn new(browser: &'a Browser) -> Self {
+        NewsBackend { browser }
+    }
+}
+
+#[cfg(feature = "api")]
+#[async_trait]
+impl SearchBackend for ApiBackend<'_> {
+    fn name(&self) -> &str {
+        "api"
+    }
+
+    fn plan(&self, params: &SearchParams) -> Result<Vec<PlannedRequest>> {
+        self.browser.plan(self.name(), params)
+    }
+
+    async fn search(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+        let response = self.browser.fetch_response(&api_params(params)).await?;
+
+        let results = response
+            .iter_topics()
+            .filter_map(|topic| Some((topic.text.clone()?, topic.first_url.clone()?)))
+            .enumerate()
+            .map(|(i, (title, url))| {
+                SearchResult::Text(LiteSearchResult {
+                    title,
+                    url: url.into(),
+                    snippet: String::new(),
+                    rank: i + 1,
+                    page: 1,
+                    backend: String::from("api"),
+                    favicon: None,
+                    link_check: None,
+                    content: None,
+                    also_on: Vec::new(),
+                })
+            })
+            .skip(params.offset)
+            .take(params.limit.unwrap_or(usize::MAX))
+            .collect();
+
+        Ok(results)
+    }
+}
+
+#[cfg(feature = "lite")]
+#[async_trait]
+impl SearchBackend for LiteBackend<'_> {
+    fn name(&self) -> &str {
+        "lite"
+    }
+
+    fn plan(&self, params: &SearchParams) -> Result<Vec<PlannedRequest>> {
+        self.browser.plan(self.name(), params)
+    }
+
+    async fn search(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+        Ok(self.search_page(params).await?.results)
+    }
+
+    async fn search_page(&self, params: &SearchParams) -> Result<SearchPage<SearchResult>> {
+        let page = self.browser.lite_search_page(params).await?;
+        Ok(page.map(SearchResult::Text))
+    }
+}
+
+#[cfg(feature = "html")]
+#[async_trait]
+impl SearchBackend for HtmlBackend<'_> {
+    fn name(&self) -> &str {
+        "html"
+    }
+
+    fn plan(&self, params: &SearchParams) -> Result<Vec<PlannedRequest>> {
+        self.browser.plan(self.name(), params)
+    }
+
+    async fn search(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+        Ok(self.search_page(params).await?.results)
+    }
+
+    async fn search_page(&self, params: &SearchParams) -> Result<SearchPage<SearchResult>> {
+        let page = self.browser.html_search(params).await?;
+        Ok(page.map(SearchResult::Text))
+    }
+}
+
+#[cfg(feature = "images")]
+#[async_trait]
+impl SearchBackend for ImagesBackend<'_> {
+    fn name(&self) -> &str {
+        "images"
+    }
+
+    fn plan(&self, params: &SearchParams) -> Result<Vec<PlannedRequest>> {
+        self.browser.plan(self.name(), params)
+    }
+
+    async fn search(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+        let results = self.browser.images(params).await?;
+        Ok(results.into_iter().map(SearchResult::Image).collect())
+    }
+}
+
+#[cfg(feature = "news")]
+#[async_trait]
+impl SearchBackend for NewsBackend<'_> {
+    fn name(&self) -> &str {
+        "news"
+    }
+
+    fn plan(&self, params: &SearchParams) -> Result<Vec<PlannedRequest>> {
+        self.browser.plan(self.name(), params)
+    }
+
+    async fn search(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+        let results = self.browser.news(params).await?;
+        Ok(results.into_iter().map(SearchResult::News).collect())
+    }
+}
+
+#[async_trait]
+impl SearchBackend for FallbackBackend<'_> {
+    fn name(&self) -> &str {
+        "auto"
+    }
+
+    fn plan(&self, params: &SearchParams) -> Result<Vec<PlannedRequest>> {
+        let mut requests = Vec::new();
+        for backend in &self.backends {
+            requests.extend(backend.plan(params)?);
+        }
+        Ok(requests)
+    }
+
+    async fn search(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+        Ok(self.search_page(params).await?.results)
+    }
+
+    async fn search_page(&self, params: &SearchParams) -> Result<SearchPage<SearchResult>> {
+        Ok(self.search_fallback(params).await?.1)
+    }
+}
+
+#[async_trait]
+impl SearchBackend for MetaBackend<'_> {
+    fn name(&self) -> &str {
+        "all"
+    }
+
+    fn plan(&self, params: &SearchParams) -> Result<Vec<PlannedRequest>> {
+        let mut requests = Vec::new();
+        for backend in &self.backends {
+            requests.extend(backend.plan(params)?);
+        }
+        Ok(requests)
+    }
+
+    async fn search(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+        self.search_merged(params).await
+    }
+}
+
+/// Returns the query parameters of an Instant Answer API search.
+#[cfg(feature = "api")]
+pub(crate) fn api_params(params: &SearchParams) -> [(&str, &str); 3] {
+    let safe_param = if params.safe_search { "1" } else { "-2" };
+    [
+        ("q", &params.query),
+        ("kl", &params.region),
+        ("kp", safe_param),
+    ]
+}
@@ -1,15 +1,81 @@
-use anyhow::Result;
 #[cfg(feature = "cli")]
 use {
-    clap::Parser,
+    anyhow::{Context, Result},
+    chrono::{Local, NaiveDateTime},
+    clap::{CommandFactory, Parser, ValueEnum, error::ErrorKind},
+    duckduckgo::alias::Aliases,
+    duckduckgo::analysis::{ResultStats, TermFrequencies},
+    duckduckgo::backend::{
+        ApiBackend, BackendResults, FallbackBackend, HtmlBackend, ImagesBackend, LiteBackend,
+        MetaBackend, NewsBackend, SearchBackend,
+    },
     duckduckgo::browser::Browser,
-    duckduckgo::cli::{Backend, Cli},
-    duckduckgo::colors::{AnsiColor, AnsiStyle},
-    duckduckgo::response::ResultFormat,
-    duckduckgo::user_agents::get,
-    urlencoding::encode,
+    duckduckgo::checkpoint::Checkpoint,
+    duckduckgo::cli::{
+        AliasCommand, Backend, Cli, Colors, Command, DiffStyle, ExitStatus, GroupBy, Hyperlinks,
+        OutputFormat, PresetsCommand, RelativeDates, UserAgentsCommand,
+    },
+    duckduckgo::colors::{self, AnsiColor, AnsiStyle},
+    duckduckgo::compare::{
+        Comparison, DiffLine, compare_regions, compare_user_agents, compare_variants, diff_results,
+    },
+    duckduckgo::curl::CurlOptions,
+    duckduckgo::date::{DateFormat, Locale},
+    duckduckgo::doctor::EndpointCheck,
+    duckduckgo::download::ImageDownloads,
+    duckduckgo::error::{Error, RetryAfter},
+    duckduckgo::exporter::{Exporter, SearchRecord},
+    duckduckgo::favicon::FaviconCache,
+    duckduckgo::filter::ResultFilters,
+    duckduckgo::har::HarArchive,
+    duckduckgo::launcher::{AlfredOutput, dmenu_line, open_url, rofi_row},
+    duckduckgo::middleware::RequestLogger,
+    duckduckgo::params::SearchParams,
+    duckduckgo::parser::ParseMode,
+    duckduckgo::preset::PresetRegistry,
+    duckduckgo::proxy::ProxyConfig,
+    duckduckgo::ratelimit::RateLimiter,
+    duckduckgo::render::{PlainRenderer, Renderer, display_width, supports_hyperlinks, truncate},
+    duckduckgo::response::{
+        Answer, Definition, ResultFormat, SearchOutcome, SearchPage, SearchResult,
+    },
+    duckduckgo::schedule::{Schedule, ScheduledSearch, SeenResults},
+    duckduckgo::stability::measure_stability_until,
+    duckduckgo::template::{Fields, Template},
+    duckduckgo::throttle::ThrottlePolicy,
+    duckduckgo::timings::Timings,
+    duckduckgo::topic::Topic,
+    duckduckgo::tor::{DEFAULT_TOR_PROXY, TorConfig, TorControl},
+    duckduckgo::user_agents::{self, UserAgentRegistry, UserAgentRotator},
+    duckduckgo::vcr::Cassette,
+    serde::Serialize,
+    std::env,
+    std::ffi::OsString,
+    std::fs,
+    std::io::IsTerminal,
+    std::net::{Ipv4Addr, Ipv6Addr},
+    std::path::{Path, PathBuf},
+    std::time::Duration,
+    tokio_util::sync::CancellationToken,
 };
 
+#[cfg(all(feature = "cli", feature = "metrics"))]
+use duckduckgo::metrics::serve_metrics;
+#[cfg(all(feature = "cli", feature = "server"))]
+use duckduckgo::server::ProxyServer;
+#[cfg(all(feature = "cli", feature = "schema"))]
+use {
+    duckduckgo::cli::SchemaDocument,
+    duckduckgo::plan::PlannedRequest,
+    duckduckgo::research::ResearchBundle,
+    duckduckgo::response::{ImageResult, LiteSearchResult, NewsResult},
+    duckduckgo::stability::StabilityReport,
+    schemars::{JsonSchema, Schema, generate::SchemaSettings},
+};
+
+#[cfg(feature = "self-update")]
+use duckduckgo::update::{CHECK_TIMEOUT, CURRENT_VERSION, UpdateCheck, UpdateConfig, Updater};
+
 /// The main entry point of the DuckDuckGo search CLI application.
 ///
 /// It parses command-line arguments using the `clap` crate, configures a `reqwest::Client` based on
@@ -38,41 +104,464 @@ use {
 ///
 /// # Errors
 /// The function handles errors gracefully and prints out error messages if the DuckDuckGo search
-/// with operators fails, if the query is missing, etc.
+/// with operators fails, if the query is missing, etc. The process exits with one of the
+/// `ExitStatus` codes.
 #[cfg(feature = "cli")]
 #[tokio::main]
-async fn main() -> Result<()> {
-    let args = Cli::parse();
-    let style = AnsiStyle {
-        bold: true,
-        color: Some(AnsiColor::Red),
+async fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(err) => {
+            let _ = err.print();
+            let status = if err.use_stderr() {
+                ExitStatus::Usage
+            } else {
+                ExitStatus::Success
+            };
+            std::process::exit(status.code());
+        }
     };
+    enable_colors(&args);
 
-    let mut client_builder = reqwest::Client::builder();
-    let mut usr_agent = "";
-    if !args.user_agent.is_empty() {
-        if let Some(agent) = get(&args.user_agent[..]) {
-            client_builder = client_builder.user_agent(agent);
-            usr_agent = agent;
-        } else {
+    let interrupt = CancellationToken::new();
+    let status = match run(&args, &interrupt).await {
+        Ok(_) if interrupt.is_cancelled() => ExitStatus::Interrupted,
+        Ok(status) => status,
+        Err(err) => {
+            report_error(&args, &err);
+            ExitStatus::from_error(&err)
+        }
+    };
+    #[cfg(feature = "self-update")]
+    if !matches!(args.command, Some(Command::SelfUpdate { .. })) {
+        notify_update(&args).await;
+    }
+    std::process::exit(status.code());
+}
+
+/// Turns the colors of the output on or off as `--color` says. Windows consoles print escape
+/// codes as is unless their virtual terminal processing is enabled, so `auto` leaves the output
+/// uncolored when it cannot be.
+#[cfg(feature = "cli")]
+fn enable_colors(args: &Cli) {
+    let enabled = match args.color {
+        Colors::On => {
+            anstyle_query::windows::enable_ansi_colors();
+            true
+        }
+        Colors::Off => false,
+        Colors::Auto => anstyle_query::windows::enable_ansi_colors().unwrap_or(true),
+    };
+    colors::set_enabled(enabled);
+}
+
+/// Parses the command-line arguments, replacing the saved search they run, if any, with its
+/// query, adding the operators of the `--preset` to the `--operators`, and making `--plain` the
+/// output format and `--oneline` or `--fields` the template.
+#[cfg(feature = "cli")]
+fn parse_args() -> Result<Cli, clap::Error> {
+    let mut args = expand_saved_search(Cli::try_parse()?)?;
+    if args.plain {
+        args.output = OutputFormat::Plain;
+    }
+    if args.oneline {
+        args.template = Some(Template::oneline());
+    }
+    if let Some(fields) = &args.fields {
+        match args.output {
+            OutputFormat::Json => {}
+            OutputFormat::Text | OutputFormat::Plain => args.template = Some(fields.template()),
+            _ => {
+                return Err(clap::Error::raw(
+                    ErrorKind::ArgumentConflict,
+                    "--fields only applies to the text, plain, and json outputs\n",
+                ));
+            }
+        }
+    }
+    if args.follow && args.backend != Backend::News {
+        return Err(clap::Error::raw(
+            ErrorKind::ArgumentConflict,
+            "--follow only applies to the news backend, use --backend news\n",
+        ));
+    }
+    if args.shared_rate_limit && args.delay == 0 {
+        return Err(clap::Error::raw(
+            ErrorKind::ArgumentConflict,
+            "--shared-rate-limit shares the --delay budget, which --delay 0 disables\n",
+        ));
+    }
+    if args.regions.len() == 1 {
+        return Err(clap::Error::raw(
+            ErrorKind::TooFewValues,
+            "--regions needs at least two regions to compare\n",
+        ));
+    }
+    if args.compare_user_agents.len() == 1 {
+        return Err(clap::Error::raw(
+            ErrorKind::TooFewValues,
+            "--compare-user-agents needs at least two user agents to compare\n",
+        ));
+    }
+    #[cfg(not(feature = "convert"))]
+    if args.image_format.is_some() {
+        return Err(clap::Error::raw(
+            ErrorKind::InvalidValue,
+            "--image-format requires building ddg with the `convert` feature\n",
+        ));
+    }
+    if let Some(name) = &args.preset {
+        args.operators = presets(&args)
+            .and_then(|presets| presets.apply(&args.operators, name))
+            .map_err(|err| clap::Error::raw(ErrorKind::InvalidValue, format!("{:#}\n", err)))?;
+    }
+    Ok(args)
+}
+
+/// Replaces the saved search the command-line arguments run, if any, with its query.
+///
+/// The arguments of the saved search up to the first option fill its template, and the
+/// options after them apply to the search, e.g. `ddg crates serde --limit 3`.
+#[cfg(feature = "cli")]
+fn expand_saved_search(args: Cli) -> Result<Cli, clap::Error> {
+    let Some(Command::Saved(words)) = &args.command else {
+        return Ok(args);
+    };
+    let Some((name, rest)) = words.split_first() else {
+        return Ok(args);
+    };
+
+    let aliases = aliases_path(&args)
+        .and_then(Aliases::load)
+        .map_err(|err| clap::Error::raw(ErrorKind::Io, format!("{:#}\n", err)))?;
+    let command = Cli::command();
+    let split = rest
+        .iter()
+        .position(|word| word == "--" || is_option(&command, word))
+        .unwrap_or(rest.len());
+    let options = match rest.get(split).map(String::as_str) {
+        Some("--") => &rest[split + 1..],
+        _ => &rest[split..],
+    };
+    let Some(query) = aliases.expand(name, &rest[..split]) else {
+        return Err(Cli::command().error(
+            ErrorKind::InvalidSubcommand,
+            format!("unrecognized subcommand or saved search '{}'", name),
+        ));
+    };
+
+    let argv: Vec<OsString> = env::args_os().collect();
+    let prefix = &argv[..argv.len() - words.len()];
+    let args = Cli::try_parse_from(
+        prefix
+            .iter()
+            .cloned()
+            .chain([OsString::from("--query"), OsString::from(query)])
+            .chain(options.iter().map(OsString::from)),
+    )?;
+    if let Some(Command::Saved(words)) = &args.command {
+        return Err(Cli::command().error(
+            ErrorKind::UnknownArgument,
+            format!(
+                "unexpected argument '{}' after the options of a saved search",
+                words[0]
+            ),
+        ));
+    }
+    Ok(args)
+}
+
+/// Returns whether a word after a saved search is an option of the command line, rather than a
+/// word of the query like `-tutorial`.
+#[cfg(feature = "cli")]
+fn is_option(command: &clap::Command, word: &str) -> bool {
+    if let Some(long) = word.strip_prefix("--") {
+        let name = long.split_once('=').map_or(long, |(name, _)| name);
+        return command.get_arguments().any(|arg| {
+            arg.get_long() == Some(name)
+                || arg
+                    .get_visible_aliases()
+                    .is_some_and(|aliases| aliases.contains(&name))
+        });
+    }
+    let Some(shorts) = word.strip_prefix('-') else {
+        return false;
+    };
+    let short = |c: char| {
+        command
+            .get_arguments()
+            .find(|arg| arg.get_short() == Some(c))
+    };
+    let mut chars = shorts.chars();
+    let Some(arg) = chars.next().and_then(short) else {
+        return false;
+    };
+    // `-l5` passes a value, `-sv` groups flags, anything else is a word of the query.
+    shorts.len() == 1 || arg.get_action().takes_values() || chars.all(|c| short(c).is_some())
+}
+
+/// Returns the built-in presets and the presets of the `--presets-file`, or of the default
+/// presets file if it exists.
+#[cfg(feature = "cli")]
+fn presets(args: &Cli) -> Result<PresetRegistry> {
+    let mut presets = PresetRegistry::default();
+    match &args.presets_file {
+        Some(path) => {
+            presets.load_file(path)?;
+        }
+        None => {
+            if let Some(path) = PresetRegistry::default_path().filter(|path| path.exists()) {
+                presets.load_file(path)?;
+            }
+        }
+    }
+    Ok(presets)
+}
+
+/// Returns the file of the saved searches: `--aliases-file`, or the default one.
+#[cfg(feature = "cli")]
+fn aliases_path(args: &Cli) -> Result<PathBuf> {
+    match &args.aliases_file {
+        Some(path) => Ok(PathBuf::from(path)),
+        None => Aliases::default_path()
+            .context("No config directory for the saved searches, set --aliases-file"),
+    }
+}
+
+/// Returns the proxies of the run: the local Tor daemon with `--tor`, otherwise `--proxy`,
+/// `--http-proxy`, and `--https-proxy`, with the proxy environment variables unless
+/// `--no-env-proxy` is passed.
+#[cfg(feature = "cli")]
+fn proxy_config(args: &Cli) -> ProxyConfig {
+    if args.tor {
+        return ProxyConfig::new().all(DEFAULT_TOR_PROXY);
+    }
+    let proxies = ProxyConfig {
+        all: Some(args.proxy.clone()).filter(|p| !p.is_empty()),
+        http: args.http_proxy.clone(),
+        https: args.https_proxy.clone(),
+        no_proxy: args.no_proxy.clone(),
+    };
+    if args.no_env_proxy {
+        proxies
+    } else {
+        proxies.with_env()
+    }
+}
+
+/// Runs the command described by the command-line arguments.
+///
+/// Searches stop paginating once `interrupt` is cancelled, on the first Ctrl-C.
+#[cfg(feature = "cli")]
+async fn run(args: &Cli, interrupt: &CancellationToken) -> Result<ExitStatus> {
+    if let Some(path) = &args.user_agents_file {
+        UserAgentRegistry::global()
+            .write()
+            .unwrap()
+            .load_file(path)?;
+    }
+
+    if let Some(Command::UserAgents { action }) = &args.command {
+        match action {
+            UserAgentsCommand::List => {
+                let registry = UserAgentRegistry::global().read().unwrap();
+                for name in registry.names() {
+                    println!("{}", name);
+                }
+            }
+        }
+        return Ok(ExitStatus::Success);
+    }
+
+    if let Some(Command::Presets { action }) = &args.command {
+        match action {
+            PresetsCommand::List => {
+                for (name, operators) in presets(args)?.iter() {
+                    println!("{} = {}", name, operators);
+                }
+            }
+        }
+        return Ok(ExitStatus::Success);
+    }
+
+    if let Some(Command::Alias { action }) = &args.command {
+        let path = aliases_path(args)?;
+        let mut aliases = Aliases::load(&path)?;
+        match action {
+            AliasCommand::Add { name, template } => {
+                if Cli::command().find_subcommand(name).is_some() {
+                    print_error(
+                        args,
+                        ExitStatus::Usage,
+                        &format!("'{}' is a built-in command", name),
+                    );
+                    return Ok(ExitStatus::Usage);
+                }
+                if let Err(err) = aliases.add(name, template) {
+                    return Ok(usage_error(args, &err));
+                }
+                aliases.save(&path)?;
+            }
+            AliasCommand::Remove { name } => {
+                if !aliases.remove(name) {
+                    print_error(
+                        args,
+                        ExitStatus::Usage,
+                        &format!("No saved search named '{}'", name),
+                    );
+                    return Ok(ExitStatus::Usage);
+                }
+                aliases.save(&path)?;
+            }
+            AliasCommand::List => {
+                for (name, template) in aliases.iter() {
+                    println!("{} = {}", name, template);
+                }
+            }
+        }
+        return Ok(ExitStatus::Success);
+    }
+
+    #[cfg(feature = "schema")]
+    if let Some(Command::Schema { document }) = &args.command {
+        print_json(&schema(*document))?;
+        return Ok(ExitStatus::Success);
+    }
+
+    let mut browser_builder = Browser::builder();
+    let usr_agent = match user_agents::resolve(&args.user_agent) {
+        Ok(agent) => agent,
+        Err(err) => {
+            print_error(args, ExitStatus::Usage, &err.to_string());
+            return Ok(ExitStatus::Usage);
+        }
+    };
+    browser_builder = browser_builder.user_agent(&usr_agent);
+    if let Some(rotation) = args.rotate_user_agent {
+        browser_builder =
+            browser_builder.user_agent_rotator(UserAgentRotator::new(rotation.into()));
+    }
+    if args.verbose {
+        browser_builder = browser_builder.with_middleware(RequestLogger);
+    }
+    if args.timings {
+        browser_builder = browser_builder.timings(true);
+    }
+    #[cfg(feature = "metrics")]
+    if let Some(Command::Daemon {
+        metrics_listen: Some(_),
+        ..
+    }) = &args.command
+    {
+        browser_builder = browser_builder.metrics(true);
+    }
+    if args.delay > 0 {
+        let mut limiter = RateLimiter::new(Duration::from_millis(args.delay));
+        if args.shared_rate_limit {
+            limiter = limiter.shared(RateLimiter::default_shared_path());
+        }
+        browser_builder = browser_builder.rate_limiter(limiter);
+    }
+    if args.page_delay > 0 {
+        let mut throttle = ThrottlePolicy::new(Duration::from_millis(args.page_delay));
+        if let Some(jitter) = args.page_jitter {
+            throttle = throttle.jitter(Duration::from_millis(jitter));
+        }
+        browser_builder = browser_builder.throttle(throttle);
+    }
+    if args.cookie {
+        browser_builder = browser_builder.cookie_store(true);
+    }
+    if let Some(path) = &args.cookie_file {
+        browser_builder = browser_builder.cookie_path(path)?;
+    }
+    let proxies = proxy_config(args);
+    if !args.tor {
+        browser_builder = match browser_builder.proxies(&proxies) {
+            Ok(builder) => builder,
+            Err(err) => return Ok(usage_error(args, &err)),
+        };
+    }
+    for path in &args.ca_cert {
+        browser_builder = browser_builder.ca_cert(path)?;
+    }
+    if let Some(version) = args.min_tls {
+        browser_builder = browser_builder.min_tls_version(version.into());
+    }
+    if let Some(version) = args.max_tls {
+        browser_builder = browser_builder.max_tls_version(version.into());
+    }
+    if args.insecure {
+        if !args.quiet {
             eprintln!(
-                "{}Error: Invalid user agent selected!{}",
-                style.escape_code(),
-                AnsiStyle::reset_code()
+                "{}",
+                paint(
+                    args,
+                    &error_style(),
+                    "Warning: TLS certificate validation is disabled!"
+                )
             );
-            std::process::exit(1);
         }
+        browser_builder = browser_builder.danger_accept_invalid_certs(true);
     }
-    if args.cookie {
-        client_builder = client_builder.cookie_store(true);
+    if args.ipv4 {
+        browser_builder = browser_builder.local_address(Ipv4Addr::UNSPECIFIED.into());
+    } else if args.ipv6 {
+        browser_builder = browser_builder.local_address(Ipv6Addr::UNSPECIFIED.into());
+    } else if let Some(interface) = &args.interface {
+        browser_builder = match browser_builder.interface(interface) {
+            Ok(builder) => builder,
+            Err(err) => return Ok(usage_error(args, &err)),
+        };
+    }
+    if args.tor {
+        let mut tor = TorConfig::default();
+        if let Some(addr) = &args.tor_control {
+            let mut control = TorControl::new(addr);
+            if let Some(password) = &args.tor_password {
+                control = control.password(password);
+            }
+            tor = tor.control(control);
+        }
+        browser_builder = browser_builder.tor(tor);
+    }
+    if let Some(dir) = &args.record {
+        browser_builder = browser_builder.cassette(Cassette::record(dir));
+    } else if let Some(dir) = &args.replay {
+        browser_builder = browser_builder.cassette(Cassette::replay(dir));
+    }
+    if let Some(path) = &args.har {
+        browser_builder = browser_builder.har(HarArchive::capture(path));
+    } else if let Some(path) = &args.replay_har {
+        browser_builder = browser_builder.har(HarArchive::replay(path)?);
     }
-    if !args.proxy.is_empty() {
-        let proxy = reqwest::Proxy::all(&args.proxy)?;
-        client_builder = client_builder.proxy(proxy);
+    if args.strict {
+        browser_builder = browser_builder.parse_mode(ParseMode::Strict);
     }
+    if let Some(dir) = &args.dump_raw {
+        browser_builder = browser_builder.dump_raw(dir);
+    }
+    let browser = browser_builder.cancel_on(interrupt.clone()).build()?;
+    let _cookies = SaveCookies {
+        args,
+        browser: &browser,
+    };
+    handle_interrupts(args, interrupt.clone());
 
-    let client = client_builder.build()?;
-    let browser = Browser::new(client);
+    if args.tor && !args.dry_run && !args.emit_curl {
+        let status = browser.check_tor().await?;
+        if !status.is_tor {
+            print_error(
+                args,
+                ExitStatus::Network,
+                &format!("Traffic is not routed through Tor (exit IP {})!", status.ip),
+            );
+            return Ok(ExitStatus::Network);
+        }
+        if args.verbose {
+            eprintln!("Connected through Tor (exit IP {}).", status.ip);
+        }
+    }
 
     let result_format = if args.format {
         ResultFormat::Detailed
@@ -81,64 +570,2519 @@ async fn main() -> Result<()> {
     };
 
     let limit = Some(args.limit);
+    let mut params = SearchParams::new(with_operators(args, &args.query))
+        .safe_search(args.safe)
+        .limit(args.limit)
+        .offset(args.offset)
+        .user_agent(usr_agent);
+    for source in &args.source {
+        params = params.source(source);
+    }
 
-    if args.query.is_empty() {
-        eprintln!(
-            "{}Error: Query is required!{}",
-            style.escape_code(),
-            AnsiStyle::reset_code()
+    if args.dry_run || args.emit_curl {
+        let plan = match &args.command {
+            Some(
+                Command::Answer { query }
+                | Command::Define { word: query }
+                | Command::Convert { expression: query }
+                | Command::Calc { expression: query },
+            ) => {
+                vec![browser.plan_instant_answer(query)?]
+            }
+            Some(
+                Command::UserAgents { .. }
+                | Command::Presets { .. }
+                | Command::Alias { .. }
+                | Command::Saved(_),
+            ) => {
+                unreachable!("handled before building the browser")
+            }
+            #[cfg(feature = "schema")]
+            Some(Command::Schema { .. }) => unreachable!("handled before building the browser"),
+            Some(Command::Daemon { schedule, .. }) => {
+                let mut plan = Vec::new();
+                let schedule = match load_schedule(schedule.as_deref()) {
+                    Ok(schedule) => schedule,
+                    Err(err) => return Ok(usage_error(args, &err)),
+                };
+                for search in &schedule.searches {
+                    let (backend, params) = match scheduled_search(&params, search) {
+                        Ok(search) => search,
+                        Err(err) => return Ok(usage_error(args, &err)),
+                    };
+                    plan.extend(backend.build(&browser).plan(&params)?);
+                }
+                plan
+            }
+            Some(Command::BenchStability { query, runs, .. }) => {
+                let params = SearchParams {
+                    query: with_operators(args, query),
+                    ..params.clone()
+                };
+                let plan = args.backend.build(&browser).plan(&params)?;
+                (0..*runs).flat_map(|_| plan.clone()).collect()
+            }
+            Some(Command::Compare { query, query2, .. }) => {
+                let backend = args.backend.build(&browser);
+                let mut plan = Vec::new();
+                for query in [query, query2] {
+                    let params = SearchParams {
+                        query: with_operators(args, query),
+                        ..params.clone()
+                    };
+                    plan.extend(backend.plan(&params)?);
+                }
+                plan
+            }
+            Some(Command::Analyze { query, .. }) => {
+                let params = SearchParams {
+                    query: with_operators(args, query),
+                    ..params.clone()
+                };
+                args.backend.build(&browser).plan(&params)?
+            }
+            #[cfg(feature = "server")]
+            Some(Command::Proxyd { .. }) => {
+                print_error(
+                    args,
+                    ExitStatus::Usage,
+                    "proxyd searches on demand, it has no requests to plan",
+                );
+                return Ok(ExitStatus::Usage);
+            }
+            Some(Command::Doctor) => {
+                print_error(
+                    args,
+                    ExitStatus::Usage,
+                    "doctor probes the endpoints, it cannot be dry-run",
+                );
+                return Ok(ExitStatus::Usage);
+            }
+            #[cfg(feature = "self-update")]
+            Some(Command::SelfUpdate { .. }) => {
+                print_error(args, ExitStatus::Usage, "self-update cannot be dry-run");
+                return Ok(ExitStatus::Usage);
+            }
+            None if args.query.is_empty() => {
+                print_error(args, ExitStatus::Usage, "Query is required!");
+                return Ok(ExitStatus::Usage);
+            }
+            None if args.answer_only => vec![browser.plan_instant_answer(&args.query)?],
+            None if !args.regions.is_empty() => {
+                let backend = args.backend.build(&browser);
+                let mut plan = Vec::new();
+                for region in &args.regions {
+                    plan.extend(backend.plan(&params.clone().region(region))?);
+                }
+                plan
+            }
+            None if !args.compare_user_agents.is_empty() => {
+                let backend = args.backend.build(&browser);
+                let mut plan = Vec::new();
+                for name in &args.compare_user_agents {
+                    let agent = match user_agents::resolve(name) {
+                        Ok(agent) => agent,
+                        Err(err) => return Ok(usage_error(args, &err)),
+                    };
+                    plan.extend(backend.plan(&params.clone().user_agent(agent))?);
+                }
+                plan
+            }
+            None => args.backend.build(&browser).plan(&params)?,
+        };
+        if args.emit_curl {
+            let curl = CurlOptions {
+                proxies,
+                ca_certs: args.ca_cert.clone(),
+                insecure: args.insecure,
+                ipv4: args.ipv4,
+                ipv6: args.ipv6,
+                interface: args.interface.clone(),
+            };
+            let commands: Vec<String> = plan.iter().map(|request| curl.command(request)).collect();
+            println!("{}", commands.join("\n\n"));
+        } else if args.output == OutputFormat::Json {
+            print_json(&plan)?;
+        } else {
+            for request in &plan {
+                println!("{}", request);
+            }
+        }
+        return Ok(ExitStatus::Success);
+    }
+
+    if let Some(Command::Daemon {
+        schedule,
+        state_dir,
+        ..
+    }) = &args.command
+    {
+        let schedule = match load_schedule(schedule.as_deref()) {
+            Ok(schedule) => schedule,
+            Err(err) => return Ok(usage_error(args, &err)),
+        };
+        let state_dir = match state_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => SeenResults::default_dir()
+                .context("No state directory for the daemon, set --state-dir")?,
+        };
+        let daemon = run_daemon(args, &browser, &params, &schedule, &state_dir);
+        #[cfg(feature = "metrics")]
+        if let (
+            Some(Command::Daemon {
+                metrics_listen: Some(addr),
+                ..
+            }),
+            Some(metrics),
+        ) = (&args.command, &browser.metrics)
+        {
+            print_notice(args, &format!("Serving metrics on http://{}/metrics", addr));
+            return tokio::select! {
+                status = daemon => status,
+                served = serve_metrics(metrics.clone(), *addr) => served.map(|_| ExitStatus::Success),
+            };
+        }
+        return daemon.await;
+    }
+
+    if let Some(Command::BenchStability {
+        query,
+        runs,
+        interval,
+    }) = &args.command
+    {
+        let params = SearchParams {
+            query: with_operators(args, query),
+            ..params.clone()
+        };
+        let found = bench_stability(args, &browser, &params, *runs, *interval).await?;
+        print_footers(args, &browser);
+        return Ok(exit_status(args, found));
+    }
+
+    if let Some(Command::Analyze { query, terms, top }) = &args.command {
+        let params = SearchParams {
+            query: with_operators(args, query),
+            ..params.clone()
+        };
+        let terms = terms.then_some(*top);
+        let found = analyze(args, &browser, &params, terms).await?;
+        print_footers(args, &browser);
+        return Ok(exit_status(args, found));
+    }
+
+    if let Some(Command::Compare {
+        query,
+        query2,
+        diff_style,
+    }) = &args.command
+    {
+        let found =
+            compare_search_queries(args, &browser, &params, [query, query2], *diff_style).await?;
+        print_footers(args, &browser);
+        return Ok(exit_status(args, found));
+    }
+
+    #[cfg(feature = "server")]
+    if let Some(Command::Proxyd {
+        listen,
+        cache_ttl,
+        cache_size,
+    }) = &args.command
+    {
+        print_notice(
+            args,
+            &format!("Serving searches on http://{}/search", listen),
         );
-        std::process::exit(1);
+        let server = ProxyServer::new(browser.clone())
+            .ttl(Duration::from_secs(*cache_ttl))
+            .capacity(*cache_size);
+        return match unless_interrupted(&browser, server.serve(*listen)).await {
+            Some(served) => served.map(|_| ExitStatus::Success),
+            None => Ok(ExitStatus::Interrupted),
+        };
+    }
+
+    if let Some(Command::Doctor) = &args.command {
+        let checks = browser.check_endpoints(&params.user_agent).await;
+        if args.output == OutputFormat::Json {
+            print_json(&checks)?;
+        } else {
+            for check in &checks {
+                let (mark, color) = if check.is_healthy() {
+                    ("✔", AnsiColor::Green)
+                } else {
+                    ("✘", AnsiColor::Red)
+                };
+                let style = AnsiStyle {
+                    bold: true,
+                    color: Some(color),
+                };
+                println!("{} {}", paint(args, &style, mark), check);
+            }
+        }
+        print_footers(args, &browser);
+        return Ok(if checks.iter().any(|check| check.bot_detection) {
+            ExitStatus::RateLimited
+        } else if checks.iter().all(EndpointCheck::is_healthy) {
+            ExitStatus::Success
+        } else {
+            ExitStatus::Network
+        });
+    }
+
+    #[cfg(feature = "self-update")]
+    if let Some(Command::SelfUpdate {
+        check,
+        skip_signature,
+    }) = &args.command
+    {
+        return self_update(args, &browser, *check, *skip_signature).await;
+    }
+
+    if args.follow && args.command.is_none() {
+        return run_follow(args, &browser, &params).await;
+    }
+
+    if let Some(command) = &args.command {
+        let found = match command {
+            Command::Answer { query } if args.output == OutputFormat::Json => {
+                let answer = browser.answer(query).await?;
+                print_json(&AnswerOutput {
+                    query,
+                    answer: answer.as_ref(),
+                })?;
+                answer.is_some()
+            }
+            Command::Convert { expression } | Command::Calc { expression } => {
+                let answer = browser.answer(expression).await?;
+                if args.output == OutputFormat::Json {
+                    print_json(&AnswerOutput {
+                        query: expression,
+                        answer: answer.as_ref(),
+                    })?;
+                } else if let Some(answer) = &answer {
+                    if args.quiet {
+                        println!("{}", answer.value());
+                    } else {
+                        println!("{} = {}", expression.trim(), answer.value());
+                    }
+                } else {
+                    print_notice(args, &format!("No result found for '{}'.", expression));
+                }
+                answer.is_some()
+            }
+            Command::Define { word } if args.output == OutputFormat::Json => {
+                let definition = browser.definition(word).await?;
+                print_json(&DefinitionOutput {
+                    query: word,
+                    definition: definition.as_ref(),
+                })?;
+                definition.is_some()
+            }
+            Command::Answer { query } => match browser.answer(query).await? {
+                Some(answer) => {
+                    println!("{}", answer.text);
+                    true
+                }
+                None => {
+                    print_notice(args, &format!("No instant answer found for '{}'.", query));
+                    false
+                }
+            },
+            Command::Define { word } => match browser.definition(word).await? {
+                Some(definition) if args.quiet => {
+                    println!("{}", definition.text);
+                    true
+                }
+                Some(definition) => {
+                    let heading = AnsiStyle {
+                        bold: true,
+                        color: Some(AnsiColor::Gold),
+                    };
+                    match &definition.part_of_speech {
+                        Some(part) => println!("{} ({})", paint(args, &heading, word), part),
+                        None => println!("{}", paint(args, &heading, word)),
+                    }
+                    println!("{}", definition.text);
+                    if let Some(source) = definition.source {
+                        println!("Source: {}", source);
+                    }
+                    if let Some(url) = definition.url {
+                        println!("URL: {}", url);
+                    }
+                    true
+                }
+                None => {
+                    print_notice(args, &format!("No definition found for '{}'.", word));
+                    false
+                }
+            },
+            Command::UserAgents { .. }
+            | Command::Presets { .. }
+            | Command::Alias { .. }
+            | Command::Saved(_) => {
+                unreachable!("handled before building the browser")
+            }
+            #[cfg(feature = "schema")]
+            Command::Schema { .. } => unreachable!("handled before building the browser"),
+            Command::Daemon { .. }
+            | Command::BenchStability { .. }
+            | Command::Analyze { .. }
+            | Command::Compare { .. }
+            | Command::Doctor => {
+                unreachable!("handled before running the commands")
+            }
+            #[cfg(feature = "server")]
+            Command::Proxyd { .. } => unreachable!("handled before running the commands"),
+            #[cfg(feature = "self-update")]
+            Command::SelfUpdate { .. } => unreachable!("handled before running the commands"),
+        };
+        print_footers(args, &browser);
+        if !found && matches!(command, Command::Define { .. }) {
+            return Ok(ExitStatus::NoResults);
+        }
+        return Ok(exit_status(args, found));
+    }
+
+    let batch = match (&args.queries_file, &args.resume) {
+        (Some(path), _) => {
+            let queries = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read queries file {}", path))?
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect();
+            let checkpoint_path = match &args.checkpoint {
+                Some(checkpoint_path) => checkpoint_path.clone(),
+                None => Path::new(path)
+                    .with_extension("checkpoint")
+                    .display()
+                    .to_string(),
+            };
+            Some((Checkpoint::new(queries), checkpoint_path))
+        }
+        (None, Some(path)) => Some((Checkpoint::load(path)?, path.clone())),
+        (None, None) => None,
+    };
+    if let Some((checkpoint, path)) = batch {
+        if checkpoint.is_done() {
+            print_notice(args, "Every query of the batch is already completed.");
+        }
+        let found = run_batch(args, &browser, &params, checkpoint, &path).await?;
+        print_footers(args, &browser);
+        return Ok(exit_status(args, found));
+    }
+
+    if args.query.is_empty() {
+        print_error(args, ExitStatus::Usage, "Query is required!");
+        return Ok(ExitStatus::Usage);
+    }
+
+    if args.answer_only {
+        let answer = browser.concise_answer(&params.query).await?;
+        if args.output == OutputFormat::Json {
+            print_json(&AnswerOutput {
+                query: &params.query,
+                answer: answer.as_ref(),
+            })?;
+        } else if let Some(answer) = &answer {
+            println!("{}", answer.text);
+        } else {
+            print_notice(
+                args,
+                &format!("No instant answer found for '{}'.", params.query),
+            );
+        }
+        print_footers(args, &browser);
+        return Ok(exit_status(args, answer.is_some()));
+    }
+
+    if !args.regions.is_empty() {
+        let found = compare_search_regions(args, &browser, &params).await?;
+        print_footers(args, &browser);
+        return Ok(exit_status(args, found));
+    }
+
+    if !args.compare_user_agents.is_empty() {
+        let found = compare_search_user_agents(args, &browser, &params).await?;
+        print_footers(args, &browser);
+        return Ok(exit_status(args, found));
+    }
+
+    if args.lucky {
+        let found = run_lucky(args, &browser, &params).await?;
+        print_footers(args, &browser);
+        return Ok(exit_status(args, found));
     }
 
-    match args.backend {
+    if args.output == OutputFormat::RagJson {
+        let mut bundle = browser.research_with(&params).await?;
+        if args.deterministic {
+            bundle.fetched_at = None;
+        }
+        print_json(&bundle)?;
+        print_footers(args, &browser);
+        return Ok(exit_status(args, !bundle.results.is_empty()));
+    }
+
+    let mut exporter = args.export.as_ref().map(Exporter::open).transpose()?;
+    let found = match args.backend {
+        Backend::All => {
+            let filters = result_filters(args);
+            let all = MetaBackend::new(vec![
+                filters.wrap(Box::new(HtmlBackend::new(&browser))),
+                filters.wrap(Box::new(ImagesBackend::new(&browser))),
+                filters.wrap(Box::new(NewsBackend::new(&browser))),
+            ]);
+            let mut sections = all.search_all(&params).await;
+            for section in &mut sections {
+                if let Ok(page) = &mut section.page {
+                    annotate_results(args, &browser, &mut page.results).await;
+                    if let Some(exporter) = &mut exporter {
+                        let search = SearchRecord::new(&params, &section.backend);
+                        exporter.export(&search, &page.results)?;
+                    }
+                    download_images(args, &browser, &params.query, &page.results).await?;
+                }
+            }
+            output_sections(args, &browser, &params, sections)?
+        }
+        Backend::Auto
+            if args.output == OutputFormat::Text && args.template.is_none() && !args.print0 =>
+        {
+            // The Instant Answer API comes first in the chain, printed in its own format.
+            let safe_param = if args.safe { "1" } else { "-2" };
+            let response = browser
+                .fetch_response(&[
+                    ("q", &params.query),
+                    ("kp", safe_param),
+                    ("no_redirect", "1"),
+                ])
+                .await;
+            let response = response.map(|mut response| {
+                let skipped = args.offset.min(response.related_topics.len());
+                response.related_topics.drain(..skipped);
+                response.into_outcome()
+            });
+            match response {
+                Ok(SearchOutcome::Redirect(url)) => {
+                    // A `!bang` query, which only redirects to the search of another site.
+                    print_served_by(args, "api");
+                    println!("{}", url);
+                    1
+                }
+                // Fewer topics than --min-results fall through to the next backends.
+                Ok(SearchOutcome::Response(response))
+                    if response.iter_topics().filter(|t| is_printable(t)).count()
+                        >= args.min_results.unwrap_or(1) =>
+                {
+                    print_served_by(args, "api");
+                    let found = response.iter_topics().filter(|t| is_printable(t)).count();
+                    if args.quiet {
+                        let topics = response.iter_topics().filter(|t| is_printable(t));
+                        for topic in topics.take(args.limit) {
+                            println!("{}", topic.first_url.as_deref().unwrap_or_default());
+                        }
+                    } else {
+                        match result_format {
+                            ResultFormat::List => browser.print_results_list(*response, limit),
+                            ResultFormat::Detailed => {
+                                browser.print_results_detailed(*response, limit)
+                            }
+                        }
+                    }
+                    found.min(args.limit)
+                }
+                response => {
+                    if let Err(err) = response
+                        && args.verbose
+                    {
+                        eprintln!("The api backend failed: {:#}", err);
+                    }
+                    let filters = result_filters(args);
+                    let fallback = FallbackBackend::new(vec![
+                        filters.wrap(Box::new(HtmlBackend::new(&browser))),
+                        filters.wrap(Box::new(LiteBackend::new(&browser))),
+                    ])
+                    .min_results(args.min_results.unwrap_or(1));
+                    let (backend, page) = fallback.search_fallback(&params).await?;
+                    print_served_by(args, backend.name());
+                    output_page(args, &browser, backend, &params, page, exporter.as_mut()).await?
+                }
+            }
+        }
         Backend::Auto => {
-            if !args.operators.is_empty() {
-                browser
-                    .search_operators(
-                        &encode(&args.query),
-                        &encode(&args.operators),
-                        args.safe,
-                        result_format,
-                        limit,
-                    )
-                    .await?;
-            } else {
-                browser
-                    .search(&encode(&args.query), args.safe, result_format, limit)
-                    .await?;
+            let filters = result_filters(args);
+            let fallback = FallbackBackend::new(vec![
+                filters.wrap(Box::new(ApiBackend::new(&browser))),
+                filters.wrap(Box::new(HtmlBackend::new(&browser))),
+                filters.wrap(Box::new(LiteBackend::new(&browser))),
+            ])
+            .min_results(args.min_results.unwrap_or(1));
+            let (backend, page) = fallback.search_fallback(&params).await?;
+            if args.output != OutputFormat::Json {
+                print_served_by(args, backend.name());
             }
+            output_page(args, &browser, backend, &params, page, exporter.as_mut()).await?
+        }
+        _ => {
+            let backend = result_filters(args).wrap(args.backend.build(&browser));
+            let page = backend.search_page(&params).await?;
+            output_page(
+                args,
+                &browser,
+                backend.as_ref(),
+                &params,
+                page,
+                exporter.as_mut(),
+            )
+            .await?
+        }
+    };
+    if let Some(exporter) = exporter {
+        exporter.finish()?;
+    }
+
+    print_footers(args, &browser);
+    Ok(count_exit_status(args, found))
+}
+
+/// Returns the filters of the results set on the command line.
+#[cfg(feature = "cli")]
+fn result_filters(args: &Cli) -> ResultFilters {
+    let mut filters = ResultFilters::new();
+    if let Some(n) = args.max_per_domain {
+        filters = filters.max_per_domain(n);
+    }
+    if let Some(similarity) = args.collapse_duplicates {
+        filters = filters.collapse_duplicates(similarity);
+    }
+    if let Some(width) = args.min_width {
+        filters = filters.min_width(width);
+    }
+    if let Some(height) = args.min_height {
+        filters = filters.min_height(height);
+    }
+    if let Some(aspect) = args.aspect {
+        filters = filters.aspect(aspect);
+    }
+    filters
+}
+
+/// Appends the `--operators` to a query.
+#[cfg(feature = "cli")]
+fn with_operators(args: &Cli, query: &str) -> String {
+    if args.operators.is_empty() {
+        query.to_string()
+    } else {
+        format!("{} {}", query, args.operators)
+    }
+}
+
+/// Cancels `interrupt` on the first Ctrl-C, so that the search stops paginating and the results
+/// fetched so far are printed, exported, and checkpointed as usual, and exits on the second.
+///
+/// `proxyd`, `daemon`, and `--follow` stop serving or polling instead.
+#[cfg(feature = "cli")]
+fn handle_interrupts(args: &Cli, interrupt: CancellationToken) {
+    let message = match &args.command {
+        #[cfg(feature = "server")]
+        Some(Command::Proxyd { .. }) => "Interrupted, shutting down (press Ctrl-C again to quit).",
+        Some(Command::Daemon { .. }) => "Interrupted, shutting down (press Ctrl-C again to quit).",
+        None if args.follow => "Interrupted, shutting down (press Ctrl-C again to quit).",
+        _ => "Interrupted, printing the results fetched so far (press Ctrl-C again to quit).",
+    };
+    let notice = (!args.quiet).then(|| paint(args, &notice_style(), message));
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_err() {
+            return;
         }
-        Backend::Lite => {
-            let results = browser
-                .lite_search(&args.query, "wt-wt", limit, usr_agent)
-                .await?;
-            for r in results {
-                println!("{}\n{}\n{}", r.title, r.url, r.snippet);
+        // Printed first, as the run may exit as soon as it is cancelled.
+        if let Some(notice) = notice {
+            eprintln!("{}", notice);
+        }
+        interrupt.cancel();
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(ExitStatus::Interrupted.code());
+        }
+    });
+}
+
+/// Waits for `future`, or returns `None` as soon as the run is interrupted.
+#[cfg(feature = "cli")]
+async fn unless_interrupted<F: Future>(browser: &Browser, future: F) -> Option<F::Output> {
+    match &browser.cancel {
+        Some(cancel) => cancel.run_until_cancelled(future).await,
+        None => Some(future.await),
+    }
+}
+
+/// Runs the queries of a batch left to run in the checkpoint, saving it to `path` after each
+/// query, and after each page of image and news searches.
+///
+/// An interrupted batch stops before its next query or page, leaving the interrupted query to
+/// `--resume`.
+///
+/// Returns whether any query found results.
+#[cfg(feature = "cli")]
+async fn run_batch(
+    args: &Cli,
+    browser: &Browser,
+    params: &SearchParams,
+    mut checkpoint: Checkpoint,
+    path: &str,
+) -> Result<bool> {
+    let mut exporter = args.export.as_ref().map(Exporter::open).transpose()?;
+    let mut found = false;
+    for query in checkpoint.remaining().to_vec() {
+        let params = SearchParams {
+            query: with_operators(args, &query),
+            ..params.clone()
+        };
+        let searched = if browser.is_cancelled() {
+            Ok(None)
+        } else {
+            search_batch_query(
+                args,
+                browser,
+                &params,
+                &query,
+                &mut checkpoint,
+                path,
+                exporter.as_mut(),
+            )
+            .await
+        };
+        match searched {
+            Ok(Some(query_found)) => found |= query_found,
+            Ok(None) => {
+                print_notice(
+                    args,
+                    &format!(
+                        "The batch was interrupted at '{}'; resume it with --resume {}",
+                        query, path
+                    ),
+                );
+                break;
             }
+            Err(err) => {
+                print_notice(
+                    args,
+                    &format!(
+                        "The batch stopped at '{}'; resume it with --resume {}",
+                        query, path
+                    ),
+                );
+                return Err(err);
+            }
+        }
+        checkpoint.complete(&query);
+        checkpoint.save(path)?;
+    }
+    if let Some(exporter) = exporter {
+        exporter.finish()?;
+    }
+    Ok(found)
+}
+
+/// Searches and prints the results of a query of a batch.
+///
+/// Image and news searches are fetched page by page, starting from the cursor saved in the
+/// checkpoint if the query was interrupted, and the checkpoint is saved after each page.
+///
+/// Returns whether the query found results, or `None` if the search was interrupted before
+/// its last page.
+#[cfg(feature = "cli")]
+async fn search_batch_query(
+    args: &Cli,
+    browser: &Browser,
+    params: &SearchParams,
+    query: &str,
+    checkpoint: &mut Checkpoint,
+    path: &str,
+    mut exporter: Option<&mut Exporter>,
+) -> Result<Option<bool>> {
+    if !matches!(args.backend, Backend::Images | Backend::News) {
+        let backend = result_filters(args).wrap(args.backend.build(browser));
+        let page = backend.search_page(params).await?;
+        return output_batch_page(args, browser, backend.name(), params, page, true, exporter)
+            .await
+            .map(Some);
+    }
+
+    let (mut cursor, mut fetched) = match checkpoint.resume_point(query) {
+        Some((cursor, fetched)) => (Some(cursor), fetched),
+        None => (None, 0),
+    };
+    let end = params
+        .limit
+        .map_or(usize::MAX, |limit| params.offset.saturating_add(limit));
+    let mut found = false;
+    let mut first = true;
+    while fetched < end {
+        if browser.is_cancelled() {
+            return Ok(None);
         }
-        Backend::Images => {
-            let results = browser
-                .images(&args.query, "wt-wt", args.safe, limit, usr_agent)
-                .await?;
-            for r in results {
-                println!("{}\n{}\n{}", r.title, r.url, r.image);
+        let (items, next, warnings): (Vec<SearchResult>, _, _) = if args.backend == Backend::Images
+        {
+            let page = browser.images_page(params, cursor.as_ref()).await?;
+            let items = page.items.into_iter().map(SearchResult::Image).collect();
+            (items, page.next, page.warnings)
+        } else {
+            let page = browser.news_page(params, cursor.as_ref()).await?;
+            let items = page.items.into_iter().map(SearchResult::News).collect();
+            (items, page.next, page.warnings)
+        };
+        if args.verbose {
+            for warning in warnings {
+                eprintln!("Skipped result #{}: {}", warning.rank, warning.reason);
             }
         }
-        Backend::News => {
-            let results = browser
-                .news(&args.query, "wt-wt", args.safe, limit, usr_agent)
-                .await?;
-            for r in results {
-                println!("{}\n{}\n{}", r.date, r.title, r.url);
+        let start = fetched;
+        fetched += items.len();
+        let results = items
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| (params.offset..end).contains(&(start + i)))
+            .map(|(_, result)| result)
+            .collect();
+        let page = SearchPage {
+            results,
+            ..Default::default()
+        };
+        let backend = if args.backend == Backend::Images {
+            "images"
+        } else {
+            "news"
+        };
+        found |= output_batch_page(
+            args,
+            browser,
+            backend,
+            params,
+            page,
+            first,
+            exporter.as_deref_mut(),
+        )
+        .await?;
+        first = false;
+
+        match next {
+            Some(next) => {
+                checkpoint.record_page(query, &next, fetched);
+                checkpoint.save(path)?;
+                cursor = Some(next);
             }
+            None => break,
         }
     }
+    Ok(Some(found))
+}
 
-    Ok(())
+/// Loads the schedule of `ddg daemon`: the `--schedule` file, or the default one.
+#[cfg(feature = "cli")]
+fn load_schedule(path: Option<&str>) -> Result<Schedule> {
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => Schedule::default_path()
+            .context("No config directory for the schedule, set --schedule")?,
+    };
+    Schedule::load(path)
 }
 
-#[cfg(not(feature = "cli"))]
-fn main() -> Result<()> {
-    Ok(())
+/// Returns the backend and the search options of a scheduled search, based on the options of
+/// the command line.
+#[cfg(feature = "cli")]
+fn scheduled_search(
+    params: &SearchParams,
+    search: &ScheduledSearch,
+) -> Result<(Backend, SearchParams)> {
+    let backend = match &search.backend {
+        Some(name) => Backend::from_str(name, true)
+            .map_err(|_| anyhow::anyhow!("Unknown backend '{}' of '{}'", name, search.name))?,
+        None => Backend::Auto,
+    };
+    let mut params = params.clone();
+    params.query = search.query.clone();
+    if let Some(limit) = search.limit {
+        params.limit = Some(limit);
+    }
+    Ok((backend, params))
 }
+
+/// Runs the scheduled searches of `ddg daemon` until it is interrupted, reporting the results
+/// that were not seen in the previous runs of each search.
+///
+/// A failing search is reported and retried at its next scheduled time.
+#[cfg(feature = "cli")]
+async fn run_daemon(
+    args: &Cli,
+    browser: &Browser,
+    params: &SearchParams,
+    schedule: &Schedule,
+    state_dir: &Path,
+) -> Result<ExitStatus> {
+    for search in &schedule.searches {
+        if let Err(err) = scheduled_search(params, search) {
+            return Ok(usage_error(args, &err));
+        }
+    }
+
+    loop {
+        let now = Local::now().naive_local();
+        let next: Vec<(NaiveDateTime, &ScheduledSearch)> = schedule
+            .searches
+            .iter()
+            .filter_map(|search| Some((search.cron.next_after(now)?, search)))
+            .collect();
+        let Some(at) = next.iter().map(|(at, _)| *at).min() else {
+            print_error(
+                args,
+                ExitStatus::Usage,
+                "No scheduled search will ever run.",
+            );
+            return Ok(ExitStatus::Usage);
+        };
+
+        if args.verbose {
+            print_notice(args, &format!("Next run at {}.", at));
+        }
+        let wait = tokio::time::sleep((at - now).to_std().unwrap_or_default());
+        if unless_interrupted(browser, wait).await.is_none() {
+            return Ok(ExitStatus::Interrupted);
+        }
+
+        for (_, search) in next.iter().filter(|(time, _)| *time == at) {
+            if let Err(err) = run_scheduled_search(args, browser, params, search, state_dir).await {
+                report_error(args, &err.context(search.name.clone()));
+            }
+            if browser.is_cancelled() {
+                return Ok(ExitStatus::Interrupted);
+            }
+        }
+    }
+}
+
+/// Installs the latest release of ddg in place of the running binary, or only prints whether
+/// there is a newer one with `--check`.
+///
+/// The signature of the binary is verified against the `public_key` of `ddg/update.json`, and
+/// nothing is installed without one, unless `--skip-signature` is passed, in which case a
+/// warning is printed before installing it.
+#[cfg(feature = "self-update")]
+async fn self_update(
+    args: &Cli,
+    browser: &Browser,
+    check_only: bool,
+    skip_signature: bool,
+) -> Result<ExitStatus> {
+    let config_path = UpdateConfig::default_path();
+    let config = match &config_path {
+        Some(path) => UpdateConfig::load(path)?,
+        None => UpdateConfig::default(),
+    };
+    if config.public_key.is_none() && !check_only && !skip_signature {
+        anyhow::bail!(
+            "No release public key is configured: set \"public_key\" in {}, or pass \
+             --skip-signature to only verify the checksum",
+            config_path.map_or_else(
+                || "ddg/update.json".to_string(),
+                |path| path.display().to_string()
+            )
+        );
+    }
+    let mut updater = Updater::new(browser.client.clone());
+    if let Some(key) = &config.public_key {
+        updater = updater.public_key(key)?;
+    }
+    if skip_signature {
+        updater = updater.insecure();
+    }
+
+    let release = updater.latest().await?;
+    if let Some(path) = UpdateCheck::default_path() {
+        UpdateCheck::now(Some(release.version().to_string())).save(path)?;
+    }
+    if !release.is_newer() {
+        print_notice(
+            args,
+            &format!(
+                "ddg {} is up to date (latest release: {}).",
+                CURRENT_VERSION,
+                release.version()
+            ),
+        );
+        return Ok(if check_only {
+            ExitStatus::NoResults
+        } else {
+            ExitStatus::Success
+        });
+    }
+    if check_only {
+        println!(
+            "ddg {} is available (current: {}): {}",
+            release.version(),
+            CURRENT_VERSION,
+            release.html_url
+        );
+        return Ok(ExitStatus::Success);
+    }
+
+    if skip_signature {
+        print_notice(
+            args,
+            "Warning: --skip-signature skips the signature check; only the checksum is verified.",
+        );
+    }
+    let binary = updater.download(&release).await?;
+    let exe = env::current_exe().context("Failed to locate the ddg binary")?;
+    Updater::install(&binary, &exe)?;
+    println!(
+        "Updated ddg from {} to {}.",
+        CURRENT_VERSION,
+        release.version()
+    );
+    Ok(ExitStatus::Success)
+}
+
+/// Prints a notice to stderr when a newer release of ddg is available, if enabled with
+/// `"notify": true` in `ddg/update.json`.
+///
+/// GitHub is checked at the end of the run, at most once per `check_interval_hours`, through
+/// the proxies of the run. The notice is skipped in quiet mode, when stderr is not a terminal,
+/// when the run is offline (`--dry-run`, `--replay`, and `--replay-har`), and with `--tor`, so
+/// that a Tor session does not contact GitHub.
+#[cfg(feature = "self-update")]
+async fn notify_update(args: &Cli) {
+    if args.quiet
+        || args.tor
+        || args.dry_run
+        || args.replay.is_some()
+        || args.replay_har.is_some()
+        || !std::io::stderr().is_terminal()
+    {
+        return;
+    }
+    let config = match UpdateConfig::default_path().map(UpdateConfig::load) {
+        Some(Ok(config)) => config,
+        Some(Err(err)) => {
+            report_error(args, &err);
+            return;
+        }
+        None => UpdateConfig::default(),
+    };
+    let Some(path) = UpdateCheck::default_path().filter(|_| config.notify) else {
+        return;
+    };
+
+    let mut check = UpdateCheck::load(&path);
+    if check.is_due(config.check_interval()) {
+        let client = proxy_config(args).build().and_then(|proxies| {
+            let builder = reqwest::Client::builder().timeout(CHECK_TIMEOUT);
+            let builder = proxies
+                .into_iter()
+                .fold(builder, |builder, proxy| builder.proxy(proxy));
+            Ok(builder.build()?)
+        });
+        let latest = match client {
+            Ok(client) => Updater::new(client).latest().await.ok(),
+            Err(_) => None,
+        };
+        check = UpdateCheck::now(
+            latest
+                .map(|release| release.version().to_string())
+                .or(check.latest),
+        );
+        let _ = check.save(&path);
+    }
+    if let Some(version) = check.newer_version() {
+        print_notice(
+            args,
+            &format!(
+                "ddg {} is available (current: {}); run `ddg self-update` to install it.",
+                version, CURRENT_VERSION
+            ),
+        );
+    }
+}
+
+/// Polls the news search every `--poll-interval` seconds until interrupted, printing the
+/// articles not seen in previous polls, oldest first, under the time they were found.
+///
+/// A failed poll is reported and retried at the next interval.
+#[cfg(feature = "cli")]
+async fn run_follow(args: &Cli, browser: &Browser, params: &SearchParams) -> Result<ExitStatus> {
+    let backend = result_filters(args).wrap(args.backend.build(browser));
+    let interval = Duration::from_secs(args.poll_interval.max(1));
+    let mut seen = SeenResults::default();
+
+    loop {
+        match backend.search(params).await {
+            Ok(mut results) => {
+                annotate_results(args, browser, &mut results).await;
+                let mut new = seen.update(results);
+                new.sort_by_key(|result| match result {
+                    SearchResult::News(news) => news.date,
+                    _ => None,
+                });
+                print_followed(args, &params.query, backend.name(), &new)?;
+            }
+            Err(err) => report_error(args, &err),
+        }
+        if browser.is_cancelled() {
+            return Ok(ExitStatus::Interrupted);
+        }
+        if args.verbose {
+            print_notice(
+                args,
+                &format!("Next poll in {} seconds.", interval.as_secs()),
+            );
+        }
+        if unless_interrupted(browser, tokio::time::sleep(interval))
+            .await
+            .is_none()
+        {
+            return Ok(ExitStatus::Interrupted);
+        }
+    }
+}
+
+/// Prints the new articles of a poll of `--follow`, if any, as a JSON line with `--output
+/// json`.
+#[cfg(feature = "cli")]
+fn print_followed(args: &Cli, query: &str, backend: &str, results: &[SearchResult]) -> Result<()> {
+    if results.is_empty() {
+        return Ok(());
+    }
+    let found_at = Local::now();
+    if args.output == OutputFormat::Json {
+        let followed = FollowedResults {
+            query,
+            backend,
+            found_at: (!args.deterministic)
+                .then(|| found_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+            results,
+        };
+        println!("{}", serde_json::to_string(&followed)?);
+        return Ok(());
+    }
+
+    if !is_plain(args) {
+        let heading = AnsiStyle {
+            bold: true,
+            color: Some(AnsiColor::Gold),
+        };
+        let new = match results.len() {
+            1 => String::from("1 new article"),
+            n => format!("{} new articles", n),
+        };
+        let heading_text = if args.deterministic {
+            format!("{}:", new)
+        } else {
+            format!("{} ({}):", found_at.format("%Y-%m-%d %H:%M:%S"), new)
+        };
+        println!("{}", paint(args, &heading, &heading_text));
+    }
+    print_results(args, results, 2);
+    Ok(())
+}
+
+/// The new articles of a poll of `--follow`, printed as a JSON line with `--output json`.
+#[cfg(feature = "cli")]
+#[derive(Serialize)]
+struct FollowedResults<'a> {
+    query: &'a str,
+    backend: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    found_at: Option<String>,
+    results: &'a [SearchResult],
+}
+
+/// Runs a scheduled search once, printing the new results and posting them to its webhook.
+#[cfg(feature = "cli")]
+async fn run_scheduled_search(
+    args: &Cli,
+    browser: &Browser,
+    params: &SearchParams,
+    search: &ScheduledSearch,
+    state_dir: &Path,
+) -> Result<()> {
+    let (backend, params) = scheduled_search(params, search)?;
+    let backend = result_filters(args).wrap(backend.build(browser));
+    let results = backend.search(&params).await;
+    #[cfg(feature = "metrics")]
+    if let Some(metrics) = &browser.metrics {
+        metrics.record_search(backend.name(), results.is_ok());
+    }
+    let mut results = results?;
+    annotate_results(args, browser, &mut results).await;
+
+    let mut seen = SeenResults::load(state_dir, &search.name)?;
+    let new = seen.update(results);
+    seen.save(state_dir, &search.name)?;
+    if new.is_empty() {
+        return Ok(());
+    }
+
+    let notification = DaemonNotification {
+        name: &search.name,
+        query: &search.query,
+        backend: backend.name(),
+        results: &new,
+    };
+    if args.output == OutputFormat::Json {
+        println!("{}", serde_json::to_string(&notification)?);
+    } else {
+        if !is_plain(args) {
+            let heading = AnsiStyle {
+                bold: true,
+                color: Some(AnsiColor::Gold),
+            };
+            println!(
+                "{}",
+                paint(
+                    args,
+                    &heading,
+                    &format!("{} ({} new):", search.name, new.len())
+                )
+            );
+        }
+        print_results(args, &new, 2);
+    }
+
+    if let Some(webhook) = &search.webhook {
+        browser
+            .client
+            .post(webhook)
+            .json(&notification)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Failed to post the new results to {}", webhook))?;
+    }
+    Ok(())
+}
+
+/// The new results of a scheduled search, printed by `ddg daemon` with `--output json` and
+/// posted to the webhook of the search.
+#[cfg(feature = "cli")]
+#[derive(Serialize)]
+struct DaemonNotification<'a> {
+    name: &'a str,
+    query: &'a str,
+    backend: &'a str,
+    results: &'a [SearchResult],
+}
+
+/// Prints, and exports to the `--export` target, a page of results of a query of a batch.
+///
+/// In JSON, each page is printed as a single line. Otherwise, the query is printed as a
+/// heading before its first page.
+///
+/// Returns whether the page has any results.
+#[cfg(feature = "cli")]
+async fn output_batch_page(
+    args: &Cli,
+    browser: &Browser,
+    backend: &str,
+    params: &SearchParams,
+    mut page: SearchPage<SearchResult>,
+    first: bool,
+    exporter: Option<&mut Exporter>,
+) -> Result<bool> {
+    annotate_results(args, browser, &mut page.results).await;
+    if let Some(exporter) = exporter {
+        exporter.export(&SearchRecord::new(params, backend), &page.results)?;
+    }
+    let found = !page.results.is_empty();
+
+    match args.output {
+        OutputFormat::Json => {
+            let stats = args.stats.then(|| ResultStats::new(&page.results));
+            let output = JsonOutput {
+                query: &params.query,
+                backend,
+                results: JsonResults::new(args, page.results),
+                corrected_query: page.corrected_query,
+                related_queries: page.related_queries,
+                errors: Vec::new(),
+                timings: None,
+                stats,
+            };
+            println!("{}", serde_json::to_string(&output)?);
+        }
+        OutputFormat::Alfred => print_json(&AlfredOutput::new(&page.results))?,
+        _ => {
+            if first && is_document(args) && !is_plain(args) {
+                print_document_heading(args, 1, &params.query);
+            } else if first && !is_plain(args) {
+                let heading = AnsiStyle {
+                    bold: true,
+                    color: Some(AnsiColor::Gold),
+                };
+                println!("{}", paint(args, &heading, &format!("{}:", params.query)));
+            }
+            print_results(args, &page.results, 2);
+            print_stats(args, &page.results);
+        }
+    }
+    Ok(found)
+}
+
+/// The JSON document printed by `--output json`.
+#[cfg(feature = "cli")]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+struct JsonOutput<'a> {
+    query: &'a str,
+    backend: &'a str,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<SearchResult>"))]
+    results: JsonResults<'a>,
+    corrected_query: Option<String>,
+    related_queries: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<JsonError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timings: Option<&'a Timings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<ResultStats>,
+}
+
+/// The results of the JSON document of `--output json`, with only the `--fields` of each
+/// result if set.
+#[cfg(feature = "cli")]
+struct JsonResults<'a> {
+    results: Vec<SearchResult>,
+    fields: Option<&'a Fields>,
+}
+
+#[cfg(feature = "cli")]
+impl<'a> JsonResults<'a> {
+    fn new(args: &'a Cli, results: Vec<SearchResult>) -> Self {
+        JsonResults {
+            results,
+            fields: args.fields.as_ref(),
+        }
+    }
+}
+
+#[cfg(feature = "cli")]
+impl Serialize for JsonResults<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.fields {
+            Some(fields) => {
+                serializer.collect_seq(self.results.iter().map(|result| fields.select(result)))
+            }
+            None => self.results.serialize(serializer),
+        }
+    }
+}
+
+/// The JSON document printed by `analyze` with `--output json`.
+#[cfg(feature = "cli")]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+struct AnalysisOutput<'a> {
+    query: &'a str,
+    backend: &'a str,
+    stats: ResultStats,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    terms: Option<TermFrequencies>,
+}
+
+/// A backend failure reported in the JSON document of `--backend all`.
+#[cfg(feature = "cli")]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+struct JsonError {
+    backend: String,
+    error: String,
+}
+
+/// The JSON document printed to stderr on errors with `--output json`.
+#[cfg(feature = "cli")]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+struct ErrorOutput {
+    error: ErrorDetails,
+}
+
+#[cfg(feature = "cli")]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+struct ErrorDetails {
+    /// What went wrong, as the name of the exit status, e.g. `RateLimited`.
+    kind: ExitStatus,
+    /// The exit code of the process.
+    code: i32,
+    message: String,
+    /// The seconds to wait before retrying, when DuckDuckGo or the circuit breaker said so.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry_after: Option<u64>,
+}
+
+#[cfg(feature = "cli")]
+impl ErrorOutput {
+    fn new(kind: ExitStatus, message: &str) -> Self {
+        Self {
+            error: ErrorDetails {
+                kind,
+                code: kind.code(),
+                message: message.to_string(),
+                retry_after: None,
+            },
+        }
+    }
+
+    /// Describes an error returned by the search, with its causes and the delay to retry after.
+    fn from_error(err: &anyhow::Error) -> Self {
+        let mut output = Self::new(ExitStatus::from_error(err), &format!("{:#}", err));
+        let delay = match err.downcast_ref::<Error>() {
+            Some(Error::CircuitOpen { retry_after }) => Some(*retry_after),
+            _ => err.downcast_ref::<RetryAfter>().map(|delay| delay.0),
+        };
+        output.error.retry_after =
+            delay.map(|delay| delay.as_secs() + u64::from(delay.subsec_nanos() > 0));
+        output
+    }
+}
+
+/// The JSON document printed by `answer` with `--output json`.
+#[cfg(feature = "cli")]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+struct AnswerOutput<'a> {
+    query: &'a str,
+    answer: Option<&'a Answer>,
+}
+
+/// The JSON document printed by `define` with `--output json`.
+#[cfg(feature = "cli")]
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+struct DefinitionOutput<'a> {
+    query: &'a str,
+    definition: Option<&'a Definition>,
+}
+
+/// Returns the JSON Schema of a JSON document printed by the tool, describing the documents
+/// as they are serialized: the fields left out when empty are optional.
+#[cfg(all(feature = "cli", feature = "schema"))]
+fn schema(document: SchemaDocument) -> Schema {
+    fn schema_for<T: JsonSchema>() -> Schema {
+        SchemaSettings::default()
+            .for_serialize()
+            .into_generator()
+            .into_root_schema_for::<T>()
+    }
+
+    match document {
+        SchemaDocument::Output => schema_for::<JsonOutput>(),
+        SchemaDocument::Result => schema_for::<SearchResult>(),
+        SchemaDocument::Text => schema_for::<LiteSearchResult>(),
+        SchemaDocument::Image => schema_for::<ImageResult>(),
+        SchemaDocument::News => schema_for::<NewsResult>(),
+        SchemaDocument::Alfred => schema_for::<AlfredOutput>(),
+        SchemaDocument::RagJson => schema_for::<ResearchBundle>(),
+        SchemaDocument::Answer => schema_for::<AnswerOutput>(),
+        SchemaDocument::Definition => schema_for::<DefinitionOutput>(),
+        SchemaDocument::Plan => schema_for::<Vec<PlannedRequest>>(),
+        SchemaDocument::Comparison => schema_for::<Comparison>(),
+        SchemaDocument::Stability => schema_for::<StabilityReport>(),
+        SchemaDocument::Analysis => schema_for::<AnalysisOutput>(),
+        SchemaDocument::Doctor => schema_for::<Vec<EndpointCheck>>(),
+        SchemaDocument::Error => schema_for::<ErrorOutput>(),
+    }
+}
+
+/// Resolves the favicons, checks the links, and fetches the pages of the results, if
+/// `--favicons`, `--check-links`, `--skip-dead-links`, or `--fetch-content` ask for it, leaving
+/// out the dead links with `--skip-dead-links`, and sorts them by rank then URL with
+/// `--deterministic`.
+#[cfg(feature = "cli")]
+async fn annotate_results(args: &Cli, browser: &Browser, results: &mut Vec<SearchResult>) {
+    if args.check_links || args.skip_dead_links {
+        browser.check_links(results).await;
+    }
+    if args.skip_dead_links {
+        results.retain(|result| !result.link_check().is_some_and(|check| check.is_dead()));
+    }
+    if let Some(read_dimensions) = args.verify_images {
+        browser.verify_images(results, read_dimensions).await;
+        results.retain(|result| match result {
+            SearchResult::Image(image) => !image
+                .image_check
+                .as_ref()
+                .is_some_and(|check| check.is_dead()),
+            _ => true,
+        });
+    }
+    if args.favicons {
+        browser
+            .resolve_favicons(results, &FaviconCache::default())
+            .await;
+    }
+    if args.fetch_content {
+        browser.fetch_contents(results).await;
+    }
+    if args.deterministic {
+        results.sort_by(|a, b| (a.rank(), a.url()).cmp(&(b.rank(), b.url())));
+    }
+}
+
+/// Downloads the image results to the `--download-images` directory, if set, printing how many
+/// were saved.
+#[cfg(feature = "cli")]
+async fn download_images(
+    args: &Cli,
+    browser: &Browser,
+    query: &str,
+    results: &[SearchResult],
+) -> Result<()> {
+    let Some(dir) = &args.download_images else {
+        return Ok(());
+    };
+    if !results
+        .iter()
+        .any(|result| matches!(result, SearchResult::Image(_)))
+    {
+        return Ok(());
+    }
+    let downloads = ImageDownloads::new(dir).name_template(args.name_template.clone());
+    #[cfg(feature = "convert")]
+    let downloads = match args.image_format {
+        Some(format) => downloads.convert(format),
+        None => downloads,
+    };
+
+    let manifest = browser.download_images(query, results, &downloads).await?;
+    print_notice(
+        args,
+        &format!(
+            "Downloaded {} images to {}, listed in {}",
+            manifest.files.len(),
+            downloads.dir().display(),
+            downloads.manifest_path().display()
+        ),
+    );
+    for failed in &manifest.failed {
+        print_notice(
+            args,
+            &format!("Failed to download {}: {}", failed.image, failed.error),
+        );
+    }
+    Ok(())
+}
+
+/// Runs the search in each of the `--regions` concurrently and prints how their results
+/// compare.
+///
+/// Returns whether any region has results.
+#[cfg(feature = "cli")]
+async fn compare_search_regions(
+    args: &Cli,
+    browser: &Browser,
+    params: &SearchParams,
+) -> Result<bool> {
+    let backend = result_filters(args).wrap(args.backend.build(browser));
+    let comparison = compare_regions(backend.as_ref(), params, &args.regions).await;
+    output_comparison(args, &comparison, "region")
+}
+
+/// Runs the search with each of the `--compare-user-agents` concurrently and prints how their
+/// results compare.
+///
+/// Returns whether any user agent has results.
+#[cfg(feature = "cli")]
+async fn compare_search_user_agents(
+    args: &Cli,
+    browser: &Browser,
+    params: &SearchParams,
+) -> Result<bool> {
+    let backend = result_filters(args).wrap(args.backend.build(browser));
+    let comparison =
+        compare_user_agents(backend.as_ref(), params, &args.compare_user_agents).await?;
+    output_comparison(args, &comparison, "user agent")
+}
+
+/// Prints a comparison: the results shared by every variant, the shared results they rank
+/// differently, then the results unique to each variant, or the whole comparison with
+/// `--output json`.
+///
+/// Returns whether any variant has results, or the error of the first variant if they all
+/// failed.
+#[cfg(feature = "cli")]
+fn output_comparison(args: &Cli, comparison: &Comparison, noun: &str) -> Result<bool> {
+    if comparison
+        .variants
+        .iter()
+        .all(|variant| variant.error.is_some())
+        && let Some(error) = comparison.variants.first().and_then(|v| v.error.as_ref())
+    {
+        anyhow::bail!("{}", error);
+    }
+    let found = comparison
+        .variants
+        .iter()
+        .any(|variant| !variant.results.is_empty());
+    if args.output == OutputFormat::Json {
+        print_json(comparison)?;
+        return Ok(found);
+    }
+
+    let heading = AnsiStyle {
+        bold: true,
+        color: Some(AnsiColor::Gold),
+    };
+    let print_heading = |title: &str| {
+        if is_document(args) && !is_plain(args) {
+            print_document_heading(args, 2, title);
+        } else if !is_plain(args) {
+            println!("{}", paint(args, &heading, &format!("{}:", title)));
+        }
+    };
+    if is_document(args) && !is_plain(args) {
+        print_document_heading(args, 1, &comparison.query);
+    }
+
+    let shared: Vec<SearchResult> = comparison.shared_results().into_iter().cloned().collect();
+    print_heading(&format!("Shared by every {} ({})", noun, shared.len()));
+    print_results(args, &shared, 3);
+    if !comparison.moved.is_empty() {
+        let names: Vec<&str> = comparison
+            .variants
+            .iter()
+            .filter(|variant| variant.error.is_none())
+            .map(|variant| variant.name.as_str())
+            .collect();
+        if !is_plain(args) {
+            println!();
+        }
+        print_heading(&format!("Ranked differently ({})", comparison.moved.len()));
+        for moved in &comparison.moved {
+            let ranks: Vec<String> = moved
+                .ranks
+                .iter()
+                .zip(&names)
+                .map(|(rank, name)| format!("#{} in {}", rank, name))
+                .collect();
+            println!("{}  ({})", moved.url, ranks.join(", "));
+        }
+    }
+    for variant in &comparison.variants {
+        if let Some(error) = &variant.error {
+            print_error(
+                args,
+                ExitStatus::Network,
+                &format!("The {} {} failed: {}", noun, variant.name, error),
+            );
+            continue;
+        }
+        let unique: Vec<SearchResult> = variant.unique_results().into_iter().cloned().collect();
+        if !is_plain(args) {
+            println!();
+        }
+        print_heading(&format!(
+            "Only in {} ({} of {})",
+            variant.name,
+            unique.len(),
+            variant.results.len()
+        ));
+        print_results(args, &unique, 3);
+    }
+    Ok(found)
+}
+
+/// Repeats the search of `bench-stability` and prints how much its results change between the
+/// runs, stopping at the runs completed so far if interrupted.
+///
+/// Returns whether any run has results.
+#[cfg(feature = "cli")]
+async fn bench_stability(
+    args: &Cli,
+    browser: &Browser,
+    params: &SearchParams,
+    runs: u32,
+    interval: u64,
+) -> Result<bool> {
+    let backend = result_filters(args).wrap(args.backend.build(browser));
+    let report = measure_stability_until(
+        backend.as_ref(),
+        params,
+        runs as usize,
+        Duration::from_secs(interval),
+        browser.cancel.as_ref(),
+    )
+    .await?;
+    // An interrupted measure only has the runs completed so far.
+    let runs = report.runs.len();
+    let found = report.runs.iter().any(|urls| !urls.is_empty());
+    if args.output == OutputFormat::Json {
+        print_json(&report)?;
+        return Ok(found);
+    }
+
+    let heading = AnsiStyle {
+        bold: true,
+        color: Some(AnsiColor::Gold),
+    };
+    let title = format!(
+        "Stability of \"{}\" over {} runs ({}):",
+        report.query,
+        runs,
+        backend.name()
+    );
+    println!("{}", paint(args, &heading, &title));
+    let counts: Vec<String> = report
+        .runs
+        .iter()
+        .map(|urls| urls.len().to_string())
+        .collect();
+    println!("  Results per run:        {}", counts.join(", "));
+    println!(
+        "  Mean overlap:           {:.1} results",
+        report.mean_overlap
+    );
+    println!("  Mean Jaccard index:     {:.2}", report.mean_jaccard);
+    match report.mean_rank_correlation {
+        Some(tau) => println!("  Mean rank correlation:  {:.2} (Kendall's tau)", tau),
+        None => println!("  Mean rank correlation:  n/a (too few shared results)"),
+    }
+    println!("  Volatility:             {:.2}", report.volatility());
+    println!("  Found by every run:     {}", report.stable.len());
+    if !report.unstable.is_empty() {
+        println!();
+        println!(
+            "{}",
+            paint(args, &heading, "Results missing from some runs:")
+        );
+        for result in &report.unstable {
+            println!("  {}/{}  {}", result.runs, runs, result.url);
+        }
+    }
+    Ok(found)
+}
+
+/// The width of the side-by-side diff of `compare` when the output is not a terminal and
+/// `--width` is not set.
+#[cfg(feature = "cli")]
+const SIDE_BY_SIDE_WIDTH: usize = 160;
+
+/// Runs the two searches of `compare` concurrently and prints the diff of their results, or
+/// their comparison with `--output json`.
+///
+/// Returns whether either search found results.
+#[cfg(feature = "cli")]
+async fn compare_search_queries(
+    args: &Cli,
+    browser: &Browser,
+    params: &SearchParams,
+    queries: [&String; 2],
+    style: DiffStyle,
+) -> Result<bool> {
+    let backend = result_filters(args).wrap(args.backend.build(browser));
+    let variants = queries
+        .iter()
+        .map(|query| {
+            let params = SearchParams {
+                query: with_operators(args, query),
+                ..params.clone()
+            };
+            (query.to_string(), params)
+        })
+        .collect();
+    let title = format!("{} vs {}", queries[0], queries[1]);
+    let comparison = compare_variants(backend.as_ref(), &title, variants).await;
+    if let Some(variant) = comparison.variants.iter().find(|v| v.error.is_some()) {
+        anyhow::bail!(
+            "The search for '{}' failed: {}",
+            variant.name,
+            variant.error.as_deref().unwrap_or_default()
+        );
+    }
+    let found = comparison.variants.iter().any(|v| !v.results.is_empty());
+    if args.output == OutputFormat::Json {
+        print_json(&comparison)?;
+        return Ok(found);
+    }
+
+    let [old, new] = [&comparison.variants[0], &comparison.variants[1]];
+    let lines = diff_results(&old.results, &new.results);
+    let removed = AnsiStyle {
+        bold: false,
+        color: Some(AnsiColor::Red),
+    };
+    let added = AnsiStyle {
+        bold: false,
+        color: Some(AnsiColor::Green),
+    };
+    let width = output_width(args).filter(|&width| width > 0);
+    match style {
+        DiffStyle::Unified => {
+            if !is_plain(args) {
+                let header = AnsiStyle {
+                    bold: true,
+                    color: None,
+                };
+                for (marker, variant) in [("---", old), ("+++", new)] {
+                    let line = format!(
+                        "{} {} ({} results)",
+                        marker,
+                        variant.name,
+                        variant.results.len()
+                    );
+                    println!("{}", paint(args, &header, &line));
+                }
+            }
+            for line in lines {
+                let (marker, result, style) = match line {
+                    DiffLine::Shared(i, _) => (' ', &old.results[i], None),
+                    DiffLine::Removed(i) => ('-', &old.results[i], Some(&removed)),
+                    DiffLine::Added(j) => ('+', &new.results[j], Some(&added)),
+                };
+                let mut text = format!("{} {}", marker, result.url());
+                if !is_plain(args) && !result.title().is_empty() {
+                    text = format!("{}  {}", text, result.title());
+                }
+                if let Some(width) = width {
+                    text = truncate(&text, width);
+                }
+                match style {
+                    Some(style) => println!("{}", paint(args, style, &text)),
+                    None => println!("{}", text),
+                }
+            }
+        }
+        DiffStyle::SideBySide => {
+            let column = (width.unwrap_or(SIDE_BY_SIDE_WIDTH).max(23) - 3) / 2;
+            let cell = |results: &[SearchResult], index: Option<usize>| match index {
+                Some(i) => {
+                    let text = truncate(&format!("{}. {}", i + 1, results[i].url()), column);
+                    let padding = column.saturating_sub(display_width(&text));
+                    format!("{}{}", text, " ".repeat(padding))
+                }
+                None => " ".repeat(column),
+            };
+            if !is_plain(args) {
+                let name = truncate(&old.name, column);
+                let padding = column.saturating_sub(display_width(&name));
+                println!(
+                    "{}{}   {}",
+                    name,
+                    " ".repeat(padding),
+                    truncate(&new.name, column)
+                );
+                println!("{}", "─".repeat(column * 2 + 3));
+            }
+            for line in lines {
+                let (left, marker, right, style) = match line {
+                    DiffLine::Shared(i, j) => (Some(i), ' ', Some(j), None),
+                    DiffLine::Removed(i) => (Some(i), '<', None, Some(&removed)),
+                    DiffLine::Added(j) => (None, '>', Some(j), Some(&added)),
+                };
+                let row = format!(
+                    "{} {} {}",
+                    cell(&old.results, left),
+                    marker,
+                    cell(&new.results, right)
+                );
+                match style {
+                    Some(style) => println!("{}", paint(args, style, row.trim_end())),
+                    None => println!("{}", row.trim_end()),
+                }
+            }
+        }
+    }
+    if !is_plain(args) {
+        println!(
+            "\n{} shared, {} only in '{}', {} only in '{}'",
+            comparison.shared.len(),
+            old.unique.len(),
+            old.name,
+            new.unique.len(),
+            new.name
+        );
+    }
+    Ok(found)
+}
+
+/// Runs the search of `analyze` and prints the statistics of its results, and their `top`
+/// most frequent terms if set.
+///
+/// Returns whether the search found results.
+#[cfg(feature = "cli")]
+async fn analyze(
+    args: &Cli,
+    browser: &Browser,
+    params: &SearchParams,
+    top: Option<usize>,
+) -> Result<bool> {
+    let backend = result_filters(args).wrap(args.backend.build(browser));
+    let results = backend.search(params).await?;
+    let stats = ResultStats::new(&results);
+    let terms = top.map(|top| {
+        let mut terms = TermFrequencies::new(&results, &params.query);
+        terms.truncate(top);
+        terms
+    });
+    if args.output == OutputFormat::Json {
+        print_json(&AnalysisOutput {
+            query: &params.query,
+            backend: backend.name(),
+            stats,
+            terms,
+        })?;
+        return Ok(!results.is_empty());
+    }
+
+    let heading = AnsiStyle {
+        bold: true,
+        color: Some(AnsiColor::Gold),
+    };
+    println!(
+        "{}",
+        paint(
+            args,
+            &heading,
+            &format!("Analysis of \"{}\":", params.query)
+        )
+    );
+    print!("{}", stats);
+    if let Some(terms) = terms {
+        println!();
+        print!("{}", terms);
+    }
+    Ok(!results.is_empty())
+}
+
+/// Prints a page of search results with its hints, re-running the search with the corrected
+/// query if `--autocorrect` is set, and exports them to the `--export` target.
+///
+/// Returns the number of results of the page.
+#[cfg(feature = "cli")]
+async fn output_page(
+    args: &Cli,
+    browser: &Browser,
+    backend: &dyn SearchBackend,
+    params: &SearchParams,
+    mut page: SearchPage<SearchResult>,
+    exporter: Option<&mut Exporter>,
+) -> Result<usize> {
+    let text = matches!(args.output, OutputFormat::Text | OutputFormat::Plain) && !is_plain(args);
+    let hint = AnsiStyle {
+        bold: false,
+        color: Some(AnsiColor::Gold),
+    };
+    let corrected_query = page.corrected_query.take();
+    let mut query = params.query.clone();
+    if let Some(corrected) = &corrected_query {
+        if args.autocorrect {
+            if text {
+                println!(
+                    "{}",
+                    paint(
+                        args,
+                        &hint,
+                        &format!(
+                            "Showing results for '{}' instead of '{}'.",
+                            corrected, params.query
+                        )
+                    )
+                );
+            }
+            let params = SearchParams {
+                query: corrected.clone(),
+                ..params.clone()
+            };
+            page = backend.search_page(&params).await?;
+            query = params.query;
+        } else if text {
+            println!(
+                "{}",
+                paint(args, &hint, &format!("Did you mean: {}?", corrected))
+            );
+        }
+    }
+    annotate_results(args, browser, &mut page.results).await;
+    if let Some(exporter) = exporter {
+        let params = SearchParams {
+            query: query.clone(),
+            ..params.clone()
+        };
+        exporter.export(&SearchRecord::new(&params, backend.name()), &page.results)?;
+    }
+    download_images(args, browser, &query, &page.results).await?;
+    let found = page.results.len();
+
+    if args.output == OutputFormat::Json {
+        let stats = args.stats.then(|| ResultStats::new(&page.results));
+        print_json(&JsonOutput {
+            query: &query,
+            backend: backend.name(),
+            results: JsonResults::new(args, page.results),
+            corrected_query,
+            related_queries: page.related_queries,
+            errors: Vec::new(),
+            timings: browser.timings.as_deref(),
+            stats,
+        })?;
+        return Ok(found);
+    }
+    if args.output == OutputFormat::Alfred {
+        print_json(&AlfredOutput::new(&page.results))?;
+        return Ok(found);
+    }
+
+    if !is_plain(args) {
+        print_document_heading(args, 1, &query);
+    }
+    print_results(args, &page.results, 2);
+    if !page.related_queries.is_empty() && !is_plain(args) && is_document(args) {
+        println!();
+        print_document_heading(args, 2, "Related searches");
+        for query in &page.related_queries {
+            println!("- {}", query);
+        }
+    } else if !page.related_queries.is_empty() && !is_plain(args) {
+        let related = AnsiStyle {
+            bold: true,
+            color: Some(AnsiColor::BrightBlue),
+        };
+        println!("\n{}", paint(args, &related, "Related searches:"));
+        for query in &page.related_queries {
+            if args.output == OutputFormat::Plain {
+                println!("{}", query);
+            } else {
+                println!("  - {}", query);
+            }
+        }
+    }
+    print_stats(args, &page.results);
+    Ok(found)
+}
+
+/// Opens the top organic result of the search in the browser, or prints its URL with
+/// `--print`.
+///
+/// The automatic backend and `--backend all` search the html backend, falling back to the
+/// lite one, since the Instant Answer API returns related topics rather than web results.
+///
+/// `!bang` queries are first sent to the Instant Answer API, and the site they redirect to is
+/// opened instead.
+///
+/// Returns whether the search found a result.
+#[cfg(feature = "cli")]
+async fn run_lucky(args: &Cli, browser: &Browser, params: &SearchParams) -> Result<bool> {
+    let is_bang = params
+        .query
+        .split_whitespace()
+        .any(|word| word.len() > 1 && word.starts_with('!'));
+    if is_bang
+        && matches!(args.backend, Backend::Auto | Backend::All)
+        && let SearchOutcome::Redirect(url) = browser.search_outcome(&params.query).await?
+    {
+        if args.print {
+            println!("{}", url);
+        } else {
+            open_url(url.as_str())?;
+        }
+        return Ok(true);
+    }
+
+    let backend: Box<dyn SearchBackend> = match args.backend {
+        Backend::Auto | Backend::All => Box::new(FallbackBackend::new(vec![
+            Box::new(HtmlBackend::new(browser)),
+            Box::new(LiteBackend::new(browser)),
+        ])),
+        ref backend => backend.build(browser),
+    };
+    let params = SearchParams {
+        limit: Some(1),
+        ..params.clone()
+    };
+    let results = result_filters(args).wrap(backend).search(&params).await?;
+    let Some(result) = results.first() else {
+        print_notice(args, "No results found.");
+        return Ok(false);
+    };
+
+    if args.print {
+        println!("{}", result.url());
+    } else {
+        open_url(result.url())?;
+    }
+    Ok(true)
+}
+
+/// Prints the results of each backend of `--backend all`, in sections or as a single JSON
+/// document.
+///
+/// Returns the number of results of all backends, or the error of the first backend if they
+/// all failed.
+#[cfg(feature = "cli")]
+fn output_sections(
+    args: &Cli,
+    browser: &Browser,
+    params: &SearchParams,
+    sections: Vec<BackendResults>,
+) -> Result<usize> {
+    let mut found = 0;
+    let mut results = Vec::new();
+    let mut corrected_query = None;
+    let mut related_queries = Vec::new();
+    let mut errors = Vec::new();
+    let mut first_err = None;
+    let mut succeeded = false;
+    let heading = AnsiStyle {
+        bold: true,
+        color: Some(AnsiColor::Gold),
+    };
+
+    if !is_plain(args) {
+        print_document_heading(args, 1, &params.query);
+    }
+    for section in sections {
+        let page = match section.page {
+            Ok(page) => page,
+            Err(err) => {
+                let err = err.context(format!("The {} backend failed", section.backend));
+                errors.push(JsonError {
+                    backend: section.backend,
+                    error: format!("{:#}", err),
+                });
+                first_err.get_or_insert(err);
+                continue;
+            }
+        };
+        succeeded = true;
+        found += page.results.len();
+
+        if matches!(args.output, OutputFormat::Json | OutputFormat::Alfred) {
+            results.extend(page.results);
+            corrected_query = corrected_query.or(page.corrected_query);
+            related_queries.extend(page.related_queries);
+            continue;
+        }
+        if is_document(args) && !is_plain(args) {
+            print_document_heading(args, 2, &section.backend);
+        } else if !is_plain(args) {
+            println!(
+                "{}",
+                paint(args, &heading, &format!("{} results:", section.backend))
+            );
+        }
+        print_results(args, &page.results, 3);
+        if !is_plain(args) {
+            println!();
+        }
+        if args.stats {
+            results.extend(page.results);
+        }
+    }
+
+    if let Some(err) = first_err
+        && !succeeded
+    {
+        return Err(err);
+    }
+    if args.output == OutputFormat::Json {
+        let stats = args.stats.then(|| ResultStats::new(&results));
+        print_json(&JsonOutput {
+            query: &params.query,
+            backend: "all",
+            results: JsonResults::new(args, results),
+            corrected_query,
+            related_queries,
+            errors,
+            timings: browser.timings.as_deref(),
+            stats,
+        })?;
+        return Ok(found);
+    }
+    for error in &errors {
+        print_error(args, ExitStatus::Network, &error.error);
+    }
+    if args.output == OutputFormat::Alfred {
+        print_json(&AlfredOutput::new(&results))?;
+    } else {
+        print_stats(args, &results);
+    }
+    Ok(found)
+}
+
+/// Prints search results, or only their essential field in quiet mode.
+///
+/// News results are grouped under headings if `--group-by` is set, of the given level in
+/// Markdown and Org-mode documents.
+#[cfg(feature = "cli")]
+fn print_results(args: &Cli, results: &[SearchResult], level: usize) {
+    let renderer = renderer(args);
+    if let Some(group_by) = args.group_by {
+        let mut groups: Vec<(String, Vec<&SearchResult>)> = Vec::new();
+        for result in results {
+            let key = match (result, group_by) {
+                (SearchResult::News(news), GroupBy::Source) if news.source.is_empty() => {
+                    news.domain.clone().unwrap_or_default()
+                }
+                (SearchResult::News(news), GroupBy::Source) => news.source.clone(),
+                (SearchResult::News(news), GroupBy::Day) => news
+                    .date
+                    .map(|date| date.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default(),
+                _ => String::new(),
+            };
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, group)) => group.push(result),
+                None => groups.push((key, vec![result])),
+            }
+        }
+
+        let heading = AnsiStyle {
+            bold: true,
+            color: Some(AnsiColor::BrightBlue),
+        };
+        let mut number = 0;
+        for (index, (key, group)) in groups.into_iter().enumerate() {
+            if is_document(args) && !is_plain(args) && !key.is_empty() {
+                if index > 0 {
+                    println!();
+                }
+                print_document_heading(args, level, &key);
+            } else if !is_plain(args) && !key.is_empty() {
+                println!("{}", paint(args, &heading, &format!("{}:", key)));
+            }
+            for result in group {
+                number += 1;
+                print_result_line(args, &renderer, result, number, results.len());
+            }
+        }
+        return;
+    }
+
+    for (index, result) in results.iter().enumerate() {
+        print_result_line(args, &renderer, result, index + 1, results.len());
+    }
+}
+
+/// Prints a search result with the template, if any, or only its essential field in quiet mode
+/// and with `--print0`.
+///
+/// The result is the `number`th of `total` results, as announced by `--plain`. It ends with a
+/// NUL character with `--print0`, and a line break otherwise.
+#[cfg(feature = "cli")]
+fn print_result_line(
+    args: &Cli,
+    renderer: &Renderer,
+    result: &SearchResult,
+    number: usize,
+    total: usize,
+) {
+    let record = if let Some(template) = &args.template {
+        template.render(result)
+    } else if args.output == OutputFormat::Markdown {
+        result.to_markdown()
+    } else if args.output == OutputFormat::Org {
+        result.to_org()
+    } else if args.output == OutputFormat::Rofi {
+        rofi_row(result)
+    } else if args.output == OutputFormat::Dmenu {
+        dmenu_line(result)
+    } else if args.quiet || args.print0 {
+        quiet_line(result).to_string()
+    } else if args.output == OutputFormat::Plain {
+        format!("{}\n", PlainRenderer::new().render(result, number, total))
+    } else {
+        renderer.render(result)
+    };
+    if args.print0 {
+        print!("{}\0", record);
+    } else {
+        println!("{}", record);
+    }
+}
+
+/// Creates the renderer of the text output, with hyperlinks if `--hyperlinks` enables them,
+/// fitting the results in `--width` columns or in the terminal.
+#[cfg(feature = "cli")]
+fn renderer(args: &Cli) -> Renderer {
+    let hyperlinks = match args.hyperlinks {
+        Hyperlinks::On => true,
+        Hyperlinks::Off => false,
+        Hyperlinks::Auto => std::io::stdout().is_terminal() && supports_hyperlinks(),
+    };
+    let width = output_width(args);
+    let mut dates = DateFormat::new()
+        .relative(match args.relative_dates {
+            RelativeDates::On => true,
+            RelativeDates::Off => false,
+            RelativeDates::Auto => !args.deterministic && std::io::stdout().is_terminal(),
+        })
+        .locale(Locale::from_env());
+    if let Some(pattern) = &args.date_format {
+        dates = dates.pattern(pattern.clone());
+    }
+    Renderer::new()
+        .hyperlinks(hyperlinks)
+        .width(width)
+        .dates(dates)
+}
+
+/// Returns the width the text output fits in: `--width`, or the width of the terminal.
+#[cfg(feature = "cli")]
+fn output_width(args: &Cli) -> Option<usize> {
+    args.width.or_else(|| {
+        std::io::stdout()
+            .is_terminal()
+            .then(terminal_size::terminal_size)
+            .flatten()
+            .map(|(terminal_size::Width(width), _)| usize::from(width))
+    })
+}
+
+/// Returns `true` if only the results are printed to stdout, without headings nor hints, as
+/// in quiet mode, with `--print0`, with a template, or for a launcher.
+#[cfg(feature = "cli")]
+fn is_plain(args: &Cli) -> bool {
+    args.quiet
+        || args.print0
+        || args.template.is_some()
+        || matches!(args.output, OutputFormat::Rofi | OutputFormat::Dmenu)
+}
+
+/// Returns `true` if the results are printed as a Markdown or Org-mode document.
+#[cfg(feature = "cli")]
+fn is_document(args: &Cli) -> bool {
+    matches!(args.output, OutputFormat::Markdown | OutputFormat::Org)
+}
+
+/// Prints a heading of the Markdown or Org-mode document, followed by a blank line.
+#[cfg(feature = "cli")]
+fn print_document_heading(args: &Cli, level: usize, title: &str) {
+    match args.output {
+        OutputFormat::Markdown => println!("{} {}\n", "#".repeat(level), title),
+        OutputFormat::Org => println!("{} {}\n", "*".repeat(level), title),
+        _ => {}
+    }
+}
+
+/// Prints a value as pretty JSON.
+#[cfg(feature = "cli")]
+fn print_json(value: &impl Serialize) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Returns `true` if a related topic of the Instant Answer API is printed as a result.
+#[cfg(feature = "cli")]
+fn is_printable(topic: &Topic) -> bool {
+    topic.text.is_some() && topic.first_url.is_some()
+}
+
+/// Reports on stderr which backend of the `auto` chain served the results, unless in quiet
+/// mode.
+#[cfg(feature = "cli")]
+fn print_served_by(args: &Cli, backend: &str) {
+    if !args.quiet {
+        let hint = AnsiStyle {
+            bold: false,
+            color: Some(AnsiColor::Gold),
+        };
+        eprintln!(
+            "{}",
+            paint(
+                args,
+                &hint,
+                &format!("Results served by the {} backend.", backend)
+            )
+        );
+    }
+}
+
+/// Returns the exit status of a successful search, given whether it found anything.
+#[cfg(feature = "cli")]
+fn exit_status(args: &Cli, found: bool) -> ExitStatus {
+    if !found && args.fail_on_empty {
+        ExitStatus::NoResults
+    } else {
+        ExitStatus::Success
+    }
+}
+
+/// Returns the exit status of a successful search, given the number of results it found: as
+/// `exit_status`, or `FewResults` when they are fewer than `--min-results`.
+#[cfg(feature = "cli")]
+fn count_exit_status(args: &Cli, count: usize) -> ExitStatus {
+    match (exit_status(args, count > 0), args.min_results) {
+        (ExitStatus::Success, Some(min)) if count < min => {
+            print_notice(
+                args,
+                &format!("Found {} results, fewer than --min-results {}.", count, min),
+            );
+            ExitStatus::FewResults
+        }
+        (status, _) => status,
+    }
+}
+
+/// Saves the `--cookie-file` jar once the run ends, whether it succeeded or not, warning about a
+/// failed save instead of failing the run. Runs that sent no requests, such as dry runs, leave
+/// the file as is.
+#[cfg(feature = "cli")]
+struct SaveCookies<'a> {
+    args: &'a Cli,
+    browser: &'a Browser,
+}
+
+#[cfg(feature = "cli")]
+impl Drop for SaveCookies<'_> {
+    fn drop(&mut self) {
+        if self.browser.stats().requests == 0 {
+            return;
+        }
+        if let Err(err) = self.browser.save_cookies() {
+            eprintln!(
+                "{}",
+                paint(self.args, &error_style(), &format!("Warning: {:#}", err))
+            );
+        }
+    }
+}
+
+/// Returns the style of error messages.
+#[cfg(feature = "cli")]
+fn error_style() -> AnsiStyle {
+    AnsiStyle {
+        bold: true,
+        color: Some(AnsiColor::Red),
+    }
+}
+
+/// Returns the style of informational messages, dimmed so that they stand apart from errors.
+#[cfg(feature = "cli")]
+fn notice_style() -> AnsiStyle {
+    AnsiStyle {
+        bold: false,
+        color: Some(AnsiColor::DarkGray),
+    }
+}
+
+/// Prints an error message to stderr, as a JSON document with `--output json`, otherwise without
+/// colors in quiet mode.
+#[cfg(feature = "cli")]
+fn print_error(args: &Cli, kind: ExitStatus, message: &str) {
+    print_error_output(args, &ErrorOutput::new(kind, message));
+}
+
+/// Prints an invalid argument or settings file as a usage error, like `print_error`, and returns
+/// the `Usage` exit status.
+#[cfg(feature = "cli")]
+fn usage_error(args: &Cli, err: &anyhow::Error) -> ExitStatus {
+    print_error(args, ExitStatus::Usage, &format!("{:#}", err));
+    ExitStatus::Usage
+}
+
+/// Prints an error returned by the search, with its causes, like `print_error`.
+#[cfg(feature = "cli")]
+fn report_error(args: &Cli, err: &anyhow::Error) {
+    print_error_output(args, &ErrorOutput::from_error(err));
+}
+
+#[cfg(feature = "cli")]
+fn print_error_output(args: &Cli, output: &ErrorOutput) {
+    if args.output == OutputFormat::Json {
+        match serde_json::to_string(output) {
+            Ok(json) => eprintln!("{}", json),
+            Err(_) => eprintln!("Error: {}", output.error.message),
+        }
+        return;
+    }
+    eprintln!(
+        "{}",
+        paint(
+            args,
+            &error_style(),
+            &format!("Error: {}", output.error.message)
+        )
+    );
+}
+
+/// Returns the text in a style, or as is in quiet mode and with `--plain`.
+#[cfg(feature = "cli")]
+fn paint(args: &Cli, style: &AnsiStyle, text: &str) -> String {
+    if args.quiet || args.output == OutputFormat::Plain {
+        text.to_string()
+    } else {
+        format!("{}{}{}", style.escape_code(), text, AnsiStyle::reset_code())
+    }
+}
+
+/// Prints an informational message to stderr, unless in quiet mode.
+#[cfg(feature = "cli")]
+fn print_notice(args: &Cli, message: &str) {
+    if !args.quiet {
+        eprintln!("{}", paint(args, &notice_style(), message));
+    }
+}
+
+/// Prints the `--stats` summary of the results, after a blank line, under a heading, in a fenced
+/// block in Markdown and Org-mode documents, or to stderr in quiet and plain modes to keep the
+/// output parseable.
+#[cfg(feature = "cli")]
+fn print_stats(args: &Cli, results: &[SearchResult]) {
+    if !args.stats || matches!(args.output, OutputFormat::Json | OutputFormat::Alfred) {
+        return;
+    }
+    let stats = ResultStats::new(results);
+    if is_plain(args) {
+        eprint!("\n{}", stats);
+        return;
+    }
+    println!();
+    match args.output {
+        OutputFormat::Markdown => {
+            print_document_heading(args, 2, "Statistics");
+            print!("```text\n{}```\n", stats);
+        }
+        OutputFormat::Org => {
+            print_document_heading(args, 2, "Statistics");
+            print!("#+begin_example\n{}#+end_example\n", stats);
+        }
+        _ => {
+            let heading = AnsiStyle {
+                bold: true,
+                color: Some(AnsiColor::Gold),
+            };
+            println!("{}", paint(args, &heading, "Statistics:"));
+            print!("{}", stats);
+        }
+    }
+}
+
+/// Prints the request timings to stderr, if they were collected and not part of the JSON
+/// output, and the totals of the requests with `--stats-summary`.
+#[cfg(feature = "cli")]
+fn print_footers(args: &Cli, browser: &Browser) {
+    if let Some(timings) = &browser.timings
+        && args.output != OutputFormat::Json
+    {
+        eprint!("\n{}", timings);
+    }
+    if args.stats_summary {
+        eprintln!("\nRequests: {}", browser.stats());
+    }
+}
+
+/// Returns the essential field of a search result: the image URL for images, the page URL
+/// otherwise.
+#[cfg(feature = "cli")]
+fn quiet_line(result: &SearchResult) -> &str {
+    match result {
+        SearchResult::Image(r) => r.image.as_str(),
+        _ => result.url(),
+    }
+}
+
+#[cfg(not(feature = "cli"))]
+fn main() {}
@@ -0,0 +1,111 @@
+use crate::error::Error;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The state of a `CircuitBreaker`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests are sent normally.
+    Closed,
+    /// Requests fail fast until the cool-down ends.
+    Open {
+        /// The time left before the next trial request is allowed.
+        retry_after: Duration,
+    },
+    /// The cool-down ended: the next request is a trial that closes or reopens the circuit.
+    HalfOpen,
+}
+
+/// A circuit breaker that stops sending requests after repeated upstream failures.
+///
+/// After `threshold` consecutive failures (transport errors, rate limiting, server errors, or
+/// bot challenges), the circuit opens and requests fail fast with `Error::CircuitOpen` for the
+/// cool-down, instead of burning through retries. A long batch can wait for the circuit with
+/// `wait_until_closed` and resume automatically.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::breaker::{CircuitBreaker, CircuitState};
+/// use std::time::Duration;
+///
+/// let breaker = CircuitBreaker::new(2, Duration::from_secs(30));
+/// breaker.record_failure();
+/// assert_eq!(breaker.state(), CircuitState::Closed);
+///
+/// breaker.record_failure();
+/// assert!(matches!(breaker.state(), CircuitState::Open { .. }));
+/// assert!(breaker.check().is_err());
+///
+/// breaker.reset();
+/// assert_eq!(breaker.state(), CircuitState::Closed);
+/// ```
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    state: Mutex<Failures>,
+}
+
+#[derive(Debug, Default)]
+struct Failures {
+    consecutive: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Creates a circuit breaker opening after `threshold` consecutive failures for `cooldown`.
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        CircuitBreaker {
+            threshold: threshold.max(1),
+            cooldown,
+            state: Mutex::new(Failures::default()),
+        }
+    }
+
+    /// Returns the current state of the circuit.
+    pub fn state(&self) -> CircuitState {
+        let failures = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match failures.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) => match self.cooldown.checked_sub(opened_at.elapsed()) {
+                Some(retry_after) if !retry_after.is_zero() => CircuitState::Open { retry_after },
+                _ => CircuitState::HalfOpen,
+            },
+        }
+    }
+
+    /// Returns an error if the circuit is open and requests must not be sent.
+    pub fn check(&self) -> Result<(), Error> {
+        match self.state() {
+            CircuitState::Open { retry_after } => Err(Error::CircuitOpen { retry_after }),
+            CircuitState::Closed | CircuitState::HalfOpen => Ok(()),
+        }
+    }
+
+    /// Waits until the circuit is no longer open.
+    pub async fn wait_until_closed(&self) {
+        while let CircuitState::Open { retry_after } = self.state() {
+            tokio::time::sleep(retry_after).await;
+        }
+    }
+
+    /// Records a successful request, closing the circuit.
+    pub fn record_success(&self) {
+        self.reset();
+    }
+
+    /// Records a failed request, opening the circuit once the threshold is reached.
+    pub fn record_failure(&self) {
+        let mut failures = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        failures.consecutive = failures.consecutive.saturating_add(1);
+        if failures.consecutive >= self.threshold {
+            // A failed trial request reopens the circuit for a full cool-down.
+            failures.opened_at = Some(Instant::now());
+        }
+    }
+
+    /// Closes the circuit and clears the failure count.
+    pub fn reset(&self) {
+        *self.state.lock().unwrap_or_else(|e| e.into_inner()) = Failures::default();
+    }
+}
@@ -1,20 +1,710 @@
+use crate::accounting::{Accounting, RequestStats};
+use crate::breaker::CircuitBreaker;
+#[cfg(feature = "api")]
 use crate::colors::AnsiColor;
+#[cfg(feature = "api")]
 use crate::colors::AnsiStyle;
+#[cfg(feature = "content")]
+use crate::content::{PageContent, extract_content};
+use crate::cookies::CookieJar;
+#[cfg(any(
+    feature = "api",
+    feature = "lite",
+    feature = "html",
+    feature = "images",
+    feature = "news"
+))]
+use crate::doctor::{EndpointCheck, PROBE_QUERY};
+use crate::dump::RawDump;
+use crate::endpoints::Endpoints;
+use crate::error::{Error, InvalidResponse, RetryAfter};
+use crate::favicon::{Favicon, FaviconCache};
+use crate::har::HarArchive;
+use crate::headers::HeaderProfile;
+use crate::image_check::{ImageCheck, image_dimensions};
+use crate::link::LinkCheck;
+#[cfg(feature = "metrics")]
+use crate::metrics::{Metrics, endpoint_name};
+use crate::middleware::Middleware;
+#[cfg(any(
+    feature = "lite",
+    feature = "html",
+    feature = "images",
+    feature = "news"
+))]
+use crate::params::SearchParams;
+use crate::parser::ParseMode;
+#[cfg(any(feature = "images", feature = "news"))]
+use crate::parser::ParseWarning;
+use crate::parser::is_bot_challenge;
+#[cfg(feature = "html")]
+use crate::parser::parse_html_page;
+#[cfg(feature = "lite")]
+use crate::parser::parse_lite_page;
+#[cfg(any(feature = "images", feature = "news"))]
+use crate::parser::parse_vqd;
+#[cfg(feature = "images")]
+use crate::parser::{check_image, parse_image};
+#[cfg(feature = "news")]
+use crate::parser::{check_news, parse_news};
+use crate::proxy::ProxyConfig;
+use crate::ratelimit::RateLimiter;
+use crate::response::SearchResult;
+#[cfg(any(
+    feature = "api",
+    feature = "lite",
+    feature = "html",
+    feature = "images",
+    feature = "news"
+))]
 use crate::response::*;
+use crate::throttle::ThrottlePolicy;
+use crate::timings::{TimingId, TimingResolver, Timings};
+#[cfg(feature = "api")]
 use crate::topic::Topic;
-use anyhow::{Context, Result};
-use chrono::TimeZone;
-use regex::Regex;
+use crate::tor::{TOR_CHECK_URL, TorConfig, TorStatus};
+use crate::user_agents::{UserAgentRotator, get};
+use crate::vcr::{Cassette, VcrMode};
+use anyhow::{Context, Result, bail};
+use futures_util::stream::{self, StreamExt};
 use reqwest;
-use scraper::{Html, Selector};
+#[cfg(any(feature = "images", feature = "news"))]
 use serde_json::Value;
-
-const BASE_URL: &str = "https://api.duckduckgo.com/";
+use std::collections::HashMap;
+use std::fs;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use tokio_util::sync::CancellationToken;
 
 /// A struct representing a browser for interacting with the DuckDuckGo API.
+///
+/// A `Browser` is `Send + Sync` and cheap to clone: the HTTP client, the rate limiter, the
+/// circuit breaker, and the other stateful parts are shared by all clones, so it can be handed
+/// to each task or web handler as is, without a `Mutex` or `Arc`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::params::SearchParams;
+///
+/// fn assert_send<T: Send>(_: &T) {}
+/// fn assert_sync<T: Sync>(_: &T) {}
+///
+/// let browser = Browser::new(reqwest::Client::new());
+/// let params = SearchParams::new("rust");
+/// assert_send(&browser);
+/// assert_sync(&browser);
+///
+/// let handler = browser.clone();
+/// let search = handler.lite_search_page(&params);
+/// assert_send(&search);
+/// let images = handler.images(&params);
+/// assert_send(&images);
+/// let news = handler.news(&params);
+/// assert_send(&news);
+/// ```
+#[derive(Clone)]
 pub struct Browser {
     /// The underlying HTTP client used for making requests.
     pub client: reqwest::Client,
+    /// The base URLs of the DuckDuckGo endpoints.
+    pub endpoints: Endpoints,
+    /// The cassette used to record and replay HTTP interactions, if any.
+    pub cassette: Option<Cassette>,
+    /// The HAR file the HTTP traffic is captured into or replayed from, if any.
+    pub har: Option<Arc<HarArchive>>,
+    /// The Tor settings, if traffic is routed through Tor.
+    pub tor: Option<TorConfig>,
+    /// The user agent of the requests that are not given one, such as Instant Answer API
+    /// queries.
+    pub user_agent: String,
+    /// The user agent rotator overriding the user agent of each request, if any.
+    pub user_agent_rotator: Option<Arc<UserAgentRotator>>,
+    /// The middleware run around every HTTP call, in order.
+    pub middleware: Vec<Arc<dyn Middleware>>,
+    /// The header profile sent with requests, or `None` to match the user agent of each request.
+    pub header_profile: Option<HeaderProfile>,
+    /// The persistent cookie jar and the file `save_cookies` saves it to, if any.
+    pub cookie_jar: Option<(Arc<CookieJar>, PathBuf)>,
+    /// The maximum size of a response body, in bytes.
+    pub max_body_size: usize,
+    /// The rate limiter all requests wait for, if any.
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+    /// The circuit breaker suspending requests after repeated failures, if any.
+    pub circuit_breaker: Option<Arc<CircuitBreaker>>,
+    /// The policy spacing out the pages of image and news searches, if any.
+    pub throttle: Option<Arc<ThrottlePolicy>>,
+    /// The per-phase durations of the requests, if timings are enabled.
+    pub timings: Option<Arc<Timings>>,
+    /// The counters of the requests, if metrics are enabled.
+    #[cfg(feature = "metrics")]
+    pub metrics: Option<Arc<Metrics>>,
+    /// The directory the raw response bodies are saved to, if any.
+    pub raw_dump: Option<Arc<RawDump>>,
+    /// How the malformed items of the image and news APIs are handled.
+    pub parse_mode: ParseMode,
+    /// Whether the original JSON documents are kept in `Response::raw` and `Page::raw`.
+    pub with_raw: bool,
+    /// The totals of the requests, shared by the clones of the browser.
+    pub accounting: Arc<Accounting>,
+    /// The token stopping the pagination of image and news searches once cancelled, if any.
+    pub cancel: Option<CancellationToken>,
+}
+
+// Fails to compile if a field makes `Browser` lose `Send`, `Sync`, or `Clone`.
+const _: fn() = || {
+    fn check<T: Send + Sync + Clone>() {}
+    check::<Browser>();
+};
+
+/// The maximum number of result sites requested at once, to check links, fetch content, or
+/// download images.
+pub(crate) const SITE_REQUEST_CONCURRENCY: usize = 8;
+
+/// The most bytes of an image file read to find its dimensions (64 KiB).
+const IMAGE_HEADER_BYTES: usize = 64 * 1024;
+
+/// How long a request to the site of a result waits for it to answer.
+const SITE_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The default maximum size of a response body (8 MiB).
+pub const DEFAULT_MAX_BODY_SIZE: usize = 8 * 1024 * 1024;
+
+/// Marks the responses answered from a cassette or a HAR file, whose bodies are not counted
+/// as downloaded.
+#[derive(Debug, Clone, Copy)]
+struct Cached;
+
+/// The time a response took to arrive, without the wait of the rate limiter, attached by
+/// `send` for the probes of `check_endpoints`.
+#[cfg(any(
+    feature = "api",
+    feature = "lite",
+    feature = "html",
+    feature = "images",
+    feature = "news"
+))]
+#[derive(Debug, Clone, Copy)]
+struct Latency(Duration);
+
+/// A builder for configuring and creating a `Browser`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::endpoints::Endpoints;
+///
+/// let browser = Browser::builder()
+///     .endpoints(Endpoints {
+///         api: String::from("http://localhost:8080/"),
+///         ..Default::default()
+///     })
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(browser.endpoints.api, "http://localhost:8080/");
+/// ```
+pub struct BrowserBuilder {
+    client: Option<reqwest::Client>,
+    client_builder: reqwest::ClientBuilder,
+    endpoints: Endpoints,
+    cassette: Option<Cassette>,
+    har: Option<Arc<HarArchive>>,
+    tor: Option<TorConfig>,
+    user_agent: String,
+    user_agent_rotator: Option<Arc<UserAgentRotator>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    header_profile: Option<HeaderProfile>,
+    cookie_jar: Option<(Arc<CookieJar>, PathBuf)>,
+    max_body_size: usize,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    throttle: Option<Arc<ThrottlePolicy>>,
+    timings: Option<Arc<Timings>>,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<Metrics>>,
+    raw_dump: Option<Arc<RawDump>>,
+    parse_mode: ParseMode,
+    with_raw: bool,
+    cancel: Option<CancellationToken>,
+}
+
+impl Default for BrowserBuilder {
+    fn default() -> Self {
+        BrowserBuilder {
+            client: None,
+            client_builder: reqwest::ClientBuilder::default(),
+            endpoints: Endpoints::default(),
+            cassette: None,
+            har: None,
+            tor: None,
+            user_agent: get("firefox").unwrap_or_default(),
+            user_agent_rotator: None,
+            middleware: Vec::new(),
+            header_profile: None,
+            cookie_jar: None,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            rate_limiter: None,
+            circuit_breaker: None,
+            throttle: None,
+            timings: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+            raw_dump: None,
+            parse_mode: ParseMode::default(),
+            with_raw: false,
+            cancel: None,
+        }
+    }
+}
+
+impl BrowserBuilder {
+    /// Creates a new `BrowserBuilder` with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the HTTP client to be used for making requests.
+    ///
+    /// A client set this way is used as-is: the client options of this builder (user agent,
+    /// cookies, proxies, Tor) are ignored.
+    ///
+    /// # Arguments
+    /// * `client` - The reqwest HTTP client.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Sets the default `User-Agent` header of the HTTP client, also sent with the requests
+    /// that are not given one, such as Instant Answer API queries.
+    ///
+    /// # Arguments
+    /// * `user_agent` - The user agent string.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.client_builder = self.client_builder.user_agent(user_agent.to_string());
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Rotates the user agent of requests with the given rotator.
+    ///
+    /// # Arguments
+    /// * `rotator` - The rotator choosing the user agent of each request.
+    pub fn user_agent_rotator(mut self, rotator: UserAgentRotator) -> Self {
+        self.user_agent_rotator = Some(Arc::new(rotator));
+        self
+    }
+
+    /// Forces the header profile sent with requests.
+    ///
+    /// By default, the profile is detected from the user agent of each request. Use
+    /// `HeaderProfile::Generic` to only send the basic headers.
+    ///
+    /// # Arguments
+    /// * `profile` - The header profile to send.
+    pub fn header_profile(mut self, profile: HeaderProfile) -> Self {
+        self.header_profile = Some(profile);
+        self
+    }
+
+    /// Enables or disables the in-memory cookie store of the HTTP client.
+    ///
+    /// # Arguments
+    /// * `enable` - Whether cookies are stored and sent with subsequent requests.
+    pub fn cookie_store(mut self, enable: bool) -> Self {
+        self.client_builder = self.client_builder.cookie_store(enable);
+        self
+    }
+
+    /// Persists cookies in the given file, loading them when the browser is built and saving
+    /// them with `Browser::save_cookies`.
+    ///
+    /// This keeps sessions and DuckDuckGo preference cookies (`kl`, `kp`) across runs, and
+    /// replaces the in-memory store enabled by `cookie_store`.
+    ///
+    /// # Arguments
+    /// * `path` - The cookie file, created on the first save if missing.
+    ///
+    /// # Returns
+    /// The builder, or an error if the file exists but cannot be read.
+    pub fn cookie_path(mut self, path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let jar = Arc::new(CookieJar::load(&path)?);
+        self.client_builder = self.client_builder.cookie_provider(jar.clone());
+        self.cookie_jar = Some((jar, path));
+        Ok(self)
+    }
+
+    /// Routes requests through the given proxy.
+    ///
+    /// # Arguments
+    /// * `proxy` - The proxy to send requests through.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.client_builder = self.client_builder.proxy(proxy);
+        self
+    }
+
+    /// Routes requests through the proxies described by the given settings.
+    ///
+    /// # Arguments
+    /// * `config` - The proxy settings (per-scheme proxies, credentials, bypassed hosts).
+    ///
+    /// # Returns
+    /// The builder, or an error if a proxy URL is invalid.
+    pub fn proxies(mut self, config: &ProxyConfig) -> Result<Self> {
+        for proxy in config.build()? {
+            self.client_builder = self.client_builder.proxy(proxy);
+        }
+        Ok(self)
+    }
+
+    /// Trusts the certificate authorities in the given PEM file, in addition to the system ones.
+    ///
+    /// This is needed behind TLS-intercepting proxies that sign certificates with a private CA.
+    ///
+    /// # Arguments
+    /// * `path` - The path of a PEM file holding one or more certificates.
+    ///
+    /// # Returns
+    /// The builder, or an error if the file cannot be read or holds no valid certificate.
+    pub fn ca_cert(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let pem = fs::read(path)
+            .with_context(|| format!("Failed to read CA certificate {}", path.display()))?;
+        let certs = reqwest::Certificate::from_pem_bundle(&pem)
+            .with_context(|| format!("Invalid CA certificate {}", path.display()))?;
+        if certs.is_empty() {
+            bail!("No certificate found in {}", path.display());
+        }
+
+        for cert in certs {
+            self.client_builder = self.client_builder.add_root_certificate(cert);
+        }
+        Ok(self)
+    }
+
+    /// Sets the minimum TLS version accepted by the HTTP client.
+    ///
+    /// # Arguments
+    /// * `version` - The minimum TLS version.
+    pub fn min_tls_version(mut self, version: reqwest::tls::Version) -> Self {
+        self.client_builder = self.client_builder.min_tls_version(version);
+        self
+    }
+
+    /// Sets the maximum TLS version accepted by the HTTP client.
+    ///
+    /// # Arguments
+    /// * `version` - The maximum TLS version.
+    pub fn max_tls_version(mut self, version: reqwest::tls::Version) -> Self {
+        self.client_builder = self.client_builder.max_tls_version(version);
+        self
+    }
+
+    /// Disables the validation of TLS certificates.
+    ///
+    /// **Warning:** any certificate is trusted, including expired ones and ones issued for other
+    /// hosts, which makes requests open to man-in-the-middle attacks. Prefer `ca_cert` whenever
+    /// possible.
+    ///
+    /// # Arguments
+    /// * `accept` - Whether invalid certificates are accepted.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.client_builder = self.client_builder.danger_accept_invalid_certs(accept);
+        self
+    }
+
+    /// Binds outgoing connections to the given local IP address.
+    ///
+    /// Binding to an unspecified address restricts connections to its IP family, so
+    /// `Ipv4Addr::UNSPECIFIED` forces IPv4 and `Ipv6Addr::UNSPECIFIED` forces IPv6.
+    ///
+    /// # Arguments
+    /// * `addr` - The local address to bind to.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::browser::Browser;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// // Avoid a broken IPv6 uplink.
+    /// let browser = Browser::builder()
+    ///     .local_address(Ipv4Addr::UNSPECIFIED.into())
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn local_address(mut self, addr: IpAddr) -> Self {
+        self.client_builder = self.client_builder.local_address(addr);
+        self
+    }
+
+    /// Sends requests from the network interface with the given IP address.
+    ///
+    /// The HTTP client can only bind to local addresses, so interfaces must be given by one of
+    /// their IP addresses rather than by name.
+    ///
+    /// # Arguments
+    /// * `interface` - The IP address of the interface (e.g. `"192.168.1.10"`).
+    ///
+    /// # Returns
+    /// The builder, or an error if `interface` is not an IP address.
+    pub fn interface(self, interface: &str) -> Result<Self> {
+        let addr = interface.parse::<IpAddr>().with_context(|| {
+            format!(
+                "Invalid interface '{}': binding by name is not supported, use one of its IP \
+                 addresses",
+                interface
+            )
+        })?;
+        Ok(self.local_address(addr))
+    }
+
+    /// Routes requests through Tor.
+    ///
+    /// # Arguments
+    /// * `tor` - The Tor proxy and, optionally, the control port used to renew circuits.
+    pub fn tor(mut self, tor: TorConfig) -> Self {
+        self.tor = Some(tor);
+        self
+    }
+
+    /// Sets the base URLs of the DuckDuckGo endpoints.
+    ///
+    /// # Arguments
+    /// * `endpoints` - The endpoints to send requests to.
+    pub fn endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+
+    /// Records HTTP interactions to, or replays them from, the given cassette.
+    ///
+    /// # Arguments
+    /// * `cassette` - The cassette to record to or replay from.
+    pub fn cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Captures the HTTP traffic into, or replays it from, the given HAR file.
+    ///
+    /// # Arguments
+    /// * `archive` - The archive to capture into or replay from.
+    pub fn har(mut self, archive: HarArchive) -> Self {
+        self.har = Some(Arc::new(archive));
+        self
+    }
+
+    /// Sets the maximum size of a response body, guarding against endpoints or proxies that
+    /// stream enormous bodies.
+    ///
+    /// # Arguments
+    /// * `bytes` - The maximum body size, in bytes (`DEFAULT_MAX_BODY_SIZE` by default).
+    pub fn max_body_size(mut self, bytes: usize) -> Self {
+        self.max_body_size = bytes;
+        self
+    }
+
+    /// Limits the number of requests sent per minute, across all backends and pagination loops.
+    ///
+    /// Requests exceeding the limit wait for their turn.
+    ///
+    /// # Arguments
+    /// * `requests_per_minute` - The maximum number of requests per minute.
+    ///
+    /// # Panics
+    /// Panics if `requests_per_minute` is zero.
+    pub fn rate_limit(self, requests_per_minute: u32) -> Self {
+        self.rate_limiter(RateLimiter::per_minute(requests_per_minute))
+    }
+
+    /// Sets the rate limiter all requests wait for.
+    ///
+    /// # Arguments
+    /// * `limiter` - The rate limiter, e.g. with a custom interval or burst size.
+    pub fn rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(Arc::new(limiter));
+        self
+    }
+
+    /// Suspends requests for `cooldown` after `threshold` consecutive failures.
+    ///
+    /// While the circuit is open, requests fail fast with `Error::CircuitOpen`. The breaker is
+    /// available as `Browser::circuit_breaker` to inspect its state or wait for it to close.
+    ///
+    /// # Arguments
+    /// * `threshold` - The number of consecutive failures opening the circuit.
+    /// * `cooldown` - How long the circuit stays open.
+    pub fn circuit_breaker(mut self, threshold: u32, cooldown: Duration) -> Self {
+        self.circuit_breaker = Some(Arc::new(CircuitBreaker::new(threshold, cooldown)));
+        self
+    }
+
+    /// Spaces out the pages of image and news searches with a delay, slowing down when
+    /// responses get slow or DuckDuckGo starts rate limiting.
+    ///
+    /// # Arguments
+    /// * `policy` - The inter-page delay, jitter, and adaptive slow-down settings.
+    pub fn throttle(mut self, policy: ThrottlePolicy) -> Self {
+        self.throttle = Some(Arc::new(policy));
+        self
+    }
+
+    /// Enables or disables the collection of per-phase request timings (DNS, time to first
+    /// byte, download, parse), available as `Browser::timings`.
+    ///
+    /// DNS lookups are only timed when the browser resolves host names itself, i.e. not through
+    /// a `socks5h` proxy or a client set with `client`.
+    ///
+    /// # Arguments
+    /// * `enable` - Whether timings are collected.
+    pub fn timings(mut self, enable: bool) -> Self {
+        if enable {
+            let timings = Arc::new(Timings::new());
+            self.client_builder = self
+                .client_builder
+                .dns_resolver(Arc::new(TimingResolver(timings.clone())));
+            self.timings = Some(timings);
+        } else {
+            self.timings = None;
+        }
+        self
+    }
+
+    /// Enables or disables the collection of request metrics (upstream requests and latency,
+    /// rate limiter waits, parse failures, and bot challenges), available as
+    /// `Browser::metrics`.
+    ///
+    /// # Arguments
+    /// * `enable` - Whether metrics are collected.
+    #[cfg(feature = "metrics")]
+    pub fn metrics(mut self, enable: bool) -> Self {
+        self.metrics = enable.then(|| Arc::new(Metrics::new()));
+        self
+    }
+
+    /// Saves the raw body of every DuckDuckGo response into the given directory, and mentions
+    /// the saved file in the errors of the responses that could not be parsed.
+    ///
+    /// # Arguments
+    /// * `dir` - The directory, created on the first response.
+    pub fn dump_raw(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.raw_dump = Some(Arc::new(RawDump::new(dir)));
+        self
+    }
+
+    /// Sets how the malformed items of the image and news APIs are handled: skipped and
+    /// reported as warnings of the page (`ParseMode::Lenient`, the default), or failing the
+    /// search (`ParseMode::Strict`).
+    ///
+    /// # Arguments
+    /// * `mode` - The parse mode.
+    pub fn parse_mode(mut self, mode: ParseMode) -> Self {
+        self.parse_mode = mode;
+        self
+    }
+
+    /// Keeps the original JSON documents of the Instant Answer API, image, and news responses
+    /// in `Response::raw` and `Page::raw`, to access the upstream fields the typed results do
+    /// not model without sending the requests again.
+    ///
+    /// # Arguments
+    /// * `enable` - Whether the documents are kept.
+    pub fn with_raw(mut self, enable: bool) -> Self {
+        self.with_raw = enable;
+        self
+    }
+
+    /// Stops the image and news searches from fetching more pages once the token is cancelled,
+    /// e.g. on Ctrl-C, returning the results fetched so far instead.
+    ///
+    /// # Arguments
+    /// * `token` - The token cancelling the searches.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    /// use duckduckgo::params::SearchParams;
+    /// use tokio_util::sync::CancellationToken;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let interrupt = CancellationToken::new();
+    ///     let browser = Browser::builder().cancel_on(interrupt.clone()).build()?;
+    ///
+    ///     tokio::spawn(async move {
+    ///         if tokio::signal::ctrl_c().await.is_ok() {
+    ///             interrupt.cancel();
+    ///         }
+    ///     });
+    ///
+    ///     let images = browser.images(&SearchParams::new("rustacean").limit(1000)).await?;
+    ///     if browser.is_cancelled() {
+    ///         println!("Interrupted with {} results", images.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn cancel_on(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Adds a middleware run around every HTTP call made by the browser.
+    ///
+    /// Middleware runs in the order it is added.
+    ///
+    /// # Arguments
+    /// * `middleware` - The middleware to add.
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Builds the `Browser`.
+    ///
+    /// # Returns
+    /// A `Result` containing the `Browser`, or an error if the HTTP client cannot be created.
+    pub fn build(self) -> Result<Browser> {
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                let mut client_builder = self.client_builder;
+                if let Some(tor) = &self.tor {
+                    client_builder = client_builder.proxy(reqwest::Proxy::all(&tor.proxy)?);
+                }
+                client_builder.build()?
+            }
+        };
+
+        Ok(Browser {
+            client,
+            endpoints: self.endpoints,
+            cassette: self.cassette,
+            har: self.har,
+            tor: self.tor,
+            user_agent: self.user_agent,
+            user_agent_rotator: self.user_agent_rotator,
+            middleware: self.middleware,
+            header_profile: self.header_profile,
+            cookie_jar: self.cookie_jar,
+            max_body_size: self.max_body_size,
+            rate_limiter: self.rate_limiter,
+            circuit_breaker: self.circuit_breaker,
+            throttle: self.throttle,
+            timings: self.timings,
+            #[cfg(feature = "metrics")]
+            metrics: self.metrics,
+            raw_dump: self.raw_dump,
+            parse_mode: self.parse_mode,
+            with_raw: self.with_raw,
+            accounting: Arc::new(Accounting::new()),
+            cancel: self.cancel,
+        })
+    }
 }
 
 impl Browser {
@@ -32,7 +722,89 @@ impl Browser {
     /// let browser = Browser::new(client);
     /// ```
     pub fn new(client: reqwest::Client) -> Self {
-        Browser { client }
+        Browser {
+            client,
+            endpoints: Endpoints::default(),
+            cassette: None,
+            har: None,
+            tor: None,
+            user_agent: get("firefox").unwrap_or_default(),
+            user_agent_rotator: None,
+            middleware: Vec::new(),
+            header_profile: None,
+            cookie_jar: None,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            rate_limiter: None,
+            circuit_breaker: None,
+            throttle: None,
+            timings: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+            raw_dump: None,
+            parse_mode: ParseMode::default(),
+            with_raw: false,
+            accounting: Arc::new(Accounting::new()),
+            cancel: None,
+        }
+    }
+
+    /// Creates a `BrowserBuilder` for configuring a new `Browser`.
+    pub fn builder() -> BrowserBuilder {
+        BrowserBuilder::new()
+    }
+
+    /// Returns `true` if the token set with `BrowserBuilder::cancel_on` was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// Returns the totals of the requests made by this browser and its clones so far: the
+    /// requests sent, the bytes downloaded, the pages of results fetched, and the requests
+    /// answered from a cache.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    /// use duckduckgo::params::SearchParams;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     for query in ["rust", "tokio", "serde"] {
+    ///         browser.html_search(&SearchParams::new(query)).await?;
+    ///     }
+    ///     println!("{}", browser.stats());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn stats(&self) -> RequestStats {
+        self.accounting.stats()
+    }
+
+    /// Saves the cookies of the jar set with `BrowserBuilder::cookie_path` to its file, if any.
+    ///
+    /// Call it once the requests of a session are done, e.g. at the end of a run.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    /// use duckduckgo::params::SearchParams;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::builder().cookie_path("cookies.json")?.build()?;
+    ///     browser.html_search(&SearchParams::new("rust")).await?;
+    ///     browser.save_cookies()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn save_cookies(&self) -> Result<()> {
+        match &self.cookie_jar {
+            Some((jar, path)) => jar.save(path),
+            None => Ok(()),
+        }
     }
 
     /// Sends an HTTP request to the given URL using the specified method and query parameters.
@@ -55,7 +827,7 @@ impl Browser {
     /// async fn main() -> anyhow::Result<()> {
     ///     let browser = Browser::new(reqwest::Client::new());
     ///     let user_agent = get("firefox").unwrap();
-    ///     let response = browser.request(Method::GET, "https://api.duckduckgo.com", user_agent, &[("test", "123")]).await?;
+    ///     let response = browser.request(Method::GET, "https://api.duckduckgo.com", &user_agent, &[("test", "123")]).await?;
     ///     assert!(response.status().is_success());
     ///     Ok(())
     /// }
@@ -67,236 +839,1232 @@ impl Browser {
         user_agent: &str,
         params: &[(&str, &str)],
     ) -> Result<reqwest::Response> {
-        let req = self
+        let rotated = self.user_agent_rotator.as_ref().map(|r| r.for_request());
+        let user_agent = rotated.as_deref().unwrap_or(user_agent);
+
+        let req = self.prepare_request(method, url, user_agent, params);
+        let resp = self.send(req).await?;
+        if let Some(err) = resp.error_for_status_ref().err() {
+            let retry_after = resp
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+                .map(Duration::from_secs);
+            // Challenge pages may come with an error status; report them as such.
+            self.read_body(resp).await?;
+            return Err(match retry_after {
+                Some(delay) => anyhow::Error::from(err).context(RetryAfter(delay)),
+                None => err.into(),
+            });
+        }
+        Ok(resp)
+    }
+
+    /// Prepares a request to a DuckDuckGo endpoint with the user agent and the headers of its
+    /// profile, as sent by `request`.
+    pub(crate) fn prepare_request(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        user_agent: &str,
+        params: &[(&str, &str)],
+    ) -> reqwest::RequestBuilder {
+        let profile = self
+            .header_profile
+            .unwrap_or_else(|| HeaderProfile::detect(user_agent));
+
+        let mut req = self
             .client
             .request(method, url)
             .query(params)
             .header("User-Agent", user_agent)
-            .header("Accept", "application/json")
-            .header("Referer", "https://duckduckgo.com/")
-            .header("Accept-Language", "en-US,en;q=0.9");
-
-        let resp = req.send().await?.error_for_status()?;
-        Ok(resp)
+            .header("Referer", "https://duckduckgo.com/");
+        for (name, value) in profile.headers(user_agent) {
+            req = req.header(name, value);
+        }
+        req
     }
 
-    /// Retrieves the `vqd` token required for JavaScript-based DuckDuckGo API endpoints.
-    ///
-    /// # Arguments
-    /// * `query` - The search query string.
-    ///
-    /// # Returns
-    /// A `Result` containing the extracted `vqd` string or an error if not found.
+    /// Sends a prepared request through the rate limiter and the middleware.
     ///
-    /// # Example
-    /// ```rust
-    /// use duckduckgo::browser::Browser;
-    /// use duckduckgo::user_agents::get;
-    ///
-    ///
-    /// #[tokio::main]
-    /// async fn main() -> anyhow::Result<()> {
-    ///     let browser = Browser::new(reqwest::Client::new());
-    ///     let user_agent = get("firefox").unwrap();
-    ///     let vqd = browser.get_vqd("rust programming", user_agent).await?;
-    ///     assert!(!vqd.is_empty());
-    ///     Ok(())
-    /// }
-    /// ```
-    pub async fn get_vqd(&self, query: &str, user_agent: &str) -> Result<String> {
-        let resp = self
+    /// All requests made by `Browser` go through this method.
+    async fn send(&self, builder: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let mut request = builder.build()?;
+        for middleware in &self.middleware {
+            middleware.on_request(&mut request)?;
+        }
+        let (method, url) = (request.method().clone(), request.url().clone());
+
+        let replaying = self.is_replaying();
+        if let Some(limiter) = self.rate_limiter.as_ref().filter(|_| !replaying) {
+            #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+            let wait = limiter.acquire().await;
+            #[cfg(feature = "metrics")]
+            if let Some(metrics) = self.metrics.as_ref().filter(|_| !wait.is_zero()) {
+                metrics.record_rate_limit_wait(wait);
+            }
+        }
+
+        if let Some(breaker) = &self.circuit_breaker {
+            breaker.check()?;
+        }
+
+        let start = Instant::now();
+        let response = self.execute(request).await;
+        let elapsed = start.elapsed();
+
+        if let Some(breaker) = &self.circuit_breaker {
+            match &response {
+                Ok(response) if is_upstream_failure(response.status()) => breaker.record_failure(),
+                Ok(_) => breaker.record_success(),
+                Err(_) => breaker.record_failure(),
+            }
+        }
+        if let Some(throttle) = self.throttle.as_ref().filter(|_| !replaying) {
+            match &response {
+                Ok(response) => throttle.record_response(response.status(), elapsed),
+                Err(_) => throttle.slow_down(),
+            }
+        }
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            let status = response.as_ref().ok().map(|r| r.status().as_u16());
+            metrics.record_request(
+                endpoint_name(&self.endpoints, url.as_str()),
+                status,
+                elapsed,
+            );
+        }
+        let mut response = response?;
+        #[cfg(any(
+            feature = "api",
+            feature = "lite",
+            feature = "html",
+            feature = "images",
+            feature = "news"
+        ))]
+        response.extensions_mut().insert(Latency(elapsed));
+
+        if let Some(timings) = &self.timings {
+            let id = timings.record_request(&method, &url, response.status().as_u16(), elapsed);
+            response.extensions_mut().insert(id);
+        }
+        for middleware in &self.middleware {
+            middleware.on_response(&response, elapsed);
+        }
+        Ok(response)
+    }
+
+    /// Returns whether responses are replayed from a cassette or a HAR file instead of being
+    /// requested, in which case the rate limiter and the throttle are skipped.
+    fn is_replaying(&self) -> bool {
+        self.cassette
+            .as_ref()
+            .is_some_and(|cassette| cassette.mode == VcrMode::Replay)
+            || self.har.as_ref().is_some_and(|har| har.is_replay())
+    }
+
+    /// Executes a request, replaying it from or capturing it into the HAR file, if any.
+    async fn execute(&self, request: reqwest::Request) -> Result<reqwest::Response> {
+        let Some(har) = &self.har else {
+            return self.execute_cassette(request).await;
+        };
+        if har.is_replay() {
+            self.accounting.record_cache_hit();
+            let mut response = har.load(&request)?;
+            response.extensions_mut().insert(Cached);
+            return Ok(response);
+        }
+
+        let captured = request.try_clone().context("Request cannot be captured")?;
+        let started = SystemTime::now();
+        let start = Instant::now();
+        let response = self.execute_cassette(request).await?;
+        har.save(&captured, response, started, start.elapsed())
+            .await
+    }
+
+    /// Executes a request, replaying or recording it if a cassette is configured.
+    async fn execute_cassette(&self, request: reqwest::Request) -> Result<reqwest::Response> {
+        let Some(cassette) = &self.cassette else {
+            self.accounting.record_request();
+            return Ok(self.client.execute(request).await?);
+        };
+
+        if let Some(mut response) = cassette.load(&request)? {
+            self.accounting.record_cache_hit();
+            response.extensions_mut().insert(Cached);
+            return Ok(response);
+        }
+
+        self.accounting.record_request();
+        let recorded = request.try_clone().context("Request cannot be recorded")?;
+        let response = self.client.execute(request).await?;
+        cassette.save(&recorded, response).await
+    }
+
+    /// Reads the body of a response, saving it to the raw dump directory, if any, and failing
+    /// with `Error::BotDetection` when DuckDuckGo served its challenge page instead of results.
+    ///
+    /// Returns the body and the path of the file it was saved to.
+    async fn read_body(&self, response: reqwest::Response) -> Result<(String, Option<PathBuf>)> {
+        let url = response.url().to_string();
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from);
+        let bytes = self.read_bytes(response).await?;
+
+        let body = String::from_utf8_lossy(&bytes).into_owned();
+        let dump = match &self.raw_dump {
+            Some(dump) => Some(dump.save(&url, content_type.as_deref(), &body)?),
+            None => None,
+        };
+        if is_bot_challenge(&body) {
+            #[cfg(feature = "metrics")]
+            if let Some(metrics) = &self.metrics {
+                metrics.record_bot_detection(endpoint_name(&self.endpoints, &url));
+            }
+            if let Some(breaker) = &self.circuit_breaker {
+                breaker.record_failure();
+            }
+            if let Some(throttle) = &self.throttle {
+                throttle.slow_down();
+            }
+            let err = anyhow::Error::from(Error::BotDetection { url });
+            return Err(match dump {
+                Some(path) => err.context(format!("Challenge page saved to {}", path.display())),
+                None => err,
+            });
+        }
+        Ok((body, dump))
+    }
+
+    /// Reads the raw body of a response, streaming it so that at most `max_body_size` bytes
+    /// are buffered.
+    ///
+    /// Fails with `Error::BodyTooLarge` when the body exceeds the limit.
+    async fn read_bytes(&self, mut response: reqwest::Response) -> Result<Vec<u8>> {
+        let start = Instant::now();
+        let timing_id = response.extensions().get::<TimingId>().copied();
+        let cached = response.extensions().get::<Cached>().is_some();
+        let url = response.url().to_string();
+        let too_large = || Error::BodyTooLarge {
+            url: url.clone(),
+            limit: self.max_body_size,
+        };
+
+        if response
+            .content_length()
+            .is_some_and(|len| len > self.max_body_size as u64)
+        {
+            return Err(too_large().into());
+        }
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .context("Failed to read response body")?
+        {
+            if bytes.len() + chunk.len() > self.max_body_size {
+                return Err(too_large().into());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        if !cached {
+            self.accounting.record_bytes(bytes.len());
+        }
+        if let (Some(timings), Some(id)) = (&self.timings, timing_id) {
+            timings.record_download(id, start.elapsed());
+        }
+        Ok(bytes)
+    }
+
+    /// Reads the body of a response and parses it with `parse`, timing both phases if timings
+    /// are enabled.
+    ///
+    /// Parse errors mention the file the body was saved to, if raw responses are dumped.
+    async fn parse_body<T>(
+        &self,
+        response: reqwest::Response,
+        parse: impl FnOnce(&str) -> Result<T>,
+    ) -> Result<T> {
+        let timing_id = response.extensions().get::<TimingId>().copied();
+        #[cfg(feature = "metrics")]
+        let endpoint = endpoint_name(&self.endpoints, response.url().as_str());
+        let url = response.url().to_string();
+        let (body, dump) = self.read_body(response).await?;
+
+        let start = Instant::now();
+        // Errors of this crate, such as malformed items, already tell what went wrong.
+        let parsed = parse(&body).map_err(|err| match err.downcast_ref::<Error>() {
+            Some(_) => err,
+            None => err.context(InvalidResponse { url }),
+        });
+        if let (Some(timings), Some(id)) = (&self.timings, timing_id) {
+            timings.record_parse(id, start.elapsed());
+        }
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = self.metrics.as_ref().filter(|_| parsed.is_err()) {
+            metrics.record_parse_failure(endpoint);
+        }
+        match dump {
+            Some(path) => {
+                parsed.with_context(|| format!("Failed to parse raw response {}", path.display()))
+            }
+            None => parsed,
+        }
+    }
+
+    /// Retrieves the `vqd` token required for JavaScript-based DuckDuckGo API endpoints.
+    ///
+    /// # Arguments
+    /// * `query` - The search query string.
+    ///
+    /// # Returns
+    /// A `Result` containing the extracted `vqd` string or an error if not found.
+    ///
+    /// # Example
+    /// ```rust
+    /// use duckduckgo::browser::Browser;
+    /// use duckduckgo::user_agents::get;
+    ///
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     let user_agent = get("firefox").unwrap();
+    ///     let vqd = browser.get_vqd("rust programming", &user_agent).await?;
+    ///     assert!(!vqd.is_empty());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(any(feature = "images", feature = "news"))]
+    pub async fn get_vqd(&self, query: &str, user_agent: &str) -> Result<String> {
+        let resp = self
             .request(
                 reqwest::Method::GET,
-                "https://duckduckgo.com/",
+                &self.endpoints.vqd,
                 user_agent,
                 &[("q", query)],
             )
             .await?;
 
-        let text = resp.text().await?;
+        self.parse_body(resp, parse_vqd).await
+    }
+
+    /// Checks whether requests made by this browser leave through the Tor network.
+    ///
+    /// # Returns
+    /// A `Result` containing the `TorStatus` reported by the Tor Project check service.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    /// use duckduckgo::tor::TorConfig;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::builder().tor(TorConfig::default()).build()?;
+    ///     let status = browser.check_tor().await?;
+    ///     assert!(status.is_tor, "exit IP {} is not a Tor exit node", status.ip);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn check_tor(&self) -> Result<TorStatus> {
+        let response = self
+            .send(self.client.get(TOR_CHECK_URL))
+            .await
+            .context("Failed to reach the Tor check service")?
+            .error_for_status()?;
+        let status = self
+            .parse_body(response, |body| Ok(serde_json::from_str(body)?))
+            .await?;
+
+        Ok(status)
+    }
+
+    /// Probes each DuckDuckGo endpoint enabled by the crate features with a search for
+    /// `PROBE_QUERY`, through the client settings of this browser (proxy, user agent, and
+    /// headers).
+    ///
+    /// The endpoints are probed one after the other. Failures, including bot challenges, are
+    /// reported in the checks instead of being returned, and the image and news APIs are
+    /// probed with the `vqd` token of the `vqd` check.
+    ///
+    /// # Arguments
+    /// * `user_agent` - The user agent of the probes, unless a rotator is configured.
+    ///
+    /// # Returns
+    /// The outcome of each probe, in the order they were sent.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    /// use duckduckgo::user_agents::get;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     let user_agent = get("firefox").unwrap();
+    ///     for check in browser.check_endpoints(&user_agent).await {
+    ///         println!("{}", check);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(any(
+        feature = "api",
+        feature = "lite",
+        feature = "html",
+        feature = "images",
+        feature = "news"
+    ))]
+    pub async fn check_endpoints(&self, user_agent: &str) -> Vec<EndpointCheck> {
+        let mut checks = Vec::new();
+        #[cfg(feature = "api")]
+        checks.push(
+            self.probe_endpoint(
+                "api",
+                reqwest::Method::GET,
+                &self.endpoints.api,
+                user_agent,
+                &[("q", PROBE_QUERY), ("format", "json")],
+            )
+            .await
+            .0,
+        );
+        #[cfg(feature = "lite")]
+        checks.push(
+            self.probe_endpoint(
+                "lite",
+                reqwest::Method::POST,
+                &self.endpoints.lite,
+                user_agent,
+                &[("q", PROBE_QUERY), ("kl", "wt-wt")],
+            )
+            .await
+            .0,
+        );
+        #[cfg(feature = "html")]
+        checks.push(
+            self.probe_endpoint(
+                "html",
+                reqwest::Method::POST,
+                &self.endpoints.html,
+                user_agent,
+                &[("q", PROBE_QUERY), ("kl", "wt-wt")],
+            )
+            .await
+            .0,
+        );
+        #[cfg(any(feature = "images", feature = "news"))]
+        {
+            let (mut check, body) = self
+                .probe_endpoint(
+                    "vqd",
+                    reqwest::Method::GET,
+                    &self.endpoints.vqd,
+                    user_agent,
+                    &[("q", PROBE_QUERY)],
+                )
+                .await;
+            let vqd = match body.map(|body| parse_vqd(&body)) {
+                Some(Ok(vqd)) => Some(vqd),
+                Some(Err(err)) => {
+                    check.error = Some(err.to_string());
+                    None
+                }
+                None => None,
+            };
+            checks.push(check);
+
+            let json_endpoints = [
+                #[cfg(feature = "images")]
+                ("images", &self.endpoints.images),
+                #[cfg(feature = "news")]
+                ("news", &self.endpoints.news),
+            ];
+            for (name, url) in json_endpoints {
+                let check = match &vqd {
+                    Some(vqd) => {
+                        let params = [
+                            ("q", PROBE_QUERY),
+                            ("l", "wt-wt"),
+                            ("vqd", vqd.as_str()),
+                            ("o", "json"),
+                        ];
+                        self.probe_endpoint(name, reqwest::Method::GET, url, user_agent, &params)
+                            .await
+                            .0
+                    }
+                    None => EndpointCheck {
+                        name: name.to_string(),
+                        url: url.clone(),
+                        error: Some(String::from("Skipped without a vqd token")),
+                        ..EndpointCheck::default()
+                    },
+                };
+                checks.push(check);
+            }
+        }
+        checks
+    }
+
+    /// Sends a probe of `check_endpoints`, returning its outcome and, if it succeeded, the
+    /// body of the response.
+    #[cfg(any(
+        feature = "api",
+        feature = "lite",
+        feature = "html",
+        feature = "images",
+        feature = "news"
+    ))]
+    async fn probe_endpoint(
+        &self,
+        name: &str,
+        method: reqwest::Method,
+        url: &str,
+        user_agent: &str,
+        params: &[(&str, &str)],
+    ) -> (EndpointCheck, Option<String>) {
+        let rotated = self.user_agent_rotator.as_ref().map(|r| r.for_request());
+        let user_agent = rotated.as_deref().unwrap_or(user_agent);
+        let mut check = EndpointCheck {
+            name: name.to_string(),
+            url: url.to_string(),
+            ..EndpointCheck::default()
+        };
+
+        let req = self.prepare_request(method, url, user_agent, params);
+        let response = match self.send(req).await {
+            Ok(response) => response,
+            Err(err) => {
+                check.error = Some(format!("{:#}", err));
+                return (check, None);
+            }
+        };
+        check.latency = response
+            .extensions()
+            .get::<Latency>()
+            .map(|latency| latency.0);
+        let status = response.status();
+        check.status = Some(status.as_u16());
 
-        let re = Regex::new(r#"vqd=.?['"]?([\d-]+)['"]?"#)?;
+        match self.read_body(response).await {
+            Ok((body, _)) if status.is_success() => (check, Some(body)),
+            Ok(_) => {
+                check.error = Some(format!("HTTP {}", status));
+                (check, None)
+            }
+            Err(err) => {
+                if let Some(Error::BotDetection { .. }) = err.downcast_ref::<Error>() {
+                    check.bot_detection = true;
+                } else {
+                    check.error = Some(format!("{:#}", err));
+                }
+                (check, None)
+            }
+        }
+    }
+
+    /// Returns the favicon of a domain, downloading it from DuckDuckGo's icon service unless it
+    /// is already cached.
+    ///
+    /// # Arguments
+    /// * `domain` - The domain of the site (e.g. `"www.rust-lang.org"`).
+    /// * `cache` - The cache the icon is looked up in and saved to.
+    ///
+    /// # Returns
+    /// The favicon, with the path of the cached icon.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    /// use duckduckgo::favicon::FaviconCache;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     let favicon = browser.favicon("www.rust-lang.org", &FaviconCache::default()).await?;
+    ///     assert!(favicon.path.is_some_and(|path| path.exists()));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn favicon(&self, domain: &str, cache: &FaviconCache) -> Result<Favicon> {
+        let url = format!("{}{}.ico", self.endpoints.icons, domain);
+        let path = match cache.get(domain) {
+            Some(path) => {
+                self.accounting.record_cache_hit();
+                path
+            }
+            None => {
+                let response = self
+                    .send(self.client.get(&url))
+                    .await
+                    .with_context(|| format!("Failed to fetch the favicon of {}", domain))?
+                    .error_for_status()?;
+                let icon = self.read_bytes(response).await?;
+                cache.store(domain, &icon)?
+            }
+        };
 
-        let vqd = re
-            .captures(&text)
-            .and_then(|c| c.get(1).map(|m| m.as_str().to_string()))
-            .context("Missing vqd in response")?;
+        Ok(Favicon {
+            url,
+            path: Some(path),
+        })
+    }
 
-        Ok(vqd)
+    /// Resolves the favicon of the site of each result, downloading each icon once.
+    ///
+    /// Results whose icon cannot be downloaded still get the URL of their icon, without a
+    /// path.
+    ///
+    /// # Arguments
+    /// * `results` - The search results to set the `favicon` of.
+    /// * `cache` - The cache the icons are looked up in and saved to.
+    pub async fn resolve_favicons(&self, results: &mut [SearchResult], cache: &FaviconCache) {
+        let mut favicons: HashMap<String, Favicon> = HashMap::new();
+        for result in results.iter_mut() {
+            let Some(domain) = result.domain().map(str::to_string) else {
+                continue;
+            };
+            if !favicons.contains_key(&domain) {
+                let favicon = match self.favicon(&domain, cache).await {
+                    Ok(favicon) => favicon,
+                    Err(_) => Favicon {
+                        url: format!("{}{}.ico", self.endpoints.icons, domain),
+                        path: None,
+                    },
+                };
+                favicons.insert(domain.clone(), favicon);
+            }
+            *result.favicon_mut() = favicons.get(&domain).cloned();
+        }
+    }
+
+    /// Checks that a URL still works with a `HEAD` request, following redirects.
+    ///
+    /// Servers rejecting `HEAD` requests (with a 405 or 501 status) are retried with `GET`,
+    /// without downloading the body. The request goes to the linked site rather than to
+    /// DuckDuckGo, so it bypasses the rate limiter and the circuit breaker.
+    ///
+    /// # Arguments
+    /// * `url` - The URL to check.
+    ///
+    /// # Returns
+    /// The status code and redirect target of the link, or the error if it is unreachable.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     let check = browser.check_link("https://www.rust-lang.org/").await;
+    ///     assert!(!check.is_dead(), "{}", check);
+    /// }
+    /// ```
+    pub async fn check_link(&self, url: &str) -> LinkCheck {
+        let mut response = self.site_request(reqwest::Method::HEAD, url).await;
+        if let Ok(resp) = &response
+            && matches!(resp.status().as_u16(), 405 | 501)
+        {
+            response = self.site_request(reqwest::Method::GET, url).await;
+        }
+
+        match response {
+            Ok(response) => LinkCheck {
+                status: Some(response.status().as_u16()),
+                redirect: (response.url().as_str() != url).then(|| response.url().to_string()),
+                error: None,
+            },
+            Err(err) => LinkCheck {
+                status: None,
+                redirect: None,
+                error: Some(format!("{:#}", err)),
+            },
+        }
+    }
+
+    /// Sends a request to the site of a search result, through the middleware and the cassette
+    /// but bypassing the rate limiter and the circuit breaker, which protect DuckDuckGo.
+    async fn site_request(&self, method: reqwest::Method, url: &str) -> Result<reqwest::Response> {
+        self.send_site_request(self.client.request(method, url))
+            .await
+    }
+
+    /// Sends a prepared request to the site of a search result (see `site_request`).
+    async fn send_site_request(
+        &self,
+        builder: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response> {
+        let mut request = builder.timeout(SITE_REQUEST_TIMEOUT).build()?;
+        for middleware in &self.middleware {
+            middleware.on_request(&mut request)?;
+        }
+        let start = Instant::now();
+        let response = self.execute(request).await?;
+        for middleware in &self.middleware {
+            middleware.on_response(&response, start.elapsed());
+        }
+        Ok(response)
+    }
+
+    /// Checks the URLs of search results concurrently, setting their `link_check`.
+    ///
+    /// # Arguments
+    /// * `results` - The search results to check.
+    pub async fn check_links(&self, results: &mut [SearchResult]) {
+        let checks: Vec<LinkCheck> = stream::iter(results.iter())
+            .map(|result| self.check_link(result.url()))
+            .buffered(SITE_REQUEST_CONCURRENCY)
+            .collect()
+            .await;
+        for (result, check) in results.iter_mut().zip(checks) {
+            *result.link_check_mut() = Some(check);
+        }
+    }
+
+    /// Verifies the file of an image with a `HEAD` request, recording its status, media type,
+    /// and size.
+    ///
+    /// To read the dimensions of the image, only the start of the file is requested instead,
+    /// with a `Range` header, and read until its header is found. Like link checks, the
+    /// requests bypass the rate limiter and the circuit breaker.
+    ///
+    /// # Arguments
+    /// * `url` - The URL of the image file.
+    /// * `read_dimensions` - Whether to read the width and height of the image.
+    ///
+    /// # Returns
+    /// The outcome of the check, and the width and height of the image if they were read.
+    pub async fn verify_image(
+        &self,
+        url: &str,
+        read_dimensions: bool,
+    ) -> (ImageCheck, Option<(u32, u32)>) {
+        let ranged = || {
+            self.client.get(url).header(
+                reqwest::header::RANGE,
+                format!("bytes=0-{}", IMAGE_HEADER_BYTES - 1),
+            )
+        };
+        let mut response = if read_dimensions {
+            self.send_site_request(ranged()).await
+        } else {
+            self.site_request(reqwest::Method::HEAD, url).await
+        };
+        if !read_dimensions
+            && let Ok(resp) = &response
+            && matches!(resp.status().as_u16(), 405 | 501)
+        {
+            response = self.send_site_request(ranged()).await;
+        }
+
+        let mut response = match response {
+            Ok(response) => response,
+            Err(err) => {
+                let check = ImageCheck {
+                    error: Some(format!("{:#}", err)),
+                    ..Default::default()
+                };
+                return (check, None);
+            }
+        };
+        let headers = response.headers();
+        let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+        let bytes = match header(reqwest::header::CONTENT_RANGE) {
+            // e.g. "bytes 0-65535/1048576"
+            Some(range) => range
+                .rsplit('/')
+                .next()
+                .and_then(|total| total.parse().ok()),
+            None => header(reqwest::header::CONTENT_LENGTH).and_then(|len| len.parse().ok()),
+        };
+        let check = ImageCheck {
+            status: Some(response.status().as_u16()),
+            content_type: header(reqwest::header::CONTENT_TYPE).map(str::to_string),
+            bytes,
+            error: None,
+        };
+        if !read_dimensions || check.is_dead() || response.status().as_u16() >= 300 {
+            return (check, None);
+        }
+
+        let mut start = Vec::new();
+        while start.len() < IMAGE_HEADER_BYTES {
+            match response.chunk().await {
+                Ok(Some(chunk)) => start.extend_from_slice(&chunk),
+                _ => break,
+            }
+            if let Some(dimensions) = image_dimensions(&start) {
+                return (check, Some(dimensions));
+            }
+        }
+        (check, None)
+    }
+
+    /// Verifies the files of image results concurrently, setting their `image_check`, and
+    /// filling in the unknown width and height of the first ones from their files.
+    ///
+    /// The results other than images are left unchanged.
+    ///
+    /// # Arguments
+    /// * `results` - The search results to verify.
+    /// * `read_dimensions` - The number of images missing their width or height to read the
+    ///   dimensions of, in the order of the results.
+    pub async fn verify_images(&self, results: &mut [SearchResult], read_dimensions: usize) {
+        let mut missing = 0;
+        let requests: Vec<(usize, String, bool)> = results
+            .iter()
+            .enumerate()
+            .filter_map(|(index, result)| match result {
+                SearchResult::Image(image) => {
+                    let read = (image.width == 0 || image.height == 0) && missing < read_dimensions;
+                    missing += usize::from(read);
+                    Some((index, image.image.to_string(), read))
+                }
+                _ => None,
+            })
+            .collect();
+        let checks: Vec<_> = stream::iter(requests)
+            .map(|(index, url, read)| async move { (index, self.verify_image(&url, read).await) })
+            .buffered(SITE_REQUEST_CONCURRENCY)
+            .collect()
+            .await;
+        for (index, (check, dimensions)) in checks {
+            if let SearchResult::Image(image) = &mut results[index] {
+                if let Some((width, height)) = dimensions {
+                    image.width = width;
+                    image.height = height;
+                }
+                image.image_check = Some(check);
+            }
+        }
+    }
+
+    /// Downloads a web page and extracts its readable content: its title, byline, and main
+    /// text.
+    ///
+    /// Like link checks, the request bypasses the rate limiter and the circuit breaker.
+    ///
+    /// # Arguments
+    /// * `url` - The URL of the page.
+    ///
+    /// # Returns
+    /// The content of the page, or an error if it cannot be downloaded or is not an HTML page.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     let content = browser.fetch_content("https://www.rust-lang.org/").await?;
+    ///     assert!(content.title.contains("Rust"));
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "content")]
+    pub async fn fetch_content(&self, url: &str) -> Result<PageContent> {
+        let response = self
+            .site_request(reqwest::Method::GET, url)
+            .await
+            .with_context(|| format!("Failed to fetch {}", url))?
+            .error_for_status()?;
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        if !content_type.is_empty() && !content_type.contains("html") {
+            anyhow::bail!("{} is not an HTML page ({})", url, content_type);
+        }
+
+        let final_url = response.url().to_string();
+        let bytes = self.read_bytes(response).await?;
+        Ok(extract_content(
+            &String::from_utf8_lossy(&bytes),
+            &final_url,
+        ))
+    }
+
+    /// Downloads a file from the site of a search result, such as the file of an image result.
+    ///
+    /// Like link checks, the request bypasses the rate limiter and the circuit breaker, and the
+    /// body is limited to `max_body_size` bytes.
+    ///
+    /// # Arguments
+    /// * `url` - The URL of the file.
+    ///
+    /// # Returns
+    /// The content of the file and its media type, if the server sent one, or an error if it
+    /// cannot be downloaded.
+    pub async fn fetch_file(&self, url: &str) -> Result<(Vec<u8>, Option<String>)> {
+        let response = self
+            .site_request(reqwest::Method::GET, url)
+            .await
+            .with_context(|| format!("Failed to fetch {}", url))?
+            .error_for_status()?;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let bytes = self.read_bytes(response).await?;
+        Ok((bytes, content_type))
+    }
+
+    /// Fetches the readable content of the pages of search results concurrently, setting their
+    /// `content`.
+    ///
+    /// The results whose page cannot be fetched are left without content.
+    ///
+    /// # Arguments
+    /// * `results` - The search results to fetch the pages of.
+    #[cfg(feature = "content")]
+    pub async fn fetch_contents(&self, results: &mut [SearchResult]) {
+        let contents: Vec<Option<PageContent>> = stream::iter(results.iter())
+            .map(|result| async { self.fetch_content(result.url()).await.ok() })
+            .buffered(SITE_REQUEST_CONCURRENCY)
+            .collect()
+            .await;
+        for (result, content) in results.iter_mut().zip(contents) {
+            *result.content_mut() = content;
+        }
+    }
+
+    /// Prepares the next page of a paginated search: waits for the throttle, if any, requests a
+    /// new Tor circuit, if a control port is configured, and rotates the user agent, if it
+    /// rotates per page.
+    #[cfg(any(feature = "images", feature = "news"))]
+    async fn next_page(&self) -> Result<()> {
+        let replaying = self.is_replaying();
+        if let Some(throttle) = self.throttle.as_ref().filter(|_| !replaying) {
+            throttle.wait_for_page().await;
+        }
+        if let Some(rotator) = &self.user_agent_rotator {
+            rotator.next_page();
+        }
+        match self.tor.as_ref().and_then(|tor| tor.control.as_ref()) {
+            Some(control) => control.new_circuit(),
+            None => Ok(()),
+        }
     }
 
     /// Performs a search using DuckDuckGo Lite, a text-only HTML interface.
     ///
     /// # Arguments
-    /// * `query` - The search query.
-    /// * `region` - The region code (e.g., `"wt-wt"` for worldwide).
-    /// * `limit` - Optional maximum number of results to return.
+    /// * `params` - The search options (query, region, limit, offset, user agent).
+    ///
+    /// # Returns
+    /// A list of `LiteSearchResult` items.
+    ///
+    /// # Example
+    /// ```rust
+    /// use duckduckgo::browser::Browser;
+    /// use duckduckgo::params::SearchParams;
+    ///
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     let params = SearchParams::new("rust language").limit(3);
+    ///     let results = browser.lite_search(&params).await?;
+    ///     assert!(results.len() <= 3);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "lite")]
+    pub async fn lite_search(
+        &self,
+        params: &SearchParams,
+    ) -> anyhow::Result<Vec<LiteSearchResult>> {
+        let page = self.lite_search_page(params).await?;
+
+        Ok(page.results)
+    }
+
+    /// Performs a DuckDuckGo Lite search and returns the results together with page hints,
+    /// such as the spelling correction DuckDuckGo applied to the query.
+    ///
+    /// # Arguments
+    /// * `params` - The search options (query, region, limit, offset, user agent).
+    ///
+    /// # Returns
+    /// A `SearchPage` holding the results and the suggested `corrected_query`, if any.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    /// use duckduckgo::params::SearchParams;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     let params = SearchParams::new("rust langauge").limit(3);
+    ///     let page = browser.lite_search_page(&params).await?;
+    ///     if let Some(corrected) = page.corrected_query {
+    ///         println!("Did you mean: {}?", corrected);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "lite")]
+    pub async fn lite_search_page(&self, params: &SearchParams) -> anyhow::Result<SearchPage> {
+        let form = text_params(params);
+        let form_ref: Vec<(&str, &str)> = form.iter().map(|(k, v)| (*k, v.as_ref())).collect();
+
+        let resp = self
+            .request(
+                reqwest::Method::POST,
+                &self.endpoints.lite,
+                &params.user_agent,
+                &form_ref,
+            )
+            .await
+            .context("Failed to send request to DuckDuckGo Lite")?;
+
+        let mut page = self
+            .parse_body(resp, |body| parse_lite_page(body, params.limit))
+            .await?;
+        self.accounting.record_page();
+        rank_from(&mut page, params.offset);
+
+        Ok(page)
+    }
+
+    /// Performs a search using DuckDuckGo HTML, the JavaScript-free version of the results page.
+    ///
+    /// Unlike DuckDuckGo Lite, the HTML version also lists related searches, which are returned
+    /// in `SearchPage::related_queries`.
+    ///
+    /// # Arguments
+    /// * `params` - The search options (query, region, limit, offset, user agent).
+    ///
+    /// # Returns
+    /// A `SearchPage` holding the results, the spelling correction, and the related searches.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    /// use duckduckgo::params::SearchParams;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     let params = SearchParams::new("rust language").limit(3);
+    ///     let page = browser.html_search(&params).await?;
+    ///     for related in page.related_queries {
+    ///         println!("{}", related);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "html")]
+    pub async fn html_search(&self, params: &SearchParams) -> Result<SearchPage> {
+        let form = text_params(params);
+        let form_ref: Vec<(&str, &str)> = form.iter().map(|(k, v)| (*k, v.as_ref())).collect();
+
+        let resp = self
+            .request(
+                reqwest::Method::POST,
+                &self.endpoints.html,
+                &params.user_agent,
+                &form_ref,
+            )
+            .await
+            .context("Failed to send request to DuckDuckGo HTML")?;
+
+        let mut page = self
+            .parse_body(resp, |body| parse_html_page(body, params.limit))
+            .await?;
+        self.accounting.record_page();
+        rank_from(&mut page, params.offset);
+
+        Ok(page)
+    }
+
+    /// Performs an image search on DuckDuckGo.
+    ///
+    /// # Arguments
+    /// * `params` - The search options (query, region, limit, offset, user agent).
+    ///
+    /// # Returns
+    /// A list of `ImageResult` items.
+    ///
+    /// # Example
+    /// ```rust
+    /// use duckduckgo::browser::Browser;
+    /// use duckduckgo::params::SearchParams;
+    ///
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     let params = SearchParams::new("rustacean").safe_search(true).limit(5);
+    ///     let images = browser.images(&params).await?;
+    ///     assert!(!images.is_empty());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "images")]
+    pub async fn images(&self, params: &SearchParams) -> Result<Vec<ImageResult>> {
+        Ok(self.images_until(params, None).await?.results)
+    }
+
+    /// Performs an image search that can be cancelled between pages.
+    ///
+    /// The token is checked before each page is fetched, so a cancelled search returns the
+    /// results of the pages fetched so far instead of aborting mid-request.
+    ///
+    /// # Arguments
+    /// * `params` - The search options (query, region, limit, offset, user agent).
+    /// * `cancel` - The token cancelling the search.
     ///
     /// # Returns
-    /// A list of `LiteSearchResult` items.
+    /// The results fetched before the search completed or was cancelled.
     ///
     /// # Example
-    /// ```rust
+    /// ```no_run
     /// use duckduckgo::browser::Browser;
-    /// use duckduckgo::user_agents::get;
-    ///
+    /// use duckduckgo::params::SearchParams;
+    /// use tokio_util::sync::CancellationToken;
     ///
     /// #[tokio::main]
     /// async fn main() -> anyhow::Result<()> {
     ///     let browser = Browser::new(reqwest::Client::new());
-    ///     let user_agent = get("firefox").unwrap();
-    ///     let results = browser.lite_search("rust language", "wt-wt", Some(3), user_agent).await?;
-    ///     assert!(results.len() <= 3);
+    ///     let params = SearchParams::new("rustacean").limit(1000);
+    ///     let cancel = CancellationToken::new();
+    ///
+    ///     let timeout = cancel.clone();
+    ///     tokio::spawn(async move {
+    ///         tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    ///         timeout.cancel();
+    ///     });
+    ///
+    ///     let images = browser.images_cancellable(&params, &cancel).await?;
+    ///     if images.cancelled {
+    ///         println!("Stopped early with {} results", images.results.len());
+    ///     }
     ///     Ok(())
     /// }
     /// ```
-    pub async fn lite_search(
+    #[cfg(feature = "images")]
+    pub async fn images_cancellable(
         &self,
-        query: &str,
-        region: &str,
-        limit: Option<usize>,
-        user_agent: &str,
-    ) -> anyhow::Result<Vec<LiteSearchResult>> {
-        let resp = self
-            .request(
-                reqwest::Method::POST,
-                "https://lite.duckduckgo.com/lite/",
-                user_agent,
-                &[("q", query), ("kl", region)],
-            )
-            .await
-            .context("Failed to send request to DuckDuckGo Lite")?;
-
-        let body = resp.text().await.context("Failed to read response body")?;
-        let doc = Html::parse_document(&body);
-        let sel = Selector::parse("table tr").map_err(|e| anyhow::anyhow!("{e}"))?;
-
-        let mut results = Vec::new();
-        let a_sel = Selector::parse("a").map_err(|e| anyhow::anyhow!("{e}"))?;
-        let snippet_sel =
-            Selector::parse("td.result-snippet").map_err(|e| anyhow::anyhow!("{e}"))?;
-
-        for tr in doc.select(&sel) {
-            if let Some(a) = tr.select(&a_sel).next() {
-                let title = a.text().collect::<String>();
-                if let Some(href) = a.value().attr("href") {
-                    let snippet = tr
-                        .select(&snippet_sel)
-                        .next()
-                        .map(|n| n.text().collect())
-                        .unwrap_or_default();
-
-                    results.push(LiteSearchResult {
-                        title,
-                        url: href.to_string(),
-                        snippet,
-                    });
-
-                    if limit.is_some_and(|l| results.len() >= l) {
-                        break;
-                    }
-                }
-            }
-        }
-
-        Ok(results)
+        params: &SearchParams,
+        cancel: &CancellationToken,
+    ) -> Result<PartialResults<ImageResult>> {
+        self.images_until(params, Some(cancel)).await
     }
 
-    /// Performs an image search on DuckDuckGo.
+    /// Fetches a single page of image results.
+    ///
+    /// Unlike `images`, this does not follow the next pages nor apply `params.limit`; the
+    /// returned cursor lets the caller fetch the next page whenever it wants, e.g. to back its
+    /// own paging UI or to resume a scrape later.
     ///
     /// # Arguments
-    /// * `query` - The search query.
-    /// * `region` - The region code (e.g., `"wt-wt"`).
-    /// * `safesearch` - Whether to enable safe search.
-    /// * `limit` - Optional maximum number of image results.
+    /// * `params` - The search options (query, region, user agent).
+    /// * `cursor` - The cursor returned with the previous page, or `None` for the first page.
     ///
     /// # Returns
-    /// A list of `ImageResult` items.
+    /// The images of the page and the cursor of the next page, if any.
     ///
     /// # Example
-    /// ```rust
+    /// ```no_run
     /// use duckduckgo::browser::Browser;
-    /// use duckduckgo::user_agents::get;
-    ///
+    /// use duckduckgo::params::SearchParams;
     ///
     /// #[tokio::main]
     /// async fn main() -> anyhow::Result<()> {
     ///     let browser = Browser::new(reqwest::Client::new());
-    ///     let user_agent = get("firefox").unwrap();
-    ///     let images = browser.images("rustacean", "wt-wt", true, Some(5), user_agent).await?;
-    ///     assert!(!images.is_empty());
+    ///     let params = SearchParams::new("rustacean");
+    ///
+    ///     let first = browser.images_page(&params, None).await?;
+    ///     if let Some(next) = &first.next {
+    ///         let second = browser.images_page(&params, Some(next)).await?;
+    ///         println!("{} more images", second.items.len());
+    ///     }
     ///     Ok(())
     /// }
     /// ```
-    pub async fn images(
+    #[cfg(feature = "images")]
+    pub async fn images_page(
         &self,
-        query: &str,
-        region: &str,
-        safesearch: bool,
-        limit: Option<usize>,
-        user_agent: &str,
-    ) -> Result<Vec<ImageResult>> {
-        let vqd = self.get_vqd(query, user_agent).await?;
-        let mut page_params = vec![
-            ("q", query.to_string()),
-            ("l", region.to_string()),
-            ("vqd", vqd),
-            ("o", "json".into()),
-            ("p", if safesearch { "1" } else { "-1" }.into()),
-        ];
+        params: &SearchParams,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<ImageResult>> {
+        let (j, next) = self
+            .json_page(&self.endpoints.images, params, &[], cursor)
+            .await?;
+        let (first, page) = page_position(cursor);
+        let (items, warnings) = self.parse_items(&j, first, check_image, |item, rank| {
+            parse_image(item, rank, page)
+        })?;
+
+        Ok(Page {
+            items,
+            next,
+            warnings,
+            raw: self.with_raw.then_some(j),
+        })
+    }
 
+    /// Fetches images results page by page until done, the limit is reached, or `cancel` fires.
+    #[cfg(feature = "images")]
+    async fn images_until(
+        &self,
+        params: &SearchParams,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<PartialResults<ImageResult>> {
         let mut results = Vec::new();
+        let mut cursor = None;
+        let mut skip = params.offset;
 
+        let cancel = cancel.or(self.cancel.as_ref());
         loop {
-            let params_ref: Vec<(&str, &str)> =
-                page_params.iter().map(|(k, v)| (*k, v.as_ref())).collect();
+            if cancel.is_some_and(CancellationToken::is_cancelled) {
+                return Ok(PartialResults::cancelled(results));
+            }
 
-            let resp = self
-                .request(
-                    reqwest::Method::GET,
-                    "https://duckduckgo.com/i.js",
-                    user_agent,
-                    &params_ref,
-                )
-                .await?;
-
-            let j: Value = resp.json().await?;
-            if let Some(array) = j.get("results").and_then(|r| r.as_array()) {
-                for item in array.iter() {
-                    results.push(ImageResult {
-                        title: item["title"].as_str().unwrap_or("").to_string(),
-                        image: item["image"].as_str().unwrap_or("").to_string(),
-                        thumbnail: item["thumbnail"].as_str().unwrap_or("").to_string(),
-                        url: item["url"].as_str().unwrap_or("").to_string(),
-                        height: item["height"].as_u64().unwrap_or(0) as u32,
-                        width: item["width"].as_u64().unwrap_or(0) as u32,
-                        source: item["source"].as_str().unwrap_or("").to_string(),
-                    });
-
-                    if limit.is_some_and(|l| results.len() >= l) {
-                        return Ok(results);
-                    }
+            let page = self.images_page(params, cursor.as_ref()).await?;
+            for image in page.items {
+                if skip > 0 {
+                    skip -= 1;
+                    continue;
+                }
+                results.push(image);
+
+                if params.limit.is_some_and(|l| results.len() >= l) {
+                    return Ok(PartialResults::complete(results));
                 }
             }
 
-            if let Some(next) = j.get("next").and_then(|n| n.as_str()) {
-                let s = next.split("s=").nth(1).unwrap_or("").to_string();
-                page_params.push(("s", s));
-            } else {
-                break;
+            match page.next {
+                Some(next) => cursor = Some(next),
+                None => break,
             }
         }
 
-        Ok(results)
+        Ok(PartialResults::complete(results))
     }
 
     /// Performs a news search using DuckDuckGo's `news.js` API.
     ///
+    /// Only the articles from `params.sources`, if any, are returned.
+    ///
     /// # Arguments
-    /// * `query` - The search query.
-    /// * `region` - Region/language code (e.g., `"wt-wt"`).
-    /// * `safesearch` - Enables/disables safe search.
-    /// * `limit` - Optional limit for number of news results.
+    /// * `params` - The search options (query, region, limit, offset, user agent, sources).
     ///
     /// # Returns
     /// A list of `NewsResult` entries, including title, source, URL, and date.
@@ -304,97 +2072,222 @@ impl Browser {
     /// # Example
     /// ```rust
     /// use duckduckgo::browser::Browser;
-    /// use duckduckgo::user_agents::get;
+    /// use duckduckgo::params::SearchParams;
     ///
     ///
     /// #[tokio::main]
     /// async fn main() -> anyhow::Result<()> {
-    ///     let user_agent = get("firefox").unwrap();
     ///     let browser = Browser::new(reqwest::Client::new());
-    ///     let news = browser.news("AI", "wt-wt", true, Some(5), user_agent).await?;
+    ///     let params = SearchParams::new("AI").safe_search(true).limit(5);
+    ///     let news = browser.news(&params).await?;
     ///     assert!(news.iter().any(|n| n.title.contains("AI")));
     ///     Ok(())
     /// }
     /// ```
-    pub async fn news(
+    #[cfg(feature = "news")]
+    pub async fn news(&self, params: &SearchParams) -> Result<Vec<NewsResult>> {
+        Ok(self.news_until(params, None).await?.results)
+    }
+
+    /// Performs a news search that can be cancelled between pages.
+    ///
+    /// The token is checked before each page is fetched, so a cancelled search returns the
+    /// results of the pages fetched so far instead of aborting mid-request.
+    ///
+    /// # Arguments
+    /// * `params` - The search options (query, region, limit, offset, user agent).
+    /// * `cancel` - The token cancelling the search.
+    ///
+    /// # Returns
+    /// The results fetched before the search completed or was cancelled.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    /// use duckduckgo::params::SearchParams;
+    /// use tokio_util::sync::CancellationToken;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     let params = SearchParams::new("AI").limit(1000);
+    ///     let cancel = CancellationToken::new();
+    ///
+    ///     let timeout = cancel.clone();
+    ///     tokio::spawn(async move {
+    ///         tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    ///         timeout.cancel();
+    ///     });
+    ///
+    ///     let news = browser.news_cancellable(&params, &cancel).await?;
+    ///     if news.cancelled {
+    ///         println!("Stopped early with {} results", news.results.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "news")]
+    pub async fn news_cancellable(
         &self,
-        query: &str,
-        region: &str,
-        safesearch: bool,
-        limit: Option<usize>,
-        user_agent: &str,
-    ) -> Result<Vec<NewsResult>> {
-        let vqd = self.get_vqd(query, user_agent).await?;
-        let mut page_params = vec![
-            ("q", query.to_string()),
-            ("l", region.to_string()),
-            ("vqd", vqd),
-            ("o", "json".into()),
-            ("p", if safesearch { "1" } else { "-1" }.into()),
-            ("noamp", "1".into()),
-        ];
+        params: &SearchParams,
+        cancel: &CancellationToken,
+    ) -> Result<PartialResults<NewsResult>> {
+        self.news_until(params, Some(cancel)).await
+    }
+
+    /// Fetches a single page of news results, keeping only the articles from `params.sources`,
+    /// if any.
+    ///
+    /// Unlike `news`, this does not follow the next pages nor apply `params.limit`; see
+    /// `images_page`.
+    ///
+    /// # Arguments
+    /// * `params` - The search options (query, region, user agent, sources).
+    /// * `cursor` - The cursor returned with the previous page, or `None` for the first page.
+    ///
+    /// # Returns
+    /// The articles of the page and the cursor of the next page, if any.
+    #[cfg(feature = "news")]
+    pub async fn news_page(
+        &self,
+        params: &SearchParams,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<NewsResult>> {
+        let (j, next) = self
+            .json_page(&self.endpoints.news, params, &[("noamp", "1")], cursor)
+            .await?;
+        let (first, page) = page_position(cursor);
+        let (mut items, warnings) = self.parse_items(&j, first, check_news, |item, rank| {
+            parse_news(item, rank, page)
+        })?;
+        items.retain(|news: &NewsResult| {
+            params.sources.is_empty() || params.sources.iter().any(|source| news.is_from(source))
+        });
+
+        Ok(Page {
+            items,
+            next,
+            warnings,
+            raw: self.with_raw.then_some(j),
+        })
+    }
+
+    /// Parses the `results` array of a page of the JSON image or news APIs, skipping the items
+    /// rejected by `check` or failing on them, depending on the parse mode.
+    ///
+    /// Returns the parsed items and the warnings of the skipped ones.
+    #[cfg(any(feature = "images", feature = "news"))]
+    fn parse_items<T>(
+        &self,
+        j: &Value,
+        first: usize,
+        check: fn(&Value) -> std::result::Result<(), String>,
+        parse: impl Fn(&Value, usize) -> T,
+    ) -> Result<(Vec<T>, Vec<ParseWarning>)> {
+        let mut items = Vec::new();
+        let mut warnings = Vec::new();
+        let array = j.get("results").and_then(|r| r.as_array());
+        for (i, item) in array.into_iter().flatten().enumerate() {
+            let rank = first + i;
+            match check(item) {
+                Ok(()) => items.push(parse(item, rank)),
+                Err(reason) if self.parse_mode == ParseMode::Strict => {
+                    return Err(Error::MalformedItem { rank, reason }.into());
+                }
+                Err(reason) => warnings.push(ParseWarning { rank, reason }),
+            }
+        }
+        Ok((items, warnings))
+    }
+
+    /// Fetches a page of the JSON image or news APIs and the cursor of the next page.
+    ///
+    /// The first page requires a `vqd` token; the next ones reuse the token of the cursor and
+    /// are prepared with `next_page`.
+    #[cfg(any(feature = "images", feature = "news"))]
+    async fn json_page(
+        &self,
+        endpoint: &str,
+        params: &SearchParams,
+        extra: &[(&str, &str)],
+        cursor: Option<&Cursor>,
+    ) -> Result<(Value, Option<Cursor>)> {
+        let vqd = match cursor {
+            Some(cursor) => {
+                self.next_page().await?;
+                cursor.vqd().to_string()
+            }
+            None => self.get_vqd(&params.query, &params.user_agent).await?,
+        };
+
+        let page_params = json_page_params(params, &vqd, extra, cursor);
+
+        let resp = self
+            .request(
+                reqwest::Method::GET,
+                endpoint,
+                &params.user_agent,
+                &page_params,
+            )
+            .await?;
+        let j: Value = self
+            .parse_body(resp, |body| Ok(serde_json::from_str(body)?))
+            .await?;
+        self.accounting.record_page();
+
+        let next = j
+            .get("next")
+            .and_then(|n| n.as_str())
+            .and_then(|next| next_offset(endpoint, next))
+            .map(|offset| Cursor::new(vqd.as_str(), offset, cursor.map_or(2, |c| c.page() + 1)));
+        Ok((j, next))
+    }
 
+    /// Fetches news results page by page until done, the limit is reached, or `cancel` fires.
+    #[cfg(feature = "news")]
+    async fn news_until(
+        &self,
+        params: &SearchParams,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<PartialResults<NewsResult>> {
         let mut results = Vec::new();
+        let mut cursor = None;
+        let mut skip = params.offset;
 
+        let cancel = cancel.or(self.cancel.as_ref());
         loop {
-            let params_ref: Vec<(&str, &str)> =
-                page_params.iter().map(|(k, v)| (*k, v.as_ref())).collect();
+            if cancel.is_some_and(CancellationToken::is_cancelled) {
+                return Ok(PartialResults::cancelled(results));
+            }
 
-            let resp = self
-                .request(
-                    reqwest::Method::GET,
-                    "https://duckduckgo.com/news.js",
-                    user_agent,
-                    &params_ref,
-                )
-                .await?;
-
-            let j: Value = resp.json().await?;
-            if let Some(array) = j.get("results").and_then(|r| r.as_array()) {
-                for item in array.iter() {
-                    let date = item["date"]
-                        .as_i64()
-                        .map(|ts| {
-                            chrono::Utc
-                                .timestamp_opt(ts, 0)
-                                .single()
-                                .unwrap_or_else(chrono::Utc::now)
-                        })
-                        .unwrap_or_else(chrono::Utc::now);
-
-                    results.push(NewsResult {
-                        date: date.to_rfc3339(),
-                        title: item["title"].as_str().unwrap_or("").to_string(),
-                        body: item["excerpt"].as_str().unwrap_or("").to_string(),
-                        url: item["url"].as_str().unwrap_or("").to_string(),
-                        image: item
-                            .get("image")
-                            .and_then(|v| v.as_str())
-                            .map(str::to_string),
-                        source: item["source"].as_str().unwrap_or("").to_string(),
-                    });
-
-                    if limit.is_some_and(|l| results.len() >= l) {
-                        return Ok(results);
-                    }
+            let page = self.news_page(params, cursor.as_ref()).await?;
+            for news in page.items {
+                if skip > 0 {
+                    skip -= 1;
+                    continue;
+                }
+                results.push(news);
+
+                if params.limit.is_some_and(|l| results.len() >= l) {
+                    return Ok(PartialResults::complete(results));
                 }
             }
 
-            if let Some(next) = j.get("next").and_then(|n| n.as_str()) {
-                let s = next.split("s=").nth(1).unwrap_or("").to_string();
-                page_params.push(("s", s));
-            } else {
-                break;
+            match page.next {
+                Some(next) => cursor = Some(next),
+                None => break,
             }
         }
 
-        Ok(results)
+        Ok(PartialResults::complete(results))
     }
 
-    /// Performs a DuckDuckGo search based on the provided path, result format, and optional result limit.
+    /// Queries the Instant Answer API with the provided parameters and prints the results in
+    /// the given format.
     ///
     /// # Arguments
-    /// * `path` - The path to be appended to the DuckDuckGo API base URL.
+    /// * `params` - The query parameters, such as `("q", "rust")`. Like the other search
+    ///   methods, their values are URL-encoded internally, so raw values should be passed.
     /// * `result_format` - The format in which the search results should be displayed (List or Detailed).
     /// * `limit` - Optional limit for the number of search results to be displayed.
     ///
@@ -402,7 +2295,7 @@ impl Browser {
     /// `Result<(), reqwest::Error>` - Result indicating success or failure of the search operation.
     ///
     /// # Examples
-    /// ```
+    /// ```no_run
     /// use duckduckgo::browser::Browser;
     /// use duckduckgo::response::ResultFormat;
     /// use reqwest::Client;
@@ -411,44 +2304,226 @@ impl Browser {
     /// async fn main() {
     ///     let client = Client::new();
     ///     let browser = Browser::new(client);
-    ///     browser.browse("?q=Rust", ResultFormat::List, Some(5)).await.unwrap();
+    ///     browser.browse(&[("q", "C++ 20")], ResultFormat::List, Some(5)).await.unwrap();
     /// }
     /// ```
+    #[cfg(feature = "api")]
     pub async fn browse(
         &self,
-        path: &str,
+        params: &[(&str, &str)],
         result_format: ResultFormat,
         limit: Option<usize>,
     ) -> Result<()> {
-        let separator = if path.contains('?') { '&' } else { '?' };
-        let url = format!("{}{}{}format=json", BASE_URL, path, separator);
+        let api_response = self.fetch_response(params).await?;
+
+        match result_format {
+            ResultFormat::List => self.print_results_list(api_response, limit),
+            ResultFormat::Detailed => self.print_results_detailed(api_response, limit),
+        }
+
+        Ok(())
+    }
+
+    /// Fetches and parses the Instant Answer API response for the provided query parameters.
+    ///
+    /// The parameters are URL-encoded internally, so raw values should be passed.
+    ///
+    /// # Arguments
+    /// * `params` - A slice of key-value string pairs to be included as query parameters.
+    ///
+    /// # Returns
+    /// A `Result` containing the deserialized `Response` or an error.
+    #[cfg(feature = "api")]
+    pub async fn fetch_response(&self, params: &[(&str, &str)]) -> Result<Response> {
+        let url = self.api_url(params)?;
+        self.fetch_url(url.as_str()).await
+    }
+
+    /// Returns the URL of an Instant Answer API request with the given query parameters.
+    #[cfg(feature = "api")]
+    pub(crate) fn api_url(&self, params: &[(&str, &str)]) -> Result<reqwest::Url> {
+        Ok(reqwest::Url::parse_with_params(
+            &self.endpoints.api,
+            params.iter().copied().chain([("format", "json")]),
+        )?)
+    }
 
+    /// Fetches and parses the Instant Answer API response for the provided URL.
+    #[cfg(feature = "api")]
+    async fn fetch_url(&self, url: &str) -> Result<Response> {
         let response = self
-            .client
-            .get(&url)
-            .send()
+            .request(reqwest::Method::GET, url, &self.user_agent, &[])
             .await
             .with_context(|| format!("Failed to send request to {}", url))?;
 
-        let status = response.status();
-        let text = response
-            .text()
+        let response = self
+            .parse_body(response, |text| {
+                let parse_error = || format!("Failed to parse JSON response: {}", text);
+                if !self.with_raw {
+                    return serde_json::from_str(text).with_context(parse_error);
+                }
+                let raw: serde_json::Value =
+                    serde_json::from_str(text).with_context(parse_error)?;
+                let mut response: Response =
+                    serde_json::from_value(raw.clone()).with_context(parse_error)?;
+                response.raw = Some(raw);
+                Ok(response)
+            })
+            .await?;
+        self.accounting.record_page();
+        Ok(response)
+    }
+
+    /// Queries the Instant Answer API for the given raw query.
+    ///
+    /// # Arguments
+    /// * `query` - The raw search query; it is URL-encoded internally.
+    ///
+    /// # Returns
+    /// A `Result` containing the deserialized `Response` or an error.
+    #[cfg(feature = "api")]
+    pub async fn instant_answer(&self, query: &str) -> Result<Response> {
+        self.fetch_response(&instant_answer_params(query))
             .await
-            .with_context(|| "Failed to read response body")?;
+            .with_context(|| format!("Failed to fetch instant answer for query '{}'", query))
+    }
 
-        if !status.is_success() {
-            anyhow::bail!("Request failed with status {}: {}", status, text);
-        }
+    /// Queries the Instant Answer API, telling the `!bang` queries, which only redirect to
+    /// another site, from the other responses.
+    ///
+    /// # Arguments
+    /// * `query` - The raw search query, e.g. `"!w rust"`; it is URL-encoded internally.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    /// use duckduckgo::response::SearchOutcome;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     if let SearchOutcome::Redirect(url) = browser.search_outcome("!w rust").await? {
+    ///         println!("{}", url);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "api")]
+    pub async fn search_outcome(&self, query: &str) -> Result<SearchOutcome> {
+        Ok(self.instant_answer(query).await?.into_outcome())
+    }
+
+    /// Looks up the instant answer (e.g. calculations, conversions) for a query.
+    ///
+    /// # Arguments
+    /// * `query` - The search query, e.g. `"2+2"`.
+    ///
+    /// # Returns
+    /// A `Result` containing the `Answer`, or `None` if DuckDuckGo has no answer for the query.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     if let Some(answer) = browser.answer("2+2").await? {
+    ///         println!("{}", answer.text);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "api")]
+    pub async fn answer(&self, query: &str) -> Result<Option<Answer>> {
+        let response = self.instant_answer(query).await?;
 
-        let api_response: Response = serde_json::from_str(&text)
-            .with_context(|| format!("Failed to parse JSON response: {}", text))?;
+        Ok(non_empty(&response.answer).map(|text| Answer {
+            text,
+            answer_type: non_empty(&response.answer_type),
+        }))
+    }
 
-        match result_format {
-            ResultFormat::List => self.print_results_list(api_response, limit),
-            ResultFormat::Detailed => self.print_results_detailed(api_response, limit),
-        }
+    /// Looks up the single line answering a query: its instant answer, or else its abstract.
+    ///
+    /// # Arguments
+    /// * `query` - The search query, e.g. `"uuid"` or `"10 miles in km"`.
+    ///
+    /// # Returns
+    /// A `Result` containing the `Answer` (see `Response::concise_answer`), or `None` if
+    /// DuckDuckGo has neither for the query.
+    #[cfg(feature = "api")]
+    pub async fn concise_answer(&self, query: &str) -> Result<Option<Answer>> {
+        Ok(self.instant_answer(query).await?.concise_answer())
+    }
 
-        Ok(())
+    /// Looks up the dictionary definition of a word or phrase.
+    ///
+    /// # Arguments
+    /// * `query` - The word or phrase to define.
+    ///
+    /// # Returns
+    /// A `Result` containing the `Definition`, or `None` if no definition is available.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     if let Some(definition) = browser.definition("duck typing").await? {
+    ///         println!("{} ({:?})", definition.text, definition.source);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "api")]
+    pub async fn definition(&self, query: &str) -> Result<Option<Definition>> {
+        let response = self.instant_answer(query).await?;
+
+        Ok(non_empty(&response.definition).map(|text| {
+            Definition::parse(
+                &text,
+                non_empty(&response.definition_source),
+                non_empty(&response.definition_url),
+            )
+        }))
+    }
+
+    /// Looks up the topic summary (abstract) for a query.
+    ///
+    /// # Arguments
+    /// * `query` - The search query.
+    ///
+    /// # Returns
+    /// A `Result` containing the `Abstract`, or `None` if no summary is available.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     if let Some(summary) = browser.abstract_of("Rust programming language").await? {
+    ///         println!("{}", summary.text);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "api")]
+    pub async fn abstract_of(&self, query: &str) -> Result<Option<Abstract>> {
+        let response = self.instant_answer(query).await?;
+
+        let text = non_empty(&response.abstract_text).or_else(|| non_empty(&response.r#abstract));
+
+        Ok(text.map(|text| Abstract {
+            heading: non_empty(&response.heading),
+            text,
+            source: non_empty(&response.abstract_source),
+            url: non_empty(&response.abstract_url),
+        }))
     }
 
     /// Prints search results in list format.
@@ -456,8 +2531,9 @@ impl Browser {
     /// # Arguments
     /// * `api_response` - The response from the DuckDuckGo API.
     /// * `limit` - Optional limit for the number of search results to be displayed.
+    #[cfg(feature = "api")]
     pub fn print_results_list(&self, api_response: Response, limit: Option<usize>) {
-        if let Some(heading) = api_response.heading {
+        if let Some(heading) = &api_response.heading {
             let style = AnsiStyle {
                 bold: true,
                 color: Some(AnsiColor::Gold),
@@ -470,12 +2546,10 @@ impl Browser {
             );
         }
 
-        let topics = &api_response.related_topics;
-
-        for (index, topic) in topics
-            .iter()
+        for (index, topic) in api_response
+            .iter_topics()
             .enumerate()
-            .take(limit.unwrap_or(topics.len()))
+            .take(limit.unwrap_or(usize::MAX))
         {
             self.print_related_topic(index + 1, topic);
         }
@@ -486,6 +2560,7 @@ impl Browser {
     /// # Arguments
     /// * `index` - The index of the related topic.
     /// * `topic` - The related topic to be printed.
+    #[cfg(feature = "api")]
     pub fn print_related_topic(&self, index: usize, topic: &Topic) {
         let style = AnsiStyle {
             bold: false,
@@ -514,8 +2589,7 @@ impl Browser {
                 color: Some(AnsiColor::BrightBlue),
             };
             if !icon.url.is_empty() {
-                let full_url = format!("https://duckduckgo.com{}", icon.url);
-                println!("Image URL: {}{}", full_url, style.escape_code());
+                println!("Image URL: {}{}", icon.url, style.escape_code());
             }
         }
         println!("--------------------------------------------");
@@ -526,8 +2600,9 @@ impl Browser {
     /// # Arguments
     /// * `api_response` - The response from the DuckDuckGo API.
     /// * `limit` - Optional limit for the number of search results to be displayed.
+    #[cfg(feature = "api")]
     pub fn print_results_detailed(&self, api_response: Response, limit: Option<usize>) {
-        if let Some(heading) = api_response.heading {
+        if let Some(heading) = &api_response.heading {
             let style = AnsiStyle {
                 bold: true,
                 color: None,
@@ -540,7 +2615,7 @@ impl Browser {
             );
         }
 
-        if let Some(abstract_text) = api_response.abstract_text {
+        if let Some(abstract_text) = &api_response.abstract_text {
             let style = AnsiStyle {
                 bold: false,
                 color: Some(AnsiColor::LightGray),
@@ -548,7 +2623,7 @@ impl Browser {
             println!("Abstract: {}{}", abstract_text, style.escape_code());
         }
 
-        if let Some(abstract_source) = api_response.abstract_source {
+        if let Some(abstract_source) = &api_response.abstract_source {
             let style = AnsiStyle {
                 bold: false,
                 color: Some(AnsiColor::Purple),
@@ -560,7 +2635,7 @@ impl Browser {
             );
         }
 
-        if let Some(abstract_url) = api_response.abstract_url {
+        if let Some(abstract_url) = &api_response.abstract_url {
             let style = AnsiStyle {
                 bold: false,
                 color: Some(AnsiColor::Silver),
@@ -568,23 +2643,20 @@ impl Browser {
             println!("Abstract URL: {}{}", abstract_url, style.escape_code());
         }
 
-        if let Some(image) = api_response.image {
+        if let Some(image) = &api_response.image {
             let style = AnsiStyle {
                 bold: false,
                 color: Some(AnsiColor::SkyBlue),
             };
             if !image.is_empty() {
-                let full_url = format!("https://duckduckgo.com{}", image);
-                println!("Image URL: {}{}", full_url, style.escape_code());
+                println!("Image URL: {}{}", image, style.escape_code());
             }
         }
 
-        let topics = &api_response.related_topics;
-
-        for (index, topic) in topics
-            .iter()
+        for (index, topic) in api_response
+            .iter_topics()
             .enumerate()
-            .take(limit.unwrap_or(topics.len()))
+            .take(limit.unwrap_or(usize::MAX))
         {
             self.print_related_topic(index + 1, topic);
         }
@@ -593,7 +2665,7 @@ impl Browser {
     /// Performs a basic DuckDuckGo search with the provided parameters.
     ///
     /// # Arguments
-    /// * `query` - The search query.
+    /// * `query` - The raw search query; it is URL-encoded internally.
     /// * `safe_search` - A boolean indicating whether safe search is enabled.
     /// * `result_format` - The format in which the search results should be displayed (List or Detailed).
     /// * `limit` - Optional limit for the number of search results to be displayed.
@@ -614,6 +2686,7 @@ impl Browser {
     ///     browser.search("Rust", true, ResultFormat::Detailed, Some(5)).await.unwrap();
     /// }
     /// ```
+    #[cfg(feature = "api")]
     pub async fn search(
         &self,
         query: &str,
@@ -621,10 +2694,9 @@ impl Browser {
         result_format: ResultFormat,
         limit: Option<usize>,
     ) -> Result<()> {
-        let safe_param = if safe_search { "&kp=1" } else { "&kp=-2" };
-        let path = format!("?q={}{}", query, safe_param);
+        let safe_param = if safe_search { "1" } else { "-2" };
 
-        self.browse(&path, result_format, limit)
+        self.browse(&[("q", query), ("kp", safe_param)], result_format, limit)
             .await
             .with_context(|| format!("Failed to perform search for query '{}'", query))
     }
@@ -632,7 +2704,7 @@ impl Browser {
     /// Performs an advanced DuckDuckGo search with additional parameters.
     ///
     /// # Arguments
-    /// * `query` - The search query.
+    /// * `query` - The raw search query; it is URL-encoded internally.
     /// * `params` - Additional search parameters.
     /// * `safe_search` - A boolean indicating whether safe search is enabled.
     /// * `result_format` - The format in which the search results should be displayed (List or Detailed).
@@ -654,6 +2726,7 @@ impl Browser {
     ///     browser.advanced_search("Rust", "lang:en", true, ResultFormat::Detailed, Some(5)).await.unwrap();
     /// }
     /// ```
+    #[cfg(feature = "api")]
     pub async fn advanced_search(
         &self,
         query: &str,
@@ -662,18 +2735,24 @@ impl Browser {
         result_format: ResultFormat,
         limit: Option<usize>,
     ) -> Result<()> {
-        let safe_param = if safe_search { "&kp=1" } else { "&kp=-2" };
-        let path = format!("?q={}&kl={}{}", query, params, safe_param);
+        let safe_param = if safe_search { "1" } else { "-2" };
 
-        self.browse(&path, result_format, limit)
-            .await
-            .with_context(|| format!("Failed to perform advanced search for query '{}'", query))
+        self.browse(
+            &[("q", query), ("kl", params), ("kp", safe_param)],
+            result_format,
+            limit,
+        )
+        .await
+        .with_context(|| format!("Failed to perform advanced search for query '{}'", query))
     }
 
     /// Performs a DuckDuckGo search with custom search operators.
     ///
+    /// The operator string can be composed with the `Operators` builder
+    /// (see [`crate::operators::Operators`]).
+    ///
     /// # Arguments
-    /// * `query` - The search query.
+    /// * `query` - The raw search query; it is URL-encoded internally.
     /// * `operators` - Custom search operators.
     /// * `safe_search` - A boolean indicating whether safe search is enabled.
     /// * `result_format` - The format in which the search results should be displayed (List or Detailed).
@@ -695,6 +2774,7 @@ impl Browser {
     ///     browser.search_operators("Rust", "site:github.com", true, ResultFormat::List, Some(5)).await.unwrap();
     /// }
     /// ```
+    #[cfg(feature = "api")]
     pub async fn search_operators(
         &self,
         query: &str,
@@ -703,11 +2783,111 @@ impl Browser {
         result_format: ResultFormat,
         limit: Option<usize>,
     ) -> Result<()> {
-        let safe_param = if safe_search { "&kp=1" } else { "&kp=-2" };
-        let path = format!("?q={}&{}{}", query, operators, safe_param);
+        let safe_param = if safe_search { "1" } else { "-2" };
+        let query = format!("{} {}", query, operators);
 
-        self.browse(&path, result_format, limit)
+        self.browse(&[("q", &query), ("kp", safe_param)], result_format, limit)
             .await
             .with_context(|| format!("Failed to perform operator search for query '{}'", query))
     }
 }
+
+/// Returns a copy of the string if it is present and not blank.
+///
+/// The Instant Answer API uses empty strings for absent fields.
+#[cfg(feature = "api")]
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// Returns the form parameters of a Lite or HTML search, starting at `params.offset`.
+#[cfg(any(feature = "lite", feature = "html"))]
+pub(crate) fn text_params(params: &SearchParams) -> Vec<(&'static str, String)> {
+    let mut form = vec![("q", params.query.clone()), ("kl", params.region.clone())];
+    if params.offset > 0 {
+        form.push(("s", params.offset.to_string()));
+        form.push(("dc", (params.offset + 1).to_string()));
+    }
+    form
+}
+
+/// Returns the query parameters of a page of the JSON image or news APIs.
+#[cfg(any(feature = "images", feature = "news"))]
+pub(crate) fn json_page_params<'a>(
+    params: &'a SearchParams,
+    vqd: &'a str,
+    extra: &[(&'a str, &'a str)],
+    cursor: Option<&'a Cursor>,
+) -> Vec<(&'a str, &'a str)> {
+    let mut page_params = vec![
+        ("q", params.query.as_str()),
+        ("l", params.region.as_str()),
+        ("vqd", vqd),
+        ("o", "json"),
+        ("p", if params.safe_search { "1" } else { "-1" }),
+    ];
+    page_params.extend_from_slice(extra);
+    if let Some(cursor) = cursor {
+        page_params.push(("s", cursor.offset()));
+    }
+    page_params
+}
+
+/// Returns the query parameters of an Instant Answer API lookup.
+///
+/// `no_redirect` makes the API return the target of `!bang` queries in the `Redirect` field,
+/// instead of redirecting the request itself to it.
+#[cfg(feature = "api")]
+pub(crate) fn instant_answer_params(query: &str) -> [(&str, &str); 4] {
+    [
+        ("q", query),
+        ("no_html", "1"),
+        ("skip_disambig", "1"),
+        ("no_redirect", "1"),
+    ]
+}
+
+/// Extracts the offset (`s` parameter) of the next page from the `next` link of a JSON page,
+/// relative to the endpoint that returned it.
+#[cfg(any(feature = "images", feature = "news"))]
+fn next_offset(endpoint: &str, next: &str) -> Option<String> {
+    let url = reqwest::Url::parse(endpoint).ok()?.join(next).ok()?;
+    url.query_pairs()
+        .find(|(key, _)| key == "s")
+        .map(|(_, value)| value.into_owned())
+        .filter(|value| !value.is_empty())
+}
+
+/// Returns the rank of the first item of a JSON page and the page number, given its cursor.
+#[cfg(any(feature = "images", feature = "news"))]
+fn page_position(cursor: Option<&Cursor>) -> (usize, usize) {
+    match cursor {
+        Some(cursor) => (
+            cursor
+                .offset()
+                .parse::<usize>()
+                .map_or(1, |offset| offset + 1),
+            cursor.page(),
+        ),
+        None => (1, 1),
+    }
+}
+
+/// Shifts the ranks of a Lite or HTML page fetched from `offset`.
+#[cfg(any(feature = "lite", feature = "html"))]
+fn rank_from(page: &mut SearchPage, offset: usize) {
+    for result in &mut page.results {
+        result.rank += offset;
+    }
+}
+
+/// Returns `true` if a status means DuckDuckGo is rate limiting, blocking, or failing.
+fn is_upstream_failure(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::FORBIDDEN
+        || status.is_server_error()
+}
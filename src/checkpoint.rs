@@ -0,0 +1,131 @@
+use crate::response::Cursor;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// The progress of a batch of queries, saved after each query and page so that an interrupted
+/// batch can be resumed where it stopped.
+///
+/// The queries are run in order: the completed queries are the first ones of the batch.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::checkpoint::Checkpoint;
+///
+/// let path = std::env::temp_dir().join("ddg-checkpoint-doctest.json");
+///
+/// let mut checkpoint = Checkpoint::new(vec!["rust".into(), "zig".into(), "go".into()]);
+/// checkpoint.complete("rust");
+/// checkpoint.save(&path).unwrap();
+///
+/// let checkpoint = Checkpoint::load(&path).unwrap();
+/// assert_eq!(checkpoint.remaining(), ["zig", "go"]);
+/// assert!(!checkpoint.is_done());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// All the queries of the batch, in order.
+    pub queries: Vec<String>,
+    /// The queries completed so far.
+    pub completed: Vec<String>,
+    /// The query being paged through when the checkpoint was saved, if any.
+    pub pending: Option<PendingQuery>,
+}
+
+/// A query interrupted while paging through its results.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingQuery {
+    /// The query.
+    pub query: String,
+    /// The cursor of the next page to fetch.
+    pub cursor: Cursor,
+    /// The number of results already fetched for the query.
+    pub fetched: usize,
+}
+
+impl Checkpoint {
+    /// Creates the checkpoint of a batch that has not started yet.
+    ///
+    /// # Arguments
+    /// * `queries` - The queries of the batch, in order.
+    pub fn new(queries: Vec<String>) -> Self {
+        Checkpoint {
+            queries,
+            ..Default::default()
+        }
+    }
+
+    /// Loads a checkpoint saved with `save`.
+    ///
+    /// # Arguments
+    /// * `path` - The checkpoint file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read checkpoint {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse checkpoint {}", path.display()))
+    }
+
+    /// Saves the checkpoint as JSON.
+    ///
+    /// The file is replaced atomically, so that an interruption while saving does not corrupt
+    /// the previous checkpoint.
+    ///
+    /// # Arguments
+    /// * `path` - The checkpoint file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write checkpoint {}", tmp.display()))?;
+        fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to write checkpoint {}", path.display()))
+    }
+
+    /// Returns the queries left to run, starting with the pending one.
+    pub fn remaining(&self) -> &[String] {
+        self.queries.get(self.completed.len()..).unwrap_or_default()
+    }
+
+    /// Returns `true` if every query of the batch is completed.
+    pub fn is_done(&self) -> bool {
+        self.remaining().is_empty()
+    }
+
+    /// Records that a query is completed.
+    ///
+    /// # Arguments
+    /// * `query` - The completed query.
+    pub fn complete(&mut self, query: &str) {
+        self.completed.push(query.to_string());
+        self.pending = None;
+    }
+
+    /// Records that a page of a query was fetched, and where the next page starts.
+    ///
+    /// # Arguments
+    /// * `query` - The query being paged through.
+    /// * `cursor` - The cursor of the next page.
+    /// * `fetched` - The number of results fetched for the query so far.
+    pub fn record_page(&mut self, query: &str, cursor: &Cursor, fetched: usize) {
+        self.pending = Some(PendingQuery {
+            query: query.to_string(),
+            cursor: cursor.clone(),
+            fetched,
+        });
+    }
+
+    /// Returns where to resume a query: the cursor of its next page and the number of results
+    /// already fetched, if it was interrupted while paging.
+    ///
+    /// # Arguments
+    /// * `query` - The query to resume.
+    pub fn resume_point(&self, query: &str) -> Option<(Cursor, usize)> {
+        self.pending
+            .as_ref()
+            .filter(|pending| pending.query == query)
+            .map(|pending| (pending.cursor.clone(), pending.fetched))
+    }
+}
@@ -1,13 +1,438 @@
+use crate::backend::{
+    FallbackBackend, HtmlBackend, ImagesBackend, LiteBackend, MetaBackend, NewsBackend,
+    SearchBackend,
+};
+use crate::browser::Browser;
+use crate::date::DatePattern;
+use crate::download::{DEFAULT_NAME_TEMPLATE, ImageFormat, NameTemplate};
+use crate::error::{Error, InvalidResponse};
+use crate::exporter::ExportTarget;
+use crate::filter::AspectRatio;
+use crate::template::{Fields, Template};
+use crate::user_agents::Rotation;
 use clap::Parser;
+use clap::Subcommand;
 use clap::ValueEnum;
 use clap::builder::styling::{AnsiColor, Effects, Styles};
+use reqwest::StatusCode;
 
 #[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
 pub enum Backend {
     Auto,
     Lite,
+    Html,
     Images,
     News,
+    All,
+}
+
+impl Backend {
+    /// Creates the search backend corresponding to this selection.
+    pub fn build<'a>(&self, browser: &'a Browser) -> Box<dyn SearchBackend + 'a> {
+        match self {
+            Backend::Auto => Box::new(FallbackBackend::auto(browser)),
+            Backend::Lite => Box::new(LiteBackend::new(browser)),
+            Backend::Html => Box::new(HtmlBackend::new(browser)),
+            Backend::Images => Box::new(ImagesBackend::new(browser)),
+            Backend::News => Box::new(NewsBackend::new(browser)),
+            Backend::All => Box::new(MetaBackend::all(browser)),
+        }
+    }
+}
+
+/// The format search results are printed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    Text,
+    /// A single JSON document, including the timings if `--timings` is set.
+    Json,
+    /// A Markdown list of links, with the snippets as blockquotes.
+    Markdown,
+    /// An Org-mode list of links, with the snippets in quote blocks.
+    Org,
+    /// One rofi script mode row per result, with the URL as the row info.
+    Rofi,
+    /// One "title | url" line per result, for dmenu.
+    Dmenu,
+    /// An Alfred script filter JSON document.
+    Alfred,
+    /// A JSON bundle of the results and the text of their pages, for LLM pipelines.
+    RagJson,
+    /// Linear, uncolored text for screen readers, one labelled field per line.
+    Plain,
+}
+
+/// A JSON document printed by the command line tool, whose schema `ddg schema` prints.
+#[cfg(feature = "schema")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SchemaDocument {
+    /// The document printed by `--output json`.
+    Output,
+    /// A single search result, as listed in `results`.
+    Result,
+    /// A text result.
+    Text,
+    /// An image result.
+    Image,
+    /// A news article result.
+    News,
+    /// The document printed by `--output alfred`.
+    Alfred,
+    /// The document printed by `--output rag-json`.
+    RagJson,
+    /// The document printed by `answer` with `--output json`.
+    Answer,
+    /// The document printed by `define` with `--output json`.
+    Definition,
+    /// The requests printed by `--dry-run` with `--output json`.
+    Plan,
+    /// The document printed by `--regions` with `--output json`.
+    Comparison,
+    /// The report printed by `bench-stability` with `--output json`.
+    Stability,
+    /// The document printed by `analyze` with `--output json`.
+    Analysis,
+    /// The checks printed by `doctor` with `--output json`.
+    Doctor,
+    /// The error printed to stderr with `--output json`.
+    Error,
+}
+
+/// When the output is colored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Colors {
+    /// Always color the output.
+    On,
+    /// Never color the output.
+    Off,
+    /// Color the output unless it goes to a Windows console that cannot interpret escape codes.
+    Auto,
+}
+
+/// When result titles are printed as clickable hyperlinks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Hyperlinks {
+    /// Always print hyperlinks.
+    On,
+    /// Never print hyperlinks, print the URLs instead.
+    Off,
+    /// Print hyperlinks if stdout is a terminal known to support them.
+    Auto,
+}
+
+/// When the dates of news results are printed relative to now, such as "3 hours ago".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RelativeDates {
+    /// Always print relative dates.
+    On,
+    /// Never print relative dates, print the dates only.
+    Off,
+    /// Print relative dates if stdout is a terminal.
+    Auto,
+}
+
+/// How news results are grouped in the text output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    /// Group articles by publisher.
+    Source,
+    /// Group articles by publication day (UTC).
+    Day,
+}
+
+/// How the `compare` subcommand prints the diff of the results of two queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DiffStyle {
+    /// One list, like `diff -u`: `-` for the results of the first query only, `+` for the
+    /// results of the second query only.
+    Unified,
+    /// Two columns, like `diff -y`, with `<` and `>` for the results of one query only.
+    SideBySide,
+}
+
+/// When the user agent is rotated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum UserAgentRotation {
+    /// A new user agent for every request.
+    Request,
+    /// A new user agent for every page of image and news searches.
+    Page,
+}
+
+impl From<UserAgentRotation> for Rotation {
+    fn from(rotation: UserAgentRotation) -> Self {
+        match rotation {
+            UserAgentRotation::Request => Rotation::PerRequest,
+            UserAgentRotation::Page => Rotation::PerPage,
+        }
+    }
+}
+
+/// A TLS protocol version selectable from the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TlsVersion {
+    #[value(name = "1.0")]
+    Tls1_0,
+    #[value(name = "1.1")]
+    Tls1_1,
+    #[value(name = "1.2")]
+    Tls1_2,
+    #[value(name = "1.3")]
+    Tls1_3,
+}
+
+impl From<TlsVersion> for reqwest::tls::Version {
+    fn from(version: TlsVersion) -> Self {
+        match version {
+            TlsVersion::Tls1_0 => reqwest::tls::Version::TLS_1_0,
+            TlsVersion::Tls1_1 => reqwest::tls::Version::TLS_1_1,
+            TlsVersion::Tls1_2 => reqwest::tls::Version::TLS_1_2,
+            TlsVersion::Tls1_3 => reqwest::tls::Version::TLS_1_3,
+        }
+    }
+}
+
+/// The exit status of the command line tool, so that scripts can branch on the outcome.
+///
+/// It serializes as its name, e.g. `"RateLimited"`, as the `kind` of the JSON errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ExitStatus {
+    /// The command succeeded.
+    Success = 0,
+    /// The command line or a settings file it names was invalid.
+    Usage = 1,
+    /// DuckDuckGo could not be reached or sent an invalid response.
+    Network = 2,
+    /// DuckDuckGo rate limited or blocked the requests.
+    RateLimited = 3,
+    /// The search returned no results and `--fail-on-empty` was set.
+    NoResults = 4,
+    /// The search returned fewer results than `--min-results`, with every fallback backend.
+    FewResults = 5,
+    /// A local operation failed, such as reading or writing a file, or setting up TLS.
+    Runtime = 6,
+    /// The search was interrupted with Ctrl-C, after printing the results fetched so far.
+    Interrupted = 130,
+}
+
+impl ExitStatus {
+    /// Returns the process exit code of this status.
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Classifies an error returned by the search.
+    ///
+    /// # Arguments
+    /// * `err` - The error to classify.
+    ///
+    /// # Returns
+    /// `RateLimited` for bot challenges, open circuit breakers, and `429`/`403` responses,
+    /// `Network` for other HTTP errors and for responses that cannot be parsed, and `Runtime` for
+    /// everything else, such as I/O errors. Invalid arguments are reported as `Usage` before any
+    /// error reaches this function.
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        for cause in err.chain() {
+            if let Some(err) = cause.downcast_ref::<Error>() {
+                return match err {
+                    Error::BotDetection { .. } | Error::CircuitOpen { .. } => {
+                        ExitStatus::RateLimited
+                    }
+                    _ => ExitStatus::Network,
+                };
+            }
+            if let Some(err) = cause.downcast_ref::<reqwest::Error>() {
+                return match err.status() {
+                    Some(StatusCode::TOO_MANY_REQUESTS | StatusCode::FORBIDDEN) => {
+                        ExitStatus::RateLimited
+                    }
+                    _ => ExitStatus::Network,
+                };
+            }
+        }
+        if err.downcast_ref::<InvalidResponse>().is_some() {
+            return ExitStatus::Network;
+        }
+        ExitStatus::Runtime
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// Prints the instant answer for a query (e.g. calculations).
+    Answer {
+        /// The query to answer.
+        query: String,
+    },
+    /// Converts a currency amount or a unit, e.g. "100 USD to EUR" or "5 miles in km".
+    Convert {
+        /// The conversion to compute.
+        expression: String,
+    },
+    /// Computes a calculation, e.g. "2^10" or "sqrt(2) * 3".
+    Calc {
+        /// The expression to compute.
+        expression: String,
+    },
+    /// Prints the dictionary definition of a word and its part of speech, exiting with status 4
+    /// if there is none.
+    Define {
+        /// The word or phrase to define.
+        word: String,
+    },
+    /// Manages the user agent presets.
+    UserAgents {
+        #[command(subcommand)]
+        action: UserAgentsCommand,
+    },
+    /// Manages the search presets.
+    Presets {
+        #[command(subcommand)]
+        action: PresetsCommand,
+    },
+    /// Prints the JSON Schema of a JSON document printed by the tool.
+    #[cfg(feature = "schema")]
+    Schema {
+        /// The document to print the schema of.
+        #[arg(value_enum)]
+        document: SchemaDocument,
+    },
+    /// Manages the saved searches, run as `ddg <alias> [ARGS]...`.
+    Alias {
+        #[command(subcommand)]
+        action: AliasCommand,
+    },
+    /// Runs the scheduled searches, printing the results not seen in previous runs and posting
+    /// them to the webhooks of the searches.
+    Daemon {
+        /// The schedule file, a JSON array of searches with a `name`, a `cron` expression, a
+        /// `query`, and optionally a `backend`, a `limit`, and a `webhook` (`ddg/schedule.json`
+        /// in the user config directory by default).
+        #[arg(long = "schedule", value_name = "PATH")]
+        schedule: Option<String>,
+        /// The directory the results seen by each search are saved in (`ddg/daemon` in the
+        /// user state directory by default).
+        #[arg(long = "state-dir", value_name = "DIR")]
+        state_dir: Option<String>,
+        /// Serves the Prometheus metrics of the searches and requests on
+        /// http://ADDR/metrics.
+        #[cfg(feature = "metrics")]
+        #[arg(long = "metrics-listen", value_name = "ADDR")]
+        metrics_listen: Option<std::net::SocketAddr>,
+    },
+    /// Repeats a search and reports how much its results change between the runs: their
+    /// overlap, Jaccard index, and rank correlation.
+    BenchStability {
+        /// The search query.
+        #[arg(short = 'q', long = "query")]
+        query: String,
+        /// The number of times the search is run.
+        #[arg(long = "runs", value_name = "N", default_value_t = 5, value_parser = clap::value_parser!(u32).range(2..))]
+        runs: u32,
+        /// The number of seconds to wait between two runs.
+        #[arg(long = "interval", value_name = "SECONDS", default_value_t = 0)]
+        interval: u64,
+    },
+    /// Runs a search and prints the statistics of its results, as with --stats, and the most
+    /// frequent terms of their titles and snippets with --terms.
+    Analyze {
+        /// The search query.
+        #[arg(short = 'q', long = "query")]
+        query: String,
+        /// Prints the most frequent terms and bigrams of the titles and snippets, without
+        /// stopwords and the words of the query.
+        #[arg(long = "terms")]
+        terms: bool,
+        /// Sets the number of terms and bigrams printed by --terms.
+        #[arg(
+            long = "top",
+            value_name = "N",
+            default_value_t = 20,
+            requires = "terms"
+        )]
+        top: usize,
+    },
+    /// Runs two searches and diffs their results: the URLs unique to each query, and the ones
+    /// they share.
+    Compare {
+        /// The first query.
+        #[arg(short = 'q', long = "query")]
+        query: String,
+        /// The second query.
+        #[arg(long = "query2", visible_alias = "q2")]
+        query2: String,
+        /// Prints the diff as one list or in two columns.
+        #[arg(long = "diff-style", value_enum, value_name = "STYLE", default_value_t = DiffStyle::Unified)]
+        diff_style: DiffStyle,
+    },
+    /// Serves searches over a local REST API (`GET /search?q=...`), with a shared cache and
+    /// identical concurrent searches collapsed into one, so that local tools share one budget.
+    #[cfg(feature = "server")]
+    Proxyd {
+        /// The address to listen on.
+        #[arg(long = "listen", value_name = "ADDR", default_value = "127.0.0.1:8787")]
+        listen: std::net::SocketAddr,
+        /// The number of seconds a search is cached, from when its results arrive.
+        #[arg(long = "cache-ttl", value_name = "SECONDS", default_value_t = 3600)]
+        cache_ttl: u64,
+        /// The maximum number of searches cached; the oldest ones are evicted first, never the
+        /// ones still running.
+        #[arg(long = "cache-size", value_name = "N", default_value_t = 1000)]
+        cache_size: usize,
+    },
+    /// Probes each DuckDuckGo endpoint through the current client settings (proxy, user agent),
+    /// reporting its reachability, its latency, and whether bot detection is triggered.
+    Doctor,
+    /// Replaces the ddg binary with the latest release, once its checksum and its Ed25519
+    /// signature, against the `public_key` of `ddg/update.json`, are verified.
+    #[cfg(feature = "self-update")]
+    SelfUpdate {
+        /// Only prints whether a newer release is available, exiting with status 4 if there is
+        /// none.
+        #[arg(long = "check")]
+        check: bool,
+        /// Installs the release without verifying its signature, only its checksum, e.g. for a
+        /// release published without a `.sig` file. Insecure: the checksum comes from the same
+        /// release as the binary.
+        #[arg(long = "skip-signature")]
+        skip_signature: bool,
+    },
+    /// Runs a saved search, with the arguments up to the first option (or `--`) in place of the
+    /// `{}` of its template.
+    #[command(external_subcommand)]
+    Saved(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum UserAgentsCommand {
+    /// Lists the user agent presets accepted by --user-agent.
+    List,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum PresetsCommand {
+    /// Lists the presets accepted by --preset and their operators.
+    List,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum AliasCommand {
+    /// Saves a search, e.g. `ddg alias add crates "site:crates.io {}"`.
+    Add {
+        /// The name the search is run with.
+        name: String,
+        /// The query, with `{}` standing for the arguments.
+        template: String,
+    },
+    /// Deletes a saved search.
+    Remove {
+        /// The name of the saved search.
+        name: String,
+    },
+    /// Lists the saved searches and their templates.
+    List,
 }
 
 fn styles() -> Styles {
@@ -53,16 +478,150 @@ FEATURES:
   - Search operators: Use the --operators or -o option to set search operators.
   - Safe search: Enable safe search with the --safe option.
   - Output format: Set the output format (list or detailed) with the --format option.
-  - Result limit: Limit the number of results with the --limit option.
-  - User agent: Set the user agent for the HTTP client with the --user-agent option.
-  - Cookie: Set the cookie for the HTTP client with the --cookie option.
-  - Proxy: Set the proxy for the HTTP client with the --proxy option.
-  - Backend: Choose the backend used for search (e.g. auto, lite, images, news)
-    with the --backend option.
-  - Verbose mode: Show debug messages with the --verbose or -v option.
+  - Result limit: Limit the number of results with the --limit option, and
+    skip the first ones with --offset.
+  - User agent: Set the user agent for the HTTP client with the --user-agent option
+    (a preset name, "random", or a raw user agent string), or rotate it with
+    --rotate-user-agent. List the presets with `ddg user-agents list` and add
+    your own with --user-agents-file <path>.
+  - Cookie: Set the cookie for the HTTP client with the --cookie option, or
+    persist cookies across runs with --cookie-file <path>.
+  - Proxy: Set the proxy for the HTTP client with the --proxy option, or
+    per scheme with --http-proxy/--https-proxy. Credentials can be given as
+    user:pass@host, and HTTP_PROXY/HTTPS_PROXY/NO_PROXY are honored.
+  - TLS: Trust extra certificate authorities with --ca-cert <pem>, restrict
+    TLS versions with --min-tls/--max-tls, or skip certificate validation
+    with --insecure (not recommended).
+  - Network: Force IPv4 or IPv6 with --ipv4/--ipv6, or send requests from a
+    specific interface address with --interface.
+  - Rate limiting: Space out requests with --delay <ms> (500 ms by default)
+    to stay under DuckDuckGo's radar during long scrapes, and share the
+    budget between concurrent ddg processes with --shared-rate-limit.
+  - Throttling: Wait --page-delay <ms> between the pages of image and news
+    searches, plus up to --page-jitter <ms>, slowing down automatically when
+    responses get slow or DuckDuckGo starts rate limiting.
+  - JSON output: Print the results as a single JSON document with --output json.
+  - Colors: Turn the colors on or off with --color on|off|auto; auto enables
+    them on Windows consoles, and leaves them out where they are unsupported.
+  - Hyperlinks: Make result titles clickable in supporting terminals, with
+    --hyperlinks on|off|auto (auto by default).
+  - Deterministic output: Sort the results by rank and URL and leave out the
+    time of the run with --deterministic, so that diffs only show real changes.
+  - Dates: Print news dates as "3 hours ago" in terminals, with
+    --relative-dates on|off|auto, or with a strftime --date-format, with month
+    names in the language of the locale.
+  - Favicons: Download the icon of each result's site with --favicons, cached
+    in ~/.cache/ddg/favicons, and print its path with the result and in JSON.
+  - Link checks: Check every result URL concurrently with --check-links, and
+    leave out the dead ones with --skip-dead-links.
+  - Image checks: Check the file of every image result with --verify-images,
+    dropping the broken ones and reading their missing dimensions.
+  - Image downloads: Save the image results with --download-images, named with
+    --name-template, converted with --image-format, and listed in a manifest.
+  - Page content: Download the page of each result and extract its readable
+    text, title, and byline with --fetch-content, or print the results and
+    their texts as a single retrieval bundle for LLMs with --output rag-json.
+  - Export: Append the results of every run to a SQLite database with
+    --export sqlite://results.db (with the sqlite feature), for analysis over
+    time, or write them to Parquet or Arrow files with
+    --export parquet://results.parquet or --export arrow://results.arrow (with
+    the parquet feature).
+  - Batches: Run every query of a file with --queries-file <path>, saving the
+    completed queries and pagination cursors to a checkpoint file, and continue
+    an interrupted batch with --resume <checkpoint>.
+  - Wrapping: Titles are truncated and snippets wrapped to the terminal width,
+    or to --width <columns> (0 disables wrapping).
+  - Notes: Print the results as a Markdown or Org-mode list of links, ready
+    to paste into notes, with --output markdown or --output org.
+  - Launchers: Back rofi, dmenu, or Alfred search plugins with --output rofi,
+    --output dmenu, or --output alfred.
+  - Templates: Print each result on its own line with --template, using the
+    {title}, {url}, {snippet}, {date}, {rank}, and {source} placeholders.
+  - Fields: Output only some fields of each result, e.g. --fields url,title,date,
+    as tab-separated values or, with --output json, as JSON objects.
+  - Lucky: Open the top result in the browser with --lucky, or print only its
+    URL with --lucky --print.
+  - Answers: Print only the instant answer of a query, or else its abstract, on
+    a single line with --answer-only.
+  - Pipelines: Print one rank<TAB>url<TAB>title line per result with --oneline,
+    or end each result with a NUL character for xargs -0 with --print0.
+  - Dry run: Print the URLs, query parameters, and headers of the requests a
+    search would send, without sending them, with --dry-run.
+  - curl: Print the curl commands sending the same requests as a search, to
+    reproduce issues or share them, with --emit-curl.
+  - Timings: Print per-request DNS/TTFB/download/parse durations with --timings.
+  - Request accounting: Print the requests, bytes, pages, and cache hits of a
+    run with --stats-summary.
+  - Backend: Choose the backend used for search (e.g. auto, lite, html, images, news)
+    with the --backend option, or search text, images, and news at once with
+    --backend all.
+    The default auto backend falls back from the Instant Answer API to the
+    html and lite backends when the API has no results.
+  - News sources: Keep only the news from given sources with --source, and
+    group news by publisher or day with --group-by source|day.
+  - News follow: Poll a news search with --follow, like tail -f, printing the
+    new articles as they appear, every --poll-interval seconds.
+  - Statistics: Summarize the results by domain, TLD, snippet length, and
+    publication day with --stats.
+  - Terms: Print the most frequent terms and bigrams of the titles and snippets
+    of the results with the analyze subcommand and --terms.
+  - Query diffs: Diff the results of two queries with the compare subcommand,
+    unified or side by side with --diff-style.
+  - Regions: Compare the results of a search across regions, shared and
+    unique to each, with --regions us-en,de-de,jp-jp.
+  - User agents: Compare the results and ranking of a search sent with several
+    user agents with --compare-user-agents chrome,safari.
+  - Stability: Repeat a search with the bench-stability subcommand, and measure
+    how much its results change between the runs.
+  - Caching proxy: Serve searches to local tools over a REST API with the
+    proxyd subcommand, with a shared cache and request coalescing, and its
+    OpenAPI document at /openapi.json (with the server feature).
+  - Metrics: Serve Prometheus metrics at /metrics from proxyd, and from the
+    daemon with --metrics-listen (with the metrics feature).
+  - Doctor: Check that each DuckDuckGo endpoint is reachable through the
+    proxy and user agent of a run, and not blocked, with the doctor subcommand.
+  - Self-update: Install the latest release, once its checksum and signature
+    are verified, with the self-update subcommand (with the self-update
+    feature).
+  - Autocorrect: Re-run misspelled queries with DuckDuckGo's suggested
+    spelling with the --autocorrect option.
+  - Tor: Route traffic through a local Tor daemon with the --tor option.
+  - Record/replay: Record HTTP traffic with --record <dir> and replay it
+    offline with --replay <dir>.
+  - HAR: Capture the HTTP traffic of a run into a HAR file with --har <file>,
+    and run the parsers on it again with --replay-har <file>.
+  - Strict parsing: Malformed image and news results are skipped, or fail the
+    search with --strict.
+  - Raw responses: Save the raw bodies of the DuckDuckGo responses with
+    --dump-raw <dir>; parse errors name the file of the body that failed.
+  - Verbose mode: Show debug messages, including the status and latency of
+    every HTTP request, with the --verbose or -v option.
+  - Quiet mode: Print only one URL per result, or the bare answer, with --quiet.
+  - Exit codes: 0 on success, 1 on usage errors, 2 on network errors, 3 when
+    rate limited, 4 on empty results with --fail-on-empty, 5 on fewer results
+    than --min-results <n>, and 130 when interrupted with Ctrl-C.
+  - Ctrl-C: Interrupting a search stops its pagination and prints the results
+    fetched so far in the selected output format, and stops proxyd, the daemon,
+    and --follow; press Ctrl-C again to quit.
+  - JSON errors: Errors are printed to stderr as a JSON document, with their
+    kind and the delay to retry after, with --output json.
+  - Instant answers: Get quick answers and definitions with the `answer`
+    and `define` subcommands, and conversions and calculations with the
+    `convert` and `calc` subcommands.
 
 USAGE:
   ddg [OPTIONS]
+  ddg <COMMAND>
+
+EXIT STATUS:
+  0    Success.
+  1    Usage error.
+  2    Network error, or an invalid response.
+  3    Rate limited or blocked by DuckDuckGo.
+  4    No results (with --fail-on-empty), or no definition (with define).
+  5    Fewer results than --min-results.
+  6    A local operation failed, e.g. reading or writing a file, or setting up TLS.
+  130  Interrupted with Ctrl-C, after printing the results fetched so far.
 
 EXAMPLES:
   - Perform a basic search:
@@ -80,21 +639,172 @@ EXAMPLES:
   - Limit the number of results to 10:
     ddg --query "rust lang" --limit 10
 
+  - Get results 21 to 40:
+    ddg --query "rust lang" --backend lite --offset 20 --limit 20
+
   - Set user agent:
     ddg --query "rust lang" --user-agent "chrome"
 
+  - Send a raw user agent string:
+    ddg --query "rust lang" --user-agent "MyCrawler/1.0 (+https://example.com)"
+
+  - List the user agent presets:
+    ddg user-agents list
+
+  - Use a preset defined in your own file of `name = user agent` lines:
+    ddg --query "rust lang" --user-agents-file agents.txt --user-agent crawler
+
+  - Slow down a long scrape to one request every two seconds:
+    ddg --query "rust lang" --backend images --limit 500 --delay 2000
+
+  - Wait about three seconds between pages, backing off when throttled:
+    ddg --query "rust lang" --backend news --limit 500 --page-delay 2000 --page-jitter 2000
+
+  - Rotate the user agent between pages of a long scrape:
+    ddg --query "rust lang" --backend images --limit 500 --rotate-user-agent page
+
   - Set cookie for subsequent requests:
     ddg --query "rust lang" --cookie
 
+  - Persist cookies across runs:
+    ddg --query "rust lang" --cookie-file ~/.ddg-cookies.json
+
   - Set proxy:
     ddg --query "rust lang" --proxy "socks5://192.168.1.1:9000"
 
+  - Set an authenticated proxy for HTTPS requests only:
+    ddg --query "rust lang" --https-proxy "user:pass@proxy.local:3128"
+
+  - Trust the CA of a TLS-intercepting corporate proxy:
+    ddg --query "rust lang" --proxy "proxy.corp:3128" --ca-cert corp-ca.pem
+
+  - Avoid a broken IPv6 uplink:
+    ddg --query "rust lang" --ipv4
+
   - Use a specific backend:
     ddg --query "rust lang" --backend news
 
+  - Search text, images, and news at once, as JSON:
+    ddg --query "rust lang" --backend all --output json
+
+  - Get the news from Reuters only, grouped by day:
+    ddg --query "rust lang" --backend news --source reuters.com --group-by day
+
+  - Follow the news on a topic, checking every minute:
+    ddg --query "kernel vulnerability" --backend news --follow --poll-interval 60
+
+  - Count the news articles per source and day:
+    ddg --query "rust lang" --backend news --limit 50 --stats
+
+  - Find the vocabulary related to a query:
+    ddg --backend html --limit 30 analyze -q "rust web framework" --terms
+
+  - Diff the results of two queries side by side:
+    ddg --backend html compare -q "tokio" --q2 "async-std" --diff-style side-by-side
+
+  - Compare the results of a search in three regions:
+    ddg --query "privacy law" --regions us-en,de-de,jp-jp
+
+  - Compare the results of two user agents:
+    ddg --query "weather" --backend html --compare-user-agents chrome,safari
+
+  - Measure how much the results of a search change over five runs:
+    ddg --backend html bench-stability -q "rust web framework" --runs 5
+
+  - Serve cached searches to local tools, one request every 2 seconds at most:
+    ddg --delay 2 proxyd --listen 127.0.0.1:8787
+
+  - Check that DuckDuckGo is reachable through a proxy:
+    ddg --proxy socks5://127.0.0.1:1080 doctor
+
+  - Run the scheduled searches, with Prometheus metrics:
+    ddg daemon --metrics-listen 127.0.0.1:9187
+
+  - Save the results to an Obsidian note:
+    ddg --query "rust lang" --backend html --output markdown >> notes/rust.md
+
+  - Open a result picked with dmenu:
+    xdg-open "$(ddg --query "rust lang" --backend html --output dmenu | dmenu | sed 's/.* | //')"
+
+  - Show the cached favicon of each result:
+    ddg --query "rust lang" --backend html --favicons
+
+  - Find older articles that are still online:
+    ddg --query "rust 1.0 release" --backend html --skip-dead-links
+
+  - Find widescreen wallpapers:
+    ddg --query "mountain wallpaper" --backend images --min-width 1920 --aspect 16:9
+
+  - Download wallpapers as PNG files named after their size:
+    ddg --query "mountain wallpaper" --backend images --download-images wallpapers \
+      --name-template "{width}x{height}-{rank}.{ext}" --image-format png
+
+  - List only the images that are still online, with their dimensions:
+    ddg --query "ferris crab" --backend images --verify-images --output json
+
+  - Save the text of the top results for offline reading:
+    ddg --query "rust ownership" --backend html --limit 5 --fetch-content --output json
+
+  - Retrieve context for an LLM agent:
+    ddg --query "rust ownership" --limit 5 --output rag-json
+
+  - Track the results of a query over many runs:
+    ddg --query "rust lang" --backend html --export sqlite://results.db
+
+  - Print tab-separated titles and URLs for fzf or awk:
+    ddg --query "rust lang" --backend html --template "{title}\t{url}"
+
+  - Run a batch of queries, and resume it after an interruption:
+    ddg --queries-file queries.txt --backend news --limit 100
+    ddg --resume queries.checkpoint --backend news --limit 100
+
+  - Re-run misspelled queries with the suggested spelling:
+    ddg --query "rust langauge" --backend lite --autocorrect
+
+  - Search through Tor, renewing the circuit between pages:
+    ddg --query "rust lang" --backend news --tor --tor-control 127.0.0.1:9051
+
+  - Record HTTP traffic and replay it later without network access:
+    ddg --query "rust lang" --backend lite --record cassettes
+    ddg --query "rust lang" --backend lite --replay cassettes
+
+  - Capture the traffic of a search for browser devtools, then parse it again:
+    ddg --query "rust lang" --backend html --har search.har
+    ddg --query "rust lang" --backend html --replay-har search.har
+
+  - Check how operators are encoded, without hitting the network:
+    ddg --query "rust lang" --operators "site:github.com" --backend lite --dry-run
+
+  - Reproduce a search with curl:
+    ddg --query "rust lang" --backend html --emit-curl | sh
+
+  - Save the pages behind a parser failure for a bug report:
+    ddg --query "rust lang" --backend html --dump-raw raw-responses
+
+  - Compare backends by timing each request:
+    ddg --query "rust lang" --backend html --timings
+
+  - Estimate the cost of a batch of queries:
+    ddg --queries-file queries.txt --backend html --stats-summary
+
   - Enable verbose mode:
     ddg --query "rust lang" --verbose
 
+  - Open every result URL in a script, failing when there are none:
+    ddg --query "rust lang" --backend lite --quiet --fail-on-empty | xargs -n1 xdg-open
+
+  - Alert when a search returns fewer than 5 results, even after falling back:
+    ddg --query "rust lang" --quiet --min-results 5 > /dev/null || echo "blocked or broken"
+
+  - Get an instant answer:
+    ddg answer "2+2"
+
+  - Define a word:
+    ddg define "ducktyping"
+
+  - Convert a currency:
+    ddg convert "100 USD to EUR"
+
 For more information, visit: https://github.com/kevin-rs/duckduckgo
 "#
 )]
@@ -102,14 +812,51 @@ pub struct Cli {
     #[arg(global = true, short, long)]
     pub verbose: bool,
 
+    /// Prints only the essential fields (e.g. one URL per result), without colors, headings,
+    /// or hints.
+    #[arg(
+        global = true,
+        long = "quiet",
+        default_value_t = false,
+        conflicts_with = "verbose"
+    )]
+    pub quiet: bool,
+
+    /// Exits with status 4 when the search returns no results.
+    #[arg(global = true, long = "fail-on-empty", default_value_t = false)]
+    pub fail_on_empty: bool,
+
+    /// Exits with status 5 when the search returns fewer than N results, once the auto backend
+    /// has fallen back to every backend, e.g. to detect blocked requests or a broken parser.
+    #[arg(
+        long = "min-results",
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub min_results: Option<usize>,
+
+    /// Runs a quick lookup instead of a search.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Sets the search query.
-    #[arg(short = 'q', long = "query")]
+    #[arg(short = 'q', long = "query", default_value_t = String::from(""))]
     pub query: String,
 
     /// Sets the search operators.
     #[arg(short = 'o', long = "operators", default_value_t = String::from(""))]
     pub operators: String,
 
+    /// Adds the operators of a preset to the search, e.g. `code` for GitHub and Stack
+    /// Overflow (see `ddg presets list`).
+    #[arg(long = "preset", value_name = "NAME")]
+    pub preset: Option<String>,
+
+    /// Loads additional presets from a file of `name = operators` lines (`ddg/presets` in the
+    /// user config directory, if it exists, by default).
+    #[arg(long = "presets-file", value_name = "PATH", global = true)]
+    pub presets_file: Option<String>,
+
     /// Enable safe search.
     #[arg(short = 's', long = "safe", default_value_t = false)]
     pub safe: bool,
@@ -122,19 +869,417 @@ pub struct Cli {
     #[arg(short = 'l', long = "limit", default_value_t = 10)]
     pub limit: usize,
 
-    /// Sets the user agent for the HTTP client.
+    /// Skips the first N results (e.g. `--offset 20 --limit 20` for results 21 to 40).
+    #[arg(long = "offset", value_name = "N", default_value_t = 0)]
+    pub offset: usize,
+
+    /// Keeps at most N results per site, fetching more results to fill the limit. Not applied
+    /// to the image and news searches of a batch, which are printed page by page.
+    #[arg(long = "max-per-domain", value_name = "N")]
+    pub max_per_domain: Option<usize>,
+
+    /// Collapses the results with near-identical titles and snippets, such as syndicated news,
+    /// into the first one, listing the others as "Also on". The similarity goes from 0 to 1.
+    /// Not applied to the image and news searches of a batch.
+    #[arg(
+        long = "collapse-duplicates",
+        value_name = "SIMILARITY",
+        num_args = 0..=1,
+        default_missing_value = "0.8"
+    )]
+    pub collapse_duplicates: Option<f64>,
+
+    /// Keeps the images at least this many pixels wide, fetching more results to fill the
+    /// limit. Images of unknown size are left out.
+    #[arg(long = "min-width", value_name = "PIXELS")]
+    pub min_width: Option<u32>,
+
+    /// Keeps the images at least this many pixels high, fetching more results to fill the
+    /// limit. Images of unknown size are left out.
+    #[arg(long = "min-height", value_name = "PIXELS")]
+    pub min_height: Option<u32>,
+
+    /// Keeps the images with this ratio of width to height (e.g. `16:9`, within 2%), fetching
+    /// more results to fill the limit.
+    #[arg(long = "aspect", value_name = "RATIO")]
+    pub aspect: Option<AspectRatio>,
+
+    /// Sets the user agent for the HTTP client: a preset name, "random", or a raw user agent
+    /// string.
     #[arg(short = 'u', long = "user-agent", default_value_t = String::from("firefox"))]
     pub user_agent: String,
 
+    /// Loads additional user agent presets from a file of `name = user agent` lines.
+    #[arg(long = "user-agents-file", value_name = "PATH", global = true)]
+    pub user_agents_file: Option<String>,
+
+    /// Sets the file of the saved searches (`ddg/aliases` in the user config directory by
+    /// default).
+    #[arg(long = "aliases-file", value_name = "PATH", global = true)]
+    pub aliases_file: Option<String>,
+
+    /// Rotates between random user agents for every request or every page.
+    #[arg(long = "rotate-user-agent", value_enum, value_name = "WHEN")]
+    pub rotate_user_agent: Option<UserAgentRotation>,
+
     /// Sets the cookie for subsequent HTTP requests.
     #[arg(short = 'c', long = "cookie", default_value_t = true)]
     pub cookie: bool,
 
-    /// Sets the proxy for the HTTP client (e.g. "socks5://192.168.1.1:9000").
+    /// Loads cookies from the given file, and saves them to it when the run ends, so sessions
+    /// persist across runs.
+    #[arg(long = "cookie-file", value_name = "PATH")]
+    pub cookie_file: Option<String>,
+
+    /// Sets the proxy for the HTTP client (e.g. "socks5://192.168.1.1:9000" or
+    /// "user:pass@proxy.local:3128").
     #[arg(short = 'p', long = "proxy", default_value_t = String::from(""))]
     pub proxy: String,
 
+    /// Sets the proxy used for plain HTTP requests.
+    #[arg(long = "http-proxy", value_name = "URL")]
+    pub http_proxy: Option<String>,
+
+    /// Sets the proxy used for HTTPS requests.
+    #[arg(long = "https-proxy", value_name = "URL")]
+    pub https_proxy: Option<String>,
+
+    /// Sets a comma-separated list of hosts that bypass the proxies.
+    #[arg(long = "no-proxy", value_name = "HOSTS")]
+    pub no_proxy: Option<String>,
+
+    /// Ignores the HTTP_PROXY, HTTPS_PROXY, ALL_PROXY, and NO_PROXY environment variables.
+    #[arg(long = "no-env-proxy", default_value_t = false)]
+    pub no_env_proxy: bool,
+
+    /// Trusts the certificate authorities in the given PEM file (e.g. for TLS-intercepting
+    /// proxies).
+    #[arg(long = "ca-cert", value_name = "PEM")]
+    pub ca_cert: Vec<String>,
+
+    /// Sets the minimum TLS version.
+    #[arg(long = "min-tls", value_enum, value_name = "VERSION")]
+    pub min_tls: Option<TlsVersion>,
+
+    /// Sets the maximum TLS version.
+    #[arg(long = "max-tls", value_enum, value_name = "VERSION")]
+    pub max_tls: Option<TlsVersion>,
+
+    /// Disables TLS certificate validation. Insecure: only use this for debugging.
+    #[arg(long = "insecure", default_value_t = false)]
+    pub insecure: bool,
+
+    /// Connects over IPv4 only.
+    #[arg(short = '4', long = "ipv4", default_value_t = false, conflicts_with_all = ["ipv6", "interface"])]
+    pub ipv4: bool,
+
+    /// Connects over IPv6 only.
+    #[arg(
+        short = '6',
+        long = "ipv6",
+        default_value_t = false,
+        conflicts_with = "interface"
+    )]
+    pub ipv6: bool,
+
+    /// Sends requests from the network interface with the given IP address.
+    #[arg(long = "interface", value_name = "IP")]
+    pub interface: Option<String>,
+
+    /// Sets the minimum delay between two requests, in milliseconds (0 to disable).
+    #[arg(long = "delay", value_name = "MS", default_value_t = 500)]
+    pub delay: u64,
+
+    /// Shares the --delay between requests with every ddg process of the user run with this
+    /// option, through a lock file in the XDG runtime directory, so that concurrent runs
+    /// respect one combined request budget. Requires a --delay above 0.
+    #[arg(long = "shared-rate-limit", global = true)]
+    pub shared_rate_limit: bool,
+
+    /// Sets the delay between two pages of image and news searches, in milliseconds (0 to
+    /// disable). The delay grows when responses get slow or DuckDuckGo starts rate limiting.
+    #[arg(long = "page-delay", value_name = "MS", default_value_t = 0)]
+    pub page_delay: u64,
+
+    /// Adds a random delay of up to this many milliseconds to each `--page-delay`.
+    #[arg(long = "page-jitter", value_name = "MS", requires = "page_delay")]
+    pub page_jitter: Option<u64>,
+
+    /// Prints the DNS, time to first byte, download, and parse durations of each request.
+    #[arg(long = "timings", default_value_t = false, global = true)]
+    pub timings: bool,
+
+    /// Prints the number of requests sent, the bytes downloaded, the pages fetched, and the
+    /// cache hits of the run to stderr, to estimate the cost of batch jobs.
+    #[arg(long = "stats-summary", global = true)]
+    pub stats_summary: bool,
+
     /// Sets the backend to use.
     #[arg(short = 'b', long = "backend", value_enum, default_value_t = Backend::Auto)]
     pub backend: Backend,
+
+    /// Only keeps news from the given source, a domain (e.g. "reuters.com") or a publisher name.
+    /// Can be repeated.
+    #[arg(long = "source", value_name = "SOURCE")]
+    pub source: Vec<String>,
+
+    /// Groups news results by source or by day.
+    #[arg(long = "group-by", value_enum, value_name = "KEY")]
+    pub group_by: Option<GroupBy>,
+
+    /// Prints a summary after the results: the number of results per domain and per top-level
+    /// domain, the distribution of the snippet lengths, and the publication days of news.
+    #[arg(long = "stats")]
+    pub stats: bool,
+
+    /// Keeps polling the news search, like `tail -f`, printing the articles that were not
+    /// found before with the time they appeared. Only applies to the news backend.
+    #[arg(long = "follow")]
+    pub follow: bool,
+
+    /// Sets the number of seconds between the polls of --follow.
+    #[arg(
+        long = "poll-interval",
+        value_name = "SECONDS",
+        default_value_t = 300,
+        requires = "follow"
+    )]
+    pub poll_interval: u64,
+
+    /// Colors the output. On Windows, `auto` enables the escape codes of the console, and leaves
+    /// the output uncolored on older consoles that do not support them.
+    #[arg(long = "color", value_enum, value_name = "WHEN", default_value_t = Colors::Auto)]
+    pub color: Colors,
+
+    /// Prints result titles as clickable OSC 8 hyperlinks instead of printing the URLs.
+    #[arg(long = "hyperlinks", value_enum, value_name = "WHEN", default_value_t = Hyperlinks::Auto)]
+    pub hyperlinks: Hyperlinks,
+
+    /// Prints the dates of news results relative to now, such as "3 hours ago", followed by
+    /// the date in a dim suffix.
+    #[arg(
+        long = "relative-dates",
+        value_enum,
+        value_name = "WHEN",
+        default_value_t = RelativeDates::Auto
+    )]
+    pub relative_dates: RelativeDates,
+
+    /// Prints the dates of news results with a strftime pattern (e.g. "%-d %B %Y, %H:%M"), in
+    /// UTC, naming the months and weekdays in the language of LC_ALL, LC_TIME, or LANG.
+    #[arg(long = "date-format", value_name = "PATTERN")]
+    pub date_format: Option<DatePattern>,
+
+    /// Makes the output reproducible, for snapshot tests and diffs between runs: sorts the
+    /// results by rank then URL, prints absolute dates unless --relative-dates is on, and leaves
+    /// out the time of the run (`fetched_at` and `found_at`).
+    #[arg(global = true, long = "deterministic")]
+    pub deterministic: bool,
+
+    /// Fits the text output in this many columns, truncating titles and wrapping snippets.
+    /// Defaults to the terminal width when stdout is a terminal; 0 disables wrapping.
+    #[arg(long = "width", value_name = "COLUMNS")]
+    pub width: Option<usize>,
+
+    /// Downloads the favicon of each result's site from DuckDuckGo's icon service, caching them
+    /// on disk, and prints their paths.
+    #[arg(long = "favicons")]
+    pub favicons: bool,
+
+    /// Checks each result URL with a HEAD request, printing its status code and redirect target.
+    #[arg(long = "check-links")]
+    pub check_links: bool,
+
+    /// Checks each result URL and leaves out the dead links: unreachable, gone (404 or 410), or
+    /// failing with a server error.
+    #[arg(long = "skip-dead-links")]
+    pub skip_dead_links: bool,
+
+    /// Checks the file of each image result with a HEAD request, printing its media type and
+    /// size, and leaves out the unreachable ones and the ones that are not images. The width and
+    /// height missing from the first N images (10 by default) are read from their files.
+    #[arg(
+        long = "verify-images",
+        value_name = "N",
+        num_args = 0..=1,
+        default_missing_value = "10"
+    )]
+    pub verify_images: Option<usize>,
+
+    /// Downloads the image results into a directory, with a `manifest.json` mapping each file
+    /// back to its source page.
+    #[arg(long = "download-images", value_name = "DIR")]
+    pub download_images: Option<String>,
+
+    /// Names the downloaded images with a template of the placeholders {query}, {rank},
+    /// {title}, {source}, {width}, {height}, and {ext}.
+    #[arg(
+        long = "name-template",
+        value_name = "TEMPLATE",
+        default_value = DEFAULT_NAME_TEMPLATE,
+        requires = "download_images"
+    )]
+    pub name_template: NameTemplate,
+
+    /// Converts the downloaded images to png, jpeg, or webp (requires the `convert` feature).
+    #[arg(
+        long = "image-format",
+        value_name = "FORMAT",
+        requires = "download_images"
+    )]
+    pub image_format: Option<ImageFormat>,
+
+    /// Downloads the page of each result and prints its readable content: its title, byline,
+    /// and main text.
+    #[arg(long = "fetch-content")]
+    pub fetch_content: bool,
+
+    /// Exports the results in addition to printing them: appends them to a SQLite database
+    /// with `sqlite://results.db`, or, when built with the `parquet` feature, writes them to a
+    /// Parquet or Arrow IPC file with `parquet://results.parquet` or `arrow://results.arrow`.
+    #[arg(long = "export", value_name = "TARGET")]
+    pub export: Option<ExportTarget>,
+
+    /// Runs every query of the file, one per line, as a batch, saving the progress to the
+    /// checkpoint file after each query and page.
+    #[arg(long = "queries-file", value_name = "PATH", conflicts_with = "resume")]
+    pub queries_file: Option<String>,
+
+    /// Sets the checkpoint file of a `--queries-file` batch (the queries file with a
+    /// `.checkpoint` extension by default).
+    #[arg(long = "checkpoint", value_name = "PATH", requires = "queries_file")]
+    pub checkpoint: Option<String>,
+
+    /// Resumes the batch saved in the given checkpoint file where it stopped, updating the
+    /// checkpoint as it goes.
+    #[arg(long = "resume", value_name = "CHECKPOINT")]
+    pub resume: Option<String>,
+
+    /// Prints the URLs, query parameters, and headers of the requests the search would send,
+    /// without sending them.
+    #[arg(long = "dry-run", global = true)]
+    pub dry_run: bool,
+
+    /// Prints the curl commands sending the requests of the search, with its cookies, proxy,
+    /// and TLS options, instead of sending them.
+    #[arg(long = "emit-curl", global = true, conflicts_with = "dry_run")]
+    pub emit_curl: bool,
+
+    /// Sets the output format.
+    #[arg(long = "output", value_enum, default_value_t = OutputFormat::Text, global = true)]
+    pub output: OutputFormat,
+
+    /// Prints strictly linear text for screen readers, without colors nor layout, announcing
+    /// each result as "Result 3 of 10:". Same as `--output plain`.
+    #[arg(long = "plain", global = true, conflicts_with = "output")]
+    pub plain: bool,
+
+    /// Prints each result with a template such as "{title}\t{url}", using the {title}, {url},
+    /// {snippet}, {date}, {rank}, and {source} placeholders.
+    #[arg(long = "template", value_name = "TEMPLATE", conflicts_with = "output")]
+    pub template: Option<Template>,
+
+    /// Outputs only the given fields of each result, such as "url,title,date": as
+    /// tab-separated values in text output, or as the members of each result with --output
+    /// json. The fields are title, url, snippet, date, rank, and source.
+    #[arg(long = "fields", value_name = "FIELDS", conflicts_with_all = ["template", "oneline"])]
+    pub fields: Option<Fields>,
+
+    /// Prints each result as a "rank<TAB>url<TAB>title" line, for grep and cut.
+    #[arg(long = "oneline", conflicts_with_all = ["output", "template"])]
+    pub oneline: bool,
+
+    /// Ends each result with a NUL character instead of a line break, for `xargs -0`. Results
+    /// are printed as their URL, unless `--template` or `--oneline` is set.
+    #[arg(short = '0', long = "print0", global = true)]
+    pub print0: bool,
+
+    /// Opens the top result of the search in the default browser, without printing the
+    /// results, like DuckDuckGo's `\` operator.
+    #[arg(long = "lucky", conflicts_with_all = ["queries_file", "resume"])]
+    pub lucky: bool,
+
+    /// Runs the search in each of the comma-separated regions concurrently (e.g.
+    /// `us-en,de-de,jp-jp`), and prints the results shared by all the regions, then the
+    /// results found in only one of them.
+    #[arg(
+        long = "regions",
+        value_name = "REGIONS",
+        value_delimiter = ',',
+        num_args = 1,
+        conflicts_with_all = ["queries_file", "resume", "lucky"]
+    )]
+    pub regions: Vec<String>,
+
+    /// Runs the search with each of the comma-separated user agents concurrently, as preset
+    /// names or raw user agent strings (e.g. `chrome,safari`), and prints the results shared by
+    /// all of them, the results they rank differently, and the results found with only one.
+    #[arg(
+        long = "compare-user-agents",
+        value_name = "AGENTS",
+        value_delimiter = ',',
+        num_args = 1,
+        conflicts_with_all = ["queries_file", "resume", "lucky", "regions", "rotate_user_agent"]
+    )]
+    pub compare_user_agents: Vec<String>,
+
+    /// Prints only the URL of the top result instead of opening it, with --lucky.
+    #[arg(long = "print", requires = "lucky")]
+    pub print: bool,
+
+    /// Prints only the instant answer of the query, or else its abstract, on a single line,
+    /// e.g. for shell prompts and scripts.
+    #[arg(long = "answer-only", conflicts_with_all = ["lucky", "queries_file", "resume"])]
+    pub answer_only: bool,
+
+    /// Re-runs the search with DuckDuckGo's spelling suggestion, if any.
+    #[arg(long = "autocorrect", default_value_t = false)]
+    pub autocorrect: bool,
+
+    /// Routes traffic through a local Tor daemon (SOCKS5 proxy on 127.0.0.1:9050).
+    #[arg(
+        long = "tor",
+        default_value_t = false,
+        conflicts_with_all = ["proxy", "http_proxy", "https_proxy"]
+    )]
+    pub tor: bool,
+
+    /// Requests a new Tor circuit through this control port between paginated requests.
+    #[arg(long = "tor-control", value_name = "ADDR", requires = "tor")]
+    pub tor_control: Option<String>,
+
+    /// Sets the password of the Tor control port.
+    #[arg(long = "tor-password", requires = "tor_control")]
+    pub tor_password: Option<String>,
+
+    /// Saves the raw HTML and JSON bodies of the DuckDuckGo responses into the given directory,
+    /// to attach to bug reports about parser failures.
+    #[arg(long = "dump-raw", value_name = "DIR", global = true)]
+    pub dump_raw: Option<String>,
+
+    /// Fails when an image or news result is malformed, instead of skipping it.
+    #[arg(long = "strict", global = true)]
+    pub strict: bool,
+
+    /// Records HTTP interactions into the given directory, replaying already recorded ones.
+    #[arg(long = "record", value_name = "DIR", conflicts_with = "replay")]
+    pub record: Option<String>,
+
+    /// Replays HTTP interactions from the given directory without hitting the network.
+    #[arg(long = "replay", value_name = "DIR")]
+    pub replay: Option<String>,
+
+    /// Captures every request and response of the run into a HAR file, for the network panel
+    /// of browser devtools.
+    #[arg(long = "har", value_name = "FILE", conflicts_with = "replay_har")]
+    pub har: Option<String>,
+
+    /// Answers the requests from a HAR file captured with --har, running the parsers again
+    /// without hitting the network.
+    #[arg(
+        long = "replay-har",
+        value_name = "FILE",
+        conflicts_with_all = ["record", "replay"]
+    )]
+    pub replay_har: Option<String>,
 }
@@ -1,3 +1,5 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 /// An enumeration representing ANSI color codes for text styling.
 pub enum AnsiColor {
     /// Cyan color.
@@ -74,6 +76,9 @@ impl AnsiColor {
     /// assert_eq!(cyan_code, "\u{001B}[36m");
     /// ```
     pub fn escape_code(&self) -> &'static str {
+        if !enabled() {
+            return "";
+        }
         match self {
             AnsiColor::Cyan => "\u{001B}[36m",
             AnsiColor::Blue => "\u{001B}[34m",
@@ -108,6 +113,33 @@ impl AnsiColor {
     }
 }
 
+/// Whether escape codes are written, for all the styles of the process.
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Turns the escape codes of every `AnsiColor` and `AnsiStyle` on or off, e.g. for consoles that
+/// print them as is. They are on by default.
+///
+/// # Arguments
+/// * `enable` - Whether escape codes are written.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::colors::{self, AnsiColor};
+///
+/// colors::set_enabled(false);
+/// assert_eq!(AnsiColor::Cyan.escape_code(), "");
+/// colors::set_enabled(true);
+/// assert_eq!(AnsiColor::Cyan.escape_code(), "\u{001B}[36m");
+/// ```
+pub fn set_enabled(enable: bool) {
+    ENABLED.store(enable, Ordering::Relaxed);
+}
+
+/// Returns `true` unless escape codes were turned off with `set_enabled`.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
 /// A structure representing ANSI text styling.
 pub struct AnsiStyle {
     /// A flag indicating whether text should be bold.
@@ -133,7 +165,7 @@ impl AnsiStyle {
     pub fn escape_code(&self) -> String {
         let mut code = String::new();
 
-        if self.bold {
+        if self.bold && enabled() {
             code.push_str("\u{001B}[1m");
         }
 
@@ -157,6 +189,6 @@ impl AnsiStyle {
     /// assert_eq!(reset_code, "\u{001B}[0m");
     /// ```
     pub fn reset_code() -> &'static str {
-        "\u{001B}[0m"
+        if enabled() { "\u{001B}[0m" } else { "" }
     }
 }
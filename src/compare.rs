@@ -0,0 +1,437 @@
+use crate::backend::SearchBackend;
+use crate::merge::result_key;
+use crate::params::SearchParams;
+use crate::response::SearchResult;
+use crate::user_agents;
+use anyhow::Result;
+use futures_util::future::join_all;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// The results of a search run in several variants, such as several regions, user agents, or
+/// queries,
+/// telling which results all the variants share, which ones they rank differently, and which
+/// ones are unique to a variant.
+///
+/// Results are the same when their URLs are, ignoring the scheme, a `www.` prefix, a fragment,
+/// and a trailing slash (see `merge::rrf`).
+///
+/// # Examples
+/// ```
+/// use duckduckgo::compare::Comparison;
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+///
+/// let list = |urls: &[&str]| -> anyhow::Result<Vec<SearchResult>> {
+///     Ok(urls
+///         .iter()
+///         .map(|url| {
+///             SearchResult::Text(LiteSearchResult {
+///                 url: (*url).into(),
+///                 ..Default::default()
+///             })
+///         })
+///         .collect())
+/// };
+/// let comparison = Comparison::new(
+///     "privacy law",
+///     vec![
+///         (String::from("us-en"), list(&["https://gdpr.eu/", "https://ftc.gov/"])),
+///         (String::from("de-de"), list(&["https://bfdi.bund.de/", "https://gdpr.eu/"])),
+///     ],
+/// );
+/// assert_eq!(comparison.shared, ["https://gdpr.eu/"]);
+/// assert_eq!(comparison.moved[0].ranks, [1, 2]);
+/// assert_eq!(comparison.variants[0].unique, ["https://ftc.gov/"]);
+/// assert_eq!(comparison.variants[1].unique, ["https://bfdi.bund.de/"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Comparison {
+    /// The search query, or the queries compared by `compare_queries`.
+    pub query: String,
+    /// The URLs of the results found by every variant that succeeded, in the order of the
+    /// first of them.
+    pub shared: Vec<String>,
+    /// The results found by every variant that succeeded, but not at the same rank in all of
+    /// them, in the order of the first of them.
+    pub moved: Vec<MovedResult>,
+    /// The results of each variant, in the order they were given.
+    pub variants: Vec<ComparedResults>,
+}
+
+/// The results of one variant of a `Comparison`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ComparedResults {
+    /// The name of the variant, such as a region code.
+    pub name: String,
+    /// The results of the variant, empty if it failed.
+    pub results: Vec<SearchResult>,
+    /// The URLs of the results no other variant found, in the order of the results.
+    pub unique: Vec<String>,
+    /// Why the search failed, if it did.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A result of a `Comparison` that the variants rank differently.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MovedResult {
+    /// The URL of the result, as found by the first variant that succeeded.
+    pub url: String,
+    /// The 1-based rank of the result in each variant that succeeded, in the order of the
+    /// variants.
+    pub ranks: Vec<usize>,
+}
+
+impl Comparison {
+    /// Compares the results of the variants of a search.
+    ///
+    /// # Arguments
+    /// * `query` - The search query.
+    /// * `variants` - The name of each variant, with its results or the error it failed with.
+    pub fn new(
+        query: impl Into<String>,
+        variants: Vec<(String, Result<Vec<SearchResult>>)>,
+    ) -> Self {
+        let variants: Vec<ComparedResults> = variants
+            .into_iter()
+            .map(|(name, results)| match results {
+                Ok(results) => ComparedResults {
+                    name,
+                    results,
+                    unique: Vec::new(),
+                    error: None,
+                },
+                Err(err) => ComparedResults {
+                    name,
+                    results: Vec::new(),
+                    unique: Vec::new(),
+                    error: Some(format!("{:#}", err)),
+                },
+            })
+            .collect();
+
+        // The 1-based rank of each result in each variant that succeeded, counting duplicates
+        // once.
+        let ranks: Vec<HashMap<String, usize>> = variants
+            .iter()
+            .filter(|v| v.error.is_none())
+            .map(|variant| {
+                let mut ranks = HashMap::new();
+                for result in &variant.results {
+                    let rank = ranks.len() + 1;
+                    ranks.entry(result_key(result)).or_insert(rank);
+                }
+                ranks
+            })
+            .collect();
+        let count = |key: &str| ranks.iter().filter(|r| r.contains_key(key)).count();
+
+        let mut comparison = Comparison {
+            query: query.into(),
+            shared: Vec::new(),
+            moved: Vec::new(),
+            variants,
+        };
+        if let Some(first) = comparison.variants.iter().find(|v| v.error.is_none())
+            && ranks.len() > 1
+        {
+            comparison.shared = unique_urls(first, |key| count(key) == ranks.len());
+            comparison.moved = comparison
+                .shared
+                .iter()
+                .filter_map(|url| {
+                    let key = first
+                        .results
+                        .iter()
+                        .find(|result| result.url() == url)
+                        .map(result_key)?;
+                    let ranks: Vec<usize> = ranks.iter().map(|r| r[&key]).collect();
+                    ranks
+                        .iter()
+                        .any(|&rank| rank != ranks[0])
+                        .then(|| MovedResult {
+                            url: url.clone(),
+                            ranks,
+                        })
+                })
+                .collect();
+        }
+        for variant in &mut comparison.variants {
+            variant.unique = unique_urls(variant, |key| count(key) == 1);
+        }
+        comparison
+    }
+
+    /// Returns the results found by every variant, as returned by the first variant that
+    /// succeeded.
+    pub fn shared_results(&self) -> Vec<&SearchResult> {
+        let Some(first) = self.variants.iter().find(|v| v.error.is_none()) else {
+            return Vec::new();
+        };
+        let shared: HashSet<&str> = self.shared.iter().map(String::as_str).collect();
+        first
+            .results
+            .iter()
+            .filter(|result| shared.contains(result.url()))
+            .collect()
+    }
+}
+
+impl ComparedResults {
+    /// Returns the results no other variant found.
+    pub fn unique_results(&self) -> Vec<&SearchResult> {
+        let unique: HashSet<&str> = self.unique.iter().map(String::as_str).collect();
+        self.results
+            .iter()
+            .filter(|result| unique.contains(result.url()))
+            .collect()
+    }
+}
+
+/// Returns the URLs of the results of a variant whose key matches, without duplicates.
+fn unique_urls(variant: &ComparedResults, keep: impl Fn(&str) -> bool) -> Vec<String> {
+    let mut seen = HashSet::new();
+    variant
+        .results
+        .iter()
+        .filter(|result| {
+            let key = result_key(result);
+            keep(&key) && seen.insert(key)
+        })
+        .map(|result| result.url().to_string())
+        .collect()
+}
+
+/// Runs a search in several regions concurrently and compares their results.
+///
+/// # Arguments
+/// * `backend` - The backend running the searches.
+/// * `params` - The search parameters, whose region is replaced by each region.
+/// * `regions` - The region codes, e.g. `["us-en", "de-de", "jp-jp"]`.
+///
+/// # Example
+/// ```no_run
+/// use duckduckgo::backend::HtmlBackend;
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::compare::compare_regions;
+/// use duckduckgo::params::SearchParams;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let browser = Browser::new(reqwest::Client::new());
+///     let backend = HtmlBackend::new(&browser);
+///     let params = SearchParams::new("privacy law");
+///     let comparison = compare_regions(&backend, &params, &["us-en", "de-de"]).await;
+///     println!("{} results in both regions", comparison.shared.len());
+/// }
+/// ```
+pub async fn compare_regions(
+    backend: &dyn SearchBackend,
+    params: &SearchParams,
+    regions: &[impl AsRef<str>],
+) -> Comparison {
+    let variants = regions
+        .iter()
+        .map(|region| {
+            let region = region.as_ref();
+            (region.to_string(), params.clone().region(region))
+        })
+        .collect();
+    compare_variants(backend, &params.query, variants).await
+}
+
+/// Runs a search with several user agents concurrently, such as a desktop and a mobile
+/// browser, and compares their results.
+///
+/// # Arguments
+/// * `backend` - The backend running the searches. Its browser must not rotate user agents.
+/// * `params` - The search parameters, whose user agent is replaced by each user agent.
+/// * `user_agents` - The user agents, as names of the global `UserAgentRegistry` or raw user
+///   agent strings, which name the variants.
+///
+/// # Errors
+/// Returns an error if a user agent is neither a registered name nor a valid user agent
+/// string.
+///
+/// # Example
+/// ```no_run
+/// use duckduckgo::backend::HtmlBackend;
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::compare::compare_user_agents;
+/// use duckduckgo::params::SearchParams;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let browser = Browser::new(reqwest::Client::new());
+///     let backend = HtmlBackend::new(&browser);
+///     let params = SearchParams::new("weather");
+///     let comparison = compare_user_agents(&backend, &params, &["chrome", "safari"]).await?;
+///     println!("{} results ranked differently", comparison.moved.len());
+///     Ok(())
+/// }
+/// ```
+pub async fn compare_user_agents(
+    backend: &dyn SearchBackend,
+    params: &SearchParams,
+    user_agents: &[impl AsRef<str>],
+) -> Result<Comparison> {
+    let mut variants = Vec::with_capacity(user_agents.len());
+    for name in user_agents {
+        let name = name.as_ref();
+        let agent = user_agents::resolve(name)?;
+        variants.push((name.to_string(), params.clone().user_agent(agent)));
+    }
+    Ok(compare_variants(backend, &params.query, variants).await)
+}
+
+/// Runs several searches concurrently and compares their results.
+///
+/// The query of the comparison is the queries, separated by `" vs "`.
+///
+/// # Arguments
+/// * `backend` - The backend running the searches.
+/// * `params` - The search parameters, whose query is replaced by each query.
+/// * `queries` - The queries, which name the variants.
+///
+/// # Example
+/// ```no_run
+/// use duckduckgo::backend::HtmlBackend;
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::compare::compare_queries;
+/// use duckduckgo::params::SearchParams;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let browser = Browser::new(reqwest::Client::new());
+///     let backend = HtmlBackend::new(&browser);
+///     let params = SearchParams::new("");
+///     let comparison = compare_queries(&backend, &params, &["tokio", "async-std"]).await;
+///     println!("{} results found by both queries", comparison.shared.len());
+/// }
+/// ```
+pub async fn compare_queries(
+    backend: &dyn SearchBackend,
+    params: &SearchParams,
+    queries: &[impl AsRef<str>],
+) -> Comparison {
+    let variants = queries
+        .iter()
+        .map(|query| {
+            let query = query.as_ref();
+            let params = SearchParams {
+                query: query.to_string(),
+                ..params.clone()
+            };
+            (query.to_string(), params)
+        })
+        .collect();
+    let query: Vec<&str> = queries.iter().map(AsRef::as_ref).collect();
+    compare_variants(backend, &query.join(" vs "), variants).await
+}
+
+/// Runs the variants of a search concurrently and compares their results.
+///
+/// # Arguments
+/// * `backend` - The backend running the searches.
+/// * `query` - The query of the comparison.
+/// * `variants` - The name and the search parameters of each variant.
+pub async fn compare_variants(
+    backend: &dyn SearchBackend,
+    query: &str,
+    variants: Vec<(String, SearchParams)>,
+) -> Comparison {
+    let searches = variants
+        .into_iter()
+        .map(|(name, params)| async move { (name, backend.search(&params).await) });
+    Comparison::new(query, join_all(searches).await)
+}
+
+/// A line of the diff of two ranked lists of results, computed by `diff_results`, with the
+/// indices of its results in the lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine {
+    /// A result of both lists, in the same order relative to the other shared results.
+    Shared(usize, usize),
+    /// A result only in the first list, or ranked out of order in the second one.
+    Removed(usize),
+    /// A result only in the second list, or ranked out of order in the first one.
+    Added(usize),
+}
+
+/// Diffs two ranked lists of results, like `diff` does with lines.
+///
+/// The diff keeps the longest sequence of results that both lists have in the same order, and
+/// lists the other results as removed from the first list or added by the second one, at their
+/// rank. Results are the same when their URLs are, ignoring the scheme, a `www.` prefix, a
+/// fragment, and a trailing slash.
+///
+/// # Arguments
+/// * `old` - The first list.
+/// * `new` - The second list.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::compare::{DiffLine, diff_results};
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+///
+/// let list = |urls: &[&str]| -> Vec<SearchResult> {
+///     urls.iter()
+///         .map(|url| {
+///             SearchResult::Text(LiteSearchResult {
+///                 url: (*url).into(),
+///                 ..Default::default()
+///             })
+///         })
+///         .collect()
+/// };
+/// let tokio = list(&["https://tokio.rs/", "https://docs.rs/tokio", "https://crates.io/"]);
+/// let async_std = list(&["https://async.rs/", "http://www.tokio.rs", "https://crates.io/"]);
+/// assert_eq!(
+///     diff_results(&tokio, &async_std),
+///     [
+///         DiffLine::Added(0),
+///         DiffLine::Shared(0, 1),
+///         DiffLine::Removed(1),
+///         DiffLine::Shared(2, 2),
+///     ]
+/// );
+/// ```
+pub fn diff_results(old: &[SearchResult], new: &[SearchResult]) -> Vec<DiffLine> {
+    let old_keys: Vec<String> = old.iter().map(result_key).collect();
+    let new_keys: Vec<String> = new.iter().map(result_key).collect();
+    let (n, m) = (old_keys.len(), new_keys.len());
+
+    // The length of the longest common subsequence of the suffixes of the lists.
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_keys[i] == new_keys[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_keys[i] == new_keys[j] {
+            lines.push(DiffLine::Shared(i, j));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(DiffLine::Removed(i));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(j));
+            j += 1;
+        }
+    }
+    lines.extend((i..n).map(DiffLine::Removed));
+    lines.extend((j..m).map(DiffLine::Added));
+    lines
+}
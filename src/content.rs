@@ -0,0 +1,217 @@
+#[cfg(feature = "content")]
+use scraper::{ElementRef, Html, Selector};
+use serde::Serialize;
+#[cfg(feature = "content")]
+use std::collections::HashMap;
+#[cfg(feature = "content")]
+use std::sync::LazyLock;
+
+/// The readable content of a web page: its title, byline, and main text, without the
+/// navigation, sidebars, and footers around it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct PageContent {
+    /// The URL the page was fetched from, after redirects.
+    pub url: String,
+    /// The title of the article, or of the page if it has no article title.
+    pub title: String,
+    /// The author of the article, if the page names one.
+    pub byline: Option<String>,
+    /// The main text of the page, with paragraphs separated by blank lines.
+    pub text: String,
+}
+
+impl PageContent {
+    /// Returns the paragraphs of the main text.
+    pub fn paragraphs(&self) -> impl Iterator<Item = &str> {
+        self.text.split("\n\n").filter(|p| !p.is_empty())
+    }
+}
+
+/// Paragraphs shorter than this are ignored when looking for the main text, as they are
+/// usually captions, buttons, or bylines.
+#[cfg(feature = "content")]
+const MIN_PARAGRAPH_LEN: usize = 25;
+
+/// The elements whose text is kept as a paragraph of the main text.
+#[cfg(feature = "content")]
+const BLOCK_TAGS: &[&str] = &[
+    "p",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "li",
+    "blockquote",
+    "pre",
+];
+
+/// The elements holding boilerplate rather than content.
+#[cfg(feature = "content")]
+const BOILERPLATE_TAGS: &[&str] = &[
+    "nav", "header", "footer", "aside", "script", "style", "noscript", "form",
+];
+
+/// Compiles a selector that is known to be valid.
+#[cfg(feature = "content")]
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).unwrap_or_else(|e| panic!("invalid selector {css:?}: {e}"))
+}
+
+#[cfg(feature = "content")]
+static PARAGRAPH: LazyLock<Selector> = LazyLock::new(|| selector("p, pre"));
+#[cfg(feature = "content")]
+static BLOCK: LazyLock<Selector> = LazyLock::new(|| selector(&BLOCK_TAGS.join(", ")));
+#[cfg(feature = "content")]
+static TITLE: LazyLock<Selector> =
+    LazyLock::new(|| selector(r#"meta[property="og:title"], meta[name="twitter:title"]"#));
+#[cfg(feature = "content")]
+static DOCUMENT_TITLE: LazyLock<Selector> = LazyLock::new(|| selector("title, h1"));
+#[cfg(feature = "content")]
+static AUTHOR: LazyLock<Selector> = LazyLock::new(|| {
+    selector(r#"meta[name="author"], meta[property="article:author"], meta[name="byl"]"#)
+});
+#[cfg(feature = "content")]
+static BYLINE: LazyLock<Selector> =
+    LazyLock::new(|| selector(r#"[rel="author"], [itemprop="author"], .byline, .author"#));
+#[cfg(feature = "content")]
+static BODY: LazyLock<Selector> = LazyLock::new(|| selector("body"));
+
+/// Extracts the readable content of an HTML page.
+///
+/// The main text is found the way reader modes do: each paragraph scores its length for its
+/// parent, and half of it for its grandparent, and the text of the best scoring element is
+/// kept, leaving out navigation, headers, footers, sidebars, and forms.
+///
+/// # Arguments
+/// * `html` - The HTML of the page.
+/// * `url` - The URL the page was fetched from.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::content::extract_content;
+///
+/// let html = r#"<html><head><title>Rust 1.0 | Rust Blog</title>
+///     <meta name="author" content="The Rust Core Team"></head>
+///     <body><nav><p>Home · Blog · Releases · Community</p></nav>
+///     <article><h1>Announcing Rust 1.0</h1>
+///     <p>Today we are very proud to announce the 1.0 release of Rust.</p>
+///     <p>The current plan is to release a new version every six weeks.</p></article>
+///     <footer><p>Maintained by the Rust Team, licensed under MIT or Apache 2.0.</p></footer>
+///     </body></html>"#;
+///
+/// let content = extract_content(html, "https://blog.rust-lang.org/2015/05/15/Rust-1.0.html");
+/// assert_eq!(content.title, "Rust 1.0 | Rust Blog");
+/// assert_eq!(content.byline.as_deref(), Some("The Rust Core Team"));
+/// assert_eq!(
+///     content.paragraphs().collect::<Vec<_>>(),
+///     vec![
+///         "Announcing Rust 1.0",
+///         "Today we are very proud to announce the 1.0 release of Rust.",
+///         "The current plan is to release a new version every six weeks.",
+///     ]
+/// );
+/// ```
+#[cfg(feature = "content")]
+pub fn extract_content(html: &str, url: &str) -> PageContent {
+    let doc = Html::parse_document(html);
+
+    let title = doc
+        .select(&TITLE)
+        .filter_map(|meta| meta.value().attr("content"))
+        .map(collapse_whitespace)
+        .chain(doc.select(&DOCUMENT_TITLE).map(|el| text_of(&el)))
+        .find(|title| !title.is_empty())
+        .unwrap_or_default();
+
+    let byline = doc
+        .select(&AUTHOR)
+        .filter_map(|meta| meta.value().attr("content"))
+        .map(collapse_whitespace)
+        .chain(doc.select(&BYLINE).map(|el| text_of(&el)))
+        .find(|byline| !byline.is_empty() && !byline.starts_with("http"));
+
+    let mut scores: HashMap<_, usize> = HashMap::new();
+    for paragraph in doc.select(&PARAGRAPH) {
+        if is_boilerplate(&paragraph) {
+            continue;
+        }
+        let len = text_of(&paragraph).len();
+        if len < MIN_PARAGRAPH_LEN {
+            continue;
+        }
+        let mut ancestors = paragraph.ancestors().filter_map(ElementRef::wrap);
+        if let Some(parent) = ancestors.next() {
+            *scores.entry(parent.id()).or_default() += len;
+        }
+        if let Some(grandparent) = ancestors.next() {
+            *scores.entry(grandparent.id()).or_default() += len / 2;
+        }
+    }
+    let root = scores
+        .into_iter()
+        .max_by_key(|&(id, score)| (score, std::cmp::Reverse(id)))
+        .and_then(|(id, _)| doc.tree.get(id))
+        .and_then(ElementRef::wrap)
+        .or_else(|| doc.select(&BODY).next());
+
+    let text = match root {
+        Some(root) => {
+            let paragraphs: Vec<String> = root
+                .select(&BLOCK)
+                .filter(|block| !is_boilerplate(block) && !is_nested_block(block, &root))
+                .map(|block| text_of(&block))
+                .filter(|text| !text.is_empty())
+                .collect();
+            if paragraphs.is_empty() {
+                text_of(&root)
+            } else {
+                paragraphs.join("\n\n")
+            }
+        }
+        None => String::new(),
+    };
+
+    PageContent {
+        url: url.to_string(),
+        title,
+        byline,
+        text,
+    }
+}
+
+/// Returns the text of an element, with runs of whitespace collapsed into single spaces.
+#[cfg(feature = "content")]
+fn text_of(element: &ElementRef) -> String {
+    collapse_whitespace(&element.text().collect::<String>())
+}
+
+/// Collapses runs of whitespace into single spaces, trimming both ends.
+#[cfg(feature = "content")]
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Returns `true` if the element is inside navigation, a header, a footer, a sidebar, or a
+/// form.
+#[cfg(feature = "content")]
+fn is_boilerplate(element: &ElementRef) -> bool {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|ancestor| BOILERPLATE_TAGS.contains(&ancestor.value().name()))
+}
+
+/// Returns `true` if the block is inside another block below `root`, whose text already
+/// includes its own.
+#[cfg(feature = "content")]
+fn is_nested_block(block: &ElementRef, root: &ElementRef) -> bool {
+    block
+        .ancestors()
+        .take_while(|node| node.id() != root.id())
+        .filter_map(ElementRef::wrap)
+        .any(|ancestor| BLOCK_TAGS.contains(&ancestor.value().name()))
+}
@@ -0,0 +1,131 @@
+use anyhow::{Context, Result};
+use cookie_store::RawCookie;
+use reqwest::Url;
+use reqwest::header::HeaderValue;
+use std::fs;
+use std::io::{BufReader, Write};
+use std::path::Path;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// The domain DuckDuckGo preference cookies (`kl`, `kp`, ...) are scoped to.
+const PREFERENCES_URL: &str = "https://duckduckgo.com/";
+
+/// A cookie store that can be saved to and loaded from a file, so sessions and DuckDuckGo
+/// preference cookies (such as `kl` for the region and `kp` for safe search) persist across runs.
+///
+/// Cookies are stored as one JSON object per line. Session cookies are saved as well, since a
+/// CLI invocation is usually shorter than a browsing session.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::cookies::CookieJar;
+///
+/// let path = std::env::temp_dir().join("ddg-cookies-doctest.json");
+///
+/// let jar = CookieJar::new();
+/// jar.set_preference("kl", "us-en").unwrap();
+/// jar.save(&path).unwrap();
+///
+/// let jar = CookieJar::load(&path).unwrap();
+/// assert_eq!(jar.preference("kl").as_deref(), Some("us-en"));
+/// ```
+#[derive(Debug, Default)]
+pub struct CookieJar {
+    store: RwLock<cookie_store::CookieStore>,
+}
+
+impl CookieJar {
+    /// Creates an empty `CookieJar`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the cookies saved in `path`, skipping expired ones.
+    ///
+    /// A missing file yields an empty jar, so the same path can be used for the first run.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let file = fs::File::open(path)
+            .with_context(|| format!("Failed to open cookie file {}", path.display()))?;
+        let store = cookie_store::CookieStore::load_json(BufReader::new(file))
+            .map_err(|e| anyhow::anyhow!("{e}"))
+            .with_context(|| format!("Failed to parse cookie file {}", path.display()))?;
+
+        Ok(CookieJar {
+            store: RwLock::new(store),
+        })
+    }
+
+    /// Saves the unexpired cookies, including session cookies, to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let mut lines = Vec::new();
+        for cookie in self.read().iter_unexpired() {
+            writeln!(lines, "{}", serde_json::to_string(cookie)?)?;
+        }
+
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+        }
+        fs::write(path, lines)
+            .with_context(|| format!("Failed to write cookie file {}", path.display()))
+    }
+
+    /// Sets a DuckDuckGo preference cookie (e.g. `kl` for the region or `kp` for safe search).
+    pub fn set_preference(&self, name: &str, value: &str) -> Result<()> {
+        let url = Url::parse(PREFERENCES_URL)?;
+        let cookie = RawCookie::build(name.to_string(), value.to_string())
+            .domain("duckduckgo.com")
+            .path("/")
+            .finish();
+        self.write()
+            .insert_raw(&cookie, &url)
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+        Ok(())
+    }
+
+    /// Returns the value of a DuckDuckGo preference cookie, if it is set.
+    pub fn preference(&self, name: &str) -> Option<String> {
+        let url = Url::parse(PREFERENCES_URL).ok()?;
+        self.read()
+            .get_request_values(&url)
+            .find(|(cookie, _)| *cookie == name)
+            .map(|(_, value)| value.to_string())
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, cookie_store::CookieStore> {
+        self.store.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, cookie_store::CookieStore> {
+        self.store.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl reqwest::cookie::CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let cookies = cookie_headers
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| RawCookie::parse(value.to_string()).ok());
+        self.write().store_response_cookies(cookies, url);
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let value = self
+            .read()
+            .get_request_values(url)
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if value.is_empty() {
+            return None;
+        }
+        HeaderValue::from_str(&value).ok()
+    }
+}
@@ -0,0 +1,90 @@
+use crate::plan::PlannedRequest;
+use crate::proxy::ProxyConfig;
+
+/// The client settings of a search that curl needs to send the same requests: proxies, TLS,
+/// and network options.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::curl::CurlOptions;
+/// use duckduckgo::params::SearchParams;
+/// use duckduckgo::proxy::ProxyConfig;
+///
+/// let browser = Browser::new(reqwest::Client::new());
+/// let plan = browser.plan("html", &SearchParams::new("it's rust")).unwrap();
+///
+/// let options = CurlOptions {
+///     proxies: ProxyConfig::new().all("socks5h://127.0.0.1:9050"),
+///     ..Default::default()
+/// };
+/// let command = options.command(&plan[0]);
+/// assert!(command.starts_with("curl -X POST 'https://html.duckduckgo.com/html/?q=it%27s+rust"));
+/// assert!(command.contains("--proxy 'socks5h://127.0.0.1:9050'"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CurlOptions {
+    /// The proxies the requests go through.
+    pub proxies: ProxyConfig,
+    /// The PEM files of the extra certificate authorities to trust.
+    pub ca_certs: Vec<String>,
+    /// Whether TLS certificate validation is disabled.
+    pub insecure: bool,
+    /// Whether to connect over IPv4 only.
+    pub ipv4: bool,
+    /// Whether to connect over IPv6 only.
+    pub ipv6: bool,
+    /// The IP address of the network interface requests are sent from, if any.
+    pub interface: Option<String>,
+}
+
+impl CurlOptions {
+    /// Returns the curl command sending a planned request, one option per line.
+    ///
+    /// Arguments are quoted for POSIX shells.
+    ///
+    /// # Arguments
+    /// * `request` - The request, as planned by `Browser::plan`.
+    pub fn command(&self, request: &PlannedRequest) -> String {
+        let mut command = String::from("curl");
+        if request.method != "GET" {
+            command.push_str(&format!(" -X {}", request.method));
+        }
+        command.push_str(&format!(" {}", quote(&request.url)));
+
+        let mut args = vec![command];
+
+        for (name, value) in &request.headers {
+            args.push(format!("-H {}", quote(&format!("{}: {}", name, value))));
+        }
+        let scheme = request.url.split("://").next().unwrap_or_default();
+        if let Some(proxy) = self.proxies.for_scheme(scheme) {
+            args.push(format!("--proxy {}", quote(proxy)));
+        }
+        if let Some(hosts) = &self.proxies.no_proxy {
+            args.push(format!("--noproxy {}", quote(hosts)));
+        }
+        for path in &self.ca_certs {
+            args.push(format!("--cacert {}", quote(path)));
+        }
+        if self.insecure {
+            args.push(String::from("--insecure"));
+        }
+        if self.ipv4 {
+            args.push(String::from("--ipv4"));
+        }
+        if self.ipv6 {
+            args.push(String::from("--ipv6"));
+        }
+        if let Some(interface) = &self.interface {
+            args.push(format!("--interface {}", quote(interface)));
+        }
+
+        args.join(" \\\n  ")
+    }
+}
+
+/// Quotes a shell argument in single quotes.
+fn quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
@@ -0,0 +1,487 @@
+use anyhow::{Result, bail};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Datelike, Utc};
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+
+/// The names of the months and weekdays of a language, used in the dates rendered by
+/// `DateFormat`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::date::Locale;
+///
+/// let french = Locale::named("fr_FR.UTF-8").unwrap();
+/// assert_eq!(french.month(3), "mars");
+/// assert_eq!(Locale::named("C").unwrap().month(3), "March");
+/// assert!(Locale::named("tlh").is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    language: &'static str,
+    months: [&'static str; 12],
+    short_months: [&'static str; 12],
+    weekdays: [&'static str; 7],
+    short_weekdays: [&'static str; 7],
+}
+
+/// The languages of the month and weekday names, the weekdays starting on Monday.
+const LOCALES: &[Locale] = &[
+    Locale {
+        language: "en",
+        months: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        short_months: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        weekdays: [
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+            "Sunday",
+        ],
+        short_weekdays: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+    },
+    Locale {
+        language: "de",
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        short_months: [
+            "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+        ],
+        weekdays: [
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+            "Sonntag",
+        ],
+        short_weekdays: ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+    },
+    Locale {
+        language: "es",
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        short_months: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic",
+        ],
+        weekdays: [
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+            "domingo",
+        ],
+        short_weekdays: ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+    },
+    Locale {
+        language: "fr",
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        short_months: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        weekdays: [
+            "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+        ],
+        short_weekdays: ["lun.", "mar.", "mer.", "jeu.", "ven.", "sam.", "dim."],
+    },
+    Locale {
+        language: "it",
+        months: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        short_months: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        weekdays: [
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+            "domenica",
+        ],
+        short_weekdays: ["lun", "mar", "mer", "gio", "ven", "sab", "dom"],
+    },
+    Locale {
+        language: "nl",
+        months: [
+            "januari",
+            "februari",
+            "maart",
+            "april",
+            "mei",
+            "juni",
+            "juli",
+            "augustus",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        short_months: [
+            "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+        ],
+        weekdays: [
+            "maandag",
+            "dinsdag",
+            "woensdag",
+            "donderdag",
+            "vrijdag",
+            "zaterdag",
+            "zondag",
+        ],
+        short_weekdays: ["ma", "di", "wo", "do", "vr", "za", "zo"],
+    },
+    Locale {
+        language: "pt",
+        months: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        short_months: [
+            "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+        ],
+        weekdays: [
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+            "domingo",
+        ],
+        short_weekdays: ["seg", "ter", "qua", "qui", "sex", "sáb", "dom"],
+    },
+];
+
+impl Default for Locale {
+    /// Returns the English locale.
+    fn default() -> Self {
+        LOCALES[0]
+    }
+}
+
+impl Locale {
+    /// Returns the locale of a POSIX locale name, such as `fr_FR.UTF-8` or `de`, or `None` if
+    /// its language is not supported. `C` and `POSIX` are English.
+    ///
+    /// # Arguments
+    /// * `name` - The locale name.
+    pub fn named(name: &str) -> Option<Self> {
+        let language = name
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let language = match language.as_str() {
+            "c" | "posix" => "en",
+            language => language,
+        };
+        LOCALES
+            .iter()
+            .find(|locale| locale.language == language)
+            .copied()
+    }
+
+    /// Returns the locale of the environment, from the `LC_ALL`, `LC_TIME`, and `LANG`
+    /// variables, in that order, or English if none is set to a supported language.
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_TIME", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::named(&value))
+            .unwrap_or_default()
+    }
+
+    /// Returns the language code of the locale, e.g. `"fr"`.
+    pub fn language(&self) -> &'static str {
+        self.language
+    }
+
+    /// Returns the name of a month, from 1 for January to 12 for December.
+    ///
+    /// # Panics
+    /// Panics if `month` is not between 1 and 12.
+    pub fn month(&self, month: u32) -> &'static str {
+        self.months[month as usize - 1]
+    }
+
+    /// Replaces the month and weekday names of a strftime pattern (`%B`, `%b`, `%h`, `%A`, and
+    /// `%a`) with their names in the locale on a date.
+    fn localize(&self, pattern: &str, date: &DateTime<Utc>) -> String {
+        let month = date.month0() as usize;
+        let weekday = date.weekday().num_days_from_monday() as usize;
+        let mut out = String::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('B') => out.push_str(self.months[month]),
+                Some('b' | 'h') => out.push_str(self.short_months[month]),
+                Some('A') => out.push_str(self.weekdays[weekday]),
+                Some('a') => out.push_str(self.short_weekdays[weekday]),
+                Some(other) => {
+                    out.push('%');
+                    out.push(other);
+                }
+                None => out.push('%'),
+            }
+        }
+        out
+    }
+}
+
+/// A strftime pattern of the dates rendered by `DateFormat`, e.g. `"%-d %B %Y, %H:%M"`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::date::DatePattern;
+///
+/// assert!("%-d %B %Y".parse::<DatePattern>().is_ok());
+/// assert!("%Q".parse::<DatePattern>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatePattern(String);
+
+impl FromStr for DatePattern {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if StrftimeItems::new(s).any(|item| item == Item::Error) {
+            bail!("Invalid date format '{}', expected a strftime pattern", s);
+        }
+        Ok(DatePattern(s.to_string()))
+    }
+}
+
+impl fmt::Display for DatePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// How publication dates are rendered: as the time elapsed since them, such as "3 hours ago",
+/// or with a strftime pattern whose month and weekday names are in a locale.
+///
+/// # Examples
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use duckduckgo::date::{DateFormat, Locale};
+///
+/// let date = Utc.with_ymd_and_hms(2025, 3, 14, 9, 30, 0).unwrap();
+/// let now = Utc.with_ymd_and_hms(2025, 3, 14, 12, 45, 0).unwrap();
+///
+/// assert_eq!(DateFormat::new().render_at(&date, &now), "2025-03-14 09:30:00 UTC");
+///
+/// let german = DateFormat::new()
+///     .pattern("%A %-d. %B %Y".parse().unwrap())
+///     .locale(Locale::named("de_DE.UTF-8").unwrap());
+/// assert_eq!(german.render_at(&date, &now), "Freitag 14. März 2025");
+///
+/// let relative = DateFormat::new().relative(true);
+/// assert_eq!(relative.render_at(&date, &now), "3 hours ago (2025-03-14 09:30:00 UTC)");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DateFormat {
+    pattern: Option<DatePattern>,
+    relative: bool,
+    locale: Locale,
+}
+
+impl DateFormat {
+    /// Creates a format rendering dates as `2025-03-14 09:30:00 UTC`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders the dates with a strftime pattern, in UTC.
+    ///
+    /// # Arguments
+    /// * `pattern` - The pattern, e.g. `"%-d %B %Y"`.
+    pub fn pattern(mut self, pattern: DatePattern) -> Self {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    /// Renders the dates as the time elapsed since them, followed by the date in parentheses.
+    ///
+    /// # Arguments
+    /// * `enable` - Whether dates are rendered relative to now.
+    pub fn relative(mut self, enable: bool) -> Self {
+        self.relative = enable;
+        self
+    }
+
+    /// Names the months and weekdays of the pattern in a locale.
+    ///
+    /// # Arguments
+    /// * `locale` - The locale, e.g. `Locale::from_env()`.
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Returns `true` if dates are rendered relative to now.
+    pub fn is_relative(&self) -> bool {
+        self.relative
+    }
+
+    /// Renders a date with the pattern, ignoring `relative`.
+    ///
+    /// # Arguments
+    /// * `date` - The date to render.
+    pub fn absolute(&self, date: &DateTime<Utc>) -> String {
+        match &self.pattern {
+            Some(pattern) => date
+                .format(&self.locale.localize(&pattern.0, date))
+                .to_string(),
+            None => date.to_string(),
+        }
+    }
+
+    /// Renders a date, relative to now if `relative` is set.
+    ///
+    /// # Arguments
+    /// * `date` - The date to render.
+    pub fn render(&self, date: &DateTime<Utc>) -> String {
+        self.render_at(date, &Utc::now())
+    }
+
+    /// Renders a date, relative to `now` if `relative` is set.
+    ///
+    /// # Arguments
+    /// * `date` - The date to render.
+    /// * `now` - The current time.
+    pub fn render_at(&self, date: &DateTime<Utc>, now: &DateTime<Utc>) -> String {
+        if self.relative {
+            format!("{} ({})", relative_time(date, now), self.absolute(date))
+        } else {
+            self.absolute(date)
+        }
+    }
+}
+
+/// Returns the time elapsed from a date to now in words, such as "3 hours ago" or "yesterday",
+/// or the time left to a future date, such as "in 5 minutes".
+///
+/// # Arguments
+/// * `date` - The date.
+/// * `now` - The current time.
+///
+/// # Examples
+/// ```
+/// use chrono::{Duration, Utc};
+/// use duckduckgo::date::relative_time;
+///
+/// let now = Utc::now();
+/// assert_eq!(relative_time(&(now - Duration::seconds(20)), &now), "just now");
+/// assert_eq!(relative_time(&(now - Duration::minutes(1)), &now), "1 minute ago");
+/// assert_eq!(relative_time(&(now - Duration::hours(30)), &now), "yesterday");
+/// assert_eq!(relative_time(&(now - Duration::days(15)), &now), "2 weeks ago");
+/// assert_eq!(relative_time(&(now + Duration::minutes(5)), &now), "in 5 minutes");
+/// ```
+pub fn relative_time(date: &DateTime<Utc>, now: &DateTime<Utc>) -> String {
+    let elapsed = *now - *date;
+    let seconds = elapsed.num_seconds().abs();
+    let (count, unit) = match seconds {
+        0..60 => return String::from("just now"),
+        60..3_600 => (seconds / 60, "minute"),
+        3_600..86_400 => (seconds / 3_600, "hour"),
+        86_400..172_800 if elapsed.num_seconds() > 0 => return String::from("yesterday"),
+        86_400..604_800 => (seconds / 86_400, "day"),
+        604_800..2_592_000 => (seconds / 604_800, "week"),
+        2_592_000..31_536_000 => (seconds / 2_592_000, "month"),
+        _ => (seconds / 31_536_000, "year"),
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    if elapsed.num_seconds() < 0 {
+        format!("in {} {}{}", count, unit, plural)
+    } else {
+        format!("{} {}{} ago", count, unit, plural)
+    }
+}
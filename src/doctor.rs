@@ -0,0 +1,82 @@
+use crate::timings::millis_opt;
+use serde::Serialize;
+use std::fmt;
+use std::time::Duration;
+
+/// The query sent to the endpoints by `Browser::check_endpoints`.
+pub const PROBE_QUERY: &str = "duckduckgo";
+
+/// The outcome of probing a DuckDuckGo endpoint, as printed by `ddg doctor`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::doctor::EndpointCheck;
+/// use std::time::Duration;
+///
+/// let html = EndpointCheck {
+///     name: String::from("html"),
+///     url: String::from("https://html.duckduckgo.com/html/"),
+///     status: Some(200),
+///     latency: Some(Duration::from_millis(182)),
+///     bot_detection: false,
+///     error: None,
+/// };
+/// assert!(html.is_healthy());
+/// assert_eq!(html.to_string(), "html: 200 in 182 ms");
+///
+/// let lite = EndpointCheck {
+///     name: String::from("lite"),
+///     url: String::from("https://lite.duckduckgo.com/lite/"),
+///     status: Some(202),
+///     latency: Some(Duration::from_millis(95)),
+///     bot_detection: true,
+///     error: None,
+/// };
+/// assert!(!lite.is_healthy());
+/// assert_eq!(lite.to_string(), "lite: 202 in 95 ms, bot detection triggered");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EndpointCheck {
+    /// The name of the endpoint: `api`, `lite`, `html`, `vqd`, `images`, or `news`.
+    pub name: String,
+    /// The URL of the endpoint.
+    pub url: String,
+    /// The HTTP status code of the response, or `None` if the endpoint was not reached.
+    pub status: Option<u16>,
+    /// The time to the response headers, including connection setup, if the endpoint was
+    /// reached.
+    #[serde(serialize_with = "millis_opt")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
+    pub latency: Option<Duration>,
+    /// Whether DuckDuckGo served its "anomaly detected" challenge instead of results.
+    pub bot_detection: bool,
+    /// Why the probe failed (e.g. a DNS, TLS, or proxy error, or an error status), if it did.
+    pub error: Option<String>,
+}
+
+impl EndpointCheck {
+    /// Returns `true` if the endpoint answered successfully without a bot challenge.
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none() && !self.bot_detection && self.status.is_some()
+    }
+}
+
+impl fmt::Display for EndpointCheck {
+    /// Formats the check as its status and latency, followed by the bot challenge or the error.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.name)?;
+        match (self.status, self.latency) {
+            (Some(status), Some(latency)) => write!(f, "{} in {} ms", status, latency.as_millis())?,
+            (Some(status), None) => write!(f, "{}", status)?,
+            (None, _) => write!(f, "not reached")?,
+        }
+        if self.bot_detection {
+            write!(f, ", bot detection triggered")?;
+        }
+        if let Some(error) = &self.error {
+            write!(f, " ({})", error)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,570 @@
+use crate::browser::{Browser, SITE_REQUEST_CONCURRENCY};
+use crate::link::Link;
+use crate::response::{ImageResult, SearchResult};
+use anyhow::{Context, Result, bail};
+use futures_util::{StreamExt, stream};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// The default template of the names of downloaded images.
+pub const DEFAULT_NAME_TEMPLATE: &str = "{query}-{rank}.{ext}";
+
+/// The name of the manifest written next to the downloaded images.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// The most characters a placeholder inserts into a file name.
+const MAX_VALUE_CHARS: usize = 64;
+
+/// The formats images can be converted to, as written on the command line.
+const FORMATS: &[&str] = &["png", "jpeg", "webp"];
+
+/// A format the downloaded images are converted to with the `convert` feature.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::download::ImageFormat;
+///
+/// let format: ImageFormat = "jpg".parse().unwrap();
+/// assert_eq!(format, ImageFormat::Jpeg);
+/// assert_eq!(format.extension(), "jpg");
+/// assert!("tiff".parse::<ImageFormat>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFormat {
+    /// Portable Network Graphics.
+    Png,
+    /// JPEG, without transparency.
+    Jpeg,
+    /// Lossless WebP.
+    Webp,
+}
+
+impl ImageFormat {
+    /// Returns the file extension of the format, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::Webp => "webp",
+        }
+    }
+
+    /// Returns the media type of the format, e.g. `image/png`.
+    pub fn media_type(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Jpeg => "image/jpeg",
+            ImageFormat::Webp => "image/webp",
+        }
+    }
+}
+
+impl FromStr for ImageFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "png" => Ok(ImageFormat::Png),
+            "jpeg" | "jpg" => Ok(ImageFormat::Jpeg),
+            "webp" => Ok(ImageFormat::Webp),
+            _ => bail!(
+                "Unsupported image format '{}', expected one of: {}",
+                s,
+                FORMATS.join(", ")
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ImageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.media_type().trim_start_matches("image/"))
+    }
+}
+
+/// A template of the names of downloaded images, e.g. `"{query}-{rank}.{ext}"`.
+///
+/// The placeholders are `{query}`, `{rank}`, `{title}`, `{source}`, `{width}`, `{height}`, and
+/// `{ext}` (the extension of the saved file). The text values are lowercased, with the runs of
+/// characters other than letters and digits replaced with a dash, and shortened to 64
+/// characters, so that they are safe in file names. `{{` and `}}` insert literal braces.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::download::NameTemplate;
+/// use duckduckgo::response::ImageResult;
+///
+/// let image = ImageResult {
+///     source: String::from("Wikimedia Commons"),
+///     rank: 7,
+///     ..Default::default()
+/// };
+/// let template = NameTemplate::default();
+/// assert_eq!(template.render("Ferris the crab", &image, "png"), "ferris-the-crab-7.png");
+///
+/// let template: NameTemplate = "{query}/{source}-{rank}.{ext}".parse().unwrap();
+/// assert_eq!(
+///     template.render("Ferris the crab", &image, "png"),
+///     "ferris-the-crab-wikimedia-commons-7.png"
+/// );
+/// assert!("{author}.{ext}".parse::<NameTemplate>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameTemplate {
+    parts: Vec<NamePart>,
+}
+
+/// A piece of a parsed name template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NamePart {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// A value that can be inserted in a name template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Query,
+    Rank,
+    Title,
+    Source,
+    Width,
+    Height,
+    Ext,
+}
+
+/// The placeholders accepted in name templates, with the value they insert.
+const PLACEHOLDERS: &[(&str, Placeholder)] = &[
+    ("query", Placeholder::Query),
+    ("rank", Placeholder::Rank),
+    ("title", Placeholder::Title),
+    ("source", Placeholder::Source),
+    ("width", Placeholder::Width),
+    ("height", Placeholder::Height),
+    ("ext", Placeholder::Ext),
+];
+
+impl Default for NameTemplate {
+    fn default() -> Self {
+        DEFAULT_NAME_TEMPLATE
+            .parse()
+            .expect("the default name template is valid")
+    }
+}
+
+impl NameTemplate {
+    /// Renders the file name of an image with the template.
+    ///
+    /// Path separators are replaced with dashes, so that the name stays in the download
+    /// directory.
+    ///
+    /// # Arguments
+    /// * `query` - The query the image was found with.
+    /// * `image` - The image result.
+    /// * `ext` - The extension of the saved file, without the dot.
+    pub fn render(&self, query: &str, image: &ImageResult, ext: &str) -> String {
+        let mut name = String::new();
+        for part in &self.parts {
+            match part {
+                NamePart::Literal(text) => name.push_str(text),
+                NamePart::Placeholder(placeholder) => name.push_str(&match placeholder {
+                    Placeholder::Query => slug(query),
+                    Placeholder::Rank => image.rank.to_string(),
+                    Placeholder::Title => slug(&image.title),
+                    Placeholder::Source => slug(&image.source),
+                    Placeholder::Width => image.width.to_string(),
+                    Placeholder::Height => image.height.to_string(),
+                    Placeholder::Ext => slug(ext),
+                }),
+            }
+        }
+        let name = name.replace(['/', '\\'], "-");
+        match name.trim_start_matches('.') {
+            "" => format!("{}.{}", image.rank, ext),
+            name => name.to_string(),
+        }
+    }
+}
+
+impl FromStr for NameTemplate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        name.push(c);
+                    }
+                    if !closed {
+                        bail!("Unclosed '{{' in name template, use '{{{{' for a literal brace");
+                    }
+                    let Some((_, placeholder)) = PLACEHOLDERS.iter().find(|(n, _)| *n == name)
+                    else {
+                        let names: Vec<String> = PLACEHOLDERS
+                            .iter()
+                            .map(|(n, _)| format!("{{{}}}", n))
+                            .collect();
+                        bail!(
+                            "Unknown placeholder {{{}}} in name template, expected one of: {}",
+                            name,
+                            names.join(", ")
+                        );
+                    };
+                    if !literal.is_empty() {
+                        parts.push(NamePart::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(NamePart::Placeholder(*placeholder));
+                }
+                '}' => bail!("Unmatched '}}' in name template, use '}}}}' for a literal brace"),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(NamePart::Literal(literal));
+        }
+        if parts.is_empty() {
+            bail!("Empty name template");
+        }
+
+        Ok(NameTemplate { parts })
+    }
+}
+
+/// Returns a value made safe for file names: lowercased, with the runs of other characters
+/// than letters and digits replaced with a dash.
+fn slug(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.chars().count() >= MAX_VALUE_CHARS {
+            break;
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Where and how `Browser::download_images` saves images.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::download::ImageDownloads;
+///
+/// let downloads = ImageDownloads::new("wallpapers")
+///     .name_template("{query}-{width}x{height}-{rank}.{ext}".parse().unwrap());
+/// assert_eq!(downloads.manifest_path(), std::path::Path::new("wallpapers/manifest.json"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageDownloads {
+    dir: PathBuf,
+    template: NameTemplate,
+    format: Option<ImageFormat>,
+}
+
+impl ImageDownloads {
+    /// Creates downloads saving the images in `dir`, which is created if needed, named with the
+    /// default template and in their original format.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        ImageDownloads {
+            dir: dir.into(),
+            template: NameTemplate::default(),
+            format: None,
+        }
+    }
+
+    /// Names the images with a template instead of `DEFAULT_NAME_TEMPLATE`.
+    ///
+    /// # Arguments
+    /// * `template` - The template of the file names.
+    pub fn name_template(mut self, template: NameTemplate) -> Self {
+        self.template = template;
+        self
+    }
+
+    /// Converts the images to a format, unless they already are in it.
+    ///
+    /// # Arguments
+    /// * `format` - The format the images are saved in.
+    #[cfg(feature = "convert")]
+    pub fn convert(mut self, format: ImageFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Returns the directory the images are saved in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the path of the manifest, `manifest.json` in the download directory.
+    pub fn manifest_path(&self) -> PathBuf {
+        self.dir.join(MANIFEST_FILE)
+    }
+}
+
+/// The images saved by `Browser::download_images`, mapping each file back to its source page.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DownloadManifest {
+    /// The query the images were found with.
+    pub query: String,
+    /// The saved images, in the order of the results.
+    pub files: Vec<DownloadedImage>,
+    /// The images that could not be saved.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failed: Vec<FailedDownload>,
+}
+
+/// An image saved by `Browser::download_images`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DownloadedImage {
+    /// The name of the file in the download directory.
+    pub file: String,
+    /// The URL the image was downloaded from.
+    pub image: Link,
+    /// The URL of the page hosting the image.
+    pub url: Link,
+    /// The title or description of the image.
+    pub title: String,
+    /// The rank of the image in the results.
+    pub rank: usize,
+    /// The media type of the saved file, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// The size of the saved file in bytes.
+    pub bytes: u64,
+    /// The media type the image was converted from, if it was converted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converted_from: Option<String>,
+}
+
+/// An image `Browser::download_images` could not save.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FailedDownload {
+    /// The URL of the image.
+    pub image: Link,
+    /// The rank of the image in the results.
+    pub rank: usize,
+    /// Why the image could not be downloaded, converted, or saved.
+    pub error: String,
+}
+
+impl DownloadManifest {
+    /// Writes the manifest as pretty-printed JSON.
+    ///
+    /// # Arguments
+    /// * `path` - The manifest file, usually `ImageDownloads::manifest_path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json + "\n")
+            .with_context(|| format!("Failed to write manifest {}", path.display()))
+    }
+}
+
+/// Returns the extension of an image file from its media type, or from its URL.
+fn extension(content_type: Option<&str>, url: &str) -> String {
+    let media_type = content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .map(|media_type| media_type.trim().to_ascii_lowercase());
+    let from_type = match media_type.as_deref() {
+        Some("image/jpeg" | "image/jpg" | "image/pjpeg") => Some("jpg"),
+        Some("image/png") => Some("png"),
+        Some("image/gif") => Some("gif"),
+        Some("image/webp") => Some("webp"),
+        Some("image/svg+xml") => Some("svg"),
+        Some("image/bmp") => Some("bmp"),
+        Some("image/avif") => Some("avif"),
+        _ => None,
+    };
+    if let Some(ext) = from_type {
+        return ext.to_string();
+    }
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    path.rsplit('/')
+        .next()
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .filter(|ext| (1..=4).contains(&ext.len()) && ext.chars().all(char::is_alphanumeric))
+        .unwrap_or_else(|| String::from("img"))
+}
+
+/// Converts an image to a format.
+#[cfg(feature = "convert")]
+fn convert(bytes: &[u8], format: ImageFormat) -> Result<Vec<u8>> {
+    let image = image::load_from_memory(bytes).context("Failed to decode the image")?;
+    // JPEG has no alpha channel, and the WebP encoder only takes 8-bit pixels.
+    let image = match format {
+        ImageFormat::Png => image,
+        ImageFormat::Jpeg => image::DynamicImage::ImageRgb8(image.to_rgb8()),
+        ImageFormat::Webp => image::DynamicImage::ImageRgba8(image.to_rgba8()),
+    };
+    let target = match format {
+        ImageFormat::Png => image::ImageFormat::Png,
+        ImageFormat::Jpeg => image::ImageFormat::Jpeg,
+        ImageFormat::Webp => image::ImageFormat::WebP,
+    };
+    let mut out = std::io::Cursor::new(Vec::new());
+    image
+        .write_to(&mut out, target)
+        .with_context(|| format!("Failed to encode the image as {}", format))?;
+    Ok(out.into_inner())
+}
+
+/// Fails to convert an image, as `ImageDownloads::convert` requires the `convert` feature.
+#[cfg(not(feature = "convert"))]
+fn convert(_bytes: &[u8], format: ImageFormat) -> Result<Vec<u8>> {
+    bail!(
+        "Converting images to {} requires the `convert` feature",
+        format
+    )
+}
+
+impl Browser {
+    /// Downloads the files of image results concurrently into a directory, naming them with a
+    /// template and optionally converting them, and writes a manifest mapping each file back to
+    /// its source page.
+    ///
+    /// The results other than images are skipped. Files with the same name are numbered, as in
+    /// `ferris-1-2.png`, rather than overwritten.
+    ///
+    /// # Arguments
+    /// * `query` - The query the images were found with, for the `{query}` placeholder.
+    /// * `results` - The search results.
+    /// * `downloads` - Where and how the images are saved.
+    ///
+    /// # Returns
+    /// The manifest, also written to `ImageDownloads::manifest_path`, or an error if the
+    /// directory or the manifest cannot be written. Images that fail to download are listed in
+    /// its `failed` entries.
+    pub async fn download_images(
+        &self,
+        query: &str,
+        results: &[SearchResult],
+        downloads: &ImageDownloads,
+    ) -> Result<DownloadManifest> {
+        fs::create_dir_all(&downloads.dir)
+            .with_context(|| format!("Failed to create directory {}", downloads.dir.display()))?;
+
+        let images: Vec<&ImageResult> = results
+            .iter()
+            .filter_map(|result| match result {
+                SearchResult::Image(image) => Some(image),
+                _ => None,
+            })
+            .collect();
+        let files: Vec<Result<(Vec<u8>, Option<String>)>> = stream::iter(&images)
+            .map(|image| self.fetch_file(image.image.as_str()))
+            .buffered(SITE_REQUEST_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut manifest = DownloadManifest {
+            query: query.to_string(),
+            ..Default::default()
+        };
+        let mut names = HashSet::new();
+        for (image, file) in images.into_iter().zip(files) {
+            match save_image(query, image, file, downloads, &mut names) {
+                Ok(saved) => manifest.files.push(saved),
+                Err(err) => manifest.failed.push(FailedDownload {
+                    image: image.image.clone(),
+                    rank: image.rank,
+                    error: format!("{:#}", err),
+                }),
+            }
+        }
+        manifest.save(downloads.manifest_path())?;
+        Ok(manifest)
+    }
+}
+
+/// Converts and writes a downloaded image, returning its manifest entry.
+fn save_image(
+    query: &str,
+    image: &ImageResult,
+    file: Result<(Vec<u8>, Option<String>)>,
+    downloads: &ImageDownloads,
+    names: &mut HashSet<String>,
+) -> Result<DownloadedImage> {
+    let (bytes, content_type) = file?;
+    let (bytes, content_type, converted_from) = match downloads.format {
+        Some(format)
+            if !content_type
+                .as_deref()
+                .is_some_and(|content_type| content_type.starts_with(format.media_type())) =>
+        {
+            let converted = convert(&bytes, format)?;
+            let media_type = Some(format.media_type().to_string());
+            (converted, media_type, content_type)
+        }
+        _ => (bytes, content_type, None),
+    };
+
+    let ext = match downloads.format {
+        Some(format) => format.extension().to_string(),
+        None => extension(content_type.as_deref(), image.image.as_str()),
+    };
+    let name = downloads.template.render(query, image, &ext);
+    let name = unique_name(&name, names);
+    let path = downloads.dir.join(&name);
+    fs::write(&path, &bytes).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    Ok(DownloadedImage {
+        file: name,
+        image: image.image.clone(),
+        url: image.url.clone(),
+        title: image.title.clone(),
+        rank: image.rank,
+        content_type,
+        bytes: bytes.len() as u64,
+        converted_from,
+    })
+}
+
+/// Returns a file name not taken yet in this download, numbering it if it is.
+fn unique_name(name: &str, names: &mut HashSet<String>) -> String {
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, Some(ext)),
+        _ => (name, None),
+    };
+    let mut candidate = name.to_string();
+    let mut n = 2;
+    while names.contains(&candidate) {
+        candidate = match ext {
+            Some(ext) => format!("{}-{}.{}", stem, n, ext),
+            None => format!("{}-{}", stem, n),
+        };
+        n += 1;
+    }
+    names.insert(candidate.clone());
+    candidate
+}
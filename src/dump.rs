@@ -0,0 +1,101 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A directory the raw bodies of the DuckDuckGo responses are saved to, to debug parser
+/// failures.
+///
+/// Each body is saved to its own file, named after the time of the run, the order of the
+/// response, and the endpoint, e.g. `1760614254123-002-html.duckduckgo.com_html.html`. Errors
+/// parsing a dumped body mention the path of its file, so that it can be attached to a bug
+/// report.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::dump::RawDump;
+///
+/// let dir = std::env::temp_dir().join("ddg-dump-doctest");
+/// let dump = RawDump::new(&dir);
+///
+/// let path = dump
+///     .save("https://duckduckgo.com/i.js?q=rust", Some("application/json"), "{}")
+///     .unwrap();
+/// assert!(path.starts_with(&dir));
+/// assert!(path.to_string_lossy().ends_with("-duckduckgo.com_i.js.json"));
+///
+/// let browser = Browser::builder().dump_raw(&dir).build().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct RawDump {
+    dir: PathBuf,
+    run: u128,
+    count: AtomicUsize,
+}
+
+impl RawDump {
+    /// Creates a dump saving the bodies into `dir`, created on the first save.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        RawDump {
+            dir: dir.into(),
+            run: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis()),
+            count: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the directory the bodies are saved to.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Saves the body of a response.
+    ///
+    /// # Arguments
+    /// * `url` - The URL of the response.
+    /// * `content_type` - The `Content-Type` of the response, which picks the file extension.
+    /// * `body` - The body of the response.
+    ///
+    /// # Returns
+    /// The path of the file the body was saved to.
+    pub fn save(&self, url: &str, content_type: Option<&str>, body: &str) -> Result<PathBuf> {
+        let count = self.count.fetch_add(1, Ordering::Relaxed) + 1;
+        let endpoint: String = url
+            .split_once("://")
+            .map_or(url, |(_, rest)| rest)
+            .split(['?', '#'])
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('/')
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let trimmed = body.trim_start();
+        let extension = match content_type {
+            Some(content_type) if content_type.contains("json") => "json",
+            Some(content_type) if content_type.contains("html") => "html",
+            _ if trimmed.starts_with('{') || trimmed.starts_with('[') => "json",
+            _ if trimmed.starts_with('<') => "html",
+            _ => "txt",
+        };
+
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create directory {}", self.dir.display()))?;
+        let path = self.dir.join(format!(
+            "{}-{:03}-{}.{}",
+            self.run, count, endpoint, extension
+        ));
+        fs::write(&path, body)
+            .with_context(|| format!("Failed to write raw response {}", path.display()))?;
+        Ok(path)
+    }
+}
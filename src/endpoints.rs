@@ -0,0 +1,46 @@
+/// The base URLs of the DuckDuckGo endpoints used by `Browser`.
+///
+/// Overriding them makes it possible to point the crate at a mock server or a self-hosted proxy.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::endpoints::Endpoints;
+///
+/// let endpoints = Endpoints {
+///     api: String::from("http://localhost:8080/api/"),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(endpoints.lite, "https://lite.duckduckgo.com/lite/");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoints {
+    /// The Instant Answer API endpoint.
+    pub api: String,
+    /// The DuckDuckGo Lite endpoint.
+    pub lite: String,
+    /// The DuckDuckGo HTML endpoint.
+    pub html: String,
+    /// The image search endpoint (`i.js`).
+    pub images: String,
+    /// The news search endpoint (`news.js`).
+    pub news: String,
+    /// The search page the `vqd` token is extracted from.
+    pub vqd: String,
+    /// The favicon service, serving the icon of a domain at `<icons><domain>.ico`.
+    pub icons: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Endpoints {
+            api: String::from("https://api.duckduckgo.com/"),
+            lite: String::from("https://lite.duckduckgo.com/lite/"),
+            html: String::from("https://html.duckduckgo.com/html/"),
+            images: String::from("https://duckduckgo.com/i.js"),
+            news: String::from("https://duckduckgo.com/news.js"),
+            vqd: String::from("https://duckduckgo.com/"),
+            icons: String::from("https://icons.duckduckgo.com/ip3/"),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Errors DuckDuckGo callers may want to react to programmatically.
+///
+/// Functions of this crate return `anyhow::Result`; these errors can be recovered with
+/// `anyhow::Error::downcast_ref`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::error::Error;
+///
+/// let err = anyhow::Error::from(Error::BotDetection {
+///     url: String::from("https://html.duckduckgo.com/html/"),
+/// });
+///
+/// match err.downcast_ref::<Error>() {
+///     Some(Error::BotDetection { .. }) => println!("Backing off"),
+///     _ => panic!("unexpected error: {err}"),
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// DuckDuckGo served its "anomaly detected" challenge instead of results.
+    #[error(
+        "DuckDuckGo flagged the request to {url} as automated traffic; try another user agent \
+         or proxy, or slow down between requests"
+    )]
+    BotDetection {
+        /// The URL that served the challenge.
+        url: String,
+    },
+    /// A response body exceeded the maximum size accepted by the `Browser`.
+    #[error("Response from {url} exceeds the maximum body size of {limit} bytes")]
+    BodyTooLarge {
+        /// The URL that served the response.
+        url: String,
+        /// The maximum body size, in bytes.
+        limit: usize,
+    },
+    /// A result item of the image or news APIs could not be parsed, in strict parse mode.
+    #[error("Result #{rank} is malformed: {reason}")]
+    MalformedItem {
+        /// The 1-based position of the item in the results.
+        rank: usize,
+        /// Why the item could not be parsed.
+        reason: String,
+    },
+    /// Requests are suspended after repeated upstream failures.
+    #[error("Too many consecutive failures, requests are suspended for {retry_after:?}")]
+    CircuitOpen {
+        /// The time left before requests are allowed again.
+        retry_after: Duration,
+    },
+}
+
+/// The delay DuckDuckGo asked to wait before retrying a rate-limited request, from the
+/// `Retry-After` header of its response.
+///
+/// It is attached as context to the HTTP error of the request, and can be recovered with
+/// `anyhow::Error::downcast_ref`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::error::RetryAfter;
+/// use std::time::Duration;
+///
+/// let err = anyhow::anyhow!("HTTP status client error (429 Too Many Requests)")
+///     .context(RetryAfter(Duration::from_secs(30)));
+///
+/// assert_eq!(
+///     err.downcast_ref::<RetryAfter>(),
+///     Some(&RetryAfter(Duration::from_secs(30)))
+/// );
+/// assert_eq!(err.to_string(), "DuckDuckGo asked to retry after 30 seconds");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryAfter(pub Duration);
+
+impl fmt::Display for RetryAfter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DuckDuckGo asked to retry after {} seconds",
+            self.0.as_secs()
+        )
+    }
+}
+
+/// A response that could not be parsed, such as a page whose layout changed or a missing `vqd`
+/// token.
+///
+/// It is attached as context to the parse error, and can be recovered with
+/// `anyhow::Error::downcast_ref`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::error::InvalidResponse;
+///
+/// let err = anyhow::anyhow!("Missing vqd in response").context(InvalidResponse {
+///     url: String::from("https://duckduckgo.com/?q=rust"),
+/// });
+///
+/// assert!(err.downcast_ref::<InvalidResponse>().is_some());
+/// assert_eq!(
+///     format!("{:#}", err),
+///     "Invalid response from https://duckduckgo.com/?q=rust: Missing vqd in response"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidResponse {
+    /// The URL that served the response.
+    pub url: String,
+}
+
+impl fmt::Display for InvalidResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid response from {}", self.url)
+    }
+}
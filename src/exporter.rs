@@ -0,0 +1,683 @@
+// Without the `sqlite` and `parquet` features, the module only parses export targets to reject
+// them, and the exporters have no variants.
+#![cfg_attr(
+    not(any(feature = "sqlite", feature = "parquet")),
+    allow(dead_code, unused_imports, unused_variables, unreachable_code)
+)]
+
+use crate::params::SearchParams;
+use crate::response::SearchResult;
+use anyhow::{Context, Result, bail};
+#[cfg(feature = "parquet")]
+use arrow_array::builder::{
+    BooleanBuilder, StringBuilder, TimestampMicrosecondBuilder, UInt32Builder,
+};
+#[cfg(feature = "parquet")]
+use arrow_array::{ArrayRef, RecordBatch};
+#[cfg(feature = "parquet")]
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "parquet")]
+use parquet::arrow::ArrowWriter;
+#[cfg(feature = "sqlite")]
+use rusqlite::{Connection, params};
+use std::fmt;
+#[cfg(feature = "parquet")]
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+#[cfg(feature = "parquet")]
+use std::sync::{Arc, LazyLock};
+
+/// The export formats, in the `<format>://` prefix of export targets.
+const FORMATS: &[&str] = &[
+    #[cfg(feature = "sqlite")]
+    "sqlite",
+    #[cfg(feature = "parquet")]
+    "parquet",
+    #[cfg(feature = "parquet")]
+    "arrow",
+];
+
+/// Where search results are exported, written `<format>://<path>` (e.g.
+/// `sqlite://results.db` or `parquet://results.parquet`).
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "sqlite")] {
+/// use duckduckgo::exporter::ExportTarget;
+/// use std::path::PathBuf;
+///
+/// let target: ExportTarget = "sqlite://results.db".parse().unwrap();
+/// assert_eq!(target, ExportTarget::Sqlite(PathBuf::from("results.db")));
+/// assert!("csv://results.csv".parse::<ExportTarget>().is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportTarget {
+    /// A SQLite database, created if it does not exist (see `SqliteExporter`).
+    #[cfg(feature = "sqlite")]
+    Sqlite(PathBuf),
+    /// A Parquet file, overwritten if it exists (see `ParquetExporter`).
+    #[cfg(feature = "parquet")]
+    Parquet(PathBuf),
+    /// An Arrow IPC file, overwritten if it exists (see `ArrowExporter`).
+    #[cfg(feature = "parquet")]
+    Arrow(PathBuf),
+}
+
+impl FromStr for ExportTarget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let Some((scheme, path)) = s.split_once("://") else {
+            bail!(
+                "Invalid export target '{}', expected e.g. 'sqlite://results.db'",
+                s
+            );
+        };
+        if path.is_empty() {
+            bail!("Missing file path in export target '{}'", s);
+        }
+        if FORMATS.is_empty() {
+            bail!("Exporting results requires the `sqlite` or `parquet` feature");
+        }
+        let path = PathBuf::from(path);
+        match scheme {
+            #[cfg(feature = "sqlite")]
+            "sqlite" => Ok(ExportTarget::Sqlite(path)),
+            #[cfg(feature = "parquet")]
+            "parquet" => Ok(ExportTarget::Parquet(path)),
+            #[cfg(feature = "parquet")]
+            "arrow" => Ok(ExportTarget::Arrow(path)),
+            _ => bail!(
+                "Unsupported export format '{}', expected one of: {}",
+                scheme,
+                FORMATS.join(", ")
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ExportTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Dereferenced so that the match stays exhaustive when no export format is enabled.
+        let (format, path): (&str, &PathBuf) = match *self {
+            #[cfg(feature = "sqlite")]
+            ExportTarget::Sqlite(ref path) => ("sqlite", path),
+            #[cfg(feature = "parquet")]
+            ExportTarget::Parquet(ref path) => ("parquet", path),
+            #[cfg(feature = "parquet")]
+            ExportTarget::Arrow(ref path) => ("arrow", path),
+        };
+        write!(f, "{}://{}", format, path.display())
+    }
+}
+
+/// The metadata of a search whose results are exported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchRecord {
+    /// The search query.
+    pub query: String,
+    /// The name of the backend that returned the results (e.g. `"html"`).
+    pub backend: String,
+    /// The region code of the search (e.g. `"wt-wt"`).
+    pub region: String,
+    /// Whether safe search was enabled.
+    pub safe_search: bool,
+    /// When the search was made.
+    pub searched_at: DateTime<Utc>,
+}
+
+impl SearchRecord {
+    /// Records a search made now with the given options.
+    ///
+    /// # Arguments
+    /// * `params` - The search options.
+    /// * `backend` - The name of the backend that returned the results.
+    pub fn new(params: &SearchParams, backend: &str) -> Self {
+        SearchRecord {
+            query: params.query.clone(),
+            backend: backend.to_string(),
+            region: params.region.clone(),
+            safe_search: params.safe_search,
+            searched_at: Utc::now(),
+        }
+    }
+}
+
+/// An exporter writing to any export target.
+///
+/// # Examples
+/// ```no_run
+/// use duckduckgo::exporter::{Exporter, SearchRecord};
+/// use duckduckgo::params::SearchParams;
+///
+/// # let results = Vec::new();
+/// let mut exporter = Exporter::open(&"sqlite://results.db".parse()?)?;
+/// exporter.export(&SearchRecord::new(&SearchParams::new("rust"), "html"), &results)?;
+/// exporter.finish()?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug)]
+pub enum Exporter {
+    /// Appends the results to a SQLite database.
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteExporter),
+    /// Writes the results to a Parquet file.
+    #[cfg(feature = "parquet")]
+    Parquet(ParquetExporter),
+    /// Writes the results to an Arrow IPC file.
+    #[cfg(feature = "parquet")]
+    Arrow(ArrowExporter),
+}
+
+impl Exporter {
+    /// Opens the exporter of a target.
+    ///
+    /// # Arguments
+    /// * `target` - Where the results are exported.
+    pub fn open(target: &ExportTarget) -> Result<Self> {
+        Ok(match *target {
+            #[cfg(feature = "sqlite")]
+            ExportTarget::Sqlite(ref path) => Exporter::Sqlite(SqliteExporter::open(path)?),
+            #[cfg(feature = "parquet")]
+            ExportTarget::Parquet(ref path) => Exporter::Parquet(ParquetExporter::create(path)?),
+            #[cfg(feature = "parquet")]
+            ExportTarget::Arrow(ref path) => Exporter::Arrow(ArrowExporter::create(path)?),
+        })
+    }
+
+    /// Exports a search and its results.
+    ///
+    /// # Arguments
+    /// * `search` - The metadata of the search.
+    /// * `results` - The results of the search.
+    pub fn export(&mut self, search: &SearchRecord, results: &[SearchResult]) -> Result<()> {
+        match *self {
+            #[cfg(feature = "sqlite")]
+            Exporter::Sqlite(ref mut exporter) => exporter.export(search, results).map(drop),
+            #[cfg(feature = "parquet")]
+            Exporter::Parquet(ref mut exporter) => exporter.export(search, results),
+            #[cfg(feature = "parquet")]
+            Exporter::Arrow(ref mut exporter) => exporter.export(search, results),
+        }
+    }
+
+    /// Finishes the export, writing the footer of file formats.
+    pub fn finish(self) -> Result<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Exporter::Sqlite(_) => Ok(()),
+            #[cfg(feature = "parquet")]
+            Exporter::Parquet(exporter) => exporter.finish(),
+            #[cfg(feature = "parquet")]
+            Exporter::Arrow(exporter) => exporter.finish(),
+        }
+    }
+}
+
+/// Returns the type of a result, as in its JSON serialization (`"text"`, `"image"`, or
+/// `"news"`).
+fn result_type(result: &SearchResult) -> &'static str {
+    match result {
+        SearchResult::Text(_) => "text",
+        SearchResult::Image(_) => "image",
+        #[cfg(feature = "news")]
+        SearchResult::News(_) => "news",
+    }
+}
+
+/// The tables of the SQLite export: one row per search in `searches`, one row per result in
+/// `results`, and the fields specific to images and news in `images` and `news`, keyed by the
+/// result id.
+#[cfg(feature = "sqlite")]
+const SQLITE_SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS searches (
+    id INTEGER PRIMARY KEY,
+    query TEXT NOT NULL,
+    backend TEXT NOT NULL,
+    region TEXT NOT NULL,
+    safe_search INTEGER NOT NULL,
+    searched_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS results (
+    id INTEGER PRIMARY KEY,
+    search_id INTEGER NOT NULL REFERENCES searches(id),
+    type TEXT NOT NULL,
+    rank INTEGER NOT NULL,
+    page INTEGER NOT NULL,
+    backend TEXT NOT NULL,
+    title TEXT NOT NULL,
+    url TEXT NOT NULL,
+    snippet TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS results_search_id ON results(search_id);
+CREATE INDEX IF NOT EXISTS results_url ON results(url);
+CREATE TABLE IF NOT EXISTS images (
+    result_id INTEGER PRIMARY KEY REFERENCES results(id),
+    image TEXT NOT NULL,
+    thumbnail TEXT NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    source TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS news (
+    result_id INTEGER PRIMARY KEY REFERENCES results(id),
+    date TEXT,
+    source TEXT NOT NULL,
+    domain TEXT,
+    image TEXT
+);
+";
+
+/// Appends search results to a SQLite database, so that the results of many runs can be
+/// analyzed over time.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::exporter::{SearchRecord, SqliteExporter};
+/// use duckduckgo::fixtures;
+/// use duckduckgo::params::SearchParams;
+/// use duckduckgo::response::SearchResult;
+///
+/// let results: Vec<SearchResult> = fixtures::lite_page()
+///     .unwrap()
+///     .results
+///     .into_iter()
+///     .map(SearchResult::Text)
+///     .collect();
+///
+/// let mut exporter = SqliteExporter::in_memory().unwrap();
+/// let search = SearchRecord::new(&SearchParams::new("rust"), "lite");
+/// exporter.export(&search, &results).unwrap();
+/// exporter.export(&search, &results).unwrap();
+///
+/// let count: usize = exporter
+///     .connection()
+///     .query_row("SELECT COUNT(*) FROM results", [], |row| row.get(0))
+///     .unwrap();
+/// assert_eq!(count, 2 * results.len());
+/// ```
+#[cfg(feature = "sqlite")]
+#[derive(Debug)]
+pub struct SqliteExporter {
+    conn: Connection,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteExporter {
+    /// Opens the database at `path`, creating it and its tables if needed.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the database file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open SQLite database {}", path.display()))?;
+        Self::with_connection(conn)
+    }
+
+    /// Creates an exporter writing to a temporary in-memory database.
+    pub fn in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    /// Creates the tables of the export in the database, if needed.
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SQLITE_SCHEMA)
+            .context("Failed to create the export tables")?;
+        Ok(SqliteExporter { conn })
+    }
+
+    /// Returns the connection to the database, e.g. to query the exported results.
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Appends a search and its results to the database, in a single transaction.
+    ///
+    /// # Arguments
+    /// * `search` - The metadata of the search.
+    /// * `results` - The results of the search.
+    ///
+    /// # Returns
+    /// The id of the search in the `searches` table.
+    pub fn export(&mut self, search: &SearchRecord, results: &[SearchResult]) -> Result<i64> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO searches (query, backend, region, safe_search, searched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                search.query,
+                search.backend,
+                search.region,
+                search.safe_search,
+                search.searched_at.to_rfc3339(),
+            ],
+        )?;
+        let search_id = tx.last_insert_rowid();
+
+        for result in results {
+            tx.execute(
+                "INSERT INTO results (search_id, type, rank, page, backend, title, url, snippet)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    search_id,
+                    result_type(result),
+                    result.rank(),
+                    result.page(),
+                    result.backend(),
+                    result.title(),
+                    result.url(),
+                    result.snippet(),
+                ],
+            )?;
+            let result_id = tx.last_insert_rowid();
+
+            match result {
+                SearchResult::Text(_) => {}
+                SearchResult::Image(image) => {
+                    tx.execute(
+                        "INSERT INTO images (result_id, image, thumbnail, width, height, source)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![
+                            result_id,
+                            image.image.as_str(),
+                            image.thumbnail.as_str(),
+                            image.width,
+                            image.height,
+                            image.source,
+                        ],
+                    )?;
+                }
+                #[cfg(feature = "news")]
+                SearchResult::News(news) => {
+                    tx.execute(
+                        "INSERT INTO news (result_id, date, source, domain, image)
+                         VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![
+                            result_id,
+                            news.date.map(|date| date.to_rfc3339()),
+                            news.source,
+                            news.domain,
+                            news.image.as_ref().map(|image| image.as_str()),
+                        ],
+                    )?;
+                }
+            }
+        }
+        tx.commit()?;
+
+        Ok(search_id)
+    }
+}
+
+/// The schema of the Parquet and Arrow exports: one row per result, with the metadata of its
+/// search, and the columns specific to images and news left null for the other results.
+///
+/// The schema is stable: columns are only ever added at the end, so that files written by
+/// different versions can be read together.
+#[cfg(feature = "parquet")]
+pub static ARROW_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    let timestamp = DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()));
+    Arc::new(Schema::new(vec![
+        Field::new("query", DataType::Utf8, false),
+        Field::new("backend", DataType::Utf8, false),
+        Field::new("region", DataType::Utf8, false),
+        Field::new("safe_search", DataType::Boolean, false),
+        Field::new("searched_at", timestamp.clone(), false),
+        Field::new("type", DataType::Utf8, false),
+        Field::new("rank", DataType::UInt32, false),
+        Field::new("page", DataType::UInt32, false),
+        Field::new("title", DataType::Utf8, false),
+        Field::new("url", DataType::Utf8, false),
+        Field::new("snippet", DataType::Utf8, false),
+        Field::new("source", DataType::Utf8, true),
+        Field::new("image", DataType::Utf8, true),
+        Field::new("thumbnail", DataType::Utf8, true),
+        Field::new("width", DataType::UInt32, true),
+        Field::new("height", DataType::UInt32, true),
+        Field::new("date", timestamp, true),
+        Field::new("domain", DataType::Utf8, true),
+    ]))
+});
+
+/// Converts a search and its results into a record batch of `ARROW_SCHEMA`.
+///
+/// # Arguments
+/// * `search` - The metadata of the search.
+/// * `results` - The results of the search, one row each.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::exporter::{SearchRecord, record_batch};
+/// use duckduckgo::fixtures;
+/// use duckduckgo::params::SearchParams;
+/// use duckduckgo::response::SearchResult;
+///
+/// let results: Vec<SearchResult> = fixtures::news()
+///     .unwrap()
+///     .into_iter()
+///     .map(SearchResult::News)
+///     .collect();
+/// let search = SearchRecord::new(&SearchParams::new("rust"), "news");
+///
+/// let batch = record_batch(&search, &results).unwrap();
+/// assert_eq!(batch.num_rows(), results.len());
+/// assert_eq!(batch.schema().field(0).name(), "query");
+/// ```
+#[cfg(feature = "parquet")]
+pub fn record_batch(search: &SearchRecord, results: &[SearchResult]) -> Result<RecordBatch> {
+    let len = results.len();
+    let utf8 = || StringBuilder::with_capacity(len, len * 16);
+    let timestamp = || TimestampMicrosecondBuilder::with_capacity(len).with_timezone("UTC");
+    let (mut query, mut backend, mut region) = (utf8(), utf8(), utf8());
+    let mut safe_search = BooleanBuilder::with_capacity(len);
+    let mut searched_at = timestamp();
+    let (mut kind, mut title, mut url, mut snippet) = (utf8(), utf8(), utf8(), utf8());
+    let (mut rank, mut page) = (
+        UInt32Builder::with_capacity(len),
+        UInt32Builder::with_capacity(len),
+    );
+    let (mut source, mut image, mut thumbnail, mut domain) = (utf8(), utf8(), utf8(), utf8());
+    let (mut width, mut height) = (
+        UInt32Builder::with_capacity(len),
+        UInt32Builder::with_capacity(len),
+    );
+    let mut date = timestamp();
+
+    for result in results {
+        query.append_value(&search.query);
+        backend.append_value(&search.backend);
+        region.append_value(&search.region);
+        safe_search.append_value(search.safe_search);
+        searched_at.append_value(search.searched_at.timestamp_micros());
+        kind.append_value(result_type(result));
+        rank.append_value(u32::try_from(result.rank()).unwrap_or(u32::MAX));
+        page.append_value(u32::try_from(result.page()).unwrap_or(u32::MAX));
+        title.append_value(result.title());
+        url.append_value(result.url());
+        snippet.append_value(result.snippet());
+
+        match result {
+            SearchResult::Text(_) => {
+                source.append_null();
+                image.append_null();
+                thumbnail.append_null();
+                width.append_null();
+                height.append_null();
+                date.append_null();
+                domain.append_option(result.domain());
+            }
+            SearchResult::Image(r) => {
+                source.append_value(&r.source);
+                image.append_value(r.image.as_str());
+                thumbnail.append_value(r.thumbnail.as_str());
+                width.append_value(r.width);
+                height.append_value(r.height);
+                date.append_null();
+                domain.append_option(result.domain());
+            }
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => {
+                source.append_value(&r.source);
+                image.append_option(r.image.as_ref().map(|image| image.as_str()));
+                thumbnail.append_null();
+                width.append_null();
+                height.append_null();
+                date.append_option(r.date.map(|date| date.timestamp_micros()));
+                domain.append_option(r.domain.as_deref());
+            }
+        }
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(query.finish()),
+        Arc::new(backend.finish()),
+        Arc::new(region.finish()),
+        Arc::new(safe_search.finish()),
+        Arc::new(searched_at.finish()),
+        Arc::new(kind.finish()),
+        Arc::new(rank.finish()),
+        Arc::new(page.finish()),
+        Arc::new(title.finish()),
+        Arc::new(url.finish()),
+        Arc::new(snippet.finish()),
+        Arc::new(source.finish()),
+        Arc::new(image.finish()),
+        Arc::new(thumbnail.finish()),
+        Arc::new(width.finish()),
+        Arc::new(height.finish()),
+        Arc::new(date.finish()),
+        Arc::new(domain.finish()),
+    ];
+    Ok(RecordBatch::try_new(ARROW_SCHEMA.clone(), columns)?)
+}
+
+/// Writes search results to a Parquet file with the schema `ARROW_SCHEMA`, to load them into
+/// Polars, pandas, or DuckDB.
+///
+/// Each call to `export` appends a row group to the file, which is complete once `finish` is
+/// called.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::exporter::{ParquetExporter, SearchRecord};
+/// use duckduckgo::fixtures;
+/// use duckduckgo::params::SearchParams;
+/// use duckduckgo::response::SearchResult;
+/// use parquet::file::reader::{FileReader, SerializedFileReader};
+///
+/// let results: Vec<SearchResult> = fixtures::lite_page()
+///     .unwrap()
+///     .results
+///     .into_iter()
+///     .map(SearchResult::Text)
+///     .collect();
+/// let path = std::env::temp_dir().join("ddg-export-doctest.parquet");
+///
+/// let mut exporter = ParquetExporter::create(&path).unwrap();
+/// exporter
+///     .export(&SearchRecord::new(&SearchParams::new("rust"), "lite"), &results)
+///     .unwrap();
+/// exporter.finish().unwrap();
+///
+/// let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+/// assert_eq!(reader.metadata().file_metadata().num_rows() as usize, results.len());
+/// ```
+#[cfg(feature = "parquet")]
+pub struct ParquetExporter {
+    writer: ArrowWriter<File>,
+}
+
+#[cfg(feature = "parquet")]
+impl fmt::Debug for ParquetExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParquetExporter").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl ParquetExporter {
+    /// Creates the Parquet file at `path`, compressed with Snappy, overwriting it if it
+    /// exists.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the Parquet file.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create Parquet file {}", path.display()))?;
+        let properties = parquet::file::properties::WriterProperties::builder()
+            .set_compression(parquet::basic::Compression::SNAPPY)
+            .build();
+        let writer = ArrowWriter::try_new(file, ARROW_SCHEMA.clone(), Some(properties))?;
+        Ok(ParquetExporter { writer })
+    }
+
+    /// Writes a search and its results to the file.
+    ///
+    /// # Arguments
+    /// * `search` - The metadata of the search.
+    /// * `results` - The results of the search.
+    pub fn export(&mut self, search: &SearchRecord, results: &[SearchResult]) -> Result<()> {
+        self.writer.write(&record_batch(search, results)?)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the footer of the file, completing it.
+    pub fn finish(self) -> Result<()> {
+        self.writer.close()?;
+        Ok(())
+    }
+}
+
+/// Writes search results to an Arrow IPC file (also known as Feather v2) with the schema
+/// `ARROW_SCHEMA`.
+///
+/// Each call to `export` appends a record batch to the file, which is complete once `finish`
+/// is called.
+#[cfg(feature = "parquet")]
+pub struct ArrowExporter {
+    writer: arrow_ipc::writer::FileWriter<File>,
+}
+
+#[cfg(feature = "parquet")]
+impl fmt::Debug for ArrowExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArrowExporter").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl ArrowExporter {
+    /// Creates the Arrow IPC file at `path`, overwriting it if it exists.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the Arrow file.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create Arrow file {}", path.display()))?;
+        let writer = arrow_ipc::writer::FileWriter::try_new(file, &ARROW_SCHEMA)?;
+        Ok(ArrowExporter { writer })
+    }
+
+    /// Writes a search and its results to the file.
+    ///
+    /// # Arguments
+    /// * `search` - The metadata of the search.
+    /// * `results` - The results of the search.
+    pub fn export(&mut self, search: &SearchRecord, results: &[SearchResult]) -> Result<()> {
+        self.writer.write(&record_batch(search, results)?)?;
+        Ok(())
+    }
+
+    /// Writes the footer of the file, completing it.
+    pub fn finish(mut self) -> Result<()> {
+        self.writer.finish()?;
+        Ok(())
+    }
+}
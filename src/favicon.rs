@@ -0,0 +1,119 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The favicon of the site a search result links to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct Favicon {
+    /// The URL of the icon on DuckDuckGo's icon service.
+    pub url: String,
+    /// The path of the icon in the cache, if it could be downloaded.
+    #[cfg_attr(feature = "server", schema(value_type = Option<String>))]
+    pub path: Option<PathBuf>,
+}
+
+impl Favicon {
+    /// Returns the path of the cached icon if there is one, its URL otherwise.
+    pub fn location(&self) -> String {
+        match &self.path {
+            Some(path) => path.display().to_string(),
+            None => self.url.clone(),
+        }
+    }
+}
+
+/// A directory caching the favicons downloaded from DuckDuckGo's icon service, one
+/// `<domain>.ico` file per site, so that each icon is only downloaded once.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::favicon::FaviconCache;
+///
+/// let cache = FaviconCache::new(std::env::temp_dir().join("ddg-favicons-doctest"));
+/// cache.store("www.rust-lang.org", b"\0\0\x01\0").unwrap();
+///
+/// let path = cache.get("www.rust-lang.org").unwrap();
+/// assert!(path.ends_with("www.rust-lang.org.ico"));
+/// assert!(cache.get("example.com").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaviconCache {
+    dir: PathBuf,
+}
+
+impl Default for FaviconCache {
+    /// Creates a cache in the `ddg/favicons` directory of the user cache directory
+    /// (`$XDG_CACHE_HOME`, or `~/.cache`), or of the temporary directory if there is none.
+    fn default() -> Self {
+        let base = env::var_os("XDG_CACHE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+            .unwrap_or_else(env::temp_dir);
+        Self::new(base.join("ddg").join("favicons"))
+    }
+}
+
+impl FaviconCache {
+    /// Creates a cache storing the icons in `dir`, which is created on the first download.
+    ///
+    /// # Arguments
+    /// * `dir` - The cache directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FaviconCache { dir: dir.into() }
+    }
+
+    /// Returns the cache directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the path the icon of a domain is cached at, whether it is cached or not.
+    ///
+    /// # Arguments
+    /// * `domain` - The domain of the site (e.g. `"www.rust-lang.org"`).
+    pub fn path(&self, domain: &str) -> PathBuf {
+        let name: String = domain
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' => c.to_ascii_lowercase(),
+                _ => '_',
+            })
+            .collect();
+        self.dir.join(format!("{}.ico", name))
+    }
+
+    /// Returns the path of the cached icon of a domain, if it was downloaded before.
+    ///
+    /// # Arguments
+    /// * `domain` - The domain of the site.
+    pub fn get(&self, domain: &str) -> Option<PathBuf> {
+        let path = self.path(domain);
+        path.is_file().then_some(path)
+    }
+
+    /// Saves the icon of a domain in the cache.
+    ///
+    /// # Arguments
+    /// * `domain` - The domain of the site.
+    /// * `icon` - The content of the `.ico` file.
+    ///
+    /// # Returns
+    /// The path the icon was saved at.
+    pub fn store(&self, domain: &str, icon: &[u8]) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir).with_context(|| {
+            format!(
+                "Failed to create favicon cache directory {}",
+                self.dir.display()
+            )
+        })?;
+        let path = self.path(domain);
+        fs::write(&path, icon)
+            .with_context(|| format!("Failed to write favicon {}", path.display()))?;
+        Ok(path)
+    }
+}
@@ -0,0 +1,457 @@
+use crate::backend::SearchBackend;
+use crate::link::Link;
+use crate::params::SearchParams;
+use crate::plan::PlannedRequest;
+use crate::response::{SearchPage, SearchResult};
+use anyhow::{Result, bail};
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+/// The most pages a `FilteredBackend` fetches after the first one to make up for the results
+/// its filters dropped.
+const MAX_BACKFILL_PAGES: usize = 4;
+
+/// The similarity from which `ResultFilters::collapse_duplicates` considers two results
+/// near-identical by default.
+pub const DEFAULT_SIMILARITY: f64 = 0.8;
+
+/// The number of consecutive words of the shingles the texts of results are compared by.
+const SHINGLE_WORDS: usize = 3;
+
+/// How far, relative to the expected ratio, the ratio of an image may be from an
+/// `AspectRatio` to match it, which absorbs rounding, e.g. 1366x768 for 16:9.
+const ASPECT_TOLERANCE: f64 = 0.02;
+
+/// The ratio of the width to the height of an image, e.g. `16:9`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::filter::AspectRatio;
+///
+/// let wide: AspectRatio = "16:9".parse().unwrap();
+/// assert!(wide.matches(1920, 1080));
+/// assert!(wide.matches(1366, 768));
+/// assert!(!wide.matches(1024, 768));
+/// assert_eq!(wide.to_string(), "16:9");
+///
+/// assert!("16x9".parse::<AspectRatio>().is_err());
+/// assert!("0:1".parse::<AspectRatio>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AspectRatio {
+    /// The relative width.
+    pub width: u32,
+    /// The relative height.
+    pub height: u32,
+}
+
+impl AspectRatio {
+    /// Returns `true` if an image of the given dimensions has this ratio, within 2%.
+    ///
+    /// # Arguments
+    /// * `width` - The width of the image in pixels.
+    /// * `height` - The height of the image in pixels.
+    pub fn matches(&self, width: u32, height: u32) -> bool {
+        if width == 0 || height == 0 {
+            return false;
+        }
+        let expected = f64::from(self.width) / f64::from(self.height);
+        let actual = f64::from(width) / f64::from(height);
+        (actual - expected).abs() <= expected * ASPECT_TOLERANCE
+    }
+}
+
+impl FromStr for AspectRatio {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let Some((width, height)) = s.split_once(':') else {
+            bail!("Invalid aspect ratio '{}', expected e.g. 16:9", s);
+        };
+        match (width.trim().parse(), height.trim().parse()) {
+            (Ok(width), Ok(height)) if width > 0 && height > 0 => Ok(AspectRatio { width, height }),
+            _ => bail!("Invalid aspect ratio '{}', expected e.g. 16:9", s),
+        }
+    }
+}
+
+impl fmt::Display for AspectRatio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.width, self.height)
+    }
+}
+
+/// Filters applied to the results of a search on the client side.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::filter::ResultFilters;
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+///
+/// let results: Vec<SearchResult> = [
+///     "https://doc.rust-lang.org/book/",
+///     "https://www.rust-lang.org/",
+///     "https://doc.rust-lang.org/std/",
+///     "https://crates.io/",
+/// ]
+/// .into_iter()
+/// .map(|url| {
+///     SearchResult::Text(LiteSearchResult {
+///         url: url.into(),
+///         ..Default::default()
+///     })
+/// })
+/// .collect();
+///
+/// let filters = ResultFilters::new().max_per_domain(1);
+/// let urls: Vec<String> = filters.apply(results).iter().map(|r| r.url().to_string()).collect();
+/// assert_eq!(
+///     urls,
+///     ["https://doc.rust-lang.org/book/", "https://www.rust-lang.org/", "https://crates.io/"]
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResultFilters {
+    max_per_domain: Option<usize>,
+    similarity: Option<f64>,
+    min_width: Option<u32>,
+    min_height: Option<u32>,
+    aspect: Option<AspectRatio>,
+}
+
+impl ResultFilters {
+    /// Creates filters keeping every result.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps at most `n` results per domain, the first ones in the order of the results.
+    ///
+    /// Domains are compared without their `www.` prefix, so that `www.rust-lang.org` and
+    /// `rust-lang.org` count as one site, while subdomains such as `doc.rust-lang.org` count as
+    /// other sites. Results without a valid URL are always kept.
+    ///
+    /// # Arguments
+    /// * `n` - The maximum number of results per domain.
+    pub fn max_per_domain(mut self, n: usize) -> Self {
+        self.max_per_domain = Some(n);
+        self
+    }
+
+    /// Collapses the results whose titles and snippets are near-identical, such as syndicated
+    /// news articles, into the first one, which records the URLs of the others in `also_on`.
+    ///
+    /// Texts are compared by their sets of three consecutive words, ignoring case and
+    /// punctuation; two results are near-identical when the share of the word sequences they
+    /// have in common is at least `similarity`.
+    ///
+    /// # Arguments
+    /// * `similarity` - The similarity from `0.0` to `1.0` above which results are collapsed,
+    ///   e.g. `DEFAULT_SIMILARITY`.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::filter::{DEFAULT_SIMILARITY, ResultFilters};
+    /// use duckduckgo::response::{LiteSearchResult, SearchResult};
+    ///
+    /// let result = |url: &str, snippet: &str| {
+    ///     SearchResult::Text(LiteSearchResult {
+    ///         title: String::from("Rust 1.88 released"),
+    ///         url: url.into(),
+    ///         snippet: snippet.to_string(),
+    ///         ..Default::default()
+    ///     })
+    /// };
+    /// let results = vec![
+    ///     result("https://blog.rust-lang.org/", "The Rust team is happy to announce Rust 1.88."),
+    ///     result("https://news.example/rust", "The Rust team is happy to announce Rust 1.88!"),
+    ///     result("https://lwn.net/", "A look at what is new in the latest Rust compiler."),
+    /// ];
+    ///
+    /// let results = ResultFilters::new().collapse_duplicates(DEFAULT_SIMILARITY).apply(results);
+    /// assert_eq!(results.len(), 2);
+    /// assert_eq!(results[0].also_on()[0], "https://news.example/rust");
+    /// ```
+    pub fn collapse_duplicates(mut self, similarity: f64) -> Self {
+        self.similarity = Some(similarity.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Keeps the images at least `width` pixels wide.
+    ///
+    /// Like the other image filters, it leaves the results other than images unchanged, and
+    /// drops the images whose dimensions are unknown.
+    ///
+    /// # Arguments
+    /// * `width` - The minimum width in pixels.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::filter::ResultFilters;
+    /// use duckduckgo::response::{ImageResult, SearchResult};
+    ///
+    /// let image = |width, height| {
+    ///     SearchResult::Image(ImageResult {
+    ///         width,
+    ///         height,
+    ///         ..Default::default()
+    ///     })
+    /// };
+    /// let results = vec![image(1920, 1080), image(640, 480), image(2048, 1536), image(0, 0)];
+    ///
+    /// let filters = ResultFilters::new()
+    ///     .min_width(1280)
+    ///     .aspect("16:9".parse().unwrap());
+    /// assert_eq!(filters.apply(results).len(), 1);
+    /// ```
+    pub fn min_width(mut self, width: u32) -> Self {
+        self.min_width = Some(width);
+        self
+    }
+
+    /// Keeps the images at least `height` pixels high (see `min_width`).
+    ///
+    /// # Arguments
+    /// * `height` - The minimum height in pixels.
+    pub fn min_height(mut self, height: u32) -> Self {
+        self.min_height = Some(height);
+        self
+    }
+
+    /// Keeps the images with the given aspect ratio, within 2% (see `min_width`).
+    ///
+    /// # Arguments
+    /// * `aspect` - The ratio of the width to the height, e.g. `16:9`.
+    pub fn aspect(mut self, aspect: AspectRatio) -> Self {
+        self.aspect = Some(aspect);
+        self
+    }
+
+    /// Returns `true` if the filters keep every result.
+    pub fn is_empty(&self) -> bool {
+        self.max_per_domain.is_none() && self.similarity.is_none() && !self.filters_images()
+    }
+
+    /// Returns `true` if any filter on the dimensions of images is set.
+    fn filters_images(&self) -> bool {
+        self.min_width.is_some() || self.min_height.is_some() || self.aspect.is_some()
+    }
+
+    /// Returns `true` if a result passes the filters on the dimensions of images.
+    fn keeps_image(&self, result: &SearchResult) -> bool {
+        let SearchResult::Image(image) = result else {
+            return true;
+        };
+        if image.width == 0 || image.height == 0 {
+            return false;
+        }
+        self.min_width.is_none_or(|min| image.width >= min)
+            && self.min_height.is_none_or(|min| image.height >= min)
+            && self
+                .aspect
+                .is_none_or(|aspect| aspect.matches(image.width, image.height))
+    }
+
+    /// Returns the results the filters keep, in their order: the images not matching the
+    /// dimension filters are dropped first, then the near-duplicates are collapsed, then the
+    /// results exceeding the maximum per domain are dropped.
+    ///
+    /// # Arguments
+    /// * `results` - The results to filter.
+    pub fn apply(&self, results: Vec<SearchResult>) -> Vec<SearchResult> {
+        let results = if self.filters_images() {
+            results
+                .into_iter()
+                .filter(|result| self.keeps_image(result))
+                .collect()
+        } else {
+            results
+        };
+        let results = match self.similarity {
+            Some(similarity) => collapse(results, similarity),
+            None => results,
+        };
+        let Some(max) = self.max_per_domain else {
+            return results;
+        };
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        results
+            .into_iter()
+            .filter(|result| match result.domain() {
+                Some(domain) => {
+                    let domain = domain.trim_start_matches("www.").to_lowercase();
+                    let count = counts.entry(domain).or_default();
+                    *count += 1;
+                    *count <= max
+                }
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Wraps a backend into a `FilteredBackend` applying these filters, unless they keep every
+    /// result.
+    ///
+    /// # Arguments
+    /// * `backend` - The backend to filter the results of.
+    pub fn wrap<'a>(&self, backend: Box<dyn SearchBackend + 'a>) -> Box<dyn SearchBackend + 'a> {
+        if self.is_empty() {
+            backend
+        } else {
+            Box::new(FilteredBackend::new(backend, self.clone()))
+        }
+    }
+}
+
+/// Collapses the near-identical results into the first one of them.
+fn collapse(results: Vec<SearchResult>, similarity: f64) -> Vec<SearchResult> {
+    let mut kept: Vec<(SearchResult, HashSet<String>)> = Vec::new();
+    for result in results {
+        let text = shingles(&format!("{} {}", result.title(), result.snippet()));
+        let original = kept
+            .iter_mut()
+            .find(|(_, kept_text)| jaccard(kept_text, &text) >= similarity);
+        match original {
+            Some((original, _)) => {
+                let also_on = original.also_on_mut();
+                also_on.push(Link::from(result.url()));
+                also_on.extend(result.also_on().iter().cloned());
+            }
+            None => kept.push((result, text)),
+        }
+    }
+    kept.into_iter().map(|(result, _)| result).collect()
+}
+
+/// Returns the sequences of consecutive words of a text, lowercased and without punctuation.
+fn shingles(text: &str) -> HashSet<String> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.len() < SHINGLE_WORDS {
+        return [words.join(" ")]
+            .into_iter()
+            .filter(|shingle| !shingle.is_empty())
+            .collect();
+    }
+    words
+        .windows(SHINGLE_WORDS)
+        .map(|window| window.join(" "))
+        .collect()
+}
+
+/// Returns the share of the shingles two texts have in common, `0.0` if either is empty.
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let common = a.intersection(b).count();
+    common as f64 / (a.len() + b.len() - common) as f64
+}
+
+/// A backend filtering the results of another backend, and fetching the next pages to make up
+/// for the filtered out results when the search has a limit.
+///
+/// # Examples
+/// ```
+/// use anyhow::Result;
+/// use async_trait::async_trait;
+/// use duckduckgo::backend::SearchBackend;
+/// use duckduckgo::filter::{FilteredBackend, ResultFilters};
+/// use duckduckgo::params::SearchParams;
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+///
+/// /// Returns a page of 2 results from the same site, then from another site.
+/// struct TwoPerSite;
+///
+/// #[async_trait]
+/// impl SearchBackend for TwoPerSite {
+///     fn name(&self) -> &str {
+///         "two-per-site"
+///     }
+///
+///     async fn search(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+///         Ok((params.offset..params.offset + 2)
+///             .map(|i| {
+///                 SearchResult::Text(LiteSearchResult {
+///                     url: format!("https://site{}.example/{}", params.offset, i).into(),
+///                     rank: i + 1,
+///                     ..Default::default()
+///                 })
+///             })
+///             .collect())
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<()> {
+///     let filters = ResultFilters::new().max_per_domain(1);
+///     let backend = FilteredBackend::new(Box::new(TwoPerSite), filters);
+///     let results = backend.search(&SearchParams::new("rust").limit(3)).await?;
+///     let ranks: Vec<usize> = results.iter().map(|r| r.rank()).collect();
+///     assert_eq!(ranks, [1, 3, 5]);
+///     Ok(())
+/// }
+/// ```
+pub struct FilteredBackend<'a> {
+    backend: Box<dyn SearchBackend + 'a>,
+    filters: ResultFilters,
+}
+
+impl<'a> FilteredBackend<'a> {
+    /// Creates a backend applying `filters` to the results of `backend`.
+    pub fn new(backend: Box<dyn SearchBackend + 'a>, filters: ResultFilters) -> Self {
+        FilteredBackend { backend, filters }
+    }
+}
+
+#[async_trait]
+impl SearchBackend for FilteredBackend<'_> {
+    fn name(&self) -> &str {
+        self.backend.name()
+    }
+
+    fn plan(&self, params: &SearchParams) -> Result<Vec<PlannedRequest>> {
+        self.backend.plan(params)
+    }
+
+    async fn search(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+        Ok(self.search_page(params).await?.results)
+    }
+
+    /// Filters the first page of results, then, while fewer results than the limit are kept,
+    /// fetches the results following the ones already fetched, up to a few more pages.
+    async fn search_page(&self, params: &SearchParams) -> Result<SearchPage<SearchResult>> {
+        let mut page = self.backend.search_page(params).await?;
+        let mut fetched = page.results.len();
+        let mut results = self.filters.apply(std::mem::take(&mut page.results));
+
+        if let Some(limit) = params.limit {
+            for _ in 0..MAX_BACKFILL_PAGES {
+                if results.len() >= limit || fetched == 0 {
+                    break;
+                }
+                let next = SearchParams {
+                    offset: params.offset + fetched,
+                    ..params.clone()
+                };
+                let more = self.backend.search(&next).await?;
+                if more.is_empty() {
+                    break;
+                }
+                fetched += more.len();
+                results.extend(more);
+                results = self.filters.apply(results);
+            }
+            results.truncate(limit);
+        }
+
+        page.results = results;
+        Ok(page)
+    }
+}
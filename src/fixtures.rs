@@ -0,0 +1,116 @@
+use crate::parser::{parse_html_page, parse_image, parse_lite_page, parse_news};
+use crate::response::{ImageResult, NewsResult, Response, SearchPage};
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+/// A stored DuckDuckGo Lite results page for the misspelled query `"rust langauge"`.
+pub const LITE_HTML: &str = include_str!("../fixtures/lite.html");
+
+/// A stored DuckDuckGo HTML results page for the query `"rust language"`.
+pub const HTML_HTML: &str = include_str!("../fixtures/html.html");
+
+/// A stored `i.js` image search response for the query `"rustacean"`.
+pub const IMAGES_JSON: &str = include_str!("../fixtures/images.json");
+
+/// A stored `news.js` news search response for the query `"rust"`.
+pub const NEWS_JSON: &str = include_str!("../fixtures/news.json");
+
+/// A stored Instant Answer API response for the query `"rust programming language"`.
+pub const API_JSON: &str = include_str!("../fixtures/api.json");
+
+/// A stored "anomaly detected" challenge page, served instead of results to suspected bots.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::fixtures::ANOMALY_HTML;
+/// use duckduckgo::parser::is_bot_challenge;
+///
+/// assert!(is_bot_challenge(ANOMALY_HTML));
+/// ```
+pub const ANOMALY_HTML: &str = include_str!("../fixtures/anomaly.html");
+
+/// Parses the stored DuckDuckGo Lite page.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::fixtures;
+///
+/// let page = fixtures::lite_page().unwrap();
+/// assert_eq!(page.results.len(), 3);
+/// assert_eq!(page.corrected_query.as_deref(), Some("rust language"));
+/// ```
+pub fn lite_page() -> Result<SearchPage> {
+    parse_lite_page(LITE_HTML, None)
+}
+
+/// Parses the stored DuckDuckGo HTML page.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::fixtures;
+///
+/// let page = fixtures::html_page().unwrap();
+/// assert_eq!(page.results[0].url, "https://www.rust-lang.org/");
+/// assert_eq!(page.related_queries.len(), 3);
+/// ```
+pub fn html_page() -> Result<SearchPage> {
+    parse_html_page(HTML_HTML, None)
+}
+
+/// Parses the stored image search response.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::fixtures;
+///
+/// let images = fixtures::images().unwrap();
+/// assert_eq!(images.len(), 3);
+/// assert_eq!(images[0].width, 600);
+/// ```
+pub fn images() -> Result<Vec<ImageResult>> {
+    Ok(results(IMAGES_JSON)?
+        .iter()
+        .enumerate()
+        .map(|(i, item)| parse_image(item, i + 1, 1))
+        .collect())
+}
+
+/// Parses the stored news search response.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::fixtures;
+///
+/// let news = fixtures::news().unwrap();
+/// assert_eq!(news[0].source, "Rust Blog");
+/// ```
+pub fn news() -> Result<Vec<NewsResult>> {
+    Ok(results(NEWS_JSON)?
+        .iter()
+        .enumerate()
+        .map(|(i, item)| parse_news(item, i + 1, 1))
+        .collect())
+}
+
+/// Parses the stored Instant Answer API response, failing on the fields `Response` does not
+/// model (see `Response::from_json_strict`).
+///
+/// # Examples
+/// ```
+/// use duckduckgo::fixtures;
+///
+/// let response = fixtures::api_response().unwrap();
+/// assert_eq!(response.related_topics.len(), 2);
+/// ```
+pub fn api_response() -> Result<Response> {
+    Response::from_json_strict(API_JSON).context("Failed to parse Instant Answer fixture")
+}
+
+/// Extracts the `results` array of a stored `i.js`/`news.js` response.
+fn results(json: &str) -> Result<Vec<Value>> {
+    let j: Value = serde_json::from_str(json).context("Failed to parse fixture")?;
+    j.get("results")
+        .and_then(|r| r.as_array())
+        .cloned()
+        .context("Missing results in fixture")
+}
@@ -0,0 +1,444 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// An HTTP Archive (HAR 1.2) file capturing the HTTP traffic of a `Browser`, or replaying it.
+///
+/// When capturing, every request sent by the browser, to DuckDuckGo or to the sites of the
+/// results, is appended to the archive with its response, and the file is rewritten after each
+/// response, so that it can be opened in the network panel of browser devtools even if the run
+/// fails. When replaying, requests are answered from the archive, matched by method, URL, and
+/// body, so the parsers can be run again on captured traffic without network access. Archives
+/// exported by browsers can be replayed too, as long as their bodies are not base64-encoded.
+///
+/// # Examples
+/// ```no_run
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::har::HarArchive;
+///
+/// let capturing = Browser::builder()
+///     .har(HarArchive::capture("search.har"))
+///     .build()
+///     .unwrap();
+///
+/// let replaying = Browser::builder()
+///     .har(HarArchive::replay("search.har").unwrap())
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct HarArchive {
+    path: PathBuf,
+    replay: bool,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    log: Log,
+    /// Whether each entry has answered a request, when replaying.
+    used: Vec<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Har {
+    log: Log,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Log {
+    version: String,
+    creator: Creator,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Creator {
+    name: String,
+    version: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    started_date_time: String,
+    /// The total time of the request, in milliseconds.
+    time: f64,
+    request: Request,
+    response: Response,
+    #[serde(default)]
+    cache: serde_json::Value,
+    #[serde(default)]
+    timings: Timings,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Request {
+    method: String,
+    url: String,
+    #[serde(default)]
+    http_version: String,
+    #[serde(default)]
+    headers: Vec<NameValue>,
+    #[serde(default)]
+    query_string: Vec<NameValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    post_data: Option<PostData>,
+    #[serde(default = "unknown_size")]
+    headers_size: i64,
+    #[serde(default = "unknown_size")]
+    body_size: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Response {
+    status: u16,
+    #[serde(default)]
+    status_text: String,
+    #[serde(default)]
+    http_version: String,
+    #[serde(default)]
+    headers: Vec<NameValue>,
+    content: Content,
+    #[serde(default, rename = "redirectURL")]
+    redirect_url: String,
+    #[serde(default = "unknown_size")]
+    headers_size: i64,
+    #[serde(default = "unknown_size")]
+    body_size: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NameValue {
+    name: String,
+    value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PostData {
+    #[serde(default)]
+    mime_type: String,
+    #[serde(default)]
+    text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Content {
+    #[serde(default)]
+    size: i64,
+    #[serde(default)]
+    mime_type: String,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encoding: Option<String>,
+}
+
+/// The phases of a request, in milliseconds; only the wait for the response is measured.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Timings {
+    #[serde(default)]
+    send: f64,
+    #[serde(default)]
+    wait: f64,
+    #[serde(default)]
+    receive: f64,
+}
+
+/// The size of headers and bodies HAR files use when it is unknown.
+fn unknown_size() -> i64 {
+    -1
+}
+
+impl HarArchive {
+    /// Creates an archive capturing the traffic into `path`, written on the first response.
+    pub fn capture(path: impl Into<PathBuf>) -> Self {
+        HarArchive {
+            path: path.into(),
+            replay: false,
+            state: Mutex::new(State {
+                log: Log {
+                    version: String::from("1.2"),
+                    creator: Creator {
+                        name: String::from(env!("CARGO_PKG_NAME")),
+                        version: String::from(env!("CARGO_PKG_VERSION")),
+                    },
+                    entries: Vec::new(),
+                },
+                used: Vec::new(),
+            }),
+        }
+    }
+
+    /// Loads an archive to answer requests from.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or is not a HAR file.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read HAR file {}", path.display()))?;
+        let har: Har = serde_json::from_str(&text)
+            .with_context(|| format!("Failed to parse HAR file {}", path.display()))?;
+        Ok(HarArchive {
+            path,
+            replay: true,
+            state: Mutex::new(State {
+                used: vec![false; har.log.entries.len()],
+                log: har.log,
+            }),
+        })
+    }
+
+    /// Returns the path of the HAR file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns whether requests are answered from the archive instead of captured into it.
+    pub fn is_replay(&self) -> bool {
+        self.replay
+    }
+
+    /// Returns the number of requests in the archive.
+    pub fn len(&self) -> usize {
+        self.state().log.entries.len()
+    }
+
+    /// Returns whether the archive has no requests.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Answers a request from the archive.
+    ///
+    /// Requests sent several times are answered by their captures in order, the last one
+    /// answering the extra requests.
+    ///
+    /// # Errors
+    /// Returns an error if the archive has no entry for the request.
+    pub fn load(&self, request: &reqwest::Request) -> Result<reqwest::Response> {
+        let body = request_body(request);
+        let mut state = self.state();
+        let State { log, used } = &mut *state;
+        let matches: Vec<usize> = log
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                entry
+                    .request
+                    .method
+                    .eq_ignore_ascii_case(request.method().as_str())
+                    && entry.request.url == request.url().as_str()
+                    && entry
+                        .request
+                        .post_data
+                        .as_ref()
+                        .map_or("", |data| &data.text)
+                        == body
+            })
+            .map(|(index, _)| index)
+            .collect();
+        let Some(&index) = matches
+            .iter()
+            .find(|index| !used[**index])
+            .or(matches.last())
+        else {
+            bail!(
+                "No captured response for {} {} in {}",
+                request.method(),
+                request.url(),
+                self.path.display()
+            );
+        };
+        used[index] = true;
+
+        let response = &log.entries[index].response;
+        if response.content.encoding.is_some() {
+            bail!(
+                "The captured response for {} is encoded, which is not supported",
+                request.url()
+            );
+        }
+        let mut builder = http::Response::builder().status(response.status);
+        for header in &response.headers {
+            // The body is stored decoded and may have been truncated.
+            let name = header.name.to_ascii_lowercase();
+            if name != "content-length" && name != "content-encoding" {
+                builder = builder.header(&header.name, &header.value);
+            }
+        }
+        let text = response.content.text.clone().unwrap_or_default();
+        Ok(builder.body(text)?.into())
+    }
+
+    /// Appends a request and its response to the archive and rewrites the HAR file.
+    ///
+    /// The body is consumed while capturing, so an equivalent response is returned.
+    ///
+    /// # Arguments
+    /// * `request` - The request sent.
+    /// * `response` - The response received.
+    /// * `started` - When the request was sent.
+    /// * `elapsed` - The time between sending the request and receiving the response headers.
+    pub async fn save(
+        &self,
+        request: &reqwest::Request,
+        response: reqwest::Response,
+        started: SystemTime,
+        elapsed: Duration,
+    ) -> Result<reqwest::Response> {
+        let status = response.status();
+        let version = response.version();
+        let headers = response.headers().clone();
+        let body = response.text().await?;
+        let wait = elapsed.as_secs_f64() * 1000.0;
+
+        let request_content_type = request
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        let post_data = request.body().map(|_| PostData {
+            mime_type: request_content_type.to_string(),
+            text: request_body(request).to_string(),
+        });
+        let entry = Entry {
+            started_date_time: iso8601(started),
+            time: wait,
+            request: Request {
+                method: request.method().to_string(),
+                url: request.url().to_string(),
+                http_version: String::from("HTTP/1.1"),
+                headers: name_values(request.headers()),
+                query_string: request
+                    .url()
+                    .query_pairs()
+                    .map(|(name, value)| NameValue {
+                        name: name.into_owned(),
+                        value: value.into_owned(),
+                    })
+                    .collect(),
+                body_size: post_data.as_ref().map_or(0, |data| data.text.len() as i64),
+                post_data,
+                headers_size: -1,
+            },
+            response: Response {
+                status: status.as_u16(),
+                status_text: status.canonical_reason().unwrap_or_default().to_string(),
+                http_version: format!("{:?}", version),
+                headers: name_values(&headers),
+                content: Content {
+                    size: body.len() as i64,
+                    mime_type: headers
+                        .get(reqwest::header::CONTENT_TYPE)
+                        .and_then(|value| value.to_str().ok())
+                        .unwrap_or_default()
+                        .to_string(),
+                    text: Some(body.clone()),
+                    encoding: None,
+                },
+                redirect_url: headers
+                    .get(reqwest::header::LOCATION)
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string(),
+                headers_size: -1,
+                body_size: body.len() as i64,
+            },
+            cache: serde_json::Value::Object(Default::default()),
+            timings: Timings {
+                send: 0.0,
+                wait,
+                receive: 0.0,
+            },
+        };
+
+        {
+            let mut state = self.state();
+            state.log.entries.push(entry);
+            let har = serde_json::json!({ "log": &state.log });
+            if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                fs::create_dir_all(dir)
+                    .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+            }
+            fs::write(&self.path, serde_json::to_string_pretty(&har)?)
+                .with_context(|| format!("Failed to write HAR file {}", self.path.display()))?;
+        }
+
+        let mut builder = http::Response::builder().status(status);
+        for (name, value) in &headers {
+            builder = builder.header(name, value);
+        }
+        Ok(builder.body(body)?.into())
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Returns the body of a request as text, or an empty string if it has none.
+fn request_body(request: &reqwest::Request) -> &str {
+    request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .and_then(|bytes| std::str::from_utf8(bytes).ok())
+        .unwrap_or_default()
+}
+
+/// Converts headers into HAR name/value pairs, skipping the values that are not text.
+fn name_values(headers: &reqwest::header::HeaderMap) -> Vec<NameValue> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            Some(NameValue {
+                name: name.to_string(),
+                value: value.to_str().ok()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Formats a time as an ISO 8601 date and time in UTC, with milliseconds.
+fn iso8601(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+    let (days, seconds_of_day) = (seconds / 86_400, seconds % 86_400);
+
+    // The civil date of a number of days since 1970-01-01 (Howard Hinnant's algorithm).
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
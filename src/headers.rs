@@ -0,0 +1,153 @@
+/// A set of request headers consistent with a browser family.
+///
+/// Sending a Chrome user agent without the client hints Chrome always sends is an easy
+/// fingerprinting tell, so `Browser::request` completes the user agent with the headers of the
+/// matching profile.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::headers::HeaderProfile;
+///
+/// let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) \
+///           Chrome/120.0.0.0 Safari/537.36";
+/// let profile = HeaderProfile::detect(ua);
+/// assert_eq!(profile, HeaderProfile::Chrome);
+///
+/// let headers = profile.headers(ua);
+/// assert!(headers.contains(&("sec-ch-ua-platform", String::from("\"Windows\""))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeaderProfile {
+    /// Google Chrome and other Chromium browsers.
+    Chrome,
+    /// Microsoft Edge (Chromium).
+    Edge,
+    /// Mozilla Firefox.
+    Firefox,
+    /// Apple Safari.
+    Safari,
+    /// Any other or legacy browser: only the basic headers are sent.
+    Generic,
+}
+
+/// The `Accept` header sent by browsers for the XHR requests of the DuckDuckGo pages.
+const XHR_ACCEPT: &str = "application/json, text/javascript, */*; q=0.01";
+
+impl HeaderProfile {
+    /// Detects the profile matching a user agent string.
+    ///
+    /// Browser versions predating client hints and fetch metadata fall back to `Generic`.
+    pub fn detect(user_agent: &str) -> Self {
+        if let Some(version) = major_version(user_agent, "Edg/") {
+            if version >= 89 {
+                return HeaderProfile::Edge;
+            }
+        } else if let Some(version) = major_version(user_agent, "Chrome/") {
+            if version >= 89 {
+                return HeaderProfile::Chrome;
+            }
+        } else if let Some(version) = major_version(user_agent, "Firefox/") {
+            if version >= 90 {
+                return HeaderProfile::Firefox;
+            }
+        } else if user_agent.contains("Safari/")
+            && major_version(user_agent, "Version/").is_some_and(|v| v >= 17)
+        {
+            return HeaderProfile::Safari;
+        }
+
+        HeaderProfile::Generic
+    }
+
+    /// Returns the headers to send along with `user_agent` for this profile.
+    ///
+    /// # Arguments
+    /// * `user_agent` - The user agent the headers must be consistent with.
+    pub fn headers(&self, user_agent: &str) -> Vec<(&'static str, String)> {
+        let mut headers = Vec::new();
+
+        match self {
+            HeaderProfile::Chrome | HeaderProfile::Edge => {
+                let version = major_version(user_agent, "Chrome/").unwrap_or_default();
+                let brand = if *self == HeaderProfile::Edge {
+                    "Microsoft Edge"
+                } else {
+                    "Google Chrome"
+                };
+
+                headers.push(("Accept", XHR_ACCEPT.to_string()));
+                headers.push(("Accept-Language", "en-US,en;q=0.9".to_string()));
+                headers.push((
+                    "sec-ch-ua",
+                    format!(
+                        "\"Chromium\";v=\"{version}\", \"{brand}\";v=\"{version}\", \
+                         \"Not?A_Brand\";v=\"99\""
+                    ),
+                ));
+                headers.push((
+                    "sec-ch-ua-mobile",
+                    if user_agent.contains("Mobile") {
+                        "?1"
+                    } else {
+                        "?0"
+                    }
+                    .to_string(),
+                ));
+                headers.push((
+                    "sec-ch-ua-platform",
+                    format!("\"{}\"", platform(user_agent)),
+                ));
+                push_fetch_metadata(&mut headers);
+            }
+            HeaderProfile::Firefox => {
+                headers.push(("Accept", XHR_ACCEPT.to_string()));
+                headers.push(("Accept-Language", "en-US,en;q=0.5".to_string()));
+                push_fetch_metadata(&mut headers);
+            }
+            HeaderProfile::Safari => {
+                headers.push(("Accept", XHR_ACCEPT.to_string()));
+                headers.push(("Accept-Language", "en-US,en;q=0.9".to_string()));
+                push_fetch_metadata(&mut headers);
+            }
+            HeaderProfile::Generic => {
+                headers.push(("Accept", "application/json".to_string()));
+                headers.push(("Accept-Language", "en-US,en;q=0.9".to_string()));
+            }
+        }
+
+        headers
+    }
+}
+
+/// Adds the fetch metadata headers of a same-origin XHR request.
+fn push_fetch_metadata(headers: &mut Vec<(&'static str, String)>) {
+    headers.push(("Sec-Fetch-Dest", "empty".to_string()));
+    headers.push(("Sec-Fetch-Mode", "cors".to_string()));
+    headers.push(("Sec-Fetch-Site", "same-origin".to_string()));
+}
+
+/// Extracts the major version following `token` (e.g. `"Chrome/"`) in a user agent.
+fn major_version(user_agent: &str, token: &str) -> Option<u32> {
+    let (_, rest) = user_agent.split_once(token)?;
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+/// Returns the `sec-ch-ua-platform` value matching a user agent.
+fn platform(user_agent: &str) -> &'static str {
+    if user_agent.contains("Windows") {
+        "Windows"
+    } else if user_agent.contains("Android") {
+        "Android"
+    } else if user_agent.contains("CrOS") {
+        "Chrome OS"
+    } else if user_agent.contains("iPhone") || user_agent.contains("iPad") {
+        "iOS"
+    } else if user_agent.contains("Macintosh") {
+        "macOS"
+    } else if user_agent.contains("Linux") {
+        "Linux"
+    } else {
+        "Unknown"
+    }
+}
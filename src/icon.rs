@@ -1,42 +1,87 @@
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 use serde_json::Value;
 
 /// A struct representing an icon associated with a DuckDuckGo search result.
+///
+/// The relative URLs returned by the Instant Answer API are resolved against
+/// `https://duckduckgo.com/` when deserialized. The API sends the dimensions as numbers, numeric
+/// strings, or empty strings when unknown, which are deserialized as `None`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::icon::Icon;
+///
+/// let icon: Icon =
+///     serde_json::from_str(r#"{"Height": "", "URL": "/i/rust.png", "Width": "64"}"#).unwrap();
+/// assert_eq!(icon.url, "https://duckduckgo.com/i/rust.png");
+/// assert_eq!(icon.height, None);
+/// assert_eq!(icon.width, Some(64));
+/// ```
 #[derive(Debug, Deserialize)]
 pub struct Icon {
-    /// The height of the icon.
-    #[serde(rename = "Height")]
-    pub height: Value,
+    /// The height of the icon in pixels, if known.
+    #[serde(rename = "Height", default, deserialize_with = "deserialize_dimension")]
+    pub height: Option<u32>,
 
-    /// The URL pointing to the icon image.
-    #[serde(rename = "URL")]
+    /// The absolute URL pointing to the icon image, empty if there is no icon.
+    #[serde(
+        rename = "URL",
+        deserialize_with = "crate::link::deserialize_absolute_url"
+    )]
     pub url: String,
 
-    /// The width of the icon.
-    #[serde(rename = "Width")]
-    pub width: Value,
+    /// The width of the icon in pixels, if known.
+    #[serde(rename = "Width", default, deserialize_with = "deserialize_dimension")]
+    pub width: Option<u32>,
 }
 
 impl Icon {
     /// Creates a new instance of `Icon` with the specified height, URL, and width.
     ///
     /// # Arguments
-    /// * `height` - The height of the icon.
+    /// * `height` - The height of the icon in pixels, if known.
     /// * `url` - The URL pointing to the icon image.
-    /// * `width` - The width of the icon.
+    /// * `width` - The width of the icon in pixels, if known.
     ///
     /// # Examples
     /// ```
-    /// use serde_json::Value;
     /// use duckduckgo::icon::Icon;
     ///
-    /// let icon = Icon {
-    ///     height: Value::Number(10.into()),
-    ///     url: String::from("https://example.com/icon.png"),
-    ///     width: Value::Number(20.into()),
-    /// };
+    /// let icon = Icon::new(Some(10), String::from("https://example.com/icon.png"), Some(20));
     /// ```
-    pub fn new(height: Value, url: String, width: Value) -> Self {
+    pub fn new(height: Option<u32>, url: String, width: Option<u32>) -> Self {
         Icon { height, url, width }
     }
 }
+
+/// Deserializes a dimension in pixels sent as a number, a numeric string, or an empty string,
+/// as `None` when it is empty or not a valid dimension.
+pub(crate) fn deserialize_dimension<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<u32>, D::Error> {
+    let dimension = match Value::deserialize(deserializer)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    };
+    Ok(dimension
+        .filter(|d| d.is_finite() && *d >= 0.0 && *d <= f64::from(u32::MAX))
+        .map(|d| d.round() as u32))
+}
+
+/// Deserializes a flag sent as a boolean, `0` or `1`, or a string of those, as `None` when it
+/// is empty or not a valid flag.
+pub(crate) fn deserialize_flag<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<bool>, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::Bool(b) => Some(b),
+        Value::Number(n) => n.as_f64().map(|n| n != 0.0),
+        Value::String(s) => match s.trim() {
+            "1" | "true" => Some(true),
+            "0" | "false" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    })
+}
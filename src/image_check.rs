@@ -0,0 +1,177 @@
+use serde::Serialize;
+use std::fmt;
+
+/// The outcome of verifying the file of an image result with `Browser::verify_images`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::image_check::ImageCheck;
+///
+/// let check = ImageCheck {
+///     status: Some(200),
+///     content_type: Some(String::from("image/png")),
+///     bytes: Some(48_213),
+///     error: None,
+/// };
+/// assert!(!check.is_dead());
+/// assert_eq!(check.to_string(), "image/png, 48.2 kB");
+///
+/// let page = ImageCheck {
+///     status: Some(200),
+///     content_type: Some(String::from("text/html; charset=utf-8")),
+///     ..Default::default()
+/// };
+/// assert!(page.is_dead());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ImageCheck {
+    /// The HTTP status code of the final response, or `None` if the request failed.
+    pub status: Option<u16>,
+    /// The media type of the file (e.g. `"image/png"`), if the server sent one.
+    pub content_type: Option<String>,
+    /// The size of the file in bytes, if the server sent it.
+    pub bytes: Option<u64>,
+    /// Why the request failed (e.g. a DNS, TLS, or timeout error), if it did.
+    pub error: Option<String>,
+}
+
+impl ImageCheck {
+    /// Returns `true` if the image is unusable: unreachable, gone (404 or 410), failing with a
+    /// server error, or served as something other than an image.
+    pub fn is_dead(&self) -> bool {
+        let status_dead = match self.status {
+            Some(status) => status == 404 || status == 410 || status >= 500,
+            None => true,
+        };
+        let not_image = self
+            .content_type
+            .as_deref()
+            .is_some_and(|content_type| !content_type.trim().starts_with("image/"));
+        status_dead || not_image
+    }
+}
+
+impl fmt::Display for ImageCheck {
+    /// Formats the check as the media type and size of the file, or as the error.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(error) = &self.error {
+            return write!(f, "unreachable ({})", error);
+        }
+        let mut parts = Vec::new();
+        if let Some(content_type) = &self.content_type {
+            parts.push(content_type.clone());
+        }
+        if let Some(bytes) = self.bytes {
+            parts.push(format_bytes(bytes));
+        }
+        if let Some(status) = self.status.filter(|status| !(200..300).contains(status)) {
+            parts.push(format!("status {}", status));
+        }
+        if parts.is_empty() {
+            return write!(f, "unknown");
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Formats a number of bytes with a decimal unit, e.g. `48.2 kB`.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["kB", "MB", "GB"];
+    if bytes < 1000 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64;
+    let mut unit = "B";
+    for next in UNITS {
+        if size < 1000.0 {
+            break;
+        }
+        size /= 1000.0;
+        unit = next;
+    }
+    format!("{:.1} {}", size, unit)
+}
+
+/// Reads the width and height of an image from the first bytes of its file.
+///
+/// The PNG, GIF, JPEG, WebP, and BMP formats are recognized. The header of most files fits in
+/// their first few kilobytes, but the dimensions of JPEG files come after their metadata, which
+/// can be larger.
+///
+/// # Arguments
+/// * `bytes` - The start of the file.
+///
+/// # Returns
+/// The width and height in pixels, or `None` if the format is not recognized or the bytes end
+/// before the dimensions.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::image_check::image_dimensions;
+///
+/// let gif = b"GIF89a\x40\x01\xf0\x00";
+/// assert_eq!(image_dimensions(gif), Some((320, 240)));
+/// assert_eq!(image_dimensions(b"<!doctype html>"), None);
+/// ```
+pub fn image_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let u16_be = |at: usize| Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?));
+    let u16_le = |at: usize| Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?));
+    let u24_le = |at: usize| {
+        let b = bytes.get(at..at + 3)?;
+        Some(u32::from(b[0]) | u32::from(b[1]) << 8 | u32::from(b[2]) << 16)
+    };
+    let u32_be = |at: usize| Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?));
+    let i32_le = |at: usize| Some(i32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?));
+
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some((u32_be(16)?, u32_be(20)?));
+    }
+    if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        return Some((u32::from(u16_le(6)?), u32::from(u16_le(8)?)));
+    }
+    if bytes.starts_with(b"BM") {
+        return Some((i32_le(18)?.unsigned_abs(), i32_le(22)?.unsigned_abs()));
+    }
+    if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        return match bytes.get(12..16)? {
+            b"VP8 " => Some((
+                u32::from(u16_le(26)? & 0x3fff),
+                u32::from(u16_le(28)? & 0x3fff),
+            )),
+            b"VP8L" => {
+                let b = bytes.get(21..25)?;
+                let width = 1 + (u32::from(b[0]) | (u32::from(b[1]) & 0x3f) << 8);
+                let height = 1
+                    + (u32::from(b[1]) >> 6
+                        | u32::from(b[2]) << 2
+                        | (u32::from(b[3]) & 0x0f) << 10);
+                Some((width, height))
+            }
+            b"VP8X" => Some((1 + u24_le(24)?, 1 + u24_le(27)?)),
+            _ => None,
+        };
+    }
+    if bytes.starts_with(b"\xff\xd8") {
+        let mut at = 2;
+        loop {
+            while *bytes.get(at)? != 0xff {
+                at += 1;
+            }
+            while *bytes.get(at)? == 0xff {
+                at += 1;
+            }
+            let marker = *bytes.get(at)?;
+            at += 1;
+            match marker {
+                0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                    return Some((u32::from(u16_be(at + 5)?), u32::from(u16_be(at + 3)?)));
+                }
+                0xd0..=0xd9 | 0x01 => {}
+                _ => at += usize::from(u16_be(at)?),
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,170 @@
+use crate::response::SearchResult;
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::env;
+use std::process::Command;
+
+/// Formats a result as a row of a rofi script mode: the title, followed by the URL as the row
+/// info, which rofi hands back in `ROFI_INFO` when the row is selected.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::launcher::rofi_row;
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+///
+/// let result = SearchResult::Text(LiteSearchResult {
+///     title: String::from("Rust"),
+///     url: "https://www.rust-lang.org/".into(),
+///     ..Default::default()
+/// });
+/// assert_eq!(rofi_row(&result), "Rust\0info\x1fhttps://www.rust-lang.org/");
+/// ```
+pub fn rofi_row(result: &SearchResult) -> String {
+    format!(
+        "{}\0info\x1f{}",
+        single_line(result.title()),
+        single_line(result.url())
+    )
+}
+
+/// Formats a result as a line for dmenu: the title and the URL, separated by `" | "`, so the URL
+/// of the selected line can be extracted with e.g. `sed 's/.* | //'`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::launcher::dmenu_line;
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+///
+/// let result = SearchResult::Text(LiteSearchResult {
+///     title: String::from("Rust"),
+///     url: "https://www.rust-lang.org/".into(),
+///     ..Default::default()
+/// });
+/// assert_eq!(dmenu_line(&result), "Rust | https://www.rust-lang.org/");
+/// ```
+pub fn dmenu_line(result: &SearchResult) -> String {
+    format!(
+        "{} | {}",
+        single_line(result.title()),
+        single_line(result.url())
+    )
+}
+
+/// The JSON document of an Alfred script filter.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::launcher::AlfredOutput;
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+///
+/// let results = vec![SearchResult::Text(LiteSearchResult {
+///     title: String::from("Rust"),
+///     url: "https://www.rust-lang.org/".into(),
+///     ..Default::default()
+/// })];
+/// let output = AlfredOutput::new(&results);
+/// assert_eq!(output.items[0].arg, "https://www.rust-lang.org/");
+/// assert_eq!(output.items[0].subtitle, "https://www.rust-lang.org/");
+/// ```
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AlfredOutput {
+    /// The items listed by Alfred, one per result.
+    pub items: Vec<AlfredItem>,
+}
+
+/// An item of an Alfred script filter.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AlfredItem {
+    /// The unique identifier Alfred uses to learn which items are picked, the result URL.
+    pub uid: String,
+    /// The title of the result.
+    pub title: String,
+    /// The snippet of the result, or its URL if it has none.
+    pub subtitle: String,
+    /// The URL passed to the next action when the item is selected.
+    pub arg: String,
+    /// The URL shown by Quick Look: the image for images, the result URL otherwise.
+    pub quicklookurl: String,
+}
+
+impl AlfredOutput {
+    /// Creates the script filter document listing the given results.
+    ///
+    /// # Arguments
+    /// * `results` - The search results, in the order they are listed.
+    pub fn new(results: &[SearchResult]) -> Self {
+        let items = results
+            .iter()
+            .map(|result| {
+                let url = result.url().to_string();
+                let snippet = single_line(result.snippet().trim());
+                AlfredItem {
+                    uid: url.clone(),
+                    title: single_line(result.title()),
+                    subtitle: if snippet.is_empty() {
+                        url.clone()
+                    } else {
+                        snippet
+                    },
+                    arg: url.clone(),
+                    quicklookurl: match result {
+                        SearchResult::Image(image) => image.image.to_string(),
+                        _ => url,
+                    },
+                }
+            })
+            .collect();
+        AlfredOutput { items }
+    }
+}
+
+/// Opens a URL in the default web browser, without waiting for the browser to exit.
+///
+/// The browser is the first command of `$BROWSER` if set, or else the opener of the desktop:
+/// `open` on macOS, the URL protocol handler on Windows, and `xdg-open` elsewhere.
+///
+/// # Arguments
+/// * `url` - The URL to open, which must be an `http` or `https` URL.
+///
+/// # Errors
+/// Fails if the URL is not a web URL, or if the browser cannot be started.
+pub fn open_url(url: &str) -> Result<()> {
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        bail!("Refusing to open '{}', which is not a web URL", url);
+    }
+
+    let browser = env::var("BROWSER").ok().and_then(|browser| {
+        browser
+            .split(':')
+            .map(str::trim)
+            .find(|command| !command.is_empty())
+            .map(String::from)
+    });
+    let mut command = match browser {
+        Some(browser) => Command::new(browser),
+        None if cfg!(target_os = "macos") => Command::new("open"),
+        None if cfg!(windows) => {
+            let mut command = Command::new("rundll32");
+            command.arg("url.dll,FileProtocolHandler");
+            command
+        }
+        None => Command::new("xdg-open"),
+    };
+    command
+        .arg(url)
+        .spawn()
+        .with_context(|| format!("Failed to open {} in the browser", url))?;
+    Ok(())
+}
+
+/// Replaces the line breaks, tabs, and the separators of rofi rows with spaces.
+fn single_line(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\n' | '\r' | '\t' | '\0' | '\x1f' => ' ',
+            c => c,
+        })
+        .collect()
+}
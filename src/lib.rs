@@ -1,10 +1,70 @@
 #![doc = include_str!("../README.md")]
 
+// DuckDuckGo is only served over HTTPS, so a TLS backend is required.
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+compile_error!("either the `native-tls` or the `rustls` feature must be enabled");
+
+pub mod accounting;
+pub mod alias;
+pub mod analysis;
+pub mod backend;
+pub mod breaker;
 pub mod browser;
+pub mod checkpoint;
 #[cfg(feature = "cli")]
 pub mod cli;
 pub mod colors;
+pub mod compare;
+pub mod content;
+pub mod cookies;
+pub mod curl;
+#[cfg(feature = "news")]
+pub mod date;
+pub mod doctor;
+pub mod download;
+pub mod dump;
+pub mod endpoints;
+pub mod error;
+#[cfg(any(feature = "sqlite", feature = "parquet", feature = "cli"))]
+pub mod exporter;
+pub mod favicon;
+pub mod filter;
+#[cfg(feature = "test-util")]
+pub mod fixtures;
+pub mod har;
+pub mod headers;
 pub mod icon;
+pub mod image_check;
+pub mod launcher;
+pub mod link;
+pub mod merge;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod middleware;
+#[cfg(feature = "test-util")]
+pub mod mock;
+pub mod operators;
+pub mod params;
+pub mod parser;
+pub mod plan;
+pub mod preset;
+pub mod proxy;
+pub mod ratelimit;
+pub mod render;
+#[cfg(all(feature = "content", feature = "html", feature = "lite"))]
+pub mod research;
 pub mod response;
+#[cfg(feature = "cli")]
+pub mod schedule;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod stability;
+pub mod template;
+pub mod throttle;
+pub mod timings;
 pub mod topic;
+pub mod tor;
+#[cfg(feature = "self-update")]
+pub mod update;
 pub mod user_agents;
+pub mod vcr;
@@ -0,0 +1,256 @@
+use reqwest::Url;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// The origin the relative URLs returned by the Instant Answer API are relative to.
+pub const DUCKDUCKGO_ORIGIN: &str = "https://duckduckgo.com/";
+
+/// Resolves a URL relative to DuckDuckGo, such as the `/i/<hash>.png` images and icons of the
+/// Instant Answer API, into an absolute URL.
+///
+/// Absolute URLs are returned unchanged, and so are empty strings, which the API uses for
+/// missing images.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::link::absolute_url;
+///
+/// assert_eq!(absolute_url("/i/rust.png"), "https://duckduckgo.com/i/rust.png");
+/// assert_eq!(absolute_url("https://example.com/a.png"), "https://example.com/a.png");
+/// assert_eq!(absolute_url(""), "");
+/// ```
+pub fn absolute_url(raw: &str) -> String {
+    if raw.is_empty() {
+        return String::new();
+    }
+    Url::parse(DUCKDUCKGO_ORIGIN)
+        .and_then(|origin| origin.join(raw))
+        .map(String::from)
+        .unwrap_or_else(|_| raw.to_string())
+}
+
+/// Deserializes a URL relative to DuckDuckGo as an absolute URL (see `absolute_url`).
+pub(crate) fn deserialize_absolute_url<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    Ok(absolute_url(&String::deserialize(deserializer)?))
+}
+
+/// Deserializes an optional URL relative to DuckDuckGo as an absolute URL (see
+/// `absolute_url`).
+pub(crate) fn deserialize_absolute_url_opt<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Ok(Option::<String>::deserialize(deserializer)?.map(|raw| absolute_url(&raw)))
+}
+
+/// A URL found in a search result.
+///
+/// DuckDuckGo occasionally returns relative, empty, or otherwise malformed URLs. Instead of
+/// dropping them, a `Link` keeps the raw string, available with `as_str`, next to the parsed
+/// `Url`, available with `url` when the string is a valid absolute URL.
+///
+/// A `Link` is serialized as its raw string, and parsed again when deserialized.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::link::Link;
+///
+/// let link = Link::new("https://www.rust-lang.org/learn");
+/// assert_eq!(link.url().unwrap().path(), "/learn");
+/// assert_eq!(link.domain(), Some("www.rust-lang.org"));
+/// assert_eq!(link, "https://www.rust-lang.org/learn");
+///
+/// let malformed = Link::new("/i/logo.png");
+/// assert!(malformed.url().is_none());
+/// assert_eq!(malformed.as_str(), "/i/logo.png");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Link {
+    raw: String,
+    url: Option<Url>,
+}
+
+impl Link {
+    /// Creates a `Link` from a raw string, parsing it as an absolute URL.
+    ///
+    /// # Arguments
+    /// * `raw` - The URL as found in the search result.
+    pub fn new(raw: impl Into<String>) -> Self {
+        let raw = raw.into();
+        let url = Url::parse(&raw).ok();
+        Link { raw, url }
+    }
+
+    /// Returns the raw string, whether or not it is a valid URL.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the parsed URL, or `None` if the raw string is not a valid absolute URL.
+    pub fn url(&self) -> Option<&Url> {
+        self.url.as_ref()
+    }
+
+    /// Returns `true` if the raw string is a valid absolute URL.
+    pub fn is_valid(&self) -> bool {
+        self.url.is_some()
+    }
+
+    /// Returns the host of the URL (e.g. `"www.rust-lang.org"`), if it is valid and has one.
+    pub fn domain(&self) -> Option<&str> {
+        self.url.as_ref().and_then(Url::host_str)
+    }
+
+    /// Returns `true` if the raw string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty()
+    }
+}
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl AsRef<str> for Link {
+    fn as_ref(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl From<String> for Link {
+    fn from(raw: String) -> Self {
+        Link::new(raw)
+    }
+}
+
+impl From<&str> for Link {
+    fn from(raw: &str) -> Self {
+        Link::new(raw)
+    }
+}
+
+impl From<Url> for Link {
+    fn from(url: Url) -> Self {
+        Link {
+            raw: url.to_string(),
+            url: Some(url),
+        }
+    }
+}
+
+impl PartialEq<str> for Link {
+    fn eq(&self, other: &str) -> bool {
+        self.raw == other
+    }
+}
+
+impl PartialEq<&str> for Link {
+    fn eq(&self, other: &&str) -> bool {
+        self.raw == *other
+    }
+}
+
+impl Serialize for Link {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for Link {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Link::new(String::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Link {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Link".into()
+    }
+
+    /// Links are serialized as their URL, which may be relative or invalid.
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "type": "string" })
+    }
+}
+
+#[cfg(feature = "server")]
+impl utoipa::PartialSchema for Link {
+    /// Links are serialized as their URL, which may be relative or invalid.
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        utoipa::openapi::ObjectBuilder::new()
+            .schema_type(utoipa::openapi::schema::Type::String)
+            .into()
+    }
+}
+
+#[cfg(feature = "server")]
+impl utoipa::ToSchema for Link {}
+
+/// The outcome of checking that the URL of a search result still works.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::link::LinkCheck;
+///
+/// let moved = LinkCheck {
+///     status: Some(200),
+///     redirect: Some(String::from("https://www.rust-lang.org/")),
+///     error: None,
+/// };
+/// assert!(!moved.is_dead());
+/// assert_eq!(moved.to_string(), "200, redirected to https://www.rust-lang.org/");
+///
+/// let gone = LinkCheck {
+///     status: Some(404),
+///     ..Default::default()
+/// };
+/// assert!(gone.is_dead());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct LinkCheck {
+    /// The HTTP status code of the final response, or `None` if the request failed.
+    pub status: Option<u16>,
+    /// The URL the link redirected to, if it redirected.
+    pub redirect: Option<String>,
+    /// Why the request failed (e.g. a DNS, TLS, or timeout error), if it did.
+    pub error: Option<String>,
+}
+
+impl LinkCheck {
+    /// Returns `true` if the link is dead: unreachable, gone (404 or 410), or failing with a
+    /// server error.
+    ///
+    /// Other client errors, such as 403 or 429, usually come from bot protections rather than
+    /// missing pages, so they do not make a link dead.
+    pub fn is_dead(&self) -> bool {
+        match self.status {
+            Some(status) => status == 404 || status == 410 || status >= 500,
+            None => true,
+        }
+    }
+}
+
+impl fmt::Display for LinkCheck {
+    /// Formats the check as its status code and redirect target, or as the error.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.status, &self.redirect) {
+            (Some(status), Some(redirect)) => write!(f, "{}, redirected to {}", status, redirect),
+            (Some(status), None) => write!(f, "{}", status),
+            (None, _) => write!(
+                f,
+                "unreachable ({})",
+                self.error.as_deref().unwrap_or("unknown error")
+            ),
+        }
+    }
+}
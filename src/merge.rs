@@ -0,0 +1,106 @@
+use crate::response::SearchResult;
+use reqwest::Url;
+use std::collections::HashMap;
+
+/// The constant added to the ranks by reciprocal-rank fusion, which keeps the top results of a
+/// single list from outweighing results found by several lists (60 in the original paper).
+pub const RRF_K: f64 = 60.0;
+
+/// Merges ranked lists of results, such as the results of several backends, pages, or regions,
+/// with reciprocal-rank fusion.
+///
+/// Each result scores `1 / (RRF_K + rank)` for every list it appears in, its rank being its
+/// 1-based position in that list, and the merged list is ordered by decreasing score, so that
+/// results ranked high by several lists come first. Ties keep the order in which the results
+/// first appear. Results are the same when their URLs are, ignoring the scheme, a `www.`
+/// prefix, a fragment, and a trailing slash; the copy from the list ranking it highest is kept.
+///
+/// # Arguments
+/// * `lists` - The ranked lists of results.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::merge::rrf;
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+///
+/// let list = |urls: &[&str]| -> Vec<SearchResult> {
+///     urls.iter()
+///         .map(|url| {
+///             SearchResult::Text(LiteSearchResult {
+///                 url: (*url).into(),
+///                 ..Default::default()
+///             })
+///         })
+///         .collect()
+/// };
+/// let html = list(&["https://a.example/", "https://b.example/", "https://c.example/"]);
+/// let lite = list(&["https://b.example/", "https://www.c.example", "https://d.example/"]);
+///
+/// let urls: Vec<String> = rrf(vec![html, lite]).iter().map(|r| r.url().to_string()).collect();
+/// assert_eq!(
+///     urls,
+///     ["https://b.example/", "https://www.c.example", "https://a.example/", "https://d.example/"]
+/// );
+/// ```
+pub fn rrf(lists: Vec<Vec<SearchResult>>) -> Vec<SearchResult> {
+    rrf_with_k(lists, RRF_K)
+}
+
+/// Merges ranked lists of results with reciprocal-rank fusion, like `rrf`, with another
+/// constant than `RRF_K`.
+///
+/// # Arguments
+/// * `lists` - The ranked lists of results.
+/// * `k` - The constant added to the ranks; the lower, the more the top ranks weigh.
+pub fn rrf_with_k(lists: Vec<Vec<SearchResult>>, k: f64) -> Vec<SearchResult> {
+    // The merged results, in the order they first appear, with their score and best rank.
+    let mut fused: Vec<(SearchResult, f64, usize)> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for list in lists {
+        for (position, result) in list.into_iter().enumerate() {
+            let rank = position + 1;
+            let score = 1.0 / (k + rank as f64);
+            match index.get(&result_key(&result)) {
+                Some(&i) => {
+                    let (kept, total, best) = &mut fused[i];
+                    *total += score;
+                    if rank < *best {
+                        *kept = result;
+                        *best = rank;
+                    }
+                }
+                None => {
+                    index.insert(result_key(&result), fused.len());
+                    fused.push((result, score, rank));
+                }
+            }
+        }
+    }
+
+    // A stable sort, so that ties keep the order of first appearance.
+    fused.sort_by(|(_, a, _), (_, b, _)| b.total_cmp(a));
+    fused.into_iter().map(|(result, _, _)| result).collect()
+}
+
+/// Returns the key identifying a result across lists: its URL without the scheme, a `www.`
+/// prefix, a fragment, and a trailing slash.
+pub(crate) fn result_key(result: &SearchResult) -> String {
+    let url = result.url();
+    let key = match Url::parse(url) {
+        Ok(parsed) => format!(
+            "{}{}{}",
+            parsed
+                .host_str()
+                .unwrap_or_default()
+                .trim_start_matches("www."),
+            parsed.path(),
+            parsed
+                .query()
+                .map(|query| format!("?{}", query))
+                .unwrap_or_default()
+        ),
+        Err(_) => url.to_string(),
+    };
+    key.trim_end_matches('/').to_string()
+}
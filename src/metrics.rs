@@ -0,0 +1,389 @@
+use crate::endpoints::Endpoints;
+use anyhow::{Context, Result};
+use hyper::header::{CONTENT_TYPE, HeaderValue};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// The upper bounds of the buckets of the upstream latency histogram, in seconds.
+pub const LATENCY_BUCKETS: [f64; 9] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+/// The content type of the Prometheus text exposition format.
+pub const CONTENT_TYPE_PROMETHEUS: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Counters of the searches and requests of a long-running process, such as `ddg proxyd` or
+/// `ddg daemon`, printed in the Prometheus text format by `Display`.
+///
+/// The `Browser` records its upstream requests, by endpoint (`api`, `lite`, `html`, `images`,
+/// `news`, `vqd`, `icons`, or `other`) and status, their latency, the time spent waiting for
+/// the rate limiter, the responses that could not be parsed, and the bot challenges. The
+/// process records its searches, by backend, and the answers of its cache.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::metrics::Metrics;
+///
+/// let metrics = Metrics::new();
+/// metrics.record_search("html", true);
+/// metrics.record_cache("miss");
+/// metrics.record_cache("hit");
+///
+/// let text = metrics.to_string();
+/// assert!(text.contains("ddg_searches_total{backend=\"html\",outcome=\"success\"} 1"));
+/// assert!(text.contains("ddg_cache_hit_ratio 0.5"));
+/// ```
+#[derive(Debug, Default)]
+pub struct Metrics {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// The searches, by backend and outcome.
+    searches: BTreeMap<(String, &'static str), u64>,
+    /// The answers of the cache, by result (`hit`, `miss`, or `coalesced`).
+    cache: BTreeMap<String, u64>,
+    /// The upstream requests, by endpoint and status, `None` for transport errors.
+    requests: BTreeMap<(&'static str, Option<u16>), u64>,
+    /// The latency of the upstream requests, by endpoint.
+    latency: BTreeMap<&'static str, Histogram>,
+    /// The requests that waited for the rate limiter.
+    rate_limit_waits: u64,
+    /// The total time spent waiting for the rate limiter.
+    rate_limit_wait: Duration,
+    /// The responses that could not be parsed, by endpoint.
+    parse_failures: BTreeMap<&'static str, u64>,
+    /// The bot challenges served instead of results, by endpoint.
+    bot_detections: BTreeMap<&'static str, u64>,
+}
+
+/// A latency histogram with the `LATENCY_BUCKETS` buckets.
+#[derive(Debug, Default)]
+struct Histogram {
+    /// The number of observations in each bucket, not cumulative.
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        if let Some(bucket) = LATENCY_BUCKETS.iter().position(|bound| seconds <= *bound) {
+            self.buckets[bucket] += 1;
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+}
+
+impl Metrics {
+    /// Creates a set of metrics with every counter at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a search.
+    ///
+    /// # Arguments
+    /// * `backend` - The name of the backend running the search.
+    /// * `success` - Whether the search succeeded.
+    pub fn record_search(&self, backend: &str, success: bool) {
+        let outcome = if success { "success" } else { "error" };
+        *self
+            .state()
+            .searches
+            .entry((backend.to_string(), outcome))
+            .or_default() += 1;
+    }
+
+    /// Records an answer of a search cache.
+    ///
+    /// # Arguments
+    /// * `result` - `hit`, `miss`, or `coalesced` (answered by a search in flight).
+    pub fn record_cache(&self, result: &str) {
+        *self.state().cache.entry(result.to_string()).or_default() += 1;
+    }
+
+    /// Returns the share of the cache answers that did not send a request, hits and coalesced
+    /// searches, or `None` if the cache has not answered yet.
+    pub fn cache_hit_ratio(&self) -> Option<f64> {
+        let state = self.state();
+        let total: u64 = state.cache.values().sum();
+        let misses = state.cache.get("miss").copied().unwrap_or_default();
+        (total > 0).then(|| (total - misses) as f64 / total as f64)
+    }
+
+    /// Records an upstream request and its latency, with `None` as the status of transport
+    /// errors.
+    pub(crate) fn record_request(
+        &self,
+        endpoint: &'static str,
+        status: Option<u16>,
+        elapsed: Duration,
+    ) {
+        let mut state = self.state();
+        *state.requests.entry((endpoint, status)).or_default() += 1;
+        state
+            .latency
+            .entry(endpoint)
+            .or_default()
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Records a request waiting for the rate limiter.
+    pub(crate) fn record_rate_limit_wait(&self, wait: Duration) {
+        let mut state = self.state();
+        state.rate_limit_waits += 1;
+        state.rate_limit_wait += wait;
+    }
+
+    /// Records a response that could not be parsed.
+    pub(crate) fn record_parse_failure(&self, endpoint: &'static str) {
+        *self.state().parse_failures.entry(endpoint).or_default() += 1;
+    }
+
+    /// Records a bot challenge served instead of results.
+    pub(crate) fn record_bot_detection(&self, endpoint: &'static str) {
+        *self.state().bot_detections.entry(endpoint).or_default() += 1;
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Display for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hit_ratio = self.cache_hit_ratio();
+        let state = self.state();
+
+        header(
+            f,
+            "ddg_searches_total",
+            "counter",
+            "Searches, by backend and outcome.",
+        )?;
+        for ((backend, outcome), count) in &state.searches {
+            writeln!(
+                f,
+                "ddg_searches_total{{backend=\"{}\",outcome=\"{}\"}} {}",
+                escape(backend),
+                outcome,
+                count
+            )?;
+        }
+
+        header(
+            f,
+            "ddg_cache_requests_total",
+            "counter",
+            "Answers of the search cache, by result.",
+        )?;
+        for (result, count) in &state.cache {
+            writeln!(
+                f,
+                "ddg_cache_requests_total{{result=\"{}\"}} {}",
+                escape(result),
+                count
+            )?;
+        }
+        header(
+            f,
+            "ddg_cache_hit_ratio",
+            "gauge",
+            "Share of the cache answers that did not send a request.",
+        )?;
+        writeln!(f, "ddg_cache_hit_ratio {}", hit_ratio.unwrap_or(0.0))?;
+
+        header(
+            f,
+            "ddg_upstream_requests_total",
+            "counter",
+            "Requests sent to DuckDuckGo, by endpoint and status (0 for transport errors).",
+        )?;
+        for ((endpoint, status), count) in &state.requests {
+            writeln!(
+                f,
+                "ddg_upstream_requests_total{{endpoint=\"{}\",status=\"{}\"}} {}",
+                endpoint,
+                status.unwrap_or_default(),
+                count
+            )?;
+        }
+
+        header(
+            f,
+            "ddg_upstream_request_duration_seconds",
+            "histogram",
+            "Time to the response headers of the requests sent to DuckDuckGo, by endpoint.",
+        )?;
+        for (endpoint, histogram) in &state.latency {
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+                cumulative += count;
+                writeln!(
+                    f,
+                    "ddg_upstream_request_duration_seconds_bucket{{endpoint=\"{}\",le=\"{}\"}} {}",
+                    endpoint, bound, cumulative
+                )?;
+            }
+            writeln!(
+                f,
+                "ddg_upstream_request_duration_seconds_bucket{{endpoint=\"{}\",le=\"+Inf\"}} {}",
+                endpoint, histogram.count
+            )?;
+            writeln!(
+                f,
+                "ddg_upstream_request_duration_seconds_sum{{endpoint=\"{}\"}} {}",
+                endpoint, histogram.sum
+            )?;
+            writeln!(
+                f,
+                "ddg_upstream_request_duration_seconds_count{{endpoint=\"{}\"}} {}",
+                endpoint, histogram.count
+            )?;
+        }
+
+        header(
+            f,
+            "ddg_rate_limit_waits_total",
+            "counter",
+            "Requests that waited for the rate limiter.",
+        )?;
+        writeln!(f, "ddg_rate_limit_waits_total {}", state.rate_limit_waits)?;
+        header(
+            f,
+            "ddg_rate_limit_wait_seconds_total",
+            "counter",
+            "Time spent waiting for the rate limiter.",
+        )?;
+        writeln!(
+            f,
+            "ddg_rate_limit_wait_seconds_total {}",
+            state.rate_limit_wait.as_secs_f64()
+        )?;
+
+        header(
+            f,
+            "ddg_parse_failures_total",
+            "counter",
+            "Responses that could not be parsed, by endpoint.",
+        )?;
+        for (endpoint, count) in &state.parse_failures {
+            writeln!(
+                f,
+                "ddg_parse_failures_total{{endpoint=\"{}\"}} {}",
+                endpoint, count
+            )?;
+        }
+
+        header(
+            f,
+            "ddg_bot_detections_total",
+            "counter",
+            "Bot challenges served instead of results, by endpoint.",
+        )?;
+        for (endpoint, count) in &state.bot_detections {
+            writeln!(
+                f,
+                "ddg_bot_detections_total{{endpoint=\"{}\"}} {}",
+                endpoint, count
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the `HELP` and `TYPE` lines of a metric.
+fn header(f: &mut fmt::Formatter<'_>, name: &str, kind: &str, help: &str) -> fmt::Result {
+    writeln!(f, "# HELP {} {}", name, help)?;
+    writeln!(f, "# TYPE {} {}", name, kind)
+}
+
+/// Escapes a label value.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('\n', r"\n")
+}
+
+/// Returns the name of the endpoint a URL belongs to, as used in the metric labels: `api`,
+/// `lite`, `html`, `images`, `news`, `vqd`, `icons`, or `other`.
+pub(crate) fn endpoint_name(endpoints: &Endpoints, url: &str) -> &'static str {
+    // The vqd endpoint is the home page, a prefix of the image and news endpoints.
+    [
+        ("api", &endpoints.api),
+        ("lite", &endpoints.lite),
+        ("html", &endpoints.html),
+        ("images", &endpoints.images),
+        ("news", &endpoints.news),
+        ("icons", &endpoints.icons),
+        ("vqd", &endpoints.vqd),
+    ]
+    .into_iter()
+    .find(|(_, base)| url.starts_with(base.as_str()))
+    .map_or("other", |(name, _)| name)
+}
+
+/// Returns the metrics in the Prometheus text format.
+pub(crate) fn metrics_response(metrics: &Metrics) -> Response<Body> {
+    let mut response = Response::new(Body::from(metrics.to_string()));
+    response.headers_mut().insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static(CONTENT_TYPE_PROMETHEUS),
+    );
+    response
+}
+
+/// Listens on an address and answers `GET /metrics` with the metrics until the process stops,
+/// for processes without a server of their own, such as `ddg daemon`.
+///
+/// # Errors
+/// Returns an error if the address cannot be bound or the server fails.
+///
+/// # Example
+/// ```no_run
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::metrics::serve_metrics;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let browser = Browser::builder().metrics(true).build()?;
+///     let metrics = browser.metrics.clone().unwrap();
+///     tokio::spawn(serve_metrics(metrics, "127.0.0.1:9187".parse()?));
+///     // Search with the browser...
+///     Ok(())
+/// }
+/// ```
+pub async fn serve_metrics(metrics: Arc<Metrics>, addr: SocketAddr) -> Result<()> {
+    let make_service = make_service_fn(move |_| {
+        let metrics = metrics.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                let metrics = metrics.clone();
+                async move {
+                    let response =
+                        if request.method() == Method::GET && request.uri().path() == "/metrics" {
+                            metrics_response(&metrics)
+                        } else {
+                            let mut response = Response::new(Body::from("Not found\n"));
+                            *response.status_mut() = StatusCode::NOT_FOUND;
+                            response
+                        };
+                    Ok::<_, Infallible>(response)
+                }
+            }))
+        }
+    });
+    hyper::Server::try_bind(&addr)
+        .with_context(|| format!("Failed to listen on {}", addr))?
+        .serve(make_service)
+        .await
+        .context("The metrics server failed")
+}
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use std::time::Duration;
+
+/// A hook into every HTTP call made by a `Browser`.
+///
+/// Middleware can mutate outgoing requests (extra headers, signing) and observe the responses
+/// (status, latency), e.g. for custom authenticating proxies, logging, or metrics. Middleware runs
+/// in the order it was added to the `BrowserBuilder`.
+///
+/// # Examples
+/// ```
+/// use anyhow::Result;
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::middleware::Middleware;
+/// use std::time::Duration;
+///
+/// struct ApiKey(&'static str);
+///
+/// impl Middleware for ApiKey {
+///     fn on_request(&self, request: &mut reqwest::Request) -> Result<()> {
+///         request.headers_mut().insert("X-Api-Key", self.0.parse()?);
+///         Ok(())
+///     }
+///
+///     fn on_response(&self, response: &reqwest::Response, elapsed: Duration) {
+///         println!("{} {} in {:?}", response.status(), response.url(), elapsed);
+///     }
+/// }
+///
+/// let browser = Browser::builder()
+///     .with_middleware(ApiKey("secret"))
+///     .build()
+///     .unwrap();
+/// ```
+pub trait Middleware: Send + Sync {
+    /// Called before a request is sent. Returning an error aborts the request.
+    ///
+    /// # Arguments
+    /// * `request` - The outgoing request, which can be modified in place.
+    fn on_request(&self, request: &mut reqwest::Request) -> Result<()> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// Called after a response is received, before its body is read.
+    ///
+    /// # Arguments
+    /// * `response` - The received response.
+    /// * `elapsed` - The time between sending the request and receiving the response headers.
+    fn on_response(&self, response: &reqwest::Response, elapsed: Duration) {
+        let _ = (response, elapsed);
+    }
+}
+
+/// Middleware printing the status, URL, and latency of every response to stderr.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestLogger;
+
+impl Middleware for RequestLogger {
+    fn on_response(&self, response: &reqwest::Response, elapsed: Duration) {
+        eprintln!(
+            "{} {} ({} ms)",
+            response.status(),
+            response.url(),
+            elapsed.as_millis()
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::backend::SearchBackend;
+use crate::fixtures;
+use crate::params::SearchParams;
+use crate::response::{ImageResult, LiteSearchResult, NewsResult, SearchPage, SearchResult};
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// A search backend serving canned results, for testing code built on this crate offline.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::backend::SearchBackend;
+/// use duckduckgo::mock::MockBackend;
+/// use duckduckgo::params::SearchParams;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let backend = MockBackend::lite()?;
+///     let results = backend.search(&SearchParams::new("rust").limit(2)).await?;
+///     assert_eq!(results.len(), 2);
+///     assert_eq!(results[0].url(), "https://www.rust-lang.org/");
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockBackend {
+    page: SearchPage<SearchResult>,
+}
+
+impl MockBackend {
+    /// Creates a new `MockBackend` returning no results.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a `MockBackend` serving the given results.
+    pub fn with_results(results: Vec<SearchResult>) -> Self {
+        MockBackend {
+            page: SearchPage {
+                results,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Creates a `MockBackend` serving the given text results.
+    pub fn with_text(results: Vec<LiteSearchResult>) -> Self {
+        Self::with_results(results.into_iter().map(SearchResult::Text).collect())
+    }
+
+    /// Creates a `MockBackend` serving the given image results.
+    pub fn with_images(results: Vec<ImageResult>) -> Self {
+        Self::with_results(results.into_iter().map(SearchResult::Image).collect())
+    }
+
+    /// Creates a `MockBackend` serving the given news results.
+    pub fn with_news(results: Vec<NewsResult>) -> Self {
+        Self::with_results(results.into_iter().map(SearchResult::News).collect())
+    }
+
+    /// Creates a `MockBackend` serving the stored DuckDuckGo Lite fixture.
+    pub fn lite() -> Result<Self> {
+        Ok(Self::with_page(
+            fixtures::lite_page()?.map(SearchResult::Text),
+        ))
+    }
+
+    /// Creates a `MockBackend` serving the stored DuckDuckGo HTML fixture.
+    pub fn html() -> Result<Self> {
+        Ok(Self::with_page(
+            fixtures::html_page()?.map(SearchResult::Text),
+        ))
+    }
+
+    /// Creates a `MockBackend` serving the stored image search fixture.
+    pub fn images() -> Result<Self> {
+        Ok(Self::with_images(fixtures::images()?))
+    }
+
+    /// Creates a `MockBackend` serving the stored news search fixture.
+    pub fn news() -> Result<Self> {
+        Ok(Self::with_news(fixtures::news()?))
+    }
+
+    /// Creates a `MockBackend` serving the given page, including its hints.
+    pub fn with_page(page: SearchPage<SearchResult>) -> Self {
+        MockBackend { page }
+    }
+}
+
+#[async_trait]
+impl SearchBackend for MockBackend {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    async fn search(&self, params: &SearchParams) -> Result<Vec<SearchResult>> {
+        Ok(self.search_page(params).await?.results)
+    }
+
+    async fn search_page(&self, params: &SearchParams) -> Result<SearchPage<SearchResult>> {
+        let mut page = self.page.clone();
+        if !params.sources.is_empty() {
+            page.results.retain(|result| match result {
+                SearchResult::News(news) => params.sources.iter().any(|s| news.is_from(s)),
+                _ => true,
+            });
+        }
+        page.results.drain(..params.offset.min(page.results.len()));
+        if let Some(limit) = params.limit {
+            page.results.truncate(limit);
+        }
+        Ok(page)
+    }
+}
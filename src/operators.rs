@@ -0,0 +1,141 @@
+use anyhow::{Result, bail};
+
+/// A builder for composing DuckDuckGo search operator strings.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::operators::Operators;
+///
+/// let operators = Operators::new()
+///     .site("github.com")
+///     .filetype("pdf")
+///     .intitle("rust")
+///     .exclude("tutorial")
+///     .exact_phrase("memory safety")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(
+///     operators,
+///     "site:github.com filetype:pdf intitle:rust -tutorial \"memory safety\""
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Operators {
+    parts: Vec<Operator>,
+}
+
+/// A single search operator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Operator {
+    Site(String),
+    Filetype(String),
+    Intitle(String),
+    Inurl(String),
+    Include(String),
+    Exclude(String),
+    ExactPhrase(String),
+}
+
+impl Operators {
+    /// Creates an empty `Operators` builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts results to the given site or domain (`site:`).
+    pub fn site(mut self, domain: impl Into<String>) -> Self {
+        self.parts.push(Operator::Site(domain.into()));
+        self
+    }
+
+    /// Restricts results to the given file type (`filetype:`).
+    pub fn filetype(mut self, extension: impl Into<String>) -> Self {
+        self.parts.push(Operator::Filetype(extension.into()));
+        self
+    }
+
+    /// Requires the given term to appear in the page title (`intitle:`).
+    pub fn intitle(mut self, term: impl Into<String>) -> Self {
+        self.parts.push(Operator::Intitle(term.into()));
+        self
+    }
+
+    /// Requires the given term to appear in the page URL (`inurl:`).
+    pub fn inurl(mut self, term: impl Into<String>) -> Self {
+        self.parts.push(Operator::Inurl(term.into()));
+        self
+    }
+
+    /// Gives more weight to results containing the given term (`+term`).
+    pub fn include(mut self, term: impl Into<String>) -> Self {
+        self.parts.push(Operator::Include(term.into()));
+        self
+    }
+
+    /// Excludes results containing the given term (`-term`).
+    pub fn exclude(mut self, term: impl Into<String>) -> Self {
+        self.parts.push(Operator::Exclude(term.into()));
+        self
+    }
+
+    /// Requires the exact phrase to appear in results (`"phrase"`).
+    pub fn exact_phrase(mut self, phrase: impl Into<String>) -> Self {
+        self.parts.push(Operator::ExactPhrase(phrase.into()));
+        self
+    }
+
+    /// Returns `true` if no operators have been added.
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    /// Validates the operators and composes them into a space-separated operator string.
+    ///
+    /// # Returns
+    /// A `Result` containing the operator string, or an error if an operator value is empty,
+    /// contains whitespace where a single term is expected, or contains a double quote.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::operators::Operators;
+    ///
+    /// assert!(Operators::new().site("").build().is_err());
+    /// assert!(Operators::new().filetype("p d f").build().is_err());
+    /// assert_eq!(Operators::new().include("async").build().unwrap(), "+async");
+    /// ```
+    pub fn build(&self) -> Result<String> {
+        let mut composed = Vec::with_capacity(self.parts.len());
+
+        for part in &self.parts {
+            composed.push(match part {
+                Operator::Site(v) => format!("site:{}", term("site", v)?),
+                Operator::Filetype(v) => {
+                    format!("filetype:{}", term("filetype", v.trim_start_matches('.'))?)
+                }
+                Operator::Intitle(v) => format!("intitle:{}", term("intitle", v)?),
+                Operator::Inurl(v) => format!("inurl:{}", term("inurl", v)?),
+                Operator::Include(v) => format!("+{}", term("include", v)?),
+                Operator::Exclude(v) => format!("-{}", term("exclude", v)?),
+                Operator::ExactPhrase(v) => {
+                    let phrase = v.trim();
+                    if phrase.is_empty() || phrase.contains('"') {
+                        bail!("Invalid exact phrase operator value: {:?}", v);
+                    }
+                    format!("\"{}\"", phrase)
+                }
+            });
+        }
+
+        Ok(composed.join(" "))
+    }
+}
+
+/// Validates a single-term operator value.
+fn term<'a>(name: &str, value: &'a str) -> Result<&'a str> {
+    let value = value.trim();
+    if value.is_empty() || value.contains(char::is_whitespace) || value.contains('"') {
+        bail!("Invalid {} operator value: {:?}", name, value);
+    }
+    Ok(value)
+}
@@ -0,0 +1,107 @@
+use crate::user_agents::get;
+
+/// The region code used when none is specified (worldwide).
+pub const DEFAULT_REGION: &str = "wt-wt";
+
+/// Options shared by the DuckDuckGo search backends.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::params::SearchParams;
+///
+/// let params = SearchParams::new("rust language")
+///     .region("us-en")
+///     .safe_search(true)
+///     .limit(5);
+///
+/// assert_eq!(params.query, "rust language");
+/// assert_eq!(params.region, "us-en");
+/// assert_eq!(params.limit, Some(5));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchParams {
+    /// The search query.
+    pub query: String,
+    /// The region code (e.g., `"wt-wt"` for worldwide).
+    pub region: String,
+    /// Whether to enable safe search.
+    pub safe_search: bool,
+    /// Optional maximum number of results to return.
+    pub limit: Option<usize>,
+    /// The number of leading results to skip.
+    pub offset: usize,
+    /// The user agent sent with each request.
+    pub user_agent: String,
+    /// The news sources (domains or publisher names) to keep, or empty to keep all news.
+    pub sources: Vec<String>,
+}
+
+impl Default for SearchParams {
+    fn default() -> Self {
+        SearchParams {
+            query: String::new(),
+            region: DEFAULT_REGION.to_string(),
+            safe_search: false,
+            limit: None,
+            offset: 0,
+            user_agent: get("firefox").unwrap_or_default(),
+            sources: Vec::new(),
+        }
+    }
+}
+
+impl SearchParams {
+    /// Creates a new `SearchParams` for the given query with default options.
+    ///
+    /// # Arguments
+    /// * `query` - The search query.
+    pub fn new(query: impl Into<String>) -> Self {
+        SearchParams {
+            query: query.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the region code (e.g., `"wt-wt"` for worldwide).
+    pub fn region(mut self, region: impl Into<String>) -> Self {
+        self.region = region.into();
+        self
+    }
+
+    /// Enables or disables safe search.
+    pub fn safe_search(mut self, safe_search: bool) -> Self {
+        self.safe_search = safe_search;
+        self
+    }
+
+    /// Sets the maximum number of results to return.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Skips the first `offset` results, e.g. `offset(20).limit(20)` returns results 21 to 40.
+    ///
+    /// The Lite and HTML backends ask DuckDuckGo for the results starting at the offset; the
+    /// other backends skip them after fetching.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets the user agent sent with each request.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Restricts news results to the given source, in addition to the ones already set.
+    ///
+    /// # Arguments
+    /// * `source` - A domain (e.g. `"reuters.com"`) or a publisher name, see
+    ///   `NewsResult::is_from`.
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.sources.push(source.into());
+        self
+    }
+}
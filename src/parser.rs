@@ -0,0 +1,438 @@
+#[cfg(feature = "news")]
+use crate::link::Link;
+#[cfg(feature = "images")]
+use crate::response::ImageResult;
+#[cfg(feature = "news")]
+use crate::response::NewsResult;
+#[cfg(any(feature = "lite", feature = "html"))]
+use crate::response::{LiteSearchResult, SearchPage};
+#[cfg(any(feature = "images", feature = "news"))]
+use anyhow::Context;
+#[cfg(any(
+    feature = "lite",
+    feature = "html",
+    feature = "images",
+    feature = "news"
+))]
+use anyhow::Result;
+#[cfg(feature = "news")]
+use chrono::TimeZone;
+#[cfg(any(feature = "images", feature = "news"))]
+use regex::Regex;
+#[cfg(any(feature = "lite", feature = "html"))]
+use scraper::{Html, Selector};
+use serde::Serialize;
+#[cfg(any(feature = "images", feature = "news"))]
+use serde_json::Value;
+#[cfg(any(
+    feature = "lite",
+    feature = "html",
+    feature = "images",
+    feature = "news"
+))]
+use std::sync::LazyLock;
+
+/// How the items of the image and news APIs that cannot be parsed are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Skips the malformed items, reporting each of them as a `ParseWarning` of the page.
+    #[default]
+    Lenient,
+    /// Fails on the first malformed item with `Error::MalformedItem`.
+    Strict,
+}
+
+/// An item skipped by the lenient parse mode because it was malformed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParseWarning {
+    /// The 1-based position of the item in the results.
+    pub rank: usize,
+    /// Why the item could not be parsed (e.g. `"field 'url' is missing"`).
+    pub reason: String,
+}
+
+/// Phrases DuckDuckGo uses to introduce a spelling correction on result pages.
+#[cfg(feature = "lite")]
+const SPELLING_HINTS: &[&str] = &["Including results for", "Did you mean"];
+
+/// Compiles a selector that is known to be valid.
+#[cfg(any(feature = "lite", feature = "html"))]
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).unwrap_or_else(|e| panic!("invalid selector {css:?}: {e}"))
+}
+
+// Regexes and selectors are compiled once and shared by all parses.
+#[cfg(any(feature = "images", feature = "news"))]
+static VQD_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"vqd=.?['"]?([\d-]+)['"]?"#).expect("invalid vqd regex"));
+#[cfg(feature = "lite")]
+static LINK: LazyLock<Selector> = LazyLock::new(|| selector("a"));
+#[cfg(feature = "lite")]
+static LITE_ROW: LazyLock<Selector> = LazyLock::new(|| selector("table tr"));
+#[cfg(feature = "lite")]
+static LITE_SNIPPET: LazyLock<Selector> = LazyLock::new(|| selector("td.result-snippet"));
+#[cfg(feature = "html")]
+static HTML_RESULT: LazyLock<Selector> = LazyLock::new(|| selector("div.result:not(.result--ad)"));
+#[cfg(feature = "html")]
+static HTML_TITLE: LazyLock<Selector> = LazyLock::new(|| selector("a.result__a"));
+#[cfg(feature = "html")]
+static HTML_SNIPPET: LazyLock<Selector> = LazyLock::new(|| selector(".result__snippet"));
+#[cfg(feature = "html")]
+static HTML_SPELLING: LazyLock<Selector> = LazyLock::new(|| selector("#did_you_mean a"));
+#[cfg(feature = "html")]
+static HTML_RELATED: LazyLock<Selector> =
+    LazyLock::new(|| selector(".related-searches a, .result--more__related a"));
+
+/// Markers of the challenge page DuckDuckGo serves when it suspects automated traffic.
+const BOT_CHALLENGE_MARKERS: &[&str] = &[
+    "anomaly-modal",
+    "challenge-form",
+    "anomaly.js",
+    "bots use DuckDuckGo too",
+];
+
+/// Returns `true` if a response body is DuckDuckGo's "anomaly detected" challenge rather than
+/// results.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::parser::is_bot_challenge;
+///
+/// let page = r#"<form id="challenge-form" action="//duckduckgo.com/anomaly.js?sv=html"></form>"#;
+/// assert!(is_bot_challenge(page));
+/// assert!(!is_bot_challenge("<table><tr><td>Rust</td></tr></table>"));
+/// ```
+pub fn is_bot_challenge(body: &str) -> bool {
+    BOT_CHALLENGE_MARKERS
+        .iter()
+        .any(|marker| body.contains(marker))
+}
+
+/// Extracts the `vqd` token from a DuckDuckGo search page.
+///
+/// # Arguments
+/// * `text` - The body of the DuckDuckGo search page.
+///
+/// # Returns
+/// A `Result` containing the extracted `vqd` string or an error if not found.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::parser::parse_vqd;
+///
+/// let vqd = parse_vqd(r#"<script>vqd="4-123456789"</script>"#).unwrap();
+/// assert_eq!(vqd, "4-123456789");
+/// ```
+#[cfg(any(feature = "images", feature = "news"))]
+pub fn parse_vqd(text: &str) -> Result<String> {
+    let vqd = VQD_RE
+        .captures(text)
+        .and_then(|c| c.get(1).map(|m| m.as_str().to_string()))
+        .context("Missing vqd in response")?;
+
+    Ok(vqd)
+}
+
+/// Parses a DuckDuckGo Lite results page.
+///
+/// # Arguments
+/// * `body` - The HTML body of the results page.
+/// * `limit` - Optional maximum number of results to return.
+///
+/// # Returns
+/// A `SearchPage` holding the results, ranked from 1, and the suggested `corrected_query`, if
+/// any.
+#[cfg(feature = "lite")]
+pub fn parse_lite_page(body: &str, limit: Option<usize>) -> Result<SearchPage> {
+    let doc = Html::parse_document(body);
+    let mut page = SearchPage::default();
+    let rows: Vec<_> = doc.select(&LITE_ROW).collect();
+
+    for (index, tr) in rows.iter().enumerate() {
+        if let Some(a) = tr.select(&LINK).next() {
+            let title = a.text().collect::<String>();
+
+            let row_text = tr.text().collect::<String>();
+            if SPELLING_HINTS.iter().any(|hint| row_text.contains(hint)) {
+                page.corrected_query.get_or_insert(title.trim().to_string());
+                continue;
+            }
+
+            if let Some(href) = a.value().attr("href") {
+                // The snippet is either in the same row or in the row following the link.
+                let snippet = tr
+                    .select(&LITE_SNIPPET)
+                    .next()
+                    .or_else(|| {
+                        rows.get(index + 1)
+                            .and_then(|next| next.select(&LITE_SNIPPET).next())
+                    })
+                    .map(|n| n.text().collect::<String>().trim().to_string())
+                    .unwrap_or_default();
+
+                page.results.push(LiteSearchResult {
+                    title,
+                    url: href.into(),
+                    snippet,
+                    rank: page.results.len() + 1,
+                    page: 1,
+                    backend: String::from("lite"),
+                    favicon: None,
+                    link_check: None,
+                    content: None,
+                    also_on: Vec::new(),
+                });
+
+                if limit.is_some_and(|l| page.results.len() >= l) {
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(page)
+}
+
+/// Parses a DuckDuckGo HTML results page.
+///
+/// # Arguments
+/// * `body` - The HTML body of the results page.
+/// * `limit` - Optional maximum number of results to return.
+///
+/// # Returns
+/// A `SearchPage` holding the results, ranked from 1, the spelling correction, and the related
+/// searches.
+#[cfg(feature = "html")]
+pub fn parse_html_page(body: &str, limit: Option<usize>) -> Result<SearchPage> {
+    let doc = Html::parse_document(body);
+    let mut page = SearchPage {
+        corrected_query: doc
+            .select(&HTML_SPELLING)
+            .next()
+            .map(|a| a.text().collect::<String>().trim().to_string()),
+        ..Default::default()
+    };
+
+    for result in doc.select(&HTML_RESULT) {
+        let Some(a) = result.select(&HTML_TITLE).next() else {
+            continue;
+        };
+        let Some(href) = a.value().attr("href") else {
+            continue;
+        };
+
+        let snippet = result
+            .select(&HTML_SNIPPET)
+            .next()
+            .map(|n| n.text().collect::<String>().trim().to_string())
+            .unwrap_or_default();
+
+        page.results.push(LiteSearchResult {
+            title: a.text().collect::<String>().trim().to_string(),
+            url: resolve_redirect(href).into(),
+            snippet,
+            rank: page.results.len() + 1,
+            page: 1,
+            backend: String::from("html"),
+            favicon: None,
+            link_check: None,
+            content: None,
+            also_on: Vec::new(),
+        });
+
+        if limit.is_some_and(|l| page.results.len() >= l) {
+            break;
+        }
+    }
+
+    for a in doc.select(&HTML_RELATED) {
+        let related = a.text().collect::<String>().trim().to_string();
+        if !related.is_empty() && !page.related_queries.contains(&related) {
+            page.related_queries.push(related);
+        }
+    }
+
+    Ok(page)
+}
+
+/// Checks that an item of the `results` array returned by `i.js` is an image that
+/// `parse_image` can parse without losing data: an object with an image URL, and with fields of
+/// the expected types.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::parser::check_image;
+/// use serde_json::json;
+///
+/// let item = json!({"image": "https://example.com/a.png", "width": 640, "height": 480});
+/// assert!(check_image(&item).is_ok());
+///
+/// let item = json!({"image": "https://example.com/a.png", "width": "wide"});
+/// assert_eq!(check_image(&item).unwrap_err(), "field 'width' is not a number");
+/// ```
+#[cfg(feature = "images")]
+pub fn check_image(item: &Value) -> Result<(), String> {
+    if !item.is_object() {
+        return Err(String::from("item is not an object"));
+    }
+    require_str(item, "image")?;
+    for field in ["title", "thumbnail", "url", "source"] {
+        optional_str(item, field)?;
+    }
+    for field in ["width", "height"] {
+        if item.get(field).is_some_and(|v| !v.is_null() && !v.is_u64()) {
+            return Err(format!("field '{}' is not a number", field));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that an item of the `results` array returned by `news.js` is an article that
+/// `parse_news` can parse without losing data: an object with a URL, and with fields of the
+/// expected types.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::parser::check_news;
+/// use serde_json::json;
+///
+/// let item = json!({"url": "https://example.com/a", "title": "A", "date": 1700000000});
+/// assert!(check_news(&item).is_ok());
+/// assert_eq!(check_news(&json!({"title": "A"})).unwrap_err(), "field 'url' is missing");
+/// ```
+#[cfg(feature = "news")]
+pub fn check_news(item: &Value) -> Result<(), String> {
+    if !item.is_object() {
+        return Err(String::from("item is not an object"));
+    }
+    require_str(item, "url")?;
+    for field in ["title", "excerpt", "image", "source"] {
+        optional_str(item, field)?;
+    }
+    if item
+        .get("date")
+        .is_some_and(|v| !v.is_null() && !v.is_i64())
+    {
+        return Err(String::from("field 'date' is not a timestamp"));
+    }
+    Ok(())
+}
+
+/// Fails if a field of a JSON object is missing, empty, or not a string.
+#[cfg(any(feature = "images", feature = "news"))]
+fn require_str(item: &Value, field: &str) -> Result<(), String> {
+    match item.get(field) {
+        None | Some(Value::Null) => Err(format!("field '{}' is missing", field)),
+        Some(Value::String(value)) if value.is_empty() => {
+            Err(format!("field '{}' is empty", field))
+        }
+        _ => optional_str(item, field),
+    }
+}
+
+/// Fails if a field of a JSON object is set and not a string.
+#[cfg(any(feature = "images", feature = "news"))]
+fn optional_str(item: &Value, field: &str) -> Result<(), String> {
+    match item.get(field) {
+        None | Some(Value::Null) | Some(Value::String(_)) => Ok(()),
+        Some(_) => Err(format!("field '{}' is not a string", field)),
+    }
+}
+
+/// Parses a single item of the `results` array returned by `i.js`.
+///
+/// # Arguments
+/// * `item` - The JSON object describing the image.
+/// * `rank` - The 1-based position of the image in the results.
+/// * `page` - The 1-based number of the page the image was found on.
+#[cfg(feature = "images")]
+pub fn parse_image(item: &Value, rank: usize, page: usize) -> ImageResult {
+    ImageResult {
+        title: item["title"].as_str().unwrap_or("").to_string(),
+        image: item["image"].as_str().unwrap_or("").into(),
+        thumbnail: item["thumbnail"].as_str().unwrap_or("").into(),
+        url: item["url"].as_str().unwrap_or("").into(),
+        height: item["height"].as_u64().unwrap_or(0) as u32,
+        width: item["width"].as_u64().unwrap_or(0) as u32,
+        source: item["source"].as_str().unwrap_or("").to_string(),
+        rank,
+        page,
+        backend: String::from("images"),
+        favicon: None,
+        link_check: None,
+        image_check: None,
+        content: None,
+        also_on: Vec::new(),
+    }
+}
+
+/// Parses a single item of the `results` array returned by `news.js`.
+///
+/// # Arguments
+/// * `item` - The JSON object describing the news article.
+/// * `rank` - The 1-based position of the article in the results.
+/// * `page` - The 1-based number of the page the article was found on.
+///
+/// The date of the article is `None` if it is missing or is not a valid Unix timestamp.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::parser::parse_news;
+/// use serde_json::json;
+///
+/// let item = json!({ "title": "Rust 1.90", "url": "https://blog.rust-lang.org/", "date": 1758153600 });
+/// assert_eq!(parse_news(&item, 1, 1).date.unwrap().to_rfc3339(), "2025-09-18T00:00:00+00:00");
+///
+/// let undated = json!({ "title": "Rust 1.90", "url": "https://blog.rust-lang.org/" });
+/// assert!(parse_news(&undated, 1, 1).date.is_none());
+/// ```
+#[cfg(feature = "news")]
+pub fn parse_news(item: &Value, rank: usize, page: usize) -> NewsResult {
+    let date = item["date"]
+        .as_i64()
+        .and_then(|ts| chrono::Utc.timestamp_opt(ts, 0).single());
+
+    let url = Link::new(item["url"].as_str().unwrap_or(""));
+    let domain = url
+        .domain()
+        .map(|host| host.trim_start_matches("www.").to_string());
+
+    NewsResult {
+        date,
+        title: item["title"].as_str().unwrap_or("").to_string(),
+        body: item["excerpt"].as_str().unwrap_or("").to_string(),
+        url,
+        image: item.get("image").and_then(|v| v.as_str()).map(Link::from),
+        source: item["source"].as_str().unwrap_or("").to_string(),
+        domain,
+        rank,
+        page,
+        backend: String::from("news"),
+        favicon: None,
+        link_check: None,
+        content: None,
+        also_on: Vec::new(),
+    }
+}
+
+/// Extracts the target URL from a DuckDuckGo redirect link (`//duckduckgo.com/l/?uddg=...`).
+///
+/// Links that are not redirects are returned unchanged.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::parser::resolve_redirect;
+///
+/// let url = resolve_redirect("//duckduckgo.com/l/?uddg=https%3A%2F%2Fwww.rust-lang.org%2F&rut=abc");
+/// assert_eq!(url, "https://www.rust-lang.org/");
+/// assert_eq!(resolve_redirect("https://example.com/"), "https://example.com/");
+/// ```
+#[cfg(feature = "html")]
+pub fn resolve_redirect(href: &str) -> String {
+    href.split_once("uddg=")
+        .map(|(_, rest)| rest.split('&').next().unwrap_or(rest))
+        .and_then(|target| urlencoding::decode(target).ok())
+        .map(|target| target.into_owned())
+        .unwrap_or_else(|| href.to_string())
+}
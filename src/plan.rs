@@ -0,0 +1,220 @@
+#[cfg(feature = "api")]
+use crate::backend::api_params;
+use crate::browser::Browser;
+#[cfg(feature = "api")]
+use crate::browser::instant_answer_params;
+#[cfg(any(feature = "images", feature = "news"))]
+use crate::browser::json_page_params;
+#[cfg(any(feature = "lite", feature = "html"))]
+use crate::browser::text_params;
+use crate::params::SearchParams;
+use anyhow::{Result, bail};
+#[cfg(any(
+    feature = "api",
+    feature = "lite",
+    feature = "html",
+    feature = "images",
+    feature = "news"
+))]
+use reqwest::cookie::CookieStore;
+use serde::Serialize;
+use std::fmt;
+
+/// The placeholder standing for the `vqd` token in the planned requests of image and news
+/// searches, since the token is only known once the token request is sent.
+pub const VQD_PLACEHOLDER: &str = "VQD_TOKEN";
+
+/// A request a search would send, built without sending it.
+///
+/// Planned requests show the exact URLs, query parameters, and headers of a search, after the
+/// middleware ran, e.g. to debug operators, regions, or encoding issues with `--dry-run`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::params::SearchParams;
+///
+/// let browser = Browser::new(reqwest::Client::new());
+/// let params = SearchParams::new("rust lang site:github.com");
+///
+/// let plan = browser.plan("lite", &params).unwrap();
+/// assert_eq!(plan[0].method, "POST");
+/// assert!(plan[0].url.ends_with("/lite/?q=rust+lang+site%3Agithub.com&kl=wt-wt"));
+/// assert!(plan[0].params.contains(&("q".into(), "rust lang site:github.com".into())));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PlannedRequest {
+    /// The backend sending the request (e.g. `"lite"`).
+    pub backend: String,
+    /// The HTTP method.
+    pub method: String,
+    /// The URL, including the encoded query string.
+    pub url: String,
+    /// The decoded query parameters.
+    pub params: Vec<(String, String)>,
+    /// The headers of the request, in order, including the cookies of the persistent cookie
+    /// jar. The other headers added by the HTTP client itself are not included.
+    pub headers: Vec<(String, String)>,
+}
+
+impl PlannedRequest {
+    /// Builds a prepared request and runs the middleware of the browser on it, as `Browser`
+    /// does before sending a request.
+    #[cfg(any(
+        feature = "api",
+        feature = "lite",
+        feature = "html",
+        feature = "images",
+        feature = "news"
+    ))]
+    fn new(browser: &Browser, backend: &str, builder: reqwest::RequestBuilder) -> Result<Self> {
+        let mut request = builder.build()?;
+        if let Some((jar, _)) = &browser.cookie_jar
+            && let Some(cookies) = jar.cookies(request.url())
+        {
+            request
+                .headers_mut()
+                .insert(reqwest::header::COOKIE, cookies);
+        }
+        for middleware in &browser.middleware {
+            middleware.on_request(&mut request)?;
+        }
+
+        Ok(PlannedRequest {
+            backend: backend.to_string(),
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            params: request
+                .url()
+                .query_pairs()
+                .map(|(name, value)| (name.into_owned(), value.into_owned()))
+                .collect(),
+            headers: request
+                .headers()
+                .iter()
+                .map(|(name, value)| {
+                    let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                    (name.to_string(), value)
+                })
+                .collect(),
+        })
+    }
+}
+
+impl fmt::Display for PlannedRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[{}] {} {}", self.backend, self.method, self.url)?;
+        if !self.params.is_empty() {
+            writeln!(f, "  Query:")?;
+            for (name, value) in &self.params {
+                writeln!(f, "    {} = {}", name, value)?;
+            }
+        }
+        if !self.headers.is_empty() {
+            writeln!(f, "  Headers:")?;
+            for (name, value) in &self.headers {
+                writeln!(f, "    {}: {}", name, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Browser {
+    /// Returns the requests a backend would send for a search, without sending them.
+    ///
+    /// Image and news searches first request a `vqd` token, which is shown as
+    /// `VQD_PLACEHOLDER` in the request of the results; only their first page is planned.
+    ///
+    /// # Arguments
+    /// * `backend` - The name of the backend: `"api"`, `"lite"`, `"html"`, `"images"`, or
+    ///   `"news"`.
+    /// * `params` - The search options.
+    ///
+    /// # Errors
+    /// Fails if the backend is unknown or not compiled in, or if a middleware rejects a request.
+    #[cfg_attr(
+        not(any(
+            feature = "api",
+            feature = "lite",
+            feature = "html",
+            feature = "images",
+            feature = "news"
+        )),
+        allow(unused_variables)
+    )]
+    pub fn plan(&self, backend: &str, params: &SearchParams) -> Result<Vec<PlannedRequest>> {
+        match backend {
+            #[cfg(feature = "api")]
+            "api" => {
+                let url = self.api_url(&api_params(params))?;
+                Ok(vec![PlannedRequest::new(
+                    self,
+                    backend,
+                    self.client.get(url),
+                )?])
+            }
+            #[cfg(feature = "lite")]
+            "lite" => {
+                let form = text_params(params);
+                let form: Vec<(&str, &str)> = form.iter().map(|(k, v)| (*k, v.as_ref())).collect();
+                let request = self.prepare_request(
+                    reqwest::Method::POST,
+                    &self.endpoints.lite,
+                    &params.user_agent,
+                    &form,
+                );
+                Ok(vec![PlannedRequest::new(self, backend, request)?])
+            }
+            #[cfg(feature = "html")]
+            "html" => {
+                let form = text_params(params);
+                let form: Vec<(&str, &str)> = form.iter().map(|(k, v)| (*k, v.as_ref())).collect();
+                let request = self.prepare_request(
+                    reqwest::Method::POST,
+                    &self.endpoints.html,
+                    &params.user_agent,
+                    &form,
+                );
+                Ok(vec![PlannedRequest::new(self, backend, request)?])
+            }
+            #[cfg(any(feature = "images", feature = "news"))]
+            "images" | "news" => {
+                let (endpoint, extra): (&str, &[(&str, &str)]) = if backend == "images" {
+                    (&self.endpoints.images, &[])
+                } else {
+                    (&self.endpoints.news, &[("noamp", "1")])
+                };
+                let token = self.prepare_request(
+                    reqwest::Method::GET,
+                    &self.endpoints.vqd,
+                    &params.user_agent,
+                    &[("q", &params.query)],
+                );
+                let page_params = json_page_params(params, VQD_PLACEHOLDER, extra, None);
+                let page = self.prepare_request(
+                    reqwest::Method::GET,
+                    endpoint,
+                    &params.user_agent,
+                    &page_params,
+                );
+                Ok(vec![
+                    PlannedRequest::new(self, backend, token)?,
+                    PlannedRequest::new(self, backend, page)?,
+                ])
+            }
+            _ => bail!("Unknown backend '{}'", backend),
+        }
+    }
+
+    /// Returns the request `instant_answer` would send for a query, without sending it.
+    ///
+    /// # Arguments
+    /// * `query` - The search query.
+    #[cfg(feature = "api")]
+    pub fn plan_instant_answer(&self, query: &str) -> Result<PlannedRequest> {
+        let url = self.api_url(&instant_answer_params(query))?;
+        PlannedRequest::new(self, "api", self.client.get(url))
+    }
+}
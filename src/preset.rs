@@ -0,0 +1,160 @@
+use anyhow::{Context, Result, bail};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The search presets shipped with the crate, loaded into every default `PresetRegistry`.
+pub const PRESETS: &[(&str, &str)] = &[
+    (
+        "academic",
+        "site:arxiv.org OR site:semanticscholar.org OR site:acm.org OR site:ieee.org",
+    ),
+    ("code", "site:github.com OR site:stackoverflow.com"),
+    (
+        "discussions",
+        "site:news.ycombinator.com OR site:lobste.rs OR site:reddit.com",
+    ),
+    (
+        "docs",
+        "site:docs.rs OR site:doc.rust-lang.org OR site:developer.mozilla.org OR site:readthedocs.io",
+    ),
+];
+
+/// Named operator strings, such as `code = site:github.com OR site:stackoverflow.com`, appended
+/// to the query of a search with `--preset`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::preset::PresetRegistry;
+///
+/// let mut presets = PresetRegistry::default();
+/// assert_eq!(presets.get("code"), Some("site:github.com OR site:stackoverflow.com"));
+///
+/// presets.register("crates", "site:crates.io");
+/// assert_eq!(presets.apply("serde", "crates").unwrap(), "serde site:crates.io");
+/// assert!(presets.apply("serde", "cargo").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetRegistry {
+    presets: BTreeMap<String, String>,
+}
+
+impl Default for PresetRegistry {
+    fn default() -> Self {
+        PresetRegistry {
+            presets: PRESETS
+                .iter()
+                .map(|(name, operators)| (name.to_string(), operators.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl PresetRegistry {
+    /// Creates a registry without any preset.
+    pub fn empty() -> Self {
+        PresetRegistry {
+            presets: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the default file of additional presets: `ddg/presets` in the user config
+    /// directory (`$XDG_CONFIG_HOME`, or `~/.config`), or `None` if there is none.
+    pub fn default_path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+            .map(|base| base.join("ddg").join("presets"))
+    }
+
+    /// Registers a preset under `name`, replacing any previous entry.
+    ///
+    /// # Returns
+    /// The operators previously registered under `name`, if any.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        operators: impl Into<String>,
+    ) -> Option<String> {
+        self.presets.insert(name.into(), operators.into())
+    }
+
+    /// Returns the operators of the preset registered under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.presets.get(name).map(String::as_str)
+    }
+
+    /// Returns the registered presets and their operators, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.presets
+            .iter()
+            .map(|(name, operators)| (name.as_str(), operators.as_str()))
+    }
+
+    /// Returns a query with the operators of a preset appended.
+    ///
+    /// # Arguments
+    /// * `query` - The search query.
+    /// * `name` - The name of the preset.
+    ///
+    /// # Errors
+    /// Fails if no preset is registered under `name`.
+    pub fn apply(&self, query: &str, name: &str) -> Result<String> {
+        match self.get(name) {
+            Some(operators) if query.is_empty() => Ok(operators.to_string()),
+            Some(operators) => Ok(format!("{} {}", query, operators)),
+            None => bail!(
+                "Unknown preset '{}', expected one of: {}",
+                name,
+                self.presets.keys().cloned().collect::<Vec<_>>().join(", ")
+            ),
+        }
+    }
+
+    /// Registers the presets listed in a file.
+    ///
+    /// Each line holds a `name = operators` entry. Blank lines and lines starting with `#` are
+    /// ignored, and entries override existing ones with the same name.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file to load.
+    ///
+    /// # Returns
+    /// The number of loaded entries, or an error if the file cannot be read or is malformed.
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<usize> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read presets file {}", path.display()))?;
+
+        let mut loaded = 0;
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((name, operators)) = line.split_once('=') else {
+                bail!(
+                    "{}:{}: expected a `name = operators` entry",
+                    path.display(),
+                    index + 1
+                );
+            };
+            let (name, operators) = (name.trim(), operators.trim());
+            if name.is_empty() || operators.is_empty() {
+                bail!(
+                    "{}:{}: empty preset name or operators",
+                    path.display(),
+                    index + 1
+                );
+            }
+
+            self.register(name, operators);
+            loaded += 1;
+        }
+
+        Ok(loaded)
+    }
+}
@@ -0,0 +1,178 @@
+use anyhow::{Context, Result, bail};
+use reqwest::{NoProxy, Proxy, Url};
+use std::env;
+
+/// Proxy settings for the HTTP client, supporting per-scheme proxies, credentials, and the
+/// standard `HTTP_PROXY`/`HTTPS_PROXY`/`ALL_PROXY`/`NO_PROXY` environment variables.
+///
+/// Proxy URLs may carry credentials (`user:pass@host:port`) and default to the `http` scheme
+/// when none is given.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::proxy::ProxyConfig;
+///
+/// let config = ProxyConfig::new()
+///     .http("user:secret@proxy.local:3128")
+///     .https("socks5://127.0.0.1:1080")
+///     .no_proxy("localhost,127.0.0.1");
+///
+/// assert_eq!(config.build().unwrap().len(), 2);
+/// assert!(ProxyConfig::new().all("http://:80").build().is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProxyConfig {
+    /// The proxy used for all requests, unless a per-scheme proxy is set.
+    pub all: Option<String>,
+    /// The proxy used for `http://` requests.
+    pub http: Option<String>,
+    /// The proxy used for `https://` requests.
+    pub https: Option<String>,
+    /// A comma-separated list of hosts that bypass the proxies.
+    pub no_proxy: Option<String>,
+}
+
+impl ProxyConfig {
+    /// Creates an empty `ProxyConfig`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the proxy used for all requests.
+    pub fn all(mut self, url: impl Into<String>) -> Self {
+        self.all = Some(url.into());
+        self
+    }
+
+    /// Sets the proxy used for `http://` requests.
+    pub fn http(mut self, url: impl Into<String>) -> Self {
+        self.http = Some(url.into());
+        self
+    }
+
+    /// Sets the proxy used for `https://` requests.
+    pub fn https(mut self, url: impl Into<String>) -> Self {
+        self.https = Some(url.into());
+        self
+    }
+
+    /// Sets the comma-separated list of hosts that bypass the proxies.
+    pub fn no_proxy(mut self, hosts: impl Into<String>) -> Self {
+        self.no_proxy = Some(hosts.into());
+        self
+    }
+
+    /// Fills the settings that are not set yet from the `ALL_PROXY`, `HTTP_PROXY`,
+    /// `HTTPS_PROXY`, and `NO_PROXY` environment variables (or their lowercase variants).
+    pub fn with_env(mut self) -> Self {
+        self.all = self.all.or_else(|| var("ALL_PROXY"));
+        self.http = self.http.or_else(|| var("HTTP_PROXY"));
+        self.https = self.https.or_else(|| var("HTTPS_PROXY"));
+        self.no_proxy = self.no_proxy.or_else(|| var("NO_PROXY"));
+        self
+    }
+
+    /// Returns `true` if no proxy is configured.
+    pub fn is_empty(&self) -> bool {
+        self.all.is_none() && self.http.is_none() && self.https.is_none()
+    }
+
+    /// Returns the proxy used for requests of the given URL scheme (`"http"` or `"https"`), if
+    /// any: the per-scheme proxy, or else the proxy for all requests.
+    pub fn for_scheme(&self, scheme: &str) -> Option<&str> {
+        let specific = match scheme {
+            "http" => self.http.as_deref(),
+            "https" => self.https.as_deref(),
+            _ => None,
+        };
+        specific.or(self.all.as_deref())
+    }
+
+    /// Validates the settings and creates the corresponding reqwest proxies.
+    ///
+    /// # Returns
+    /// A `Result` containing the proxies, most specific first, or an error if a proxy URL is
+    /// invalid.
+    pub fn build(&self) -> Result<Vec<Proxy>> {
+        let no_proxy = self.no_proxy.as_deref().and_then(NoProxy::from_string);
+        let mut proxies = Vec::new();
+
+        let configured = [
+            (&self.http, Proxy::http as fn(Url) -> reqwest::Result<Proxy>),
+            (&self.https, Proxy::https),
+            (&self.all, Proxy::all),
+        ];
+
+        for (url, new) in configured {
+            let Some(url) = url else {
+                continue;
+            };
+            let url = parse_proxy_url(url)?;
+            let credentials = credentials(&url);
+
+            let mut proxy = new(url.clone())
+                .with_context(|| format!("Invalid proxy URL {}", redact(&url)))?
+                .no_proxy(no_proxy.clone());
+            if let Some((username, password)) = credentials
+                && url.scheme().starts_with("http")
+            {
+                proxy = proxy.basic_auth(&username, &password);
+            }
+            proxies.push(proxy);
+        }
+
+        Ok(proxies)
+    }
+}
+
+/// Parses a proxy URL, defaulting to the `http` scheme.
+fn parse_proxy_url(raw: &str) -> Result<Url> {
+    let raw = raw.trim();
+    let url = if raw.contains("://") {
+        Url::parse(raw)
+    } else {
+        Url::parse(&format!("http://{}", raw))
+    }
+    .context("Invalid proxy URL")?;
+
+    if url.host_str().is_none_or(str::is_empty) {
+        bail!("Proxy URL {} has no host", redact(&url));
+    }
+
+    Ok(url)
+}
+
+/// Returns the percent-decoded credentials of a proxy URL, if any.
+fn credentials(url: &Url) -> Option<(String, String)> {
+    if url.username().is_empty() {
+        return None;
+    }
+
+    let decode = |s: &str| {
+        urlencoding::decode(s)
+            .map(|s| s.into_owned())
+            .unwrap_or_else(|_| s.to_string())
+    };
+
+    Some((
+        decode(url.username()),
+        decode(url.password().unwrap_or_default()),
+    ))
+}
+
+/// Formats a proxy URL without its password, for error messages.
+fn redact(url: &Url) -> String {
+    let mut url = url.clone();
+    if url.password().is_some() {
+        let _ = url.set_password(Some("***"));
+    }
+    url.to_string()
+}
+
+/// Reads an environment variable, falling back to its lowercase variant.
+fn var(name: &str) -> Option<String> {
+    env::var(name)
+        .or_else(|_| env::var(name.to_lowercase()))
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+}
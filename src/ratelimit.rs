@@ -0,0 +1,320 @@
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// A token-bucket rate limiter shared by all the requests of a `Browser`.
+///
+/// Each request takes a token; tokens refill at a steady rate up to the burst size. When the
+/// bucket is empty, requests wait for their turn instead of failing, so batch jobs and
+/// pagination loops spread their requests over time.
+///
+/// The bucket can be shared by every process on the machine with `shared`, so that concurrent
+/// runs, such as a cron job and an interactive search, respect one combined budget.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::ratelimit::RateLimiter;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let limiter = RateLimiter::new(Duration::from_millis(20)).burst(2);
+///
+///     let start = std::time::Instant::now();
+///     for _ in 0..3 {
+///         limiter.acquire().await;
+///     }
+///     // The first two requests use the burst, the third one waits for a new token.
+///     assert!(start.elapsed() >= Duration::from_millis(20));
+///
+///     let browser = Browser::builder().rate_limit(30).build().unwrap();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    burst: u32,
+    state: Mutex<Bucket>,
+    shared: Option<PathBuf>,
+    /// Whether the fallback from an unusable shared file was reported.
+    fallback_reported: AtomicBool,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// The available tokens; negative values are tokens reserved by waiting requests.
+    tokens: f64,
+    last: Instant,
+}
+
+/// The bucket of a shared rate limiter, as saved in its file.
+#[derive(Debug, Serialize, Deserialize)]
+struct SharedBucket {
+    /// The available tokens; negative values are tokens reserved by waiting requests.
+    tokens: f64,
+    /// When the tokens were last updated, in seconds since the Unix epoch.
+    last: f64,
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter allowing one request per `interval`, without bursts.
+    pub fn new(interval: Duration) -> Self {
+        RateLimiter {
+            interval,
+            burst: 1,
+            state: Mutex::new(Bucket {
+                tokens: 1.0,
+                last: Instant::now(),
+            }),
+            shared: None,
+            fallback_reported: AtomicBool::new(false),
+        }
+    }
+
+    /// Creates a rate limiter allowing `requests` requests per minute, without bursts.
+    ///
+    /// # Panics
+    /// Panics if `requests` is zero.
+    pub fn per_minute(requests: u32) -> Self {
+        assert!(
+            requests > 0,
+            "the rate limit must allow at least one request"
+        );
+        Self::new(Duration::from_secs(60) / requests)
+    }
+
+    /// Sets the number of requests that can be sent back to back before being spaced out.
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self.state
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+            .tokens = f64::from(self.burst);
+        self
+    }
+
+    /// Shares the bucket with every process using the same file, such as
+    /// `default_shared_path`.
+    ///
+    /// The file is locked while a request takes its token, so the processes together send at
+    /// most one request per interval, after the burst; they should use the same interval and
+    /// burst size. If the file cannot be used, requests fall back to the bucket of this
+    /// limiter, and a warning with the reason is printed to stderr the first time.
+    ///
+    /// # Arguments
+    /// * `path` - The file the tokens are kept in, created if missing.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::ratelimit::RateLimiter;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let path = std::env::temp_dir().join("ddg-doctest-ratelimit.json");
+    ///     let _ = std::fs::remove_file(&path);
+    ///     // Two limiters sharing a file, as two processes would.
+    ///     let cron = RateLimiter::new(Duration::from_millis(20)).shared(&path);
+    ///     let interactive = RateLimiter::new(Duration::from_millis(20)).shared(&path);
+    ///
+    ///     let start = Instant::now();
+    ///     cron.acquire().await;
+    ///     interactive.acquire().await;
+    ///     assert!(start.elapsed() >= Duration::from_millis(20));
+    /// }
+    /// ```
+    pub fn shared(mut self, path: impl Into<PathBuf>) -> Self {
+        self.shared = Some(path.into());
+        self
+    }
+
+    /// Returns the file of the bucket shared with other processes, if any.
+    pub fn shared_path(&self) -> Option<&Path> {
+        self.shared.as_deref()
+    }
+
+    /// Returns the default file of a shared bucket: `ddg/ratelimit.json` in the XDG runtime
+    /// directory, or in the user cache directory (`$XDG_CACHE_HOME`, or `~/.cache`), or, as a
+    /// last resort, in a per-user directory of the temporary directory.
+    ///
+    /// On Unix, a directory of the bucket owned by another user is refused, so that the
+    /// predictable name of the last one cannot be taken over.
+    pub fn default_shared_path() -> PathBuf {
+        let dir = |var: &str| {
+            env::var_os(var)
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from)
+        };
+        dir("XDG_RUNTIME_DIR")
+            .or_else(|| dir("XDG_CACHE_HOME"))
+            .or_else(|| dir("HOME").map(|home| home.join(".cache")))
+            .map(|dir| dir.join("ddg"))
+            .unwrap_or_else(|| {
+                let user = env::var("USER").unwrap_or_default();
+                env::temp_dir().join(format!("ddg-{}", user))
+            })
+            .join("ratelimit.json")
+    }
+
+    /// Returns the time between two requests once the burst is used.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Waits until a request may be sent, and returns how long it waited.
+    pub async fn acquire(&self) -> Duration {
+        let wait = match &self.shared {
+            Some(path) => match self.reserve_shared(path).await {
+                Ok(wait) => wait,
+                Err(err) => {
+                    if !self.fallback_reported.swap(true, Ordering::Relaxed) {
+                        eprintln!(
+                            "Warning: the shared rate limit file {} cannot be used ({}), \
+                             limiting the requests of this process only",
+                            path.display(),
+                            err
+                        );
+                    }
+                    self.reserve()
+                }
+            },
+            None => self.reserve(),
+        };
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+        wait
+    }
+
+    /// Takes a token from the bucket of this limiter, returning how long to wait for it.
+    fn reserve(&self) -> Duration {
+        let mut bucket = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last);
+        bucket.last = now;
+        self.take(&mut bucket.tokens, elapsed)
+    }
+
+    /// Takes a token from the bucket shared in a file, returning how long to wait for it.
+    ///
+    /// The file is locked for the time it is read and written. While another process holds the
+    /// lock, the lock is retried every millisecond rather than blocking the async runtime.
+    async fn reserve_shared(&self, path: &Path) -> io::Result<Duration> {
+        let mut file = open_shared(path)?;
+        loop {
+            match file.try_lock() {
+                Ok(()) => break,
+                Err(TryLockError::WouldBlock) => {
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                }
+                Err(TryLockError::Error(err)) => return Err(err),
+            }
+        }
+
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        // A missing or corrupt file starts with a full bucket.
+        let mut bucket = serde_json::from_str(&content).unwrap_or(SharedBucket {
+            tokens: f64::from(self.burst),
+            last: now,
+        });
+        // The clock may go back; time never refills the bucket negatively.
+        let elapsed = Duration::from_secs_f64((now - bucket.last).max(0.0));
+        bucket.last = now;
+        let wait = self.take(&mut bucket.tokens, elapsed);
+
+        file.set_len(0)?;
+        file.rewind()?;
+        file.write_all(serde_json::to_string(&bucket)?.as_bytes())?;
+        file.unlock()?;
+        Ok(wait)
+    }
+
+    /// Refills tokens for the time elapsed since the last request and takes one, returning how
+    /// long to wait for it.
+    fn take(&self, tokens: &mut f64, elapsed: Duration) -> Duration {
+        if !self.interval.is_zero() {
+            let refilled = elapsed.as_secs_f64() / self.interval.as_secs_f64();
+            *tokens = (*tokens + refilled).min(f64::from(self.burst));
+        }
+        *tokens -= 1.0;
+
+        if *tokens >= 0.0 || self.interval.is_zero() {
+            Duration::ZERO
+        } else {
+            self.interval.mul_f64(-*tokens)
+        }
+    }
+}
+
+/// Opens the file of a shared bucket, creating it and its directory, readable only by the user,
+/// if needed.
+///
+/// On Unix, the file must be a regular file readable by the user only, and its directory must
+/// belong to the owner of the file, unless it is a sticky directory such as `/tmp`.
+#[cfg(unix)]
+fn open_shared(path: &Path) -> io::Result<File> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, OpenOptionsExt};
+
+    let refuse = |path: &Path, reason: &str| {
+        io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("{} {}", path.display(), reason),
+        )
+    };
+
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+    if let Some(dir) = dir {
+        fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(dir)?;
+    }
+    if fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+        return Err(refuse(path, "is a symbolic link"));
+    }
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .mode(0o600)
+        .open(path)?;
+    // Only its owner can open a file no one else may read, so the file belongs to the user.
+    let metadata = file.metadata()?;
+    if metadata.mode() & 0o077 != 0 {
+        return Err(refuse(path, "is readable by other users"));
+    }
+    if let Some(dir) = dir {
+        let dir_metadata = fs::metadata(dir)?;
+        if dir_metadata.uid() != metadata.uid() && dir_metadata.mode() & 0o1000 == 0 {
+            return Err(refuse(dir, "belongs to another user"));
+        }
+    }
+    Ok(file)
+}
+
+/// Opens the file of a shared bucket, creating it and its directory if needed.
+#[cfg(not(unix))]
+fn open_shared(path: &Path) -> io::Result<File> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
@@ -0,0 +1,480 @@
+#[cfg(feature = "news")]
+use crate::colors;
+#[cfg(feature = "news")]
+use crate::date::{DateFormat, relative_time};
+use crate::link::Link;
+use crate::response::SearchResult;
+use std::env;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// The `TERM_PROGRAM` values of terminals supporting OSC 8 hyperlinks.
+const HYPERLINK_PROGRAMS: &[&str] = &[
+    "ghostty",
+    "Hyper",
+    "iTerm.app",
+    "terminology",
+    "vscode",
+    "WezTerm",
+];
+
+/// The `TERM` prefixes of terminals supporting OSC 8 hyperlinks.
+const HYPERLINK_TERMS: &[&str] = &["alacritty", "foot", "xterm-ghostty", "xterm-kitty"];
+
+/// Returns `true` if the terminal is known to support OSC 8 hyperlinks.
+///
+/// The terminal is detected from the environment variables it sets (e.g. `TERM_PROGRAM`,
+/// `VTE_VERSION`, or `WT_SESSION`). `FORCE_HYPERLINK=1` forces hyperlinks on and
+/// `FORCE_HYPERLINK=0` off. Whether stdout is a terminal at all is left to the caller.
+pub fn supports_hyperlinks() -> bool {
+    if let Ok(force) = env::var("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+    let var = |name: &str| env::var(name).unwrap_or_default();
+
+    let term = var("TERM");
+    if term == "dumb" || env::var_os("CI").is_some() {
+        return false;
+    }
+    env::var_os("DOMTERM").is_some()
+        || env::var_os("WT_SESSION").is_some()
+        || env::var_os("KONSOLE_VERSION").is_some()
+        || var("VTE_VERSION").parse::<u32>().is_ok_and(|v| v >= 5000)
+        || HYPERLINK_PROGRAMS.contains(&var("TERM_PROGRAM").as_str())
+        || HYPERLINK_TERMS
+            .iter()
+            .any(|prefix| term.starts_with(prefix))
+}
+
+/// Wraps text in an OSC 8 escape sequence, making it a clickable link to `url` in the
+/// terminals that support it.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::render::hyperlink;
+///
+/// assert_eq!(
+///     hyperlink("https://www.rust-lang.org/", "Rust"),
+///     "\x1b]8;;https://www.rust-lang.org/\x1b\\Rust\x1b]8;;\x1b\\"
+/// );
+/// ```
+pub fn hyperlink(url: &str, text: &str) -> String {
+    format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+}
+
+/// Returns the number of terminal columns the text occupies.
+///
+/// Wide characters, such as CJK ideographs and most emoji, take two columns, and combining marks
+/// none.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::render::display_width;
+///
+/// assert_eq!(display_width("Rust"), 4);
+/// assert_eq!(display_width("錆"), 2);
+/// ```
+pub fn display_width(text: &str) -> usize {
+    text.width()
+}
+
+/// Truncates the text to at most `width` terminal columns, ending it with `…` when it is cut.
+///
+/// The text is cut between grapheme clusters, so that accents and emoji sequences are never
+/// split.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::render::truncate;
+///
+/// assert_eq!(truncate("Rust Programming Language", 10), "Rust Prog…");
+/// assert_eq!(truncate("Rust", 10), "Rust");
+/// assert_eq!(truncate("錆びない言語", 7), "錆びな…");
+/// ```
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let Some(available) = width.checked_sub(1) else {
+        return String::new();
+    };
+    let mut out = String::new();
+    let mut used = 0;
+    for grapheme in text.graphemes(true) {
+        let w = grapheme.width();
+        if used + w > available {
+            break;
+        }
+        used += w;
+        out.push_str(grapheme);
+    }
+    out.push('…');
+    out
+}
+
+/// Wraps the text at word boundaries into lines of at most `width` terminal columns.
+///
+/// Runs of whitespace, line breaks included, are collapsed into single spaces. Words wider than
+/// a whole line are split between grapheme clusters.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::render::wrap;
+///
+/// assert_eq!(
+///     wrap("A language empowering everyone", 12),
+///     vec!["A language", "empowering", "everyone"]
+/// );
+/// assert_eq!(wrap("Supercalifragilistic", 8), vec!["Supercal", "ifragili", "stic"]);
+/// ```
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut used = 0;
+
+    for word in text.split_whitespace() {
+        let w = word.width();
+        if used > 0 && used + 1 + w <= width {
+            line.push(' ');
+            line.push_str(word);
+            used += 1 + w;
+            continue;
+        }
+        if used > 0 {
+            lines.push(std::mem::take(&mut line));
+            used = 0;
+        }
+        if w <= width {
+            line.push_str(word);
+            used = w;
+            continue;
+        }
+        for grapheme in word.graphemes(true) {
+            let w = grapheme.width();
+            if used > 0 && used + w > width {
+                lines.push(std::mem::take(&mut line));
+                used = 0;
+            }
+            line.push_str(grapheme);
+            used += w;
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Renders search results as the text printed by the CLI.
+///
+/// By default, a result is rendered as its `Display` output, with the URL on its own line. With
+/// hyperlinks enabled, the title links to the URL instead, and the URL line is left out, unless
+/// the URL is malformed.
+///
+/// With a width set, titles are truncated and snippets wrapped to fit in that many columns. URLs
+/// are always printed whole, so that they can still be copied.
+///
+/// Results with a resolved favicon end with a `Favicon:` line, giving the path of the cached
+/// icon, or its URL if it could not be downloaded. Results whose link was checked end with a
+/// `Status:` line, and results whose page was fetched end with its byline and main text.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::render::Renderer;
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+///
+/// let result = SearchResult::Text(LiteSearchResult {
+///     title: String::from("Rust"),
+///     url: "https://www.rust-lang.org/".into(),
+///     snippet: String::from("Fast and reliable."),
+///     ..Default::default()
+/// });
+///
+/// let plain = Renderer::new();
+/// assert_eq!(plain.render(&result), "Rust\nhttps://www.rust-lang.org/\nFast and reliable.");
+///
+/// let linked = Renderer::new().hyperlinks(true);
+/// assert_eq!(
+///     linked.render(&result),
+///     "\x1b]8;;https://www.rust-lang.org/\x1b\\Rust\x1b]8;;\x1b\\\nFast and reliable."
+/// );
+///
+/// let narrow = Renderer::new().width(Some(10));
+/// assert_eq!(narrow.render(&result), "Rust\nhttps://www.rust-lang.org/\nFast and\nreliable.");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Renderer {
+    hyperlinks: bool,
+    width: Option<usize>,
+    #[cfg(feature = "news")]
+    dates: DateFormat,
+}
+
+impl Renderer {
+    /// Creates a renderer printing results as plain text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes the titles clickable OSC 8 hyperlinks to the results.
+    ///
+    /// # Arguments
+    /// * `enable` - Whether titles are rendered as hyperlinks.
+    pub fn hyperlinks(mut self, enable: bool) -> Self {
+        self.hyperlinks = enable;
+        self
+    }
+
+    /// Fits the rendered results in a number of terminal columns.
+    ///
+    /// # Arguments
+    /// * `width` - The number of columns, or `None` to never truncate nor wrap.
+    pub fn width(mut self, width: Option<usize>) -> Self {
+        self.width = width.filter(|&width| width > 0);
+        self
+    }
+
+    /// Renders the publication dates of news articles in a format.
+    ///
+    /// When the format is relative, the date, such as "3 hours ago", is followed by the
+    /// absolute date in a suffix, dimmed unless colors are turned off with
+    /// `colors::set_enabled`.
+    ///
+    /// # Arguments
+    /// * `dates` - The format of the dates.
+    #[cfg(feature = "news")]
+    pub fn dates(mut self, dates: DateFormat) -> Self {
+        self.dates = dates;
+        self
+    }
+
+    /// Renders a search result.
+    ///
+    /// # Arguments
+    /// * `result` - The search result to render.
+    ///
+    /// # Returns
+    /// The rendered result, without a trailing line break.
+    pub fn render(&self, result: &SearchResult) -> String {
+        let mut lines = match result {
+            SearchResult::Text(r) => {
+                let mut lines = self.title(&r.url, &r.title);
+                lines.extend(self.wrap(&r.snippet));
+                lines
+            }
+            SearchResult::Image(r) => {
+                let mut lines = self.title(&r.url, &r.title);
+                lines.push(r.image.to_string());
+                if let Some(check) = &r.image_check {
+                    lines.push(format!("File: {}", check));
+                }
+                lines
+            }
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => {
+                let date = match &r.date {
+                    Some(date) if self.dates.is_relative() => {
+                        let (dim, normal) = if colors::enabled() {
+                            ("\x1b[2m", "\x1b[22m")
+                        } else {
+                            ("", "")
+                        };
+                        format!(
+                            "{} {}({}){}",
+                            relative_time(date, &chrono::Utc::now()),
+                            dim,
+                            self.dates.absolute(date),
+                            normal
+                        )
+                    }
+                    Some(date) => self.dates.absolute(date),
+                    None => String::from("Unknown date"),
+                };
+                let mut lines = vec![date];
+                lines.extend(self.title(&r.url, &r.title));
+                lines
+            }
+        };
+        if let Some(favicon) = result.favicon() {
+            lines.push(format!("Favicon: {}", favicon.location()));
+        }
+        if let Some(check) = result.link_check() {
+            lines.push(format!("Status: {}", check));
+        }
+        if !result.also_on().is_empty() {
+            let sites: Vec<&str> = result
+                .also_on()
+                .iter()
+                .map(|link| link.domain().unwrap_or(link.as_str()))
+                .collect();
+            lines.push(format!("Also on: {}", sites.join(", ")));
+        }
+        if let Some(content) = result.content() {
+            if let Some(byline) = &content.byline {
+                lines.push(format!("By {}", byline));
+            }
+            for paragraph in content.paragraphs() {
+                lines.push(String::new());
+                lines.extend(self.wrap(paragraph));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Returns the title line, followed by the URL line unless the title links to it.
+    fn title(&self, url: &Link, title: &str) -> Vec<String> {
+        let title = match self.width {
+            Some(width) => truncate(title, width),
+            None => title.to_string(),
+        };
+        match self.link(url, &title) {
+            Some(link) => vec![link],
+            None => vec![title, url.to_string()],
+        }
+    }
+
+    /// Returns the lines of a snippet, wrapped to the width if there is one.
+    fn wrap(&self, snippet: &str) -> Vec<String> {
+        match self.width {
+            Some(width) => wrap(snippet, width),
+            None => vec![snippet.to_string()],
+        }
+    }
+
+    /// Returns the text as a hyperlink to the URL, if hyperlinks are enabled and the URL is
+    /// valid.
+    fn link(&self, url: &Link, text: &str) -> Option<String> {
+        (self.hyperlinks && url.is_valid()).then(|| hyperlink(url.as_str(), text))
+    }
+}
+
+/// Renders search results as strictly linear text for screen readers, as printed by
+/// `--plain`.
+///
+/// Every line holds a single field, introduced by a spoken label such as `Link:` instead of
+/// punctuation or layout, and results start with their position, e.g. `Result 3 of 10:`. No
+/// escape sequences are ever written, and lines are never truncated nor wrapped, leaving that
+/// to the screen reader.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::render::PlainRenderer;
+/// use duckduckgo::response::{ImageResult, LiteSearchResult, SearchResult};
+///
+/// let result = SearchResult::Text(LiteSearchResult {
+///     title: String::from("Rust"),
+///     url: "https://www.rust-lang.org/".into(),
+///     snippet: String::from("Fast and reliable."),
+///     ..Default::default()
+/// });
+/// assert_eq!(
+///     PlainRenderer::new().render(&result, 3, 10),
+///     "Result 3 of 10: Rust\nLink: https://www.rust-lang.org/\nSummary: Fast and reliable."
+/// );
+///
+/// let image = SearchResult::Image(ImageResult {
+///     title: String::from("Ferris"),
+///     image: "https://rustacean.net/ferris.png".into(),
+///     url: "https://rustacean.net/".into(),
+///     width: 800,
+///     height: 600,
+///     ..Default::default()
+/// });
+/// assert_eq!(
+///     PlainRenderer::new().render(&image, 1, 1),
+///     "Result 1 of 1: Ferris\nLink: https://rustacean.net/\n\
+///      Image: https://rustacean.net/ferris.png\nSize: 800 by 600 pixels"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PlainRenderer;
+
+impl PlainRenderer {
+    /// Creates a plain renderer.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Renders a search result, announcing its position among the results.
+    ///
+    /// # Arguments
+    /// * `result` - The search result to render.
+    /// * `number` - The position of the result, starting at 1.
+    /// * `total` - The number of results.
+    ///
+    /// # Returns
+    /// The rendered result, without a trailing line break.
+    pub fn render(&self, result: &SearchResult, number: usize, total: usize) -> String {
+        let mut lines = vec![format!(
+            "Result {} of {}: {}",
+            number,
+            total,
+            plain_text(result.title())
+        )];
+        lines.push(format!("Link: {}", result.url()));
+        match result {
+            SearchResult::Text(r) => {
+                if !r.snippet.trim().is_empty() {
+                    lines.push(format!("Summary: {}", plain_text(&r.snippet)));
+                }
+            }
+            SearchResult::Image(r) => {
+                lines.push(format!("Image: {}", r.image));
+                if r.width > 0 && r.height > 0 {
+                    lines.push(format!("Size: {} by {} pixels", r.width, r.height));
+                }
+                if !r.source.is_empty() {
+                    lines.push(format!("Source: {}", r.source));
+                }
+                if let Some(check) = &r.image_check {
+                    lines.push(format!("File: {}", check));
+                }
+            }
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => {
+                if !r.source.is_empty() {
+                    lines.push(format!("Source: {}", r.source));
+                }
+                if let Some(date) = &r.date {
+                    lines.push(format!(
+                        "Published: {}",
+                        date.format("%-d %B %Y at %H:%M UTC")
+                    ));
+                }
+                if !r.body.trim().is_empty() {
+                    lines.push(format!("Summary: {}", plain_text(&r.body)));
+                }
+            }
+        }
+        if let Some(check) = result.link_check() {
+            lines.push(format!("Status: {}", check));
+        }
+        if !result.also_on().is_empty() {
+            let sites: Vec<&str> = result
+                .also_on()
+                .iter()
+                .map(|link| link.domain().unwrap_or(link.as_str()))
+                .collect();
+            lines.push(format!("Also on: {}", sites.join(" and ")));
+        }
+        if let Some(content) = result.content() {
+            if let Some(byline) = &content.byline {
+                lines.push(format!("Author: {}", plain_text(byline)));
+            }
+            for paragraph in content.paragraphs() {
+                lines.push(plain_text(paragraph));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Collapses the whitespace of a text, line breaks included, into single spaces, and drops its
+/// control characters, so that it is read as a single line.
+fn plain_text(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| word.chars().filter(|c| !c.is_control()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,107 @@
+use crate::backend::{FallbackBackend, HtmlBackend, LiteBackend};
+use crate::browser::Browser;
+use crate::content::PageContent;
+use crate::params::SearchParams;
+use crate::response::SearchResult;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// The results of a search bundled with the readable text of their pages, ready to be handed
+/// to a language model as retrieved context.
+///
+/// It is serialized as `{query, backend, results, extracted_texts, fetched_at}`, the document
+/// printed by `--output rag-json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResearchBundle {
+    /// The search query.
+    pub query: String,
+    /// The name of the backend that served the results (e.g. `"html"`).
+    pub backend: String,
+    /// The search results, without their page content, which is in `extracted_texts`.
+    pub results: Vec<SearchResult>,
+    /// The readable content of the result pages that could be fetched.
+    pub extracted_texts: Vec<ExtractedText>,
+    /// When the results and pages were fetched, unless left out for reproducible output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetched_at: Option<DateTime<Utc>>,
+}
+
+/// The readable content of the page of a search result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExtractedText {
+    /// The rank of the result the page belongs to.
+    pub rank: usize,
+    /// The content of the page.
+    #[serde(flatten)]
+    pub content: PageContent,
+}
+
+impl Browser {
+    /// Searches DuckDuckGo and fetches the readable content of the top results in one call, for
+    /// retrieval-augmented generation.
+    ///
+    /// # Arguments
+    /// * `query` - The search query.
+    /// * `n` - The number of results to return and fetch the pages of.
+    ///
+    /// # Returns
+    /// The results and the text extracted from their pages.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use duckduckgo::browser::Browser;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let browser = Browser::new(reqwest::Client::new());
+    ///     let bundle = browser.research("rust ownership", 3).await?;
+    ///     for text in &bundle.extracted_texts {
+    ///         println!("[{}] {}\n{}", text.rank, text.content.title, text.content.text);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn research(&self, query: &str, n: usize) -> Result<ResearchBundle> {
+        self.research_with(&SearchParams::new(query).limit(n)).await
+    }
+
+    /// Searches DuckDuckGo with the given options and fetches the readable content of the
+    /// results, like `research`.
+    ///
+    /// The search is served by DuckDuckGo HTML, falling back to DuckDuckGo Lite.
+    ///
+    /// # Arguments
+    /// * `params` - The search options, whose limit is the number of pages fetched.
+    pub async fn research_with(&self, params: &SearchParams) -> Result<ResearchBundle> {
+        let fallback = FallbackBackend::new(vec![
+            Box::new(HtmlBackend::new(self)),
+            Box::new(LiteBackend::new(self)),
+        ]);
+        let fetched_at = Utc::now();
+        let (backend, page) = fallback.search_fallback(params).await?;
+        let mut results = page.results;
+        self.fetch_contents(&mut results).await;
+
+        let extracted_texts = results
+            .iter_mut()
+            .filter_map(|result| {
+                let rank = result.rank();
+                result
+                    .content_mut()
+                    .take()
+                    .map(|content| ExtractedText { rank, content })
+            })
+            .collect();
+
+        Ok(ResearchBundle {
+            query: params.query.clone(),
+            backend: backend.name().to_string(),
+            results,
+            extracted_texts,
+            fetched_at: Some(fetched_at),
+        })
+    }
+}
@@ -1,6 +1,17 @@
-use crate::topic::Topic;
-use serde::Deserialize;
+use crate::content::PageContent;
+use crate::favicon::Favicon;
+use crate::image_check::ImageCheck;
+use crate::link::{Link, LinkCheck};
+use crate::parser::ParseWarning;
+use crate::topic::{LeafTopics, Topic};
+#[cfg(feature = "news")]
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
 
 /// A struct representing the response received from the DuckDuckGo API.
 #[derive(Debug, Deserialize)]
@@ -49,21 +60,37 @@ pub struct Response {
     #[serde(rename = "Heading")]
     pub heading: Option<String>,
 
-    /// The URL of the image associated with the search result.
-    #[serde(rename = "Image")]
+    /// The absolute URL of the image associated with the search result.
+    #[serde(
+        rename = "Image",
+        default,
+        deserialize_with = "crate::link::deserialize_absolute_url_opt"
+    )]
     pub image: Option<String>,
 
-    /// The height of the image.
-    #[serde(rename = "ImageHeight")]
-    pub image_height: Value,
+    /// The height of the image in pixels, if known.
+    #[serde(
+        rename = "ImageHeight",
+        default,
+        deserialize_with = "crate::icon::deserialize_dimension"
+    )]
+    pub image_height: Option<u32>,
 
-    /// Indicates whether the image is a logo.
-    #[serde(rename = "ImageIsLogo")]
-    pub image_is_logo: Value,
+    /// Whether the image is a logo, if known.
+    #[serde(
+        rename = "ImageIsLogo",
+        default,
+        deserialize_with = "crate::icon::deserialize_flag"
+    )]
+    pub image_is_logo: Option<bool>,
 
-    /// The width of the image.
-    #[serde(rename = "ImageWidth")]
-    pub image_width: Value,
+    /// The width of the image in pixels, if known.
+    #[serde(
+        rename = "ImageWidth",
+        default,
+        deserialize_with = "crate::icon::deserialize_dimension"
+    )]
+    pub image_width: Option<u32>,
 
     /// The infobox associated with the search result.
     #[serde(rename = "Infobox")]
@@ -92,6 +119,205 @@ pub struct Response {
     /// The date when the response was created.
     #[serde(rename = "CreatedDate")]
     pub created_date: Option<String>,
+
+    /// The metadata of the source of the instant answer.
+    #[serde(rename = "meta")]
+    pub meta: Option<Value>,
+
+    /// The fields of the response this struct does not model, by name, so that the fields
+    /// DuckDuckGo adds remain accessible. `from_json_strict` rejects responses with any.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+
+    /// The original JSON document, if the browser keeps it (see `BrowserBuilder::with_raw`),
+    /// to read the fields this struct does not model.
+    #[serde(skip)]
+    pub raw: Option<Value>,
+}
+
+impl Response {
+    /// Parses a response, failing if it has fields this struct does not model, to detect
+    /// changes of the API early, e.g. in tests.
+    ///
+    /// # Arguments
+    /// * `json` - The JSON document sent by the Instant Answer API.
+    ///
+    /// # Errors
+    /// Fails if the document is not a valid response, or if it has unknown fields, which are
+    /// named in the error.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::response::Response;
+    ///
+    /// let json = r#"{"ImageHeight": "", "ImageIsLogo": "", "ImageWidth": "",
+    ///     "RelatedTopics": [], "Results": [], "Type": "", "Sponsored": true}"#;
+    ///
+    /// let response: Response = serde_json::from_str(json).unwrap();
+    /// assert_eq!(response.extra["Sponsored"], true);
+    ///
+    /// let err = Response::from_json_strict(json).unwrap_err();
+    /// assert_eq!(err.to_string(), "Unknown fields in Instant Answer response: Sponsored");
+    /// ```
+    pub fn from_json_strict(json: &str) -> anyhow::Result<Self> {
+        let response: Response = serde_json::from_str(json)?;
+        if !response.extra.is_empty() {
+            let fields: Vec<&str> = response.extra.keys().map(String::as_str).collect();
+            anyhow::bail!(
+                "Unknown fields in Instant Answer response: {}",
+                fields.join(", ")
+            );
+        }
+        Ok(response)
+    }
+
+    /// Returns the URL the query redirects to, for `!bang` queries such as `!w rust`, if it is
+    /// a valid absolute URL.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::response::Response;
+    ///
+    /// let response: Response = serde_json::from_str(
+    ///     r#"{"Redirect": "https://en.wikipedia.org/wiki/Special:Search?search=rust",
+    ///         "ImageHeight": "", "ImageIsLogo": "", "ImageWidth": "",
+    ///         "RelatedTopics": [], "Results": [], "Type": "E"}"#,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     response.redirect_url().unwrap().as_str(),
+    ///     "https://en.wikipedia.org/wiki/Special:Search?search=rust"
+    /// );
+    /// ```
+    pub fn redirect_url(&self) -> Option<Url> {
+        self.redirect
+            .as_deref()
+            .map(str::trim)
+            .filter(|redirect| !redirect.is_empty())
+            .and_then(|redirect| Url::parse(redirect).ok())
+    }
+
+    /// Returns the single most relevant line of the response: the instant answer, such as a
+    /// calculation or a conversion, or else the abstract, with `answer_type` set to `None`.
+    ///
+    /// Line breaks and runs of whitespace are collapsed into single spaces.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::response::Response;
+    ///
+    /// let response: Response = serde_json::from_str(
+    ///     r#"{"Answer": "", "AbstractText": "Rust is a general-purpose\nprogramming language.",
+    ///         "ImageHeight": "", "ImageIsLogo": "", "ImageWidth": "",
+    ///         "RelatedTopics": [], "Results": [], "Type": "A"}"#,
+    /// )
+    /// .unwrap();
+    /// let answer = response.concise_answer().unwrap();
+    /// assert_eq!(answer.text, "Rust is a general-purpose programming language.");
+    /// assert_eq!(answer.answer_type, None);
+    /// ```
+    pub fn concise_answer(&self) -> Option<Answer> {
+        let single_line = |text: &Option<String>| {
+            let text = text.as_deref()?.split_whitespace().collect::<Vec<_>>();
+            (!text.is_empty()).then(|| text.join(" "))
+        };
+        match single_line(&self.answer) {
+            Some(text) => Some(Answer {
+                text,
+                answer_type: single_line(&self.answer_type),
+            }),
+            None => single_line(&self.abstract_text).map(|text| Answer {
+                text,
+                answer_type: None,
+            }),
+        }
+    }
+
+    /// Returns the leaf topics of the related topics, descending into the groups of topics
+    /// the API sends for ambiguous queries.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::response::Response;
+    ///
+    /// let response: Response = serde_json::from_str(
+    ///     r#"{"ImageHeight": "", "ImageIsLogo": "", "ImageWidth": "", "Results": [], "Type": "D",
+    ///         "AbstractURL": "https://en.wikipedia.org/wiki/Rust",
+    ///         "RelatedTopics": [
+    ///             {"FirstURL": "https://duckduckgo.com/Rust_(programming_language)",
+    ///              "Text": "Rust (programming language)"},
+    ///             {"Name": "Biology", "Topics": [
+    ///                 {"FirstURL": "https://duckduckgo.com/Rust_(fungus)", "Text": "Rust (fungus)"}
+    ///             ]}
+    ///         ]}"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let texts: Vec<_> = response.iter_topics().filter_map(|t| t.text.as_deref()).collect();
+    /// assert_eq!(texts, ["Rust (programming language)", "Rust (fungus)"]);
+    /// assert_eq!(
+    ///     response.urls(),
+    ///     [
+    ///         "https://en.wikipedia.org/wiki/Rust",
+    ///         "https://duckduckgo.com/Rust_(programming_language)",
+    ///         "https://duckduckgo.com/Rust_(fungus)",
+    ///     ]
+    /// );
+    /// assert_eq!(response.best_answer(), Some("Rust (programming language)"));
+    /// ```
+    pub fn iter_topics(&self) -> LeafTopics<'_> {
+        LeafTopics::new(&self.related_topics)
+    }
+
+    /// Returns the URLs the response links to, without duplicates: the URL of the abstract,
+    /// then the URLs of the leaf topics.
+    pub fn urls(&self) -> Vec<&str> {
+        let mut urls: Vec<&str> = Vec::new();
+        let topic_urls = self
+            .iter_topics()
+            .filter_map(|topic| topic.first_url.as_deref());
+        for url in self.abstract_url.as_deref().into_iter().chain(topic_urls) {
+            let url = url.trim();
+            if !url.is_empty() && !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+        urls
+    }
+
+    /// Returns the most direct answer of the response: the instant answer, else the abstract,
+    /// else the definition, else the text of the first topic.
+    pub fn best_answer(&self) -> Option<&str> {
+        let first_topic = self.iter_topics().find_map(|topic| topic.text.as_deref());
+        [
+            self.answer.as_deref(),
+            self.abstract_text.as_deref(),
+            self.definition.as_deref(),
+            first_topic,
+        ]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|text| !text.is_empty())
+    }
+
+    /// Returns the outcome of the query: the redirect of a `!bang` query, or the response.
+    pub fn into_outcome(self) -> SearchOutcome {
+        match self.redirect_url() {
+            Some(url) => SearchOutcome::Redirect(url),
+            None => SearchOutcome::Response(Box::new(self)),
+        }
+    }
+}
+
+/// The outcome of an Instant Answer API query.
+#[derive(Debug)]
+pub enum SearchOutcome {
+    /// The query is a `!bang` redirecting to the search of another site, e.g. `!w rust` to
+    /// Wikipedia.
+    Redirect(Url),
+    /// The response to display.
+    Response(Box<Response>),
 }
 
 /// Enum representing different result formats for DuckDuckGo searches.
@@ -103,46 +329,760 @@ pub enum ResultFormat {
 }
 
 /// Represents a single image search result from DuckDuckGo.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct ImageResult {
     /// The title or description of the image.
     pub title: String,
     /// The direct URL to the full-sized image.
-    pub image: String,
+    pub image: Link,
     /// The URL to the image thumbnail (smaller preview).
-    pub thumbnail: String,
+    pub thumbnail: Link,
     /// The URL of the page hosting the image.
-    pub url: String,
+    pub url: Link,
     /// The height of the image in pixels.
     pub height: u32,
     /// The width of the image in pixels.
     pub width: u32,
     /// The source or provider of the image.
     pub source: String,
+    /// The 1-based position of the result in DuckDuckGo's results for the query.
+    pub rank: usize,
+    /// The 1-based number of the page the result was found on, counting the pages fetched by
+    /// the search.
+    pub page: usize,
+    /// The name of the backend that returned the result (e.g. `"lite"`).
+    pub backend: String,
+    /// The favicon of the site, if favicons were resolved (see `Browser::resolve_favicons`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub favicon: Option<Favicon>,
+    /// The outcome of checking the URL, if links were checked (see `Browser::check_links`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_check: Option<LinkCheck>,
+    /// The outcome of verifying the image file, if images were verified (see
+    /// `Browser::verify_images`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_check: Option<ImageCheck>,
+    /// The readable content of the page, if it was fetched (see `Browser::fetch_contents`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<PageContent>,
+    /// The URLs of the near-duplicate results collapsed into this one, if duplicates were
+    /// collapsed (see `ResultFilters::collapse_duplicates`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub also_on: Vec<Link>,
 }
 
 /// Represents a single news article result from DuckDuckGo.
+#[cfg(feature = "news")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct NewsResult {
-    /// The publication date of the news article in ISO-8601 format.
-    pub date: String,
+    /// The publication date of the news article, if DuckDuckGo sent a valid one.
+    pub date: Option<DateTime<Utc>>,
     /// The headline or title of the news article.
     pub title: String,
     /// A short excerpt or summary of the news article.
     pub body: String,
     /// The URL linking to the full news article.
-    pub url: String,
+    pub url: Link,
     /// Optional URL of an image associated with the news article.
-    pub image: Option<String>,
+    pub image: Option<Link>,
     /// The source or publisher of the news article.
     pub source: String,
+    /// The domain of the article URL without its `www.` prefix (e.g. `"reuters.com"`), if the
+    /// URL is valid.
+    pub domain: Option<String>,
+    /// The 1-based position of the result in DuckDuckGo's results for the query.
+    pub rank: usize,
+    /// The 1-based number of the page the result was found on, counting the pages fetched by
+    /// the search.
+    pub page: usize,
+    /// The name of the backend that returned the result (e.g. `"lite"`).
+    pub backend: String,
+    /// The favicon of the site, if favicons were resolved (see `Browser::resolve_favicons`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub favicon: Option<Favicon>,
+    /// The outcome of checking the URL, if links were checked (see `Browser::check_links`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_check: Option<LinkCheck>,
+    /// The readable content of the page, if it was fetched (see `Browser::fetch_contents`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<PageContent>,
+    /// The URLs of the near-duplicate results collapsed into this one, if duplicates were
+    /// collapsed (see `ResultFilters::collapse_duplicates`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub also_on: Vec<Link>,
+}
+
+#[cfg(feature = "news")]
+impl NewsResult {
+    /// Returns `true` if the article comes from the given source.
+    ///
+    /// # Arguments
+    /// * `source` - A domain, matching the article domain and its subdomains (e.g.
+    ///   `"reuters.com"`), or a publisher name, matched case-insensitively (e.g. `"Rust Blog"`).
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::fixtures;
+    ///
+    /// let news = fixtures::news().unwrap();
+    /// assert!(news[0].is_from("rust-lang.org"));
+    /// assert!(news[0].is_from("rust blog"));
+    /// assert!(!news[0].is_from("lwn.net"));
+    /// ```
+    pub fn is_from(&self, source: &str) -> bool {
+        let source = source.trim().trim_start_matches("www.").to_lowercase();
+        let from_domain = self.domain.as_deref().is_some_and(|domain| {
+            domain == source
+                || domain
+                    .strip_suffix(source.as_str())
+                    .is_some_and(|sub| sub.ends_with('.'))
+        });
+        from_domain || self.source.to_lowercase() == source
+    }
 }
 
 /// Represents a single search result from DuckDuckGo Lite search.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct LiteSearchResult {
     /// The title or headline of the search result.
     pub title: String,
     /// The URL linked by the search result.
-    pub url: String,
+    pub url: Link,
     /// A short snippet or preview text from the search result.
     pub snippet: String,
+    /// The 1-based position of the result in DuckDuckGo's results for the query.
+    pub rank: usize,
+    /// The 1-based number of the page the result was found on, counting the pages fetched by
+    /// the search.
+    pub page: usize,
+    /// The name of the backend that returned the result (e.g. `"lite"`).
+    pub backend: String,
+    /// The favicon of the site, if favicons were resolved (see `Browser::resolve_favicons`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub favicon: Option<Favicon>,
+    /// The outcome of checking the URL, if links were checked (see `Browser::check_links`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_check: Option<LinkCheck>,
+    /// The readable content of the page, if it was fetched (see `Browser::fetch_contents`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<PageContent>,
+    /// The URLs of the near-duplicate results collapsed into this one, if duplicates were
+    /// collapsed (see `ResultFilters::collapse_duplicates`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub also_on: Vec<Link>,
+}
+
+/// Represents a single result returned by any search backend.
+///
+/// Results can be compared and hashed, e.g. to drop duplicates with a `HashSet`, and displayed
+/// the way the CLI prints them.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::fixtures;
+/// use duckduckgo::response::SearchResult;
+/// use std::collections::HashSet;
+///
+/// let page = fixtures::lite_page().unwrap();
+/// let mut results: Vec<SearchResult> = page.results.into_iter().map(SearchResult::Text).collect();
+/// results.push(results[0].clone());
+///
+/// let unique: HashSet<&SearchResult> = results.iter().collect();
+/// assert_eq!(unique.len(), results.len() - 1);
+/// assert!(results[0].to_string().starts_with(results[0].title()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "lowercase")]
+// The variants are close in size when the `news` feature is enabled, as it is by default.
+#[cfg_attr(not(feature = "news"), allow(clippy::large_enum_variant))]
+pub enum SearchResult {
+    /// A text result from the Instant Answer API, DuckDuckGo Lite, or DuckDuckGo HTML.
+    Text(LiteSearchResult),
+    /// An image result.
+    Image(ImageResult),
+    /// A news article result.
+    #[cfg(feature = "news")]
+    News(NewsResult),
+}
+
+impl SearchResult {
+    /// Returns the title of the result.
+    pub fn title(&self) -> &str {
+        match self {
+            SearchResult::Text(r) => &r.title,
+            SearchResult::Image(r) => &r.title,
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => &r.title,
+        }
+    }
+
+    /// Returns the URL of the page the result links to.
+    pub fn url(&self) -> &str {
+        match self {
+            SearchResult::Text(r) => r.url.as_str(),
+            SearchResult::Image(r) => r.url.as_str(),
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => r.url.as_str(),
+        }
+    }
+
+    /// Returns the snippet or excerpt of the result, if any.
+    pub fn snippet(&self) -> &str {
+        match self {
+            SearchResult::Text(r) => &r.snippet,
+            SearchResult::Image(_) => "",
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => &r.body,
+        }
+    }
+
+    /// Returns the 1-based position of the result in DuckDuckGo's results for the query.
+    pub fn rank(&self) -> usize {
+        match self {
+            SearchResult::Text(r) => r.rank,
+            SearchResult::Image(r) => r.rank,
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => r.rank,
+        }
+    }
+
+    /// Returns the 1-based number of the page the result was found on.
+    pub fn page(&self) -> usize {
+        match self {
+            SearchResult::Text(r) => r.page,
+            SearchResult::Image(r) => r.page,
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => r.page,
+        }
+    }
+
+    /// Returns the favicon of the site the result links to, if favicons were resolved.
+    pub fn favicon(&self) -> Option<&Favicon> {
+        match self {
+            SearchResult::Text(r) => r.favicon.as_ref(),
+            SearchResult::Image(r) => r.favicon.as_ref(),
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => r.favicon.as_ref(),
+        }
+    }
+
+    /// Returns the outcome of checking the URL of the result, if links were checked.
+    pub fn link_check(&self) -> Option<&LinkCheck> {
+        match self {
+            SearchResult::Text(r) => r.link_check.as_ref(),
+            SearchResult::Image(r) => r.link_check.as_ref(),
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => r.link_check.as_ref(),
+        }
+    }
+
+    /// Returns a mutable reference to the outcome of checking the URL of the result.
+    pub(crate) fn link_check_mut(&mut self) -> &mut Option<LinkCheck> {
+        match self {
+            SearchResult::Text(r) => &mut r.link_check,
+            SearchResult::Image(r) => &mut r.link_check,
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => &mut r.link_check,
+        }
+    }
+
+    /// Returns the readable content of the page the result links to, if it was fetched.
+    pub fn content(&self) -> Option<&PageContent> {
+        match self {
+            SearchResult::Text(r) => r.content.as_ref(),
+            SearchResult::Image(r) => r.content.as_ref(),
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => r.content.as_ref(),
+        }
+    }
+
+    /// Returns a mutable reference to the readable content of the page of the result.
+    #[cfg(feature = "content")]
+    pub(crate) fn content_mut(&mut self) -> &mut Option<PageContent> {
+        match self {
+            SearchResult::Text(r) => &mut r.content,
+            SearchResult::Image(r) => &mut r.content,
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => &mut r.content,
+        }
+    }
+
+    /// Returns the domain of the page the result links to, if its URL is valid.
+    pub(crate) fn domain(&self) -> Option<&str> {
+        match self {
+            SearchResult::Text(r) => r.url.domain(),
+            SearchResult::Image(r) => r.url.domain(),
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => r.url.domain(),
+        }
+    }
+
+    /// Returns the URLs of the near-duplicate results collapsed into this one.
+    pub fn also_on(&self) -> &[Link] {
+        match self {
+            SearchResult::Text(r) => &r.also_on,
+            SearchResult::Image(r) => &r.also_on,
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => &r.also_on,
+        }
+    }
+
+    /// Returns a mutable reference to the URLs of the near-duplicate results collapsed into
+    /// this one.
+    pub(crate) fn also_on_mut(&mut self) -> &mut Vec<Link> {
+        match self {
+            SearchResult::Text(r) => &mut r.also_on,
+            SearchResult::Image(r) => &mut r.also_on,
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => &mut r.also_on,
+        }
+    }
+
+    /// Returns a mutable reference to the favicon of the result.
+    pub(crate) fn favicon_mut(&mut self) -> &mut Option<Favicon> {
+        match self {
+            SearchResult::Text(r) => &mut r.favicon,
+            SearchResult::Image(r) => &mut r.favicon,
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => &mut r.favicon,
+        }
+    }
+
+    /// Returns the name of the backend that returned the result.
+    pub fn backend(&self) -> &str {
+        match self {
+            SearchResult::Text(r) => &r.backend,
+            SearchResult::Image(r) => &r.backend,
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => &r.backend,
+        }
+    }
+
+    /// Formats the result as a Markdown list item linking to the result, followed by its
+    /// snippet, or the image for images, as a blockquote.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::response::{LiteSearchResult, SearchResult};
+    ///
+    /// let result = SearchResult::Text(LiteSearchResult {
+    ///     title: String::from("Rust [language]"),
+    ///     url: "https://www.rust-lang.org/".into(),
+    ///     snippet: String::from("Fast and reliable."),
+    ///     ..Default::default()
+    /// });
+    /// assert_eq!(
+    ///     result.to_markdown(),
+    ///     "- [Rust \\[language\\]](https://www.rust-lang.org/)\n  > Fast and reliable."
+    /// );
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let escape = |text: &str| {
+            text.replace('\\', "\\\\")
+                .replace('[', "\\[")
+                .replace(']', "\\]")
+        };
+        let destination = |url: &str| {
+            if url.contains([' ', '(', ')', '<', '>']) {
+                format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+            } else {
+                url.to_string()
+            }
+        };
+
+        let mut item = format!("- [{}]({})", escape(self.title()), destination(self.url()));
+        let quote = match self {
+            SearchResult::Image(r) => {
+                format!("![{}]({})", escape(&r.title), destination(r.image.as_str()))
+            }
+            _ => self.snippet().trim().to_string(),
+        };
+        for line in quote.lines().filter(|line| !line.trim().is_empty()) {
+            item.push_str("\n  > ");
+            item.push_str(line.trim());
+        }
+        item
+    }
+
+    /// Formats the result as an Org-mode list item linking to the result, followed by its
+    /// snippet, or the image for images, in a quote block.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::response::{LiteSearchResult, SearchResult};
+    ///
+    /// let result = SearchResult::Text(LiteSearchResult {
+    ///     title: String::from("Rust"),
+    ///     url: "https://www.rust-lang.org/".into(),
+    ///     snippet: String::from("Fast and reliable."),
+    ///     ..Default::default()
+    /// });
+    /// assert_eq!(
+    ///     result.to_org(),
+    ///     "- [[https://www.rust-lang.org/][Rust]]\n  #+begin_quote\n  Fast and reliable.\n  #+end_quote"
+    /// );
+    /// ```
+    pub fn to_org(&self) -> String {
+        // Org has no escape for brackets in links, so they are replaced or percent-encoded.
+        let description = |text: &str| text.replace('[', "(").replace(']', ")");
+        let target = |url: &str| url.replace('[', "%5B").replace(']', "%5D");
+
+        let mut item = format!(
+            "- [[{}][{}]]",
+            target(self.url()),
+            description(self.title())
+        );
+        let quote = match self {
+            SearchResult::Image(r) => format!("[[{}]]", target(r.image.as_str())),
+            _ => self.snippet().trim().to_string(),
+        };
+        let lines: Vec<&str> = quote
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        if !lines.is_empty() {
+            item.push_str("\n  #+begin_quote");
+            for line in lines {
+                item.push_str("\n  ");
+                item.push_str(line);
+            }
+            item.push_str("\n  #+end_quote");
+        }
+        item
+    }
+}
+
+impl fmt::Display for LiteSearchResult {
+    /// Formats the result as its title, URL, and snippet, one per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n{}\n{}", self.title, self.url, self.snippet)
+    }
+}
+
+impl fmt::Display for ImageResult {
+    /// Formats the result as its title, page URL, and image URL, one per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n{}\n{}", self.title, self.url, self.image)
+    }
+}
+
+#[cfg(feature = "news")]
+impl fmt::Display for NewsResult {
+    /// Formats the result as its publication date, if known, title, and URL, one per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(date) = &self.date {
+            writeln!(f, "{}", date)?;
+        }
+        write!(f, "{}\n{}", self.title, self.url)
+    }
+}
+
+impl fmt::Display for SearchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchResult::Text(r) => r.fmt(f),
+            SearchResult::Image(r) => r.fmt(f),
+            #[cfg(feature = "news")]
+            SearchResult::News(r) => r.fmt(f),
+        }
+    }
+}
+
+/// An opaque position in a paginated image or news search, used to fetch the next page.
+///
+/// A cursor can be kept as a string (with `to_string` and `parse`) or serialized, so a search
+/// can be resumed later without fetching the earlier pages again. It is only valid for the
+/// query it was returned for.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::response::Cursor;
+///
+/// let cursor: Cursor = "4-123456789:100:2".parse().unwrap();
+/// assert_eq!(cursor.to_string(), "4-123456789:100:2");
+/// assert!("no-offset".parse::<Cursor>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Cursor {
+    vqd: String,
+    offset: String,
+    page: usize,
+}
+
+impl Cursor {
+    /// Creates a cursor from the `vqd` token of a search, and the offset and number of the next
+    /// page.
+    pub(crate) fn new(vqd: impl Into<String>, offset: impl Into<String>, page: usize) -> Self {
+        Cursor {
+            vqd: vqd.into(),
+            offset: offset.into(),
+            page,
+        }
+    }
+
+    /// Returns the `vqd` token of the search.
+    #[cfg(any(feature = "images", feature = "news"))]
+    pub(crate) fn vqd(&self) -> &str {
+        &self.vqd
+    }
+
+    /// Returns the offset of the next page, sent as the `s` parameter.
+    #[cfg(any(feature = "images", feature = "news"))]
+    pub(crate) fn offset(&self) -> &str {
+        &self.offset
+    }
+
+    /// Returns the 1-based number of the next page.
+    #[cfg(any(feature = "images", feature = "news"))]
+    pub(crate) fn page(&self) -> usize {
+        self.page
+    }
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.vqd, self.offset, self.page)
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut parts = s.rsplitn(3, ':');
+        let (page, offset, vqd) = (parts.next(), parts.next(), parts.next());
+        match (vqd, offset, page.and_then(|p| p.parse().ok())) {
+            (Some(vqd), Some(offset), Some(page)) if !vqd.is_empty() && !offset.is_empty() => {
+                Ok(Cursor::new(vqd, offset, page))
+            }
+            _ => anyhow::bail!("Invalid cursor '{}'", s),
+        }
+    }
+}
+
+/// A single page of a paginated search.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Page<T> {
+    /// The items of the page.
+    pub items: Vec<T>,
+    /// The cursor of the next page, or `None` if this is the last page.
+    pub next: Option<Cursor>,
+    /// The malformed items skipped in the lenient parse mode.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ParseWarning>,
+    /// The original JSON document of the page, if the browser keeps it (see
+    /// `BrowserBuilder::with_raw`), to read the fields the result types do not model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw: Option<Value>,
+}
+
+/// The results of a paginated search that may have been cancelled before completing.
+#[derive(Debug, Clone)]
+pub struct PartialResults<T> {
+    /// The results fetched before the search completed or was cancelled.
+    pub results: Vec<T>,
+    /// Whether the search was cancelled, in which case `results` may be incomplete.
+    pub cancelled: bool,
+}
+
+impl<T> PartialResults<T> {
+    /// Creates the results of a search that ran to completion.
+    pub fn complete(results: Vec<T>) -> Self {
+        PartialResults {
+            results,
+            cancelled: false,
+        }
+    }
+
+    /// Creates the results of a search that was cancelled.
+    pub fn cancelled(results: Vec<T>) -> Self {
+        PartialResults {
+            results,
+            cancelled: true,
+        }
+    }
+}
+
+/// Represents a page of search results together with the hints DuckDuckGo attached to it.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SearchPage<T = LiteSearchResult> {
+    /// The search results found on the page.
+    pub results: Vec<T>,
+    /// The spelling correction suggested by DuckDuckGo, if any.
+    pub corrected_query: Option<String>,
+    /// The related searches suggested by DuckDuckGo, if any.
+    pub related_queries: Vec<String>,
+}
+
+impl<T> Default for SearchPage<T> {
+    fn default() -> Self {
+        SearchPage {
+            results: Vec::new(),
+            corrected_query: None,
+            related_queries: Vec::new(),
+        }
+    }
+}
+
+impl<T> SearchPage<T> {
+    /// Converts the results of the page, keeping the page hints.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> SearchPage<U> {
+        SearchPage {
+            results: self.results.into_iter().map(f).collect(),
+            corrected_query: self.corrected_query,
+            related_queries: self.related_queries,
+        }
+    }
+}
+
+/// Represents an instant answer (e.g. a calculation or conversion) from the DuckDuckGo API.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Answer {
+    /// The answer text.
+    pub text: String,
+    /// The type of answer provided (e.g. `"calc"`), if available.
+    pub answer_type: Option<String>,
+}
+
+impl Answer {
+    /// Returns the result of a calculation or conversion answer, without the expression it
+    /// echoes: the text after its last `=`, or the whole text if there is none.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::response::Answer;
+    ///
+    /// let answer = Answer {
+    ///     text: String::from("2 ^ 10 = 1,024"),
+    ///     answer_type: Some(String::from("calc")),
+    /// };
+    /// assert_eq!(answer.value(), "1,024");
+    ///
+    /// let answer = Answer {
+    ///     text: String::from("3.6 km"),
+    ///     answer_type: Some(String::from("conversions")),
+    /// };
+    /// assert_eq!(answer.value(), "3.6 km");
+    /// ```
+    pub fn value(&self) -> &str {
+        match self.text.rsplit_once('=') {
+            Some((_, value)) if !value.trim().is_empty() => value.trim(),
+            _ => self.text.trim(),
+        }
+    }
+}
+
+/// The parts of speech recognized at the start of dictionary definitions.
+const PARTS_OF_SPEECH: &[&str] = &[
+    "abbreviation",
+    "adjective",
+    "adverb",
+    "article",
+    "conjunction",
+    "determiner",
+    "idiom",
+    "interjection",
+    "noun",
+    "phrase",
+    "prefix",
+    "preposition",
+    "pronoun",
+    "suffix",
+    "verb",
+];
+
+/// Represents a dictionary definition from the DuckDuckGo API.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Definition {
+    /// The definition text, without the defined word nor the part of speech.
+    pub text: String,
+    /// The part of speech of the defined word (e.g. `"noun"`), if the definition gives it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_of_speech: Option<String>,
+    /// The source of the definition, if available.
+    pub source: Option<String>,
+    /// The URL associated with the definition, if available.
+    pub url: Option<String>,
+}
+
+impl Definition {
+    /// Parses the `Definition` field of the API, such as `"cat definition: noun. A small
+    /// domesticated carnivore."`, into its part of speech and its text.
+    ///
+    /// # Arguments
+    /// * `text` - The definition, as sent by the API.
+    /// * `source` - The source of the definition, if available.
+    /// * `url` - The URL of the definition, if available.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::response::Definition;
+    ///
+    /// let text = "cat definition: noun. A small domesticated carnivore.";
+    /// let definition = Definition::parse(text, None, None);
+    /// assert_eq!(definition.part_of_speech.as_deref(), Some("noun"));
+    /// assert_eq!(definition.text, "A small domesticated carnivore.");
+    ///
+    /// let definition = Definition::parse("Nounal usage of a word.", None, None);
+    /// assert_eq!(definition.part_of_speech, None);
+    /// assert_eq!(definition.text, "Nounal usage of a word.");
+    /// ```
+    pub fn parse(text: &str, source: Option<String>, url: Option<String>) -> Self {
+        let text = text.trim();
+        let text = match text.split_once(" definition:") {
+            Some((_, definition)) => definition.trim_start(),
+            None => text,
+        };
+
+        let word = text
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches(['.', ',', ':']);
+        let part_of_speech = PARTS_OF_SPEECH
+            .iter()
+            .find(|part| word.eq_ignore_ascii_case(part))
+            .filter(|_| text.len() > word.len());
+        let text = match part_of_speech {
+            Some(_) => text[text.find(word).unwrap_or(0) + word.len()..]
+                .trim_start_matches(['.', ',', ':'])
+                .trim_start(),
+            None => text,
+        };
+
+        Definition {
+            text: text.to_string(),
+            part_of_speech: part_of_speech.map(|part| part.to_string()),
+            source,
+            url,
+        }
+    }
+}
+
+/// Represents a topic summary (abstract) from the DuckDuckGo API.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Abstract {
+    /// The heading or title of the topic, if available.
+    pub heading: Option<String>,
+    /// The abstract text.
+    pub text: String,
+    /// The source of the abstract, if available.
+    pub source: Option<String>,
+    /// The URL associated with the abstract, if available.
+    pub url: Option<String>,
 }
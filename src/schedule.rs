@@ -0,0 +1,355 @@
+use crate::response::SearchResult;
+use anyhow::{Context, Result, bail};
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashSet;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A cron expression: the minutes, hours, days of the month, months, and days of the week a
+/// scheduled search runs at.
+///
+/// Each of the five fields is `*`, a value, a range `a-b`, or a comma-separated list of them,
+/// optionally followed by a step `/n`. Days of the week go from `0` (Sunday) to `6`, `7` being
+/// Sunday too. As in cron, when both the day of the month and the day of the week are
+/// restricted, a day matching either runs. The `@hourly`, `@daily`, `@weekly`, and `@monthly`
+/// shortcuts are accepted as well.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use duckduckgo::schedule::Cron;
+///
+/// let cron: Cron = "*/15 9-17 * * 1-5".parse().unwrap();
+///
+/// // Friday 17:50, then Monday 9:00.
+/// let friday = NaiveDate::from_ymd_opt(2025, 6, 6).unwrap().and_hms_opt(17, 50, 0).unwrap();
+/// let next = cron.next_after(friday).unwrap();
+/// assert_eq!(next.to_string(), "2025-06-09 09:00:00");
+///
+/// assert!("61 * * * *".parse::<Cron>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cron {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Cron {
+    /// Returns the first time strictly after `time`, at the start of a minute, the schedule
+    /// runs at, or `None` if it never runs (e.g. on February 30th).
+    ///
+    /// # Arguments
+    /// * `time` - The time to start from, in the time zone the schedule is meant for.
+    pub fn next_after(&self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut next = time.with_second(0)?.with_nanosecond(0)? + TimeDelta::minutes(1);
+        // Every combination of day of the month and day of the week repeats within 28 years.
+        let end = next.date().checked_add_days(Days::new(366 * 28))?;
+
+        while next.date() < end {
+            if !has(self.months, next.month()) {
+                let (year, month) = match next.month() {
+                    12 => (next.year() + 1, 1),
+                    month => (next.year(), month + 1),
+                };
+                next = NaiveDate::from_ymd_opt(year, month, 1)?.and_time(NaiveTime::MIN);
+            } else if !self.matches_day(next.date()) {
+                next = next.date().succ_opt()?.and_time(NaiveTime::MIN);
+            } else if !has(self.hours, next.hour()) {
+                next = next.with_minute(0)? + TimeDelta::hours(1);
+            } else if !has(self.minutes, next.minute()) {
+                next += TimeDelta::minutes(1);
+            } else {
+                return Some(next);
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day = has(self.days, date.day());
+        let weekday = has(self.weekdays, date.weekday().num_days_from_sunday());
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday,
+            (false, true) => day,
+            (false, false) => day || weekday,
+        }
+    }
+}
+
+fn has(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Parses a field of a cron expression into the set of its values, as a bit set.
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64> {
+    let mut set = 0;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().ok().filter(|step| *step > 0)),
+            None => (item, Some(1)),
+        };
+        let Some(step) = step else {
+            bail!("invalid step in '{}'", item);
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (start.parse()?, end.parse()?),
+                None if step > 1 => (range.parse()?, max),
+                None => (range.parse()?, range.parse()?),
+            },
+        };
+        if start < min || end > max || start > end {
+            bail!("'{}' is out of the range {}-{}", item, min, max);
+        }
+        for value in (start..=end).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+impl FromStr for Cron {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let expression = match s.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            expression => expression,
+        };
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minutes, hours, days, months, weekdays] = fields[..] else {
+            bail!("Invalid cron expression '{}': expected 5 fields", s);
+        };
+
+        let parse = |field, min, max| {
+            parse_field(field, min, max).with_context(|| format!("Invalid cron expression '{}'", s))
+        };
+        let mut weekday_set = parse(weekdays, 0, 7)?;
+        if has(weekday_set, 7) {
+            weekday_set |= 1;
+        }
+        Ok(Cron {
+            expression: s.trim().to_string(),
+            minutes: parse(minutes, 0, 59)?,
+            hours: parse(hours, 0, 23)?,
+            days: parse(days, 1, 31)?,
+            months: parse(months, 1, 12)?,
+            weekdays: weekday_set,
+            any_day: days == "*",
+            any_weekday: weekdays == "*",
+        })
+    }
+}
+
+impl fmt::Display for Cron {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cron {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// A search run on a schedule by `ddg daemon`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduledSearch {
+    /// The name of the search, naming its state file.
+    pub name: String,
+    /// When the search runs, in local time.
+    pub cron: Cron,
+    /// The search query, operators included.
+    pub query: String,
+    /// The backend to search with (e.g. `"news"`), the automatic fallback if unset.
+    #[serde(default)]
+    pub backend: Option<String>,
+    /// The number of results to fetch, the command line limit if unset.
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// The URL the new results are posted to as JSON, if any.
+    #[serde(default)]
+    pub webhook: Option<String>,
+}
+
+/// The schedule of `ddg daemon`: the searches it runs.
+///
+/// The schedule is a JSON array of searches, each with a `name`, a `cron` expression, a
+/// `query`, and optionally a `backend`, a `limit`, and a `webhook`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::schedule::Schedule;
+///
+/// let schedule: Schedule = r#"[
+///     {"name": "rust-news", "cron": "0 9 * * *", "query": "rust release", "backend": "news"}
+/// ]"#
+/// .parse()
+/// .unwrap();
+/// assert_eq!(schedule.searches[0].cron.to_string(), "0 9 * * *");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+    /// The scheduled searches.
+    pub searches: Vec<ScheduledSearch>,
+}
+
+impl Schedule {
+    /// Returns the default schedule file: `ddg/schedule.json` in the user config directory
+    /// (`$XDG_CONFIG_HOME`, or `~/.config`), or `None` if there is none.
+    pub fn default_path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+            .map(|base| base.join("ddg").join("schedule.json"))
+    }
+
+    /// Loads a schedule file.
+    ///
+    /// # Arguments
+    /// * `path` - The schedule file.
+    ///
+    /// # Errors
+    /// Fails if the file cannot be read, is invalid, or names two searches the same.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        fs::read_to_string(path)
+            .with_context(|| format!("Failed to read schedule {}", path.display()))?
+            .parse()
+            .with_context(|| format!("Failed to parse schedule {}", path.display()))
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let searches: Vec<ScheduledSearch> = serde_json::from_str(s)?;
+        let mut names = HashSet::new();
+        for search in &searches {
+            if !names.insert(&search.name) {
+                bail!("Duplicate scheduled search '{}'", search.name);
+            }
+        }
+        Ok(Schedule { searches })
+    }
+}
+
+/// The results a scheduled search has already seen, so that only the new ones are reported.
+///
+/// The state is saved as JSON in `<dir>/<name>.json`, together with the results of the last run.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+/// use duckduckgo::schedule::SeenResults;
+///
+/// let result = SearchResult::Text(LiteSearchResult {
+///     url: "https://www.rust-lang.org/".into(),
+///     ..Default::default()
+/// });
+///
+/// let mut seen = SeenResults::default();
+/// assert_eq!(seen.update(vec![result.clone()]).len(), 1);
+/// assert!(seen.update(vec![result]).is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeenResults {
+    /// The URLs of every result seen so far.
+    pub urls: Vec<String>,
+    /// The results of the last run.
+    #[serde(skip_deserializing)]
+    pub last: Vec<SearchResult>,
+}
+
+impl SeenResults {
+    /// Returns the default state directory: `ddg/daemon` in the user state directory
+    /// (`$XDG_STATE_HOME`, or `~/.local/state`), or `None` if there is none.
+    pub fn default_dir() -> Option<PathBuf> {
+        env::var_os("XDG_STATE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("HOME").map(|home| Path::new(&home).join(".local").join("state"))
+            })
+            .map(|base| base.join("ddg").join("daemon"))
+    }
+
+    /// Loads the state of a scheduled search, or an empty state if it never ran.
+    ///
+    /// # Arguments
+    /// * `dir` - The state directory.
+    /// * `name` - The name of the scheduled search.
+    pub fn load(dir: impl AsRef<Path>, name: &str) -> Result<Self> {
+        let path = Self::path(dir.as_ref(), name);
+        match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)
+                .with_context(|| format!("Failed to parse state {}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => {
+                Err(err).with_context(|| format!("Failed to read state {}", path.display()))
+            }
+        }
+    }
+
+    /// Saves the state of a scheduled search, creating the state directory if needed.
+    ///
+    /// # Arguments
+    /// * `dir` - The state directory.
+    /// * `name` - The name of the scheduled search.
+    pub fn save(&self, dir: impl AsRef<Path>, name: &str) -> Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+        let path = Self::path(dir, name);
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write state {}", path.display()))
+    }
+
+    /// Records the results of a run, returning the ones not seen before.
+    ///
+    /// # Arguments
+    /// * `results` - The results of the run.
+    pub fn update(&mut self, results: Vec<SearchResult>) -> Vec<SearchResult> {
+        let mut seen: HashSet<String> = self.urls.iter().cloned().collect();
+        let new: Vec<SearchResult> = results
+            .iter()
+            .filter(|result| seen.insert(result.url().to_string()))
+            .cloned()
+            .collect();
+        self.urls
+            .extend(new.iter().map(|result| result.url().to_string()));
+        self.last = results;
+        new
+    }
+
+    fn path(dir: &Path, name: &str) -> PathBuf {
+        let name: String = name
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect();
+        dir.join(format!("{}.json", name))
+    }
+}
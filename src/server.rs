@@ -0,0 +1,489 @@
+use crate::backend::{
+    FallbackBackend, HtmlBackend, ImagesBackend, LiteBackend, MetaBackend, NewsBackend,
+    SearchBackend,
+};
+use crate::browser::Browser;
+#[cfg(feature = "metrics")]
+use crate::metrics::{Metrics, metrics_response};
+use crate::params::SearchParams;
+use crate::response::{SearchPage, SearchResult};
+use anyhow::{Context, Result};
+use hyper::header::{CONTENT_TYPE, HeaderValue};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use reqwest::Url;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+use utoipa::{OpenApi, ToSchema};
+
+/// How long `ProxyServer` caches a search by default: one hour.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// How many searches `ProxyServer` caches by default.
+pub const DEFAULT_CAPACITY: usize = 1000;
+
+/// The backends `ProxyServer` searches with, by their `--backend` name.
+const BACKENDS: [&str; 6] = ["auto", "lite", "html", "images", "news", "all"];
+
+/// A local HTTP server answering searches from a shared cache, as run by `ddg proxyd`.
+///
+/// Every search goes through one `Browser`, so the tools querying the server share its rate
+/// limiter, user agent, proxy, and cookies. Results are cached for `ttl` after they arrive, and
+/// identical searches arriving while the first one is still running wait for its results
+/// instead of sending their own requests.
+///
+/// The server answers `GET /search` with the parameters:
+///
+/// | Parameter | Description | Default |
+/// |-----------|-------------|---------|
+/// | `q` | The search query (required). | |
+/// | `backend` | `auto`, `lite`, `html`, `images`, `news`, or `all`. | `auto` |
+/// | `limit` | The maximum number of results. | |
+/// | `offset` | The number of results to skip. | `0` |
+/// | `region` | The region code (e.g. `fr-fr`). | `wt-wt` |
+/// | `safe` | Whether safe search is enabled (`true` or `false`). | `false` |
+///
+/// The body is a JSON page with the `query`, the `backend`, the `results`, the
+/// `corrected_query`, and the `related_queries`, and the `X-Cache` header tells whether it
+/// was a `HIT`, a `MISS`, or `COALESCED` with a search in flight. Errors are JSON objects with
+/// an `error` message. `GET /health` answers `{"status":"ok"}`, `GET /openapi.json` the
+/// OpenAPI document of these routes (see `openapi`), and, with the `metrics`
+/// feature, `GET /metrics` answers the metrics of the browser and the cache in the Prometheus
+/// text format (see `metrics::Metrics`).
+///
+/// # Example
+/// ```no_run
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::server::ProxyServer;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let browser = Browser::builder().rate_limit(30).build()?;
+///     ProxyServer::new(browser)
+///         .ttl(Duration::from_secs(600))
+///         .serve("127.0.0.1:8787".parse()?)
+///         .await
+/// }
+/// ```
+pub struct ProxyServer {
+    browser: Browser,
+    ttl: Duration,
+    capacity: usize,
+    cache: Mutex<HashMap<String, Arc<OnceCell<CacheEntry>>>>,
+}
+
+/// The body of a cached search, or its error message.
+type CachedBody = std::result::Result<Arc<str>, String>;
+
+/// A search in the cache, with the time its results arrived, which its `ttl` counts from.
+struct CacheEntry {
+    fetched: Instant,
+    body: CachedBody,
+}
+
+/// Whether a search was answered from the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CacheStatus {
+    /// The results were cached.
+    Hit,
+    /// The results were fetched.
+    Miss,
+    /// The results were fetched by an identical search in flight.
+    Coalesced,
+}
+
+/// The page `ProxyServer` answers a search with.
+#[derive(Debug, Serialize, ToSchema)]
+struct SearchResponse<'a> {
+    /// The search query.
+    query: &'a str,
+    /// The backend the search was run with.
+    backend: &'a str,
+    /// The results of the search.
+    results: Vec<SearchResult>,
+    /// The query DuckDuckGo searched instead, if it corrected a typo.
+    corrected_query: Option<String>,
+    /// The searches DuckDuckGo suggests next.
+    related_queries: Vec<String>,
+}
+
+/// The body of the errors of `ProxyServer`.
+#[derive(Debug, Serialize, ToSchema)]
+struct ErrorResponse<'a> {
+    /// Why the request failed.
+    error: &'a str,
+}
+
+/// The body `ProxyServer` answers `GET /health` with.
+#[derive(Debug, Serialize, ToSchema)]
+struct HealthResponse {
+    /// Always `ok`.
+    status: &'static str,
+}
+
+impl ProxyServer {
+    /// Creates a server searching with a browser, caching `DEFAULT_CAPACITY` searches for
+    /// `DEFAULT_TTL`.
+    ///
+    /// With the `metrics` feature, the metrics of the browser are enabled if they are not.
+    pub fn new(browser: Browser) -> Self {
+        #[cfg(feature = "metrics")]
+        let browser = Browser {
+            metrics: browser.metrics.or_else(|| Some(Arc::new(Metrics::new()))),
+            ..browser
+        };
+        ProxyServer {
+            browser,
+            ttl: DEFAULT_TTL,
+            capacity: DEFAULT_CAPACITY,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets how long searches are cached.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets how many searches are cached; the oldest ones are evicted first, and searches still
+    /// running are never evicted.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Listens on an address and answers requests until the process stops.
+    ///
+    /// # Errors
+    /// Returns an error if the address cannot be bound or the server fails.
+    pub async fn serve(self, addr: SocketAddr) -> Result<()> {
+        let server = Arc::new(self);
+        let make_service = make_service_fn(move |_| {
+            let server = server.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let server = server.clone();
+                    async move { Ok::<_, Infallible>(server.handle(request).await) }
+                }))
+            }
+        });
+        hyper::Server::try_bind(&addr)
+            .with_context(|| format!("Failed to listen on {}", addr))?
+            .serve(make_service)
+            .await
+            .context("The server failed")
+    }
+
+    /// Answers a request.
+    async fn handle(&self, request: Request<Body>) -> Response<Body> {
+        if request.method() != Method::GET {
+            return error(
+                StatusCode::METHOD_NOT_ALLOWED,
+                "Only GET requests are served",
+            );
+        }
+        match request.uri().path() {
+            "/search" => search(self, request.uri().query().unwrap_or_default()).await,
+            "/health" => health(),
+            "/openapi.json" => openapi_json(),
+            #[cfg(feature = "metrics")]
+            "/metrics" => metrics(self),
+            path => error(StatusCode::NOT_FOUND, &format!("No route for {}", path)),
+        }
+    }
+
+    /// Returns the cache cell of a search, creating it if it is missing or expired.
+    ///
+    /// Searches still running never expire. When the cache is full of them, the cell of a new
+    /// search is not cached.
+    fn cached(&self, key: &str) -> (Arc<OnceCell<CacheEntry>>, CacheStatus) {
+        let expired = |cell: &OnceCell<CacheEntry>| {
+            cell.get()
+                .is_some_and(|entry| entry.fetched.elapsed() >= self.ttl)
+        };
+        let mut cache = self.cache.lock().unwrap();
+        if let Some(cell) = cache.get(key)
+            && !expired(cell)
+        {
+            let status = if cell.initialized() {
+                CacheStatus::Hit
+            } else {
+                CacheStatus::Coalesced
+            };
+            return (cell.clone(), status);
+        }
+
+        if cache.len() >= self.capacity {
+            cache.retain(|_, cell| !expired(cell));
+        }
+        if cache.len() >= self.capacity
+            && let Some(oldest) = cache
+                .iter()
+                .filter_map(|(key, cell)| Some((key, cell.get()?.fetched)))
+                .min_by_key(|(_, fetched)| *fetched)
+                .map(|(key, _)| key.clone())
+        {
+            cache.remove(&oldest);
+        }
+        let cell = Arc::new(OnceCell::new());
+        if cache.len() < self.capacity {
+            cache.insert(key.to_string(), cell.clone());
+        }
+        (cell, CacheStatus::Miss)
+    }
+
+    /// Runs a search and serializes its page.
+    async fn fetch(&self, backend: &str, params: &SearchParams) -> CachedBody {
+        let search = build_backend(&self.browser, backend).ok_or("Unknown backend")?;
+        let page = search.search_page(params).await;
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.browser.metrics {
+            metrics.record_search(backend, page.is_ok());
+        }
+        let page: SearchPage<SearchResult> = page.map_err(|error| format!("{:#}", error))?;
+        let response = SearchResponse {
+            query: &params.query,
+            backend,
+            results: page.results,
+            corrected_query: page.corrected_query,
+            related_queries: page.related_queries,
+        };
+        serde_json::to_string(&response)
+            .map(Arc::from)
+            .map_err(|error| error.to_string())
+    }
+}
+
+/// Answers `GET /search`.
+#[utoipa::path(
+    get,
+    path = "/search",
+    params(
+        ("q" = String, Query, description = "The search query."),
+        ("backend" = Option<String>, Query, description = "`auto`, `lite`, `html`, `images`, `news`, or `all`; `auto` by default."),
+        ("limit" = Option<usize>, Query, description = "The maximum number of results."),
+        ("offset" = Option<usize>, Query, description = "The number of results to skip; `0` by default."),
+        ("region" = Option<String>, Query, description = "The region code (e.g. `fr-fr`); `wt-wt` by default."),
+        ("safe" = Option<bool>, Query, description = "Whether safe search is enabled; `false` by default."),
+    ),
+    responses(
+        (status = 200, description = "The page of the search.", body = SearchResponse, headers(
+            ("x-cache" = String, description = "`HIT`, `MISS`, or `COALESCED`."),
+        )),
+        (status = 400, description = "The parameters are invalid.", body = ErrorResponse),
+        (status = 502, description = "The search failed.", body = ErrorResponse),
+    ),
+)]
+async fn search(server: &ProxyServer, query: &str) -> Response<Body> {
+    let (backend, params) = match parse_query(query) {
+        Ok(parsed) => parsed,
+        Err(message) => return error(StatusCode::BAD_REQUEST, &message),
+    };
+    let key = format!(
+        "{}\n{}\n{}\n{}\n{:?}\n{}",
+        backend, params.region, params.safe_search, params.offset, params.limit, params.query
+    );
+
+    let (cell, status) = server.cached(&key);
+    #[cfg(feature = "metrics")]
+    if let Some(metrics) = &server.browser.metrics {
+        metrics.record_cache(match status {
+            CacheStatus::Hit => "hit",
+            CacheStatus::Miss => "miss",
+            CacheStatus::Coalesced => "coalesced",
+        });
+    }
+    let result = cell
+        .get_or_init(|| async {
+            let body = server.fetch(&backend, &params).await;
+            CacheEntry {
+                fetched: Instant::now(),
+                body,
+            }
+        })
+        .await
+        .body
+        .clone();
+    match result {
+        Ok(page) => {
+            let mut response = json(StatusCode::OK, page.to_string());
+            let value = match status {
+                CacheStatus::Hit => "HIT",
+                CacheStatus::Miss => "MISS",
+                CacheStatus::Coalesced => "COALESCED",
+            };
+            response
+                .headers_mut()
+                .insert("x-cache", HeaderValue::from_static(value));
+            response
+        }
+        Err(message) => {
+            // Failed searches are not cached, so that the next request retries.
+            let mut cache = server.cache.lock().unwrap();
+            if cache
+                .get(&key)
+                .is_some_and(|cached| Arc::ptr_eq(cached, &cell))
+            {
+                cache.remove(&key);
+            }
+            error(StatusCode::BAD_GATEWAY, &message)
+        }
+    }
+}
+
+/// Answers `GET /health`.
+#[utoipa::path(
+    get,
+    path = "/health",
+    responses((status = 200, description = "The server is running.", body = HealthResponse)),
+)]
+fn health() -> Response<Body> {
+    let body = serde_json::to_string(&HealthResponse { status: "ok" })
+        .unwrap_or_else(|_| r#"{"status":"ok"}"#.into());
+    json(StatusCode::OK, body)
+}
+
+/// Answers `GET /openapi.json`.
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    responses((status = 200, description = "The OpenAPI document of the server.", body = Object)),
+)]
+fn openapi_json() -> Response<Body> {
+    match openapi().to_json() {
+        Ok(document) => json(StatusCode::OK, document),
+        Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    }
+}
+
+/// Answers `GET /metrics`.
+#[cfg(feature = "metrics")]
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    responses(
+        (status = 200, description = "The metrics in the Prometheus text format.", body = String, content_type = "text/plain"),
+        (status = 404, description = "The metrics are disabled.", body = ErrorResponse),
+    ),
+)]
+fn metrics(server: &ProxyServer) -> Response<Body> {
+    match &server.browser.metrics {
+        Some(metrics) => metrics_response(metrics),
+        None => error(StatusCode::NOT_FOUND, "Metrics are disabled"),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "ddg proxyd",
+        description = "Searches DuckDuckGo through a shared cache, as served by `ddg proxyd`."
+    ),
+    paths(search, health, openapi_json),
+    components(schemas(SearchResponse, ErrorResponse, HealthResponse))
+)]
+struct ApiDoc;
+
+#[cfg(feature = "metrics")]
+#[derive(OpenApi)]
+#[openapi(paths(metrics))]
+struct MetricsApi;
+
+/// Returns the OpenAPI document of the routes served by `ProxyServer`, with the schemas of
+/// their responses, to generate clients in other languages.
+///
+/// # Example
+/// ```
+/// use duckduckgo::server;
+///
+/// let document = server::openapi();
+/// assert!(document.paths.paths.contains_key("/search"));
+/// assert!(document.to_json().unwrap().contains("SearchResponse"));
+/// ```
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    #[allow(unused_mut)]
+    let mut document = ApiDoc::openapi();
+    #[cfg(feature = "metrics")]
+    document.merge(MetricsApi::openapi());
+    document
+}
+
+/// Parses the query string of `GET /search` into a backend name and search parameters.
+fn parse_query(query: &str) -> std::result::Result<(String, SearchParams), String> {
+    let url = Url::parse(&format!("http://localhost/search?{}", query))
+        .map_err(|error| error.to_string())?;
+    let mut backend = "auto".to_string();
+    let mut params = SearchParams::new("");
+    for (name, value) in url.query_pairs() {
+        match &*name {
+            "q" => params.query = value.into_owned(),
+            "backend" => backend = value.into_owned(),
+            "region" => params.region = value.into_owned(),
+            "limit" => {
+                params.limit = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("Invalid limit: {}", value))?,
+                )
+            }
+            "offset" => {
+                params.offset = value
+                    .parse()
+                    .map_err(|_| format!("Invalid offset: {}", value))?
+            }
+            "safe" => {
+                params.safe_search = value
+                    .parse()
+                    .map_err(|_| format!("Invalid safe search flag: {}", value))?
+            }
+            _ => return Err(format!("Unknown parameter: {}", name)),
+        }
+    }
+    if params.query.trim().is_empty() {
+        return Err("Missing query: set the q parameter".into());
+    }
+    if !BACKENDS.contains(&backend.as_str()) {
+        return Err(format!(
+            "Unknown backend: {} (expected auto, lite, html, images, news, or all)",
+            backend
+        ));
+    }
+    Ok((backend, params))
+}
+
+/// Creates a backend from its `--backend` name, or returns `None` if the name is unknown.
+fn build_backend<'a>(browser: &'a Browser, name: &str) -> Option<Box<dyn SearchBackend + 'a>> {
+    Some(match name {
+        "auto" => Box::new(FallbackBackend::auto(browser)),
+        "lite" => Box::new(LiteBackend::new(browser)),
+        "html" => Box::new(HtmlBackend::new(browser)),
+        "images" => Box::new(ImagesBackend::new(browser)),
+        "news" => Box::new(NewsBackend::new(browser)),
+        "all" => Box::new(MetaBackend::all(browser)),
+        _ => return None,
+    })
+}
+
+/// Creates a JSON response.
+fn json(status: StatusCode, body: String) -> Response<Body> {
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+/// Creates a JSON error response.
+fn error(status: StatusCode, message: &str) -> Response<Body> {
+    let body = serde_json::to_string(&ErrorResponse { error: message })
+        .unwrap_or_else(|_| r#"{"error":"Unknown error"}"#.into());
+    json(status, body)
+}
@@ -0,0 +1,332 @@
+use crate::backend::SearchBackend;
+use crate::merge::result_key;
+use crate::params::SearchParams;
+use crate::response::SearchResult;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// How much the results of a query change when it is repeated.
+///
+/// Every run is compared with every other run: the number of results they share, the Jaccard
+/// index of their result sets, and Kendall's rank correlation of the results they share.
+/// Results are the same when their URLs are, ignoring the scheme, a `www.` prefix, a fragment,
+/// and a trailing slash (see `merge::rrf`).
+///
+/// # Examples
+/// ```
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+/// use duckduckgo::stability::StabilityReport;
+///
+/// let run = |urls: &[&str]| -> Vec<SearchResult> {
+///     urls.iter()
+///         .map(|url| {
+///             SearchResult::Text(LiteSearchResult {
+///                 url: (*url).into(),
+///                 ..Default::default()
+///             })
+///         })
+///         .collect()
+/// };
+/// let report = StabilityReport::new(
+///     "rust web framework",
+///     vec![
+///         run(&["https://axum.rs/", "https://actix.rs/", "https://rocket.rs/"]),
+///         run(&["https://axum.rs/", "https://actix.rs/", "https://rocket.rs/"]),
+///         run(&["https://actix.rs/", "https://axum.rs/", "https://poem.rs/"]),
+///     ],
+/// );
+/// assert_eq!(report.pairs.len(), 3);
+/// assert_eq!(report.pairs[0].jaccard, 1.0);
+/// assert_eq!(report.pairs[1].jaccard, 0.5);
+/// assert_eq!(report.pairs[1].rank_correlation, Some(-1.0));
+/// assert_eq!(report.stable, ["https://axum.rs/", "https://actix.rs/"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StabilityReport {
+    /// The search query.
+    pub query: String,
+    /// The URLs of the results of each run, in order.
+    pub runs: Vec<Vec<String>>,
+    /// The comparison of every pair of runs.
+    pub pairs: Vec<RunComparison>,
+    /// The mean number of results shared by two runs.
+    pub mean_overlap: f64,
+    /// The mean Jaccard index of two runs, from 0 (no result in common) to 1 (the same
+    /// results).
+    pub mean_jaccard: f64,
+    /// The mean rank correlation of two runs, from -1 (reversed) to 1 (the same order), or
+    /// `None` if no two runs share two results.
+    pub mean_rank_correlation: Option<f64>,
+    /// The URLs of the results found by every run, in the order of the first run.
+    pub stable: Vec<String>,
+    /// The URLs of the results missing from some runs, with the number of runs finding them,
+    /// most frequent first.
+    pub unstable: Vec<UnstableResult>,
+}
+
+/// The comparison of two runs of a `StabilityReport`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RunComparison {
+    /// The index of the first run.
+    pub first: usize,
+    /// The index of the second run.
+    pub second: usize,
+    /// The number of results both runs found.
+    pub overlap: usize,
+    /// The Jaccard index of the results of the runs.
+    pub jaccard: f64,
+    /// Kendall's rank correlation of the results both runs found, or `None` if they share
+    /// fewer than two results.
+    pub rank_correlation: Option<f64>,
+}
+
+/// A result missing from some runs of a `StabilityReport`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UnstableResult {
+    /// The URL of the result.
+    pub url: String,
+    /// The number of runs that found the result.
+    pub runs: usize,
+}
+
+impl StabilityReport {
+    /// Compares the results of the runs of a query.
+    ///
+    /// # Arguments
+    /// * `query` - The search query.
+    /// * `runs` - The results of each run, in order.
+    pub fn new(query: impl Into<String>, runs: Vec<Vec<SearchResult>>) -> Self {
+        // The keys of the results of each run, in order and without duplicates.
+        let keys: Vec<Vec<String>> = runs
+            .iter()
+            .map(|results| {
+                let mut seen = HashSet::new();
+                results
+                    .iter()
+                    .map(result_key)
+                    .filter(|key| seen.insert(key.clone()))
+                    .collect()
+            })
+            .collect();
+
+        let mut pairs = Vec::new();
+        for first in 0..keys.len() {
+            for second in first + 1..keys.len() {
+                let (a, b) = (&keys[first], &keys[second]);
+                let overlap = a.iter().filter(|key| b.contains(key)).count();
+                pairs.push(RunComparison {
+                    first,
+                    second,
+                    overlap,
+                    jaccard: jaccard(a, b),
+                    rank_correlation: kendall_tau(a, b),
+                });
+            }
+        }
+
+        // The number of runs finding each result, and its URL in the first run finding it.
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        let mut urls: Vec<(&str, &str)> = Vec::new();
+        for (results, run) in runs.iter().zip(&keys) {
+            for key in run {
+                let count = counts.entry(key).or_default();
+                if *count == 0 {
+                    let url = results
+                        .iter()
+                        .find(|result| result_key(result) == *key)
+                        .map_or(key.as_str(), SearchResult::url);
+                    urls.push((key, url));
+                }
+                *count += 1;
+            }
+        }
+        let stable = urls
+            .iter()
+            .filter(|(key, _)| counts[key] == runs.len())
+            .map(|(_, url)| url.to_string())
+            .collect();
+        let mut unstable: Vec<UnstableResult> = urls
+            .iter()
+            .filter(|(key, _)| counts[key] < runs.len())
+            .map(|(key, url)| UnstableResult {
+                url: url.to_string(),
+                runs: counts[key],
+            })
+            .collect();
+        // A stable sort, so that ties keep the order of first appearance.
+        unstable.sort_by_key(|result| std::cmp::Reverse(result.runs));
+
+        let correlations: Vec<f64> = pairs
+            .iter()
+            .filter_map(|pair| pair.rank_correlation)
+            .collect();
+        StabilityReport {
+            query: query.into(),
+            runs: runs
+                .iter()
+                .map(|results| results.iter().map(|r| r.url().to_string()).collect())
+                .collect(),
+            mean_overlap: mean(pairs.iter().map(|pair| pair.overlap as f64)).unwrap_or(0.0),
+            mean_jaccard: mean(pairs.iter().map(|pair| pair.jaccard)).unwrap_or(1.0),
+            mean_rank_correlation: mean(correlations.into_iter()),
+            pairs,
+            stable,
+            unstable,
+        }
+    }
+
+    /// Returns how volatile the results are, from 0 (every run finds the same results) to 1
+    /// (no two runs find a result in common): one minus the mean Jaccard index.
+    pub fn volatility(&self) -> f64 {
+        1.0 - self.mean_jaccard
+    }
+}
+
+/// Returns the mean of some values, or `None` if there are none.
+fn mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+/// Returns the Jaccard index of two lists: the number of items in both, divided by the number
+/// of items in either, or 1 if both are empty.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::stability::jaccard;
+///
+/// assert_eq!(jaccard(&["a", "b", "c"], &["b", "c", "d"]), 0.5);
+/// ```
+pub fn jaccard<T: Eq + std::hash::Hash>(a: &[T], b: &[T]) -> f64 {
+    let a: HashSet<&T> = a.iter().collect();
+    let b: HashSet<&T> = b.iter().collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// Returns Kendall's rank correlation of the items two rankings share, from -1 (in reverse
+/// order) to 1 (in the same order), or `None` if they share fewer than two items.
+///
+/// The rankings must not contain duplicates.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::stability::kendall_tau;
+///
+/// assert_eq!(kendall_tau(&["a", "b", "c"], &["a", "b", "c"]), Some(1.0));
+/// assert_eq!(kendall_tau(&["a", "b", "c"], &["c", "x", "a"]), Some(-1.0));
+/// assert_eq!(kendall_tau(&["a", "b"], &["b", "c"]), None);
+/// ```
+pub fn kendall_tau<T: PartialEq>(a: &[T], b: &[T]) -> Option<f64> {
+    // The rank in `b` of each item of `a` found in `b`, in the order of `a`.
+    let ranks: Vec<usize> = a
+        .iter()
+        .filter_map(|item| b.iter().position(|other| other == item))
+        .collect();
+    let n = ranks.len();
+    if n < 2 {
+        return None;
+    }
+    let mut score = 0i64;
+    for i in 0..n {
+        for j in i + 1..n {
+            score += if ranks[i] < ranks[j] { 1 } else { -1 };
+        }
+    }
+    Some(score as f64 / (n * (n - 1) / 2) as f64)
+}
+
+/// Runs a search several times in a row and measures how much its results change.
+///
+/// # Arguments
+/// * `backend` - The backend running the searches.
+/// * `params` - The search parameters.
+/// * `runs` - The number of runs.
+/// * `interval` - The time to wait between two runs.
+///
+/// # Errors
+/// Returns the error of the first run that fails.
+///
+/// # Example
+/// ```no_run
+/// use duckduckgo::backend::HtmlBackend;
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::params::SearchParams;
+/// use duckduckgo::stability::measure_stability;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let browser = Browser::new(reqwest::Client::new());
+///     let backend = HtmlBackend::new(&browser);
+///     let params = SearchParams::new("rust web framework");
+///     let report = measure_stability(&backend, &params, 5, Duration::ZERO).await?;
+///     println!("volatility: {:.2}", report.volatility());
+///     Ok(())
+/// }
+/// ```
+pub async fn measure_stability(
+    backend: &dyn SearchBackend,
+    params: &SearchParams,
+    runs: usize,
+    interval: Duration,
+) -> Result<StabilityReport> {
+    measure_stability_until(backend, params, runs, interval, None).await
+}
+
+/// Runs a search several times in a row and measures how much its results change, stopping
+/// early if cancelled.
+///
+/// The token is checked before each run and interrupts the wait between two runs, so a
+/// cancelled measure reports the runs completed so far.
+///
+/// # Arguments
+/// * `backend` - The backend running the searches.
+/// * `params` - The search parameters.
+/// * `runs` - The maximum number of runs.
+/// * `interval` - The time to wait between two runs.
+/// * `cancel` - The token cancelling the remaining runs.
+///
+/// # Errors
+/// Returns the error of the first run that fails.
+pub async fn measure_stability_until(
+    backend: &dyn SearchBackend,
+    params: &SearchParams,
+    runs: usize,
+    interval: Duration,
+    cancel: Option<&CancellationToken>,
+) -> Result<StabilityReport> {
+    let cancelled = || cancel.is_some_and(CancellationToken::is_cancelled);
+    let mut results = Vec::with_capacity(runs);
+    for run in 0..runs {
+        if run > 0 && !interval.is_zero() {
+            match cancel {
+                Some(cancel) => {
+                    cancel
+                        .run_until_cancelled(tokio::time::sleep(interval))
+                        .await;
+                }
+                None => tokio::time::sleep(interval).await,
+            }
+        }
+        if cancelled() {
+            break;
+        }
+        results.push(
+            backend
+                .search(params)
+                .await
+                .with_context(|| format!("Run {} of {} failed", run + 1, runs))?,
+        );
+    }
+    Ok(StabilityReport::new(params.query.clone(), results))
+}
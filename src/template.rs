@@ -0,0 +1,336 @@
+use crate::response::SearchResult;
+use anyhow::{Result, bail};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::str::FromStr;
+
+/// A template rendering each search result on its own, e.g. `"{title}\t{url}"`.
+///
+/// The placeholders are `{title}`, `{url}`, `{snippet}`, `{date}` (the publication date of news
+/// articles in RFC 3339 format, empty otherwise), `{rank}`, and `{source}` (the publisher of
+/// news articles and images, the domain of text results). Tabs and line breaks inside the
+/// values are replaced with spaces, so that each result stays on a single line.
+///
+/// The escapes `\t`, `\n`, and `\\` insert a tab, a line break, and a backslash, and `{{` and
+/// `}}` insert literal braces.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+/// use duckduckgo::template::Template;
+///
+/// let template: Template = r"{rank}. {title}\t{url}".parse().unwrap();
+/// let result = SearchResult::Text(LiteSearchResult {
+///     title: String::from("Rust Programming Language"),
+///     url: "https://www.rust-lang.org/".into(),
+///     rank: 1,
+///     ..Default::default()
+/// });
+/// assert_eq!(
+///     template.render(&result),
+///     "1. Rust Programming Language\thttps://www.rust-lang.org/"
+/// );
+///
+/// assert!("{author}".parse::<Template>().is_err());
+/// assert!("{title".parse::<Template>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+/// A piece of a parsed template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Field(Field),
+}
+
+/// A field of a search result that can be inserted in a template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Title,
+    Url,
+    Snippet,
+    Date,
+    Rank,
+    Source,
+}
+
+/// The placeholders accepted in templates, with the field they insert.
+const FIELDS: &[(&str, Field)] = &[
+    ("title", Field::Title),
+    ("url", Field::Url),
+    ("snippet", Field::Snippet),
+    ("date", Field::Date),
+    ("rank", Field::Rank),
+    ("source", Field::Source),
+];
+
+impl Template {
+    /// Returns the template of `--oneline`: the rank, URL, and title of each result, separated
+    /// by tabs, as in `{rank}\t{url}\t{title}`.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::response::{LiteSearchResult, SearchResult};
+    /// use duckduckgo::template::Template;
+    ///
+    /// let result = SearchResult::Text(LiteSearchResult {
+    ///     title: String::from("Rust Programming Language"),
+    ///     url: "https://www.rust-lang.org/".into(),
+    ///     rank: 1,
+    ///     ..Default::default()
+    /// });
+    /// assert_eq!(
+    ///     Template::oneline().render(&result),
+    ///     "1\thttps://www.rust-lang.org/\tRust Programming Language"
+    /// );
+    /// ```
+    pub fn oneline() -> Self {
+        Template {
+            parts: vec![
+                Part::Field(Field::Rank),
+                Part::Literal(String::from("\t")),
+                Part::Field(Field::Url),
+                Part::Literal(String::from("\t")),
+                Part::Field(Field::Title),
+            ],
+        }
+    }
+
+    /// Renders a search result with the template.
+    ///
+    /// # Arguments
+    /// * `result` - The search result to render.
+    ///
+    /// # Returns
+    /// The rendered result, without a trailing line break.
+    pub fn render(&self, result: &SearchResult) -> String {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => out.push_str(text),
+                Part::Field(field) => {
+                    let value = field_value(*field, result);
+                    out.extend(value.chars().map(|c| match c {
+                        '\t' | '\n' | '\r' => ' ',
+                        c => c,
+                    }));
+                }
+            }
+        }
+        out
+    }
+}
+
+impl Field {
+    /// Returns the name of the placeholder inserting the field.
+    fn name(self) -> &'static str {
+        FIELDS
+            .iter()
+            .find(|(_, field)| *field == self)
+            .map(|(name, _)| *name)
+            .unwrap_or_default()
+    }
+}
+
+/// Returns the value a placeholder inserts for a search result.
+fn field_value(field: Field, result: &SearchResult) -> String {
+    match (field, result) {
+        (Field::Title, _) => result.title().to_string(),
+        (Field::Url, _) => result.url().to_string(),
+        (Field::Snippet, _) => result.snippet().to_string(),
+        (Field::Rank, _) => result.rank().to_string(),
+        #[cfg(feature = "news")]
+        (Field::Date, SearchResult::News(news)) => news
+            .date
+            .map(|date| date.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+            .unwrap_or_default(),
+        (Field::Date, _) => String::new(),
+        #[cfg(feature = "news")]
+        (Field::Source, SearchResult::News(news)) => news.source.clone(),
+        (Field::Source, SearchResult::Image(image)) => image.source.clone(),
+        (Field::Source, SearchResult::Text(text)) => {
+            text.url.domain().unwrap_or_default().to_string()
+        }
+    }
+}
+
+impl FromStr for Template {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('t') => literal.push('\t'),
+                    Some('n') => literal.push('\n'),
+                    Some('\\') => literal.push('\\'),
+                    Some(other) => {
+                        literal.push('\\');
+                        literal.push(other);
+                    }
+                    None => literal.push('\\'),
+                },
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        name.push(c);
+                    }
+                    if !closed {
+                        bail!("Unclosed '{{' in template, use '{{{{' for a literal brace");
+                    }
+                    let Some((_, field)) = FIELDS.iter().find(|(n, _)| *n == name.trim()) else {
+                        let names: Vec<String> =
+                            FIELDS.iter().map(|(n, _)| format!("{{{}}}", n)).collect();
+                        bail!(
+                            "Unknown placeholder {{{}}} in template, expected one of: {}",
+                            name,
+                            names.join(", ")
+                        );
+                    };
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Field(*field));
+                }
+                '}' => bail!("Unmatched '}}' in template, use '}}}}' for a literal brace"),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+
+        Ok(Template { parts })
+    }
+}
+
+/// A selection of the fields of the search results to output, such as `url,title,date`.
+///
+/// The fields are the placeholders of `Template`: `title`, `url`, `snippet`, `date`, `rank`, and
+/// `source`. They are output in the order they are listed, as a line of tab-separated values
+/// with `template`, or as a JSON object with `select`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::response::{LiteSearchResult, SearchResult};
+/// use duckduckgo::template::Fields;
+///
+/// let fields: Fields = "url,title,rank".parse().unwrap();
+/// let result = SearchResult::Text(LiteSearchResult {
+///     title: String::from("Rust Programming Language"),
+///     url: "https://www.rust-lang.org/".into(),
+///     rank: 1,
+///     ..Default::default()
+/// });
+///
+/// assert_eq!(
+///     fields.template().render(&result),
+///     "https://www.rust-lang.org/\tRust Programming Language\t1"
+/// );
+/// assert_eq!(
+///     serde_json::to_string(&fields.select(&result)).unwrap(),
+///     r#"{"url":"https://www.rust-lang.org/","title":"Rust Programming Language","rank":1}"#
+/// );
+///
+/// assert!("url,author".parse::<Fields>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fields {
+    fields: Vec<Field>,
+}
+
+impl Fields {
+    /// Returns the template printing the fields of a result as tab-separated values.
+    pub fn template(&self) -> Template {
+        let mut parts = Vec::new();
+        for (index, field) in self.fields.iter().enumerate() {
+            if index > 0 {
+                parts.push(Part::Literal(String::from("\t")));
+            }
+            parts.push(Part::Field(*field));
+        }
+        Template { parts }
+    }
+
+    /// Returns the fields of a search result, serialized as a JSON object with one member per
+    /// field, in order.
+    ///
+    /// The rank is a number, and the date `null` for results without one.
+    ///
+    /// # Arguments
+    /// * `result` - The search result to select the fields of.
+    pub fn select<'a>(&'a self, result: &'a SearchResult) -> SelectedFields<'a> {
+        SelectedFields {
+            fields: self,
+            result,
+        }
+    }
+}
+
+impl FromStr for Fields {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut fields = Vec::new();
+        for name in s.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            let Some((_, field)) = FIELDS.iter().find(|(n, _)| *n == name) else {
+                let names: Vec<&str> = FIELDS.iter().map(|(n, _)| *n).collect();
+                bail!(
+                    "Unknown field '{}', expected one of: {}",
+                    name,
+                    names.join(", ")
+                );
+            };
+            if !fields.contains(field) {
+                fields.push(*field);
+            }
+        }
+        if fields.is_empty() {
+            bail!("No fields selected");
+        }
+        Ok(Fields { fields })
+    }
+}
+
+/// The selected fields of a search result, serialized as a JSON object by `Fields::select`.
+#[derive(Debug, Clone, Copy)]
+pub struct SelectedFields<'a> {
+    fields: &'a Fields,
+    result: &'a SearchResult,
+}
+
+impl Serialize for SelectedFields<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.fields.fields.len()))?;
+        for field in &self.fields.fields {
+            match field {
+                Field::Rank => map.serialize_entry(field.name(), &self.result.rank())?,
+                Field::Date => {
+                    let date = field_value(*field, self.result);
+                    map.serialize_entry(field.name(), &Some(date).filter(|d| !d.is_empty()))?
+                }
+                _ => map.serialize_entry(field.name(), &field_value(*field, self.result))?,
+            }
+        }
+        map.end()
+    }
+}
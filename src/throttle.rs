@@ -0,0 +1,154 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The policy spacing out the pages of a paginated search.
+///
+/// Before each page after the first one, the search waits for the page delay plus a random
+/// jitter, so that pagination loops do not fire their requests back to back. The delay adapts
+/// to how DuckDuckGo responds: it doubles, up to `max_delay`, on each slow response or soft-ban
+/// signal (rate limiting, `403`, server errors, bot challenges, transport errors), and shrinks
+/// back towards the configured delay as responses are fast and successful again.
+///
+/// Unlike `RateLimiter`, which spaces out all requests, the throttle only delays the next pages
+/// of image and news searches.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::throttle::ThrottlePolicy;
+/// use reqwest::StatusCode;
+/// use std::time::Duration;
+///
+/// let throttle = ThrottlePolicy::new(Duration::from_secs(1))
+///     .jitter(Duration::from_millis(500))
+///     .max_delay(Duration::from_secs(30));
+/// assert_eq!(throttle.delay(), Duration::from_secs(1));
+///
+/// throttle.record_response(StatusCode::TOO_MANY_REQUESTS, Duration::from_millis(100));
+/// assert_eq!(throttle.delay(), Duration::from_secs(2));
+///
+/// throttle.record_response(StatusCode::OK, Duration::from_millis(100));
+/// assert!(throttle.delay() < Duration::from_secs(2));
+///
+/// let browser = Browser::builder().throttle(throttle).build().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ThrottlePolicy {
+    delay: Duration,
+    jitter: Duration,
+    max_delay: Duration,
+    slow_latency: Duration,
+    state: Mutex<Throttle>,
+}
+
+#[derive(Debug)]
+struct Throttle {
+    /// The factor the configured delay is multiplied by, at least 1.
+    factor: f64,
+    /// When the last page was allowed to start.
+    last_page: Option<Instant>,
+}
+
+/// The default maximum delay between two pages.
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// The default latency above which a response is considered slow.
+const DEFAULT_SLOW_LATENCY: Duration = Duration::from_secs(3);
+
+/// How much the delay shrinks after each fast and successful response.
+const RECOVERY: f64 = 0.75;
+
+impl Default for ThrottlePolicy {
+    /// Waits one second between pages, with up to half a second of jitter.
+    fn default() -> Self {
+        ThrottlePolicy::new(Duration::from_secs(1)).jitter(Duration::from_millis(500))
+    }
+}
+
+impl ThrottlePolicy {
+    /// Creates a throttle waiting `delay` between two pages, without jitter.
+    pub fn new(delay: Duration) -> Self {
+        ThrottlePolicy {
+            delay,
+            jitter: Duration::ZERO,
+            max_delay: DEFAULT_MAX_DELAY.max(delay),
+            slow_latency: DEFAULT_SLOW_LATENCY,
+            state: Mutex::new(Throttle {
+                factor: 1.0,
+                last_page: None,
+            }),
+        }
+    }
+
+    /// Sets the maximum random time added to each delay.
+    pub fn jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the longest the delay can grow to when slowing down.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay.max(self.delay);
+        self
+    }
+
+    /// Sets the latency above which a response slows down the next pages.
+    pub fn slow_latency(mut self, latency: Duration) -> Self {
+        self.slow_latency = latency;
+        self
+    }
+
+    /// Returns the current delay between two pages, without jitter.
+    pub fn delay(&self) -> Duration {
+        let factor = self.state.lock().unwrap_or_else(|e| e.into_inner()).factor;
+        self.delay.mul_f64(factor).min(self.max_delay)
+    }
+
+    /// Records the status and latency of a response, slowing down if it was slow or a soft-ban
+    /// signal, and recovering otherwise.
+    ///
+    /// # Arguments
+    /// * `status` - The status of the response.
+    /// * `latency` - The time until the response headers were received.
+    pub fn record_response(&self, status: reqwest::StatusCode, latency: Duration) {
+        let soft_ban = status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            || status == reqwest::StatusCode::FORBIDDEN
+            || status.is_server_error();
+        if soft_ban || latency > self.slow_latency {
+            self.slow_down();
+        } else {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            state.factor = (state.factor * RECOVERY).max(1.0);
+        }
+    }
+
+    /// Doubles the delay between pages, up to the maximum delay, e.g. after a bot challenge or
+    /// a transport error.
+    pub fn slow_down(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if self.delay.mul_f64(state.factor) < self.max_delay {
+            state.factor *= 2.0;
+        }
+    }
+
+    /// Waits until the next page may be fetched: the current delay plus a random jitter after
+    /// the previous page started.
+    pub async fn wait_for_page(&self) {
+        let wait = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let jitter = Duration::from_millis(fastrand::u64(0..=self.jitter.as_millis() as u64));
+            let delay = self.delay.mul_f64(state.factor).min(self.max_delay) + jitter;
+            let wait = match state.last_page {
+                Some(last) => delay.saturating_sub(now.duration_since(last)),
+                None => delay,
+            };
+            state.last_page = Some(now + wait);
+            wait
+        };
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The phase durations of a single HTTP request.
+///
+/// The connection setup (TCP and TLS) is not exposed by the HTTP client, so it is included in
+/// `ttfb`.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RequestTiming {
+    /// The HTTP method.
+    pub method: String,
+    /// The requested URL.
+    pub url: String,
+    /// The response status code.
+    pub status: u16,
+    /// The DNS lookup, if the host was resolved for this request.
+    #[serde(serialize_with = "millis_opt")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
+    pub dns: Option<Duration>,
+    /// The time to the response headers, including connection setup.
+    #[serde(serialize_with = "millis")]
+    #[cfg_attr(feature = "schema", schemars(with = "f64"))]
+    pub ttfb: Duration,
+    /// The download of the response body, once it is read.
+    #[serde(serialize_with = "millis_opt")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
+    pub download: Option<Duration>,
+    /// The parsing of the response body, once it is parsed.
+    #[serde(serialize_with = "millis_opt")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
+    pub parse: Option<Duration>,
+}
+
+impl RequestTiming {
+    /// Returns the sum of all the phases.
+    pub fn total(&self) -> Duration {
+        self.dns.unwrap_or_default()
+            + self.ttfb
+            + self.download.unwrap_or_default()
+            + self.parse.unwrap_or_default()
+    }
+}
+
+/// Identifies the `RequestTiming` of a response, stored in its extensions.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TimingId(pub(crate) usize);
+
+/// Collects the per-phase durations of the requests made by a `Browser`.
+///
+/// Enable it with `BrowserBuilder::timings`; the collected timings are printed as a summary table
+/// by the `Display` implementation, or serialized with their `total`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::browser::Browser;
+///
+/// let browser = Browser::builder().timings(true).build().unwrap();
+/// let timings = browser.timings.as_ref().unwrap();
+/// assert!(timings.requests().is_empty());
+/// ```
+#[derive(Debug, Default)]
+pub struct Timings {
+    requests: Mutex<Vec<RequestTiming>>,
+    lookups: Mutex<HashMap<String, Duration>>,
+}
+
+impl Timings {
+    /// Creates an empty collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the timings of the requests made so far.
+    pub fn requests(&self) -> Vec<RequestTiming> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Returns the total time spent in each phase, over all requests.
+    pub fn total(&self) -> RequestTiming {
+        let requests = self.requests();
+        let sum = |phase: fn(&RequestTiming) -> Option<Duration>| {
+            let durations: Vec<_> = requests.iter().filter_map(phase).collect();
+            (!durations.is_empty()).then(|| durations.into_iter().sum())
+        };
+
+        RequestTiming {
+            method: String::new(),
+            url: format!("{} requests", requests.len()),
+            status: 0,
+            dns: sum(|r| r.dns),
+            ttfb: requests.iter().map(|r| r.ttfb).sum(),
+            download: sum(|r| r.download),
+            parse: sum(|r| r.parse),
+        }
+    }
+
+    /// Clears the collected timings.
+    pub fn clear(&self) {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Records a request whose response headers were received after `ttfb`.
+    pub(crate) fn record_request(
+        &self,
+        method: &reqwest::Method,
+        url: &reqwest::Url,
+        status: u16,
+        ttfb: Duration,
+    ) -> TimingId {
+        let dns = url.host_str().and_then(|host| {
+            self.lookups
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(host)
+        });
+
+        let mut requests = self.requests.lock().unwrap_or_else(|e| e.into_inner());
+        requests.push(RequestTiming {
+            method: method.to_string(),
+            url: url.to_string(),
+            status,
+            dns,
+            ttfb,
+            download: None,
+            parse: None,
+        });
+        TimingId(requests.len() - 1)
+    }
+
+    /// Records the body download of a request.
+    pub(crate) fn record_download(&self, id: TimingId, duration: Duration) {
+        if let Some(timing) = self
+            .requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_mut(id.0)
+        {
+            timing.download = Some(duration);
+        }
+    }
+
+    /// Records the body parsing of a request.
+    pub(crate) fn record_parse(&self, id: TimingId, duration: Duration) {
+        if let Some(timing) = self
+            .requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_mut(id.0)
+        {
+            timing.parse = Some(duration);
+        }
+    }
+
+    fn record_lookup(&self, host: &str, duration: Duration) {
+        self.lookups
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(host.to_string(), duration);
+    }
+}
+
+impl Serialize for Timings {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Timings", 2)?;
+        state.serialize_field("requests", &self.requests())?;
+        state.serialize_field("total", &self.total())?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Timings {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Timings".into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "The timings of the requests, and their sum as `total`.",
+            "type": "object",
+            "properties": {
+                "requests": generator.subschema_for::<Vec<RequestTiming>>(),
+                "total": generator.subschema_for::<RequestTiming>(),
+            },
+            "required": ["requests", "total"],
+        })
+    }
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let phase = |d: Option<Duration>| d.map_or_else(|| String::from("-"), format_millis);
+
+        writeln!(
+            f,
+            "{:<48} {:>6} {:>9} {:>9} {:>9} {:>9} {:>9}",
+            "REQUEST", "STATUS", "DNS", "TTFB", "DOWNLOAD", "PARSE", "TOTAL"
+        )?;
+
+        let requests = self.requests();
+        let total = self.total();
+        for timing in requests.iter().chain([&total]) {
+            let mut label = format!("{} {}", timing.method, timing.url)
+                .trim()
+                .to_string();
+            if label.chars().count() > 48 {
+                label = label.chars().take(47).collect::<String>() + "…";
+            }
+            let status = if timing.status == 0 {
+                String::new()
+            } else {
+                timing.status.to_string()
+            };
+
+            writeln!(
+                f,
+                "{:<48} {:>6} {:>9} {:>9} {:>9} {:>9} {:>9}",
+                label,
+                status,
+                phase(timing.dns),
+                format_millis(timing.ttfb),
+                phase(timing.download),
+                phase(timing.parse),
+                format_millis(timing.total()),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A DNS resolver recording the duration of each lookup in a `Timings` collector.
+pub(crate) struct TimingResolver(pub(crate) Arc<Timings>);
+
+impl reqwest::dns::Resolve for TimingResolver {
+    fn resolve(&self, name: hyper::client::connect::dns::Name) -> reqwest::dns::Resolving {
+        let timings = self.0.clone();
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let start = Instant::now();
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            timings.record_lookup(&host, start.elapsed());
+
+            let addrs: reqwest::dns::Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+fn format_millis(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn millis<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
+
+pub(crate) fn millis_opt<S: Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => millis(duration, serializer),
+        None => serializer.serialize_none(),
+    }
+}
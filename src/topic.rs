@@ -23,4 +23,57 @@ pub struct Topic {
     /// The URL associated with the topic.
     #[serde(rename = "URL")]
     pub url: Option<String>,
+
+    /// The name of the group, if the topic is a group of topics, such as a category of the
+    /// meanings of an ambiguous query.
+    #[serde(rename = "Name")]
+    pub name: Option<String>,
+
+    /// The topics of the group, empty if the topic is not a group.
+    #[serde(rename = "Topics", default)]
+    pub topics: Vec<Topic>,
+}
+
+impl Topic {
+    /// Returns `true` if the topic is a named group of other topics.
+    pub fn is_group(&self) -> bool {
+        !self.topics.is_empty()
+    }
+}
+
+/// An iterator over the leaf topics of a list of topics, descending into the groups, in
+/// depth-first order. Returned by `Response::iter_topics`.
+#[derive(Debug, Clone)]
+pub struct LeafTopics<'a> {
+    stack: Vec<std::slice::Iter<'a, Topic>>,
+}
+
+impl<'a> LeafTopics<'a> {
+    /// Creates an iterator over the leaf topics of `topics`.
+    pub fn new(topics: &'a [Topic]) -> Self {
+        LeafTopics {
+            stack: vec![topics.iter()],
+        }
+    }
+}
+
+impl<'a> Iterator for LeafTopics<'a> {
+    type Item = &'a Topic;
+
+    fn next(&mut self) -> Option<&'a Topic> {
+        loop {
+            let topic = match self.stack.last_mut()?.next() {
+                Some(topic) => topic,
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+            if topic.is_group() {
+                self.stack.push(topic.topics.iter());
+            } else if topic.name.is_none() {
+                return Some(topic);
+            }
+        }
+    }
 }
@@ -0,0 +1,127 @@
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// The SOCKS5 proxy of a local Tor daemon. `socks5h` resolves host names through Tor as well.
+pub const DEFAULT_TOR_PROXY: &str = "socks5h://127.0.0.1:9050";
+
+/// The control port of a local Tor daemon.
+pub const DEFAULT_TOR_CONTROL: &str = "127.0.0.1:9051";
+
+/// The Tor Project service reporting whether a request came through the Tor network.
+pub const TOR_CHECK_URL: &str = "https://check.torproject.org/api/ip";
+
+/// Settings for routing `Browser` traffic through Tor.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::tor::{TorConfig, TorControl};
+///
+/// let browser = Browser::builder()
+///     .tor(TorConfig::default().control(TorControl::new("127.0.0.1:9051")))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TorConfig {
+    /// The SOCKS5 proxy URL of the Tor daemon.
+    pub proxy: String,
+    /// The control port used to request a new circuit between paginated requests, if any.
+    pub control: Option<TorControl>,
+}
+
+impl Default for TorConfig {
+    fn default() -> Self {
+        TorConfig {
+            proxy: DEFAULT_TOR_PROXY.to_string(),
+            control: None,
+        }
+    }
+}
+
+impl TorConfig {
+    /// Sets the SOCKS5 proxy URL of the Tor daemon.
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy = proxy.into();
+        self
+    }
+
+    /// Requests a new circuit through the given control port between paginated requests.
+    pub fn control(mut self, control: TorControl) -> Self {
+        self.control = Some(control);
+        self
+    }
+}
+
+/// A connection to the control port of a Tor daemon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TorControl {
+    /// The address of the control port (e.g., `"127.0.0.1:9051"`).
+    pub addr: String,
+    /// The control port password, if `HashedControlPassword` is configured.
+    pub password: Option<String>,
+}
+
+impl TorControl {
+    /// Creates a new `TorControl` for the given control port address.
+    pub fn new(addr: impl Into<String>) -> Self {
+        TorControl {
+            addr: addr.into(),
+            password: None,
+        }
+    }
+
+    /// Sets the control port password.
+    pub fn password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Asks Tor to switch to clean circuits (`SIGNAL NEWNYM`) so later requests use a new
+    /// exit node.
+    ///
+    /// # Returns
+    /// An error if the control port cannot be reached or rejects the request.
+    pub fn new_circuit(&self) -> Result<()> {
+        let addr = self
+            .addr
+            .to_socket_addrs()?
+            .next()
+            .with_context(|| format!("Invalid Tor control address {}", self.addr))?;
+        let stream = TcpStream::connect_timeout(&addr, Duration::from_secs(5))
+            .with_context(|| format!("Failed to connect to Tor control port {}", self.addr))?;
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut writer = stream;
+
+        let password = self.password.as_deref().unwrap_or_default();
+        let authenticate = format!("AUTHENTICATE \"{}\"\r\n", password.replace('"', "\\\""));
+        for command in [authenticate.as_str(), "SIGNAL NEWNYM\r\n"] {
+            writer.write_all(command.as_bytes())?;
+
+            let mut reply = String::new();
+            reader.read_line(&mut reply)?;
+            if !reply.starts_with("250") {
+                bail!("Tor control port rejected the request: {}", reply.trim());
+            }
+        }
+
+        writer.write_all(b"QUIT\r\n")?;
+        Ok(())
+    }
+}
+
+/// The answer of the Tor Project check service.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TorStatus {
+    /// Whether the request reached the service through the Tor network.
+    #[serde(rename = "IsTor")]
+    pub is_tor: bool,
+    /// The exit IP address seen by the service.
+    #[serde(rename = "IP")]
+    pub ip: String,
+}
@@ -0,0 +1,467 @@
+use anyhow::{Context, Result, bail};
+use ring::digest::{SHA256, digest};
+use ring::signature::{ED25519, UnparsedPublicKey};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The GitHub API URL of the latest release of `ddg`.
+pub const RELEASES_URL: &str = "https://api.github.com/repos/kevin-rs/duckduckgo/releases/latest";
+
+/// The version of the running `ddg` binary.
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The timeout of the check for a newer release made at the end of a run, so that an
+/// unreachable GitHub does not delay it.
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The settings of the update checks, read from `ddg/update.json` in the user config directory.
+///
+/// Every member is optional:
+///
+/// ```json
+/// {
+///   "notify": true,
+///   "check_interval_hours": 24,
+///   "public_key": "<hex-encoded Ed25519 public key>"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UpdateConfig {
+    /// Whether a notice is printed to stderr when a newer release is available; off by default,
+    /// so that ddg only contacts GitHub when asked to.
+    pub notify: bool,
+    /// The number of hours between two checks for a newer release.
+    pub check_interval_hours: u64,
+    /// The hex-encoded Ed25519 public key the release binaries must be signed with. No key is
+    /// embedded in ddg, so `ddg self-update` refuses to install a binary until one is set,
+    /// unless the signature check is skipped.
+    pub public_key: Option<String>,
+}
+
+impl Default for UpdateConfig {
+    fn default() -> Self {
+        Self {
+            notify: false,
+            check_interval_hours: 24,
+            public_key: None,
+        }
+    }
+}
+
+impl UpdateConfig {
+    /// Returns the default settings file: `ddg/update.json` in the user config directory
+    /// (`$XDG_CONFIG_HOME`, or `~/.config`), or `None` if there is none.
+    pub fn default_path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+            .map(|base| base.join("ddg").join("update.json"))
+    }
+
+    /// Loads the settings of a file, or the default settings if the file does not exist.
+    ///
+    /// # Arguments
+    /// * `path` - The settings file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to read update settings {}", path.display()));
+            }
+        };
+        serde_json::from_str(&text)
+            .with_context(|| format!("Failed to parse update settings {}", path.display()))
+    }
+
+    /// Returns the time between two checks for a newer release.
+    pub fn check_interval(&self) -> Duration {
+        Duration::from_secs(self.check_interval_hours * 3600)
+    }
+}
+
+/// The last check for a newer release, saved between runs so that the passive notice does not
+/// query GitHub on every run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateCheck {
+    /// When the check ran, in seconds since the Unix epoch.
+    pub checked_at: u64,
+    /// The version of the latest release, if the check succeeded.
+    pub latest: Option<String>,
+}
+
+impl UpdateCheck {
+    /// Returns the default file of the last check: `ddg/update-check.json` in the user state
+    /// directory (`$XDG_STATE_HOME`, or `~/.local/state`), or `None` if there is none.
+    pub fn default_path() -> Option<PathBuf> {
+        env::var_os("XDG_STATE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("HOME").map(|home| Path::new(&home).join(".local").join("state"))
+            })
+            .map(|base| base.join("ddg").join("update-check.json"))
+    }
+
+    /// Loads the last check, or a check that never ran if the file does not exist or is invalid.
+    ///
+    /// # Arguments
+    /// * `path` - The file of the last check.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    /// Saves the check, creating the parent directory if needed.
+    ///
+    /// # Arguments
+    /// * `path` - The file of the last check.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write update check {}", path.display()))
+    }
+
+    /// Records a check that ran now, with the version of the latest release if it was found.
+    ///
+    /// # Arguments
+    /// * `latest` - The version of the latest release.
+    pub fn now(latest: Option<String>) -> Self {
+        Self {
+            checked_at: unix_time(SystemTime::now()),
+            latest,
+        }
+    }
+
+    /// Returns whether the check is older than `interval`, and must run again.
+    ///
+    /// # Arguments
+    /// * `interval` - The time between two checks.
+    pub fn is_due(&self, interval: Duration) -> bool {
+        unix_time(SystemTime::now()) >= self.checked_at.saturating_add(interval.as_secs())
+    }
+
+    /// Returns the version of the latest release if it is newer than the running binary.
+    pub fn newer_version(&self) -> Option<&str> {
+        self.latest
+            .as_deref()
+            .filter(|latest| is_newer(latest, CURRENT_VERSION))
+    }
+}
+
+/// A release of `ddg` on GitHub, as returned by the releases API.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Release {
+    /// The tag of the release, e.g. `v0.3.0`.
+    pub tag_name: String,
+    /// The page of the release.
+    pub html_url: String,
+    /// The files attached to the release.
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+/// A file attached to a release.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ReleaseAsset {
+    /// The file name, e.g. `ddg-x86_64-linux`.
+    pub name: String,
+    /// The download URL of the file.
+    #[serde(rename = "browser_download_url")]
+    pub url: String,
+}
+
+impl Release {
+    /// Returns the version of the release, its tag without the leading `v`.
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    /// Returns whether the release is newer than the running binary.
+    pub fn is_newer(&self) -> bool {
+        is_newer(self.version(), CURRENT_VERSION)
+    }
+
+    /// Returns the asset with the given file name, if the release has one.
+    ///
+    /// # Arguments
+    /// * `name` - The file name of the asset.
+    pub fn asset(&self, name: &str) -> Option<&ReleaseAsset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
+
+/// Returns the file name of the release binary for this platform: `ddg-<arch>-<os>`, with an
+/// `.exe` extension on Windows.
+///
+/// Its SHA-256 checksum is attached to the release as `<name>.sha256`, and its Ed25519
+/// signature as `<name>.sig`, both hex-encoded.
+pub fn asset_name() -> String {
+    format!(
+        "ddg-{}-{}{}",
+        env::consts::ARCH,
+        env::consts::OS,
+        env::consts::EXE_SUFFIX
+    )
+}
+
+/// Returns whether `version` is newer than `current`, comparing their dot-separated numbers.
+///
+/// A pre-release (`1.0.0-rc.1`) is older than the release it precedes.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::update::is_newer;
+///
+/// assert!(is_newer("0.10.0", "0.9.3"));
+/// assert!(is_newer("1.0.0", "1.0.0-rc.1"));
+/// assert!(!is_newer("0.2.0", "0.2.0"));
+/// assert!(!is_newer("0.2.0-beta", "0.2.0"));
+/// ```
+pub fn is_newer(version: &str, current: &str) -> bool {
+    fn parse(version: &str) -> (Vec<u64>, bool) {
+        let (numbers, pre) = match version.split_once('-') {
+            Some((numbers, _)) => (numbers, true),
+            None => (version, false),
+        };
+        let numbers = numbers
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect();
+        (numbers, pre)
+    }
+
+    let (version, version_pre) = parse(version);
+    let (current, current_pre) = parse(current);
+    match version.cmp(&current) {
+        std::cmp::Ordering::Equal => current_pre && !version_pre,
+        ordering => ordering.is_gt(),
+    }
+}
+
+/// Checks for, downloads, and installs the releases of `ddg` published on GitHub.
+///
+/// Binaries are only downloaded once their SHA-256 checksum and their Ed25519 signature, checked
+/// against the key set with [`Updater::public_key`], match the ones attached to the release.
+/// Without a key, [`Updater::download`] fails unless the signature check is skipped with
+/// [`Updater::insecure`].
+///
+/// # Examples
+/// ```no_run
+/// use duckduckgo::update::Updater;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let key = std::env::var("DDG_RELEASE_KEY")?;
+///     let updater = Updater::new(reqwest::Client::new()).public_key(&key)?;
+///     let release = updater.latest().await?;
+///     if release.is_newer() {
+///         let binary = updater.download(&release).await?;
+///         Updater::install(&binary, &std::env::current_exe()?)?;
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Updater {
+    client: reqwest::Client,
+    releases_url: String,
+    public_key: Option<Vec<u8>>,
+    insecure: bool,
+}
+
+impl Updater {
+    /// Creates an updater checking the releases of the `kevin-rs/duckduckgo` repository, with
+    /// no public key: set one with [`Updater::public_key`] before downloading a binary.
+    ///
+    /// # Arguments
+    /// * `client` - The HTTP client of the requests, with the proxy and TLS settings to use.
+    pub fn new(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            releases_url: RELEASES_URL.to_string(),
+            public_key: None,
+            insecure: false,
+        }
+    }
+
+    /// Checks another GitHub-compatible releases API, e.g. a mirror.
+    ///
+    /// # Arguments
+    /// * `url` - The URL returning the latest release.
+    pub fn releases_url(mut self, url: impl Into<String>) -> Self {
+        self.releases_url = url.into();
+        self
+    }
+
+    /// Requires the binaries to be signed with an Ed25519 key.
+    ///
+    /// # Arguments
+    /// * `key` - The hex-encoded public key.
+    ///
+    /// # Errors
+    /// Fails if the key is not 32 hex-encoded bytes.
+    pub fn public_key(mut self, key: &str) -> Result<Self> {
+        let key = decode_hex(key).context("Invalid public key")?;
+        if key.len() != 32 {
+            bail!("Invalid public key: expected 32 bytes, got {}", key.len());
+        }
+        self.public_key = Some(key);
+        Ok(self)
+    }
+
+    /// Skips the verification of the signature of the binaries, only matching their SHA-256
+    /// checksum, e.g. for a release published without signatures.
+    ///
+    /// The checksum is attached to the same release as the binary, so it only detects a
+    /// corrupted download, not a tampered release.
+    pub fn insecure(mut self) -> Self {
+        self.insecure = true;
+        self
+    }
+
+    /// Returns the latest release.
+    pub async fn latest(&self) -> Result<Release> {
+        let response = self
+            .client
+            .get(&self.releases_url)
+            .header("User-Agent", format!("ddg/{}", CURRENT_VERSION))
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await
+            .context("Failed to check the latest release")?
+            .error_for_status()?;
+        response
+            .json()
+            .await
+            .context("Failed to parse the latest release")
+    }
+
+    /// Downloads the binary of a release for this platform, verifying its checksum and, unless
+    /// [`Updater::insecure`] is set, its signature.
+    ///
+    /// # Arguments
+    /// * `release` - The release to download.
+    ///
+    /// # Errors
+    /// Fails if no public key is set and the signature check is not skipped, if the release has
+    /// no binary, checksum, or signature for this platform, or if they do not match.
+    pub async fn download(&self, release: &Release) -> Result<Vec<u8>> {
+        let name = asset_name();
+        let asset = |name: &str| {
+            release
+                .asset(name)
+                .with_context(|| format!("Release {} has no {} asset", release.tag_name, name))
+        };
+
+        let binary_asset = asset(&name)?;
+        let checksum_asset = asset(&format!("{}.sha256", name))?;
+        let signature_asset = match (&self.public_key, self.insecure) {
+            (_, true) => None,
+            (Some(key), false) => Some((key, asset(&format!("{}.sig", name))?)),
+            (None, false) => bail!("No public key to verify the signature of {} with", name),
+        };
+
+        let binary = self.fetch_bytes(binary_asset).await?;
+        let checksum = self.fetch(checksum_asset).await?;
+        let expected = decode_hex(first_token(&checksum)).context("Invalid checksum file")?;
+        if digest(&SHA256, &binary).as_ref() != expected.as_slice() {
+            bail!("Checksum mismatch for {}", name);
+        }
+
+        if let Some((key, signature_asset)) = signature_asset {
+            let signature = self.fetch(signature_asset).await?;
+            let signature =
+                decode_hex(first_token(&signature)).context("Invalid signature file")?;
+            UnparsedPublicKey::new(&ED25519, key)
+                .verify(&binary, &signature)
+                .map_err(|_| anyhow::anyhow!("Invalid signature for {}", name))?;
+        }
+        Ok(binary)
+    }
+
+    /// Replaces a binary with a new one.
+    ///
+    /// The new binary is written next to the old one, then renamed over it. On Windows, where
+    /// a running binary cannot be replaced, the old one is first renamed to `<name>.old`.
+    ///
+    /// # Arguments
+    /// * `binary` - The new binary.
+    /// * `exe` - The path of the binary to replace, usually `std::env::current_exe()`.
+    pub fn install(binary: &[u8], exe: &Path) -> Result<()> {
+        let sibling = |extension: &str| {
+            let mut name = exe.file_name().unwrap_or_default().to_os_string();
+            name.push(extension);
+            exe.with_file_name(name)
+        };
+        let staged = sibling(".new");
+        fs::write(&staged, binary)
+            .with_context(|| format!("Failed to write {}", staged.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+        }
+        #[cfg(windows)]
+        {
+            let old = sibling(".old");
+            let _ = fs::remove_file(&old);
+            fs::rename(exe, &old).with_context(|| format!("Failed to move {}", exe.display()))?;
+        }
+        fs::rename(&staged, exe).with_context(|| format!("Failed to replace {}", exe.display()))
+    }
+
+    async fn fetch(&self, asset: &ReleaseAsset) -> Result<String> {
+        let bytes = self.fetch_bytes(asset).await?;
+        String::from_utf8(bytes).with_context(|| format!("{} is not text", asset.name))
+    }
+
+    async fn fetch_bytes(&self, asset: &ReleaseAsset) -> Result<Vec<u8>> {
+        let response = self
+            .client
+            .get(&asset.url)
+            .header("User-Agent", format!("ddg/{}", CURRENT_VERSION))
+            .send()
+            .await
+            .with_context(|| format!("Failed to download {}", asset.name))?
+            .error_for_status()?;
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
+fn first_token(text: &str) -> &str {
+    text.split_whitespace().next().unwrap_or_default()
+}
+
+fn decode_hex(text: &str) -> Result<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        bail!("odd number of hex digits");
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| {
+            text.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .context("invalid hex digit")
+        })
+        .collect()
+}
+
+fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
@@ -1,5 +1,11 @@
+use anyhow::{Context, Result, bail};
 use phf::phf_map;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock, RwLock, RwLockReadGuard};
 
+/// The user agent presets shipped with the crate, loaded into every default `UserAgentRegistry`.
 // Crawled from: https://www.useragentstring.com/pages/Browserlist/
 pub static USER_AGENTS: phf::Map<&'static str, &'static str> = phf_map! {
     "mozilla" => "Mozilla/5.0 (compatible; U; ABrowse 0.6; Syllable) AppleWebKit/420+ (KHTML, like Gecko)",
@@ -9601,6 +9607,358 @@ pub static USER_AGENTS: phf::Map<&'static str, &'static str> = phf_map! {
     "firefox_2592" => "Mozilla/5.0 (Windows; U; Windows NT 5.1; en-US; rv:1.8.1.6) Gecko/20070801 Firefox/2.0 Wyzo/0.5.3",
 };
 
-pub fn get(name: &str) -> Option<&'static str> {
-    USER_AGENTS.get(name).copied()
+/// A set of named user agents, used to resolve `--user-agent` selections.
+///
+/// The default registry ships the presets of `USER_AGENTS`. Applications can register or override
+/// entries at runtime and load additional agents from a file, either on their own registry or on
+/// the global one used by `get`, `random`, and `resolve`.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::user_agents::UserAgentRegistry;
+///
+/// let mut registry = UserAgentRegistry::default();
+/// registry.register("crawler", "MyCrawler/1.0 (+https://example.com)");
+///
+/// assert_eq!(registry.get("crawler"), Some("MyCrawler/1.0 (+https://example.com)"));
+/// assert!(registry.get("firefox").is_some());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserAgentRegistry {
+    agents: BTreeMap<String, String>,
+}
+
+impl Default for UserAgentRegistry {
+    fn default() -> Self {
+        UserAgentRegistry {
+            agents: USER_AGENTS
+                .entries()
+                .map(|(name, agent)| (name.to_string(), agent.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl UserAgentRegistry {
+    /// Creates a registry without any preset.
+    pub fn empty() -> Self {
+        UserAgentRegistry {
+            agents: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the global registry used by `get`, `random`, and `resolve`.
+    pub fn global() -> &'static RwLock<UserAgentRegistry> {
+        static GLOBAL: OnceLock<RwLock<UserAgentRegistry>> = OnceLock::new();
+        GLOBAL.get_or_init(|| RwLock::new(UserAgentRegistry::default()))
+    }
+
+    /// Registers a user agent under `name`, replacing any previous entry.
+    ///
+    /// # Returns
+    /// The user agent previously registered under `name`, if any.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        agent: impl Into<String>,
+    ) -> Option<String> {
+        self.agents.insert(name.into(), agent.into())
+    }
+
+    /// Removes the user agent registered under `name`.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.agents.remove(name)
+    }
+
+    /// Returns the user agent registered under `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.agents.get(name).map(String::as_str)
+    }
+
+    /// Returns the registered names in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.agents.keys().map(String::as_str)
+    }
+
+    /// Returns the registered user agents, ordered by name.
+    pub fn agents(&self) -> impl Iterator<Item = &str> {
+        self.agents.values().map(String::as_str)
+    }
+
+    /// Returns the number of registered user agents.
+    pub fn len(&self) -> usize {
+        self.agents.len()
+    }
+
+    /// Returns `true` if no user agent is registered.
+    pub fn is_empty(&self) -> bool {
+        self.agents.is_empty()
+    }
+
+    /// Returns a random registered user agent.
+    pub fn random(&self) -> Option<&str> {
+        if self.agents.is_empty() {
+            return None;
+        }
+        self.agents().nth(fastrand::usize(..self.agents.len()))
+    }
+
+    /// Registers the user agents listed in a file.
+    ///
+    /// Each line holds a `name = user agent` entry. Blank lines and lines starting with `#` are
+    /// ignored, and entries override existing ones with the same name.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file to load.
+    ///
+    /// # Returns
+    /// The number of loaded entries, or an error if the file cannot be read or is malformed.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::user_agents::UserAgentRegistry;
+    ///
+    /// let path = std::env::temp_dir().join("ddg-user-agents-doctest.txt");
+    /// std::fs::write(&path, "# custom agents\ncrawler = MyCrawler/1.0\n").unwrap();
+    ///
+    /// let mut registry = UserAgentRegistry::empty();
+    /// assert_eq!(registry.load_file(&path).unwrap(), 1);
+    /// assert_eq!(registry.get("crawler"), Some("MyCrawler/1.0"));
+    /// ```
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<usize> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read user agents file {}", path.display()))?;
+
+        let mut loaded = 0;
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((name, agent)) = line.split_once('=') else {
+                bail!(
+                    "{}:{}: expected a `name = user agent` entry",
+                    path.display(),
+                    index + 1
+                );
+            };
+            let (name, agent) = (name.trim(), agent.trim());
+            if name.is_empty() || agent.is_empty() {
+                bail!(
+                    "{}:{}: empty user agent name or value",
+                    path.display(),
+                    index + 1
+                );
+            }
+
+            self.register(name, agent);
+            loaded += 1;
+        }
+
+        Ok(loaded)
+    }
+
+    /// Resolves a user agent selection: a registered name, `"random"` for a random registered
+    /// user agent, or a raw user agent string.
+    ///
+    /// Raw user agents must be valid header values and look like a user agent, i.e. start with a
+    /// `Product/Version` token, so misspelled names are reported instead of being sent as-is.
+    ///
+    /// # Examples
+    /// ```
+    /// use duckduckgo::user_agents::UserAgentRegistry;
+    ///
+    /// let registry = UserAgentRegistry::default();
+    /// assert_eq!(registry.resolve("firefox").unwrap(), registry.get("firefox").unwrap());
+    /// assert_eq!(
+    ///     registry.resolve("MyCrawler/1.0 (+https://example.com)").unwrap(),
+    ///     "MyCrawler/1.0 (+https://example.com)"
+    /// );
+    /// assert!(registry.resolve("firefx").is_err());
+    /// ```
+    pub fn resolve(&self, value: &str) -> Result<String> {
+        if value == "random" {
+            return self
+                .random()
+                .map(str::to_string)
+                .context("No user agent registered");
+        }
+        if let Some(agent) = self.get(value) {
+            return Ok(agent.to_string());
+        }
+
+        let value = value.trim();
+        let product = value.split_whitespace().next().unwrap_or_default();
+        let is_product = product
+            .split_once('/')
+            .is_some_and(|(name, version)| !name.is_empty() && !version.is_empty());
+        if !is_product {
+            bail!(
+                "Unknown user agent preset '{}' (raw user agents must start with a \
+                 Product/Version token, run `ddg user-agents list` for the presets)",
+                value
+            );
+        }
+        if value.len() > MAX_USER_AGENT_LEN {
+            bail!(
+                "User agent is too long ({} > {} characters)",
+                value.len(),
+                MAX_USER_AGENT_LEN
+            );
+        }
+        if value.chars().any(|c| !(' '..='~').contains(&c)) {
+            bail!("User agent must only contain printable ASCII characters");
+        }
+
+        Ok(value.to_string())
+    }
+}
+
+/// The maximum length accepted for a raw user agent string.
+const MAX_USER_AGENT_LEN: usize = 512;
+
+/// Returns the user agent registered under `name` in the global registry.
+pub fn get(name: &str) -> Option<String> {
+    read_global().get(name).map(str::to_string)
+}
+
+/// Registers a user agent under `name` in the global registry, replacing any previous entry.
+pub fn register(name: impl Into<String>, agent: impl Into<String>) -> Option<String> {
+    UserAgentRegistry::global()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .register(name, agent)
+}
+
+/// Resolves a user agent selection against the global registry.
+///
+/// See `UserAgentRegistry::resolve`.
+pub fn resolve(value: &str) -> Result<String> {
+    read_global().resolve(value)
+}
+
+/// Returns a random user agent from the global registry.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::user_agents::{UserAgentRegistry, random};
+///
+/// let agent = random();
+/// assert!(UserAgentRegistry::default().agents().any(|a| a == agent));
+/// ```
+pub fn random() -> String {
+    read_global()
+        .random()
+        .map(str::to_string)
+        .unwrap_or_default()
+}
+
+fn read_global() -> RwLockReadGuard<'static, UserAgentRegistry> {
+    UserAgentRegistry::global()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// When a `UserAgentRotator` switches to a new user agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    /// A new user agent is picked for every request.
+    PerRequest,
+    /// A new user agent is picked for every page of paginated searches.
+    PerPage,
+}
+
+/// Rotates between user agents to reduce blocking on long scrapes.
+///
+/// When set on a `Browser`, the rotator's user agent replaces the one passed to requests.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::user_agents::{Rotation, UserAgentRotator};
+///
+/// let rotator = UserAgentRotator::with_agents(vec!["agent-a", "agent-b"], Rotation::PerRequest);
+/// let first = rotator.next();
+/// assert_ne!(rotator.next(), first);
+///
+/// let browser = Browser::builder()
+///     .user_agent_rotator(UserAgentRotator::new(Rotation::PerPage))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct UserAgentRotator {
+    agents: Vec<String>,
+    rotation: Rotation,
+    current: Mutex<usize>,
+}
+
+impl UserAgentRotator {
+    /// Creates a rotator over all the user agents of the global registry.
+    pub fn new(rotation: Rotation) -> Self {
+        let agents: Vec<String> = read_global().agents().map(str::to_string).collect();
+        Self::with_agents(agents, rotation)
+    }
+
+    /// Creates a rotator over the given user agents.
+    ///
+    /// # Panics
+    /// Panics if `agents` is empty.
+    pub fn with_agents<I, S>(agents: I, rotation: Rotation) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let agents: Vec<String> = agents.into_iter().map(Into::into).collect();
+        assert!(
+            !agents.is_empty(),
+            "UserAgentRotator needs at least one user agent"
+        );
+
+        let current = fastrand::usize(..agents.len());
+        UserAgentRotator {
+            agents,
+            rotation,
+            current: Mutex::new(current),
+        }
+    }
+
+    /// Returns when the rotator switches to a new user agent.
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
+    }
+
+    /// Returns the user agent currently in use.
+    pub fn current(&self) -> String {
+        let current = *self.current.lock().unwrap_or_else(|e| e.into_inner());
+        self.agents[current].clone()
+    }
+
+    /// Switches to a different random user agent and returns it.
+    pub fn next(&self) -> String {
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        if self.agents.len() > 1 {
+            // Skip over the current entry so consecutive user agents always differ.
+            let offset = fastrand::usize(1..self.agents.len());
+            *current = (*current + offset) % self.agents.len();
+        }
+        self.agents[*current].clone()
+    }
+
+    /// Returns the user agent for a new request, rotating first if the rotation is per request.
+    pub fn for_request(&self) -> String {
+        match self.rotation {
+            Rotation::PerRequest => self.next(),
+            Rotation::PerPage => self.current(),
+        }
+    }
+
+    /// Notifies the rotator that a paginated search moves to its next page.
+    pub fn next_page(&self) {
+        if self.rotation == Rotation::PerPage {
+            self.next();
+        }
+    }
 }
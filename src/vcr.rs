@@ -0,0 +1,170 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Whether a `Cassette` records new interactions or only replays existing ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VcrMode {
+    /// Replays recorded interactions and records the ones that are missing.
+    Record,
+    /// Only replays recorded interactions; missing ones are an error.
+    Replay,
+}
+
+/// A directory of recorded HTTP interactions used to record and replay DuckDuckGo traffic.
+///
+/// Each interaction is stored as a JSON file named after a hash of the request method, URL,
+/// and body, so parser regressions can be reproduced without hitting the network.
+///
+/// # Examples
+/// ```
+/// use duckduckgo::browser::Browser;
+/// use duckduckgo::vcr::Cassette;
+///
+/// let browser = Browser::builder()
+///     .cassette(Cassette::replay("tests/cassettes"))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    /// The directory the interactions are stored in.
+    pub dir: PathBuf,
+    /// Whether missing interactions are recorded.
+    pub mode: VcrMode,
+}
+
+/// A single recorded request/response pair.
+#[derive(Debug, Serialize, Deserialize)]
+struct Interaction {
+    method: String,
+    url: String,
+    status: u16,
+    headers: BTreeMap<String, String>,
+    body: String,
+}
+
+impl Cassette {
+    /// Creates a cassette that records missing interactions into `dir`.
+    pub fn record(dir: impl Into<PathBuf>) -> Self {
+        Cassette {
+            dir: dir.into(),
+            mode: VcrMode::Record,
+        }
+    }
+
+    /// Creates a cassette that only replays interactions from `dir`.
+    pub fn replay(dir: impl Into<PathBuf>) -> Self {
+        Cassette {
+            dir: dir.into(),
+            mode: VcrMode::Replay,
+        }
+    }
+
+    /// Returns the path of the file the interaction for `request` is stored in.
+    pub fn path(&self, request: &reqwest::Request) -> PathBuf {
+        let body = request
+            .body()
+            .and_then(|b| b.as_bytes())
+            .unwrap_or_default();
+
+        let mut hash = Fnv1a::default();
+        hash.write(request.method().as_str().as_bytes());
+        hash.write(request.url().as_str().as_bytes());
+        hash.write(body);
+
+        self.dir.join(format!("{:016x}.json", hash.0))
+    }
+
+    /// Loads the recorded response for `request`.
+    ///
+    /// # Returns
+    /// The recorded response, `None` if it is missing and the cassette is recording, or an error
+    /// if it is missing and the cassette only replays.
+    pub fn load(&self, request: &reqwest::Request) -> Result<Option<reqwest::Response>> {
+        let path = self.path(request);
+        if !path.exists() {
+            if self.mode == VcrMode::Replay {
+                bail!(
+                    "No recorded response for {} {} in {}",
+                    request.method(),
+                    request.url(),
+                    self.dir.display()
+                );
+            }
+            return Ok(None);
+        }
+
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read cassette {}", path.display()))?;
+        let interaction: Interaction = serde_json::from_str(&text)
+            .with_context(|| format!("Failed to parse cassette {}", path.display()))?;
+
+        let mut builder = http::Response::builder().status(interaction.status);
+        for (name, value) in &interaction.headers {
+            builder = builder.header(name, value);
+        }
+
+        Ok(Some(builder.body(interaction.body)?.into()))
+    }
+
+    /// Stores `response` as the recorded interaction for `request`.
+    ///
+    /// The body is consumed while recording, so an equivalent response is returned.
+    pub async fn save(
+        &self,
+        request: &reqwest::Request,
+        response: reqwest::Response,
+    ) -> Result<reqwest::Response> {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await?;
+
+        let interaction = Interaction {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            status: status.as_u16(),
+            headers: headers
+                .iter()
+                .filter(|(name, _)| *name != reqwest::header::CONTENT_LENGTH)
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+            body,
+        };
+
+        let path = self.path(request);
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create cassette dir {}", self.dir.display()))?;
+        fs::write(&path, serde_json::to_string_pretty(&interaction)?)
+            .with_context(|| format!("Failed to write cassette {}", path.display()))?;
+
+        let mut builder = http::Response::builder().status(status);
+        for (name, value) in &headers {
+            builder = builder.header(name, value);
+        }
+
+        Ok(builder.body(interaction.body)?.into())
+    }
+}
+
+/// A 64-bit FNV-1a hasher, used for cassette file names that are stable across Rust versions.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Fnv1a(0xcbf29ce484222325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}